@@ -264,6 +264,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .with_color(Color::rgba8(60, 60, 60, 255))
         )));
     
+    // Right-clicking the normal container offers the same toggle as a context menu
+    let toggle_for_menu = toggle_signal.clone();
+    let normal_container_element = normal_container.into_container_element()
+        .with_context_menu(move || vec![
+            MenuItem::checkbox("Show toggled container", toggle_for_menu.clone()),
+            MenuItem::separator(),
+            MenuItem::action("Log click").on_select(|| println!("Context menu action selected")),
+        ]);
+    
     // Create a container with percentage sizing (always visible for comparison)
     let perc_container = container()
         .with_width_perc(80.0) // 80% of available width
@@ -285,7 +294,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .with_child(Element::new_widget(Box::new(theme_dropdown)))
         .with_child(Element::new_widget(Box::new(toggle_button)))
         .with_child(toggle_container.into_container_element())
-        .with_child(normal_container.into_container_element()) // Normal container
+        .with_child(normal_container_element) // Normal container
         .with_child(floating_container.into_container_element()) // Absolute positioned container
         .with_child(floating_info.into_container_element()) // Another absolute container
         .with_child(perc_container.into_container_element())
@@ -293,7 +302,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .with_child(Element::new_widget(Box::new(perc_button_1)))
         .with_child(Element::new_widget(Box::new(perc_button_2)))
         // .with_child(Element::new_widget(Box::new(counter_slider)))
-        .with_child(Element::new_widget(Box::new(click_button)));
+        .with_child(Element::new_widget(Box::new(click_button)).with_tooltip("Adds one to the counter"));
 
     //     // Create the inner container with responsive shadow
     // let container2 = container()
@@ -0,0 +1,999 @@
+use crate::event::{Point, Rect};
+use gui_reactive::{Signal, global_frame_scheduler};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use vello::peniko::Color;
+
+/// A value that can be tweened: `lerp` blends from `self` to `to` as `t` goes 0..1
+pub trait Animatable: Clone + Send + Sync + 'static {
+    fn lerp(&self, to: &Self, t: f32) -> Self;
+}
+
+impl Animatable for f32 {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Animatable for f64 {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t as f64
+    }
+}
+
+impl Animatable for (f32, f32) {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        (self.0.lerp(&to.0, t), self.1.lerp(&to.1, t))
+    }
+}
+
+impl Animatable for Color {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        let channel = |from: u8, to: u8| (from as f32).lerp(&(to as f32), t).round().clamp(0.0, 255.0) as u8;
+        Color::rgba8(channel(self.r, to.r), channel(self.g, to.g), channel(self.b, to.b), channel(self.a, to.a))
+    }
+}
+
+impl Animatable for Point {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Point::new(self.x.lerp(&to.x, t), self.y.lerp(&to.y, t))
+    }
+}
+
+impl Animatable for Rect {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Rect::new(
+            self.origin.x.lerp(&to.origin.x, t),
+            self.origin.y.lerp(&to.origin.y, t),
+            self.size.width.lerp(&to.size.width, t),
+            self.size.height.lerp(&to.size.height, t),
+        )
+    }
+}
+
+/// Maps linear progress in 0..1 onto the eased progress of a tween
+#[derive(Clone, Copy, Debug, Default)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    #[default]
+    EaseInOut,
+    /// Overshoots the target slightly before settling
+    EaseOutBack,
+    EaseOutBounce,
+    Custom(fn(f32) -> f32),
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            },
+            Easing::EaseOutBack => {
+                let c1 = 1.70158;
+                let c3 = c1 + 1.0;
+                1.0 + c3 * (t - 1.0).powi(3) + c1 * (t - 1.0).powi(2)
+            },
+            Easing::EaseOutBounce => {
+                let (n1, d1) = (7.5625, 2.75);
+                if t < 1.0 / d1 {
+                    n1 * t * t
+                } else if t < 2.0 / d1 {
+                    let t = t - 1.5 / d1;
+                    n1 * t * t + 0.75
+                } else if t < 2.5 / d1 {
+                    let t = t - 2.25 / d1;
+                    n1 * t * t + 0.9375
+                } else {
+                    let t = t - 2.625 / d1;
+                    n1 * t * t + 0.984375
+                }
+            },
+            Easing::Custom(curve) => curve(t),
+        }
+    }
+}
+
+/// How many times an animation plays
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Repeat {
+    Times(u32),
+    Forever,
+}
+
+// Type-erased tween so sequences can mix signals of different types
+trait Track: Send {
+    fn duration(&self) -> Duration;
+    fn set_easing(&mut self, easing: Easing);
+    fn apply(&mut self, elapsed: Duration);
+}
+
+struct Tween<T: Animatable> {
+    target: Signal<T>,
+    // Taken from the signal when the tween first runs, unless given up front
+    from: Option<T>,
+    to: T,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl<T: Animatable> Track for Tween<T> {
+    fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    fn set_easing(&mut self, easing: Easing) {
+        self.easing = easing;
+    }
+
+    fn apply(&mut self, elapsed: Duration) {
+        let from = self.from.get_or_insert_with(|| self.target.get());
+        let progress = if self.duration.is_zero() {
+            1.0
+        } else {
+            elapsed.as_secs_f32() / self.duration.as_secs_f32()
+        };
+        self.target.set(from.lerp(&self.to, self.easing.apply(progress)));
+    }
+}
+
+enum AnimationKind {
+    Tween(Box<dyn Track>),
+    Wait(Duration),
+    Sequence(Vec<Animation>),
+    Parallel(Vec<Animation>),
+}
+
+/// A tween, a pause, or a group of animations, with delay, repeat, yoyo and a
+/// completion callback. Build one, then `start` it on the global animator.
+pub struct Animation {
+    kind: AnimationKind,
+    delay: Duration,
+    repeat: Repeat,
+    yoyo: bool,
+    on_complete: Option<Box<dyn FnOnce() + Send>>,
+    // Whether any of it has played yet, so scrubbing back can reset it
+    started: bool,
+}
+
+impl Animation {
+    fn from_kind(kind: AnimationKind) -> Self {
+        Self {
+            kind,
+            delay: Duration::ZERO,
+            repeat: Repeat::Times(1),
+            yoyo: false,
+            on_complete: None,
+            started: false,
+        }
+    }
+
+    /// Move `target` from its value when the tween starts to `to`
+    pub fn tween<T: Animatable>(target: Signal<T>, to: T, duration: Duration) -> Self {
+        Self::from_kind(AnimationKind::Tween(Box::new(Tween {
+            target,
+            from: None,
+            to,
+            duration,
+            easing: Easing::default(),
+        })))
+    }
+
+    pub fn tween_from<T: Animatable>(target: Signal<T>, from: T, to: T, duration: Duration) -> Self {
+        Self::from_kind(AnimationKind::Tween(Box::new(Tween {
+            target,
+            from: Some(from),
+            to,
+            duration,
+            easing: Easing::default(),
+        })))
+    }
+
+    pub fn wait(duration: Duration) -> Self {
+        Self::from_kind(AnimationKind::Wait(duration))
+    }
+
+    /// Play `animations` one after another
+    pub fn sequence(animations: Vec<Animation>) -> Self {
+        Self::from_kind(AnimationKind::Sequence(animations))
+    }
+
+    /// Play `animations` together; the group ends with the longest one
+    pub fn parallel(animations: Vec<Animation>) -> Self {
+        Self::from_kind(AnimationKind::Parallel(animations))
+    }
+
+    /// Easing for this tween, or every tween in this group
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.set_easing(easing);
+        self
+    }
+
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn repeat(mut self, times: u32) -> Self {
+        self.repeat = Repeat::Times(times.max(1));
+        self
+    }
+
+    pub fn repeat_forever(mut self) -> Self {
+        self.repeat = Repeat::Forever;
+        self
+    }
+
+    /// Play every other repetition backwards
+    pub fn yoyo(mut self) -> Self {
+        self.yoyo = true;
+        self
+    }
+
+    pub fn on_complete<F>(mut self, callback: F) -> Self
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Run on the global animator, starting this frame
+    pub fn start(self) -> AnimationHandle {
+        global_animator().start(self)
+    }
+
+    fn set_easing(&mut self, easing: Easing) {
+        match &mut self.kind {
+            AnimationKind::Tween(track) => track.set_easing(easing),
+            AnimationKind::Wait(_) => {},
+            AnimationKind::Sequence(children) | AnimationKind::Parallel(children) => {
+                for child in children {
+                    child.set_easing(easing);
+                }
+            },
+        }
+    }
+
+    /// Length of one play-through, or None if it never ends
+    fn cycle_duration(&self) -> Option<Duration> {
+        match &self.kind {
+            AnimationKind::Tween(track) => Some(track.duration()),
+            AnimationKind::Wait(duration) => Some(*duration),
+            AnimationKind::Sequence(children) => children.iter()
+                .map(Animation::total_duration)
+                .sum::<Option<Duration>>(),
+            AnimationKind::Parallel(children) => children.iter()
+                .map(Animation::total_duration)
+                .try_fold(Duration::ZERO, |longest, duration| duration.map(|d| longest.max(d))),
+        }
+    }
+
+    /// Delay plus every repetition, or None if it never ends
+    pub fn total_duration(&self) -> Option<Duration> {
+        match self.repeat {
+            Repeat::Forever => None,
+            Repeat::Times(times) => self.cycle_duration().map(|cycle| self.delay + cycle * times),
+        }
+    }
+
+    /// Set everything this animation drives to where it is `elapsed` after
+    /// starting. Returns true once it has finished, firing `on_complete` the
+    /// first time.
+    fn sample(&mut self, elapsed: Duration) -> bool {
+        if elapsed < self.delay {
+            // Scrubbed back before its delay: show its starting state again
+            if self.started {
+                self.sample_cycle(Duration::ZERO);
+            }
+            return false;
+        }
+        self.started = true;
+        let local = elapsed - self.delay;
+        let Some(cycle) = self.cycle_duration() else {
+            // Endless content plays straight through; repeats never come round
+            self.sample_cycle(local);
+            return false;
+        };
+
+        let (iteration, within, finished) = if cycle.is_zero() {
+            (0, cycle, true)
+        } else {
+            let iteration = (local.as_secs_f64() / cycle.as_secs_f64()) as u32;
+            match self.repeat {
+                Repeat::Times(times) if iteration >= times => (times - 1, cycle, true),
+                _ => (iteration, local - cycle * iteration, false),
+            }
+        };
+        let position = if self.yoyo && iteration % 2 == 1 { cycle - within } else { within };
+        self.sample_cycle(position);
+
+        if finished {
+            if let Some(callback) = self.on_complete.take() {
+                callback();
+            }
+        }
+        finished
+    }
+
+    fn sample_cycle(&mut self, position: Duration) {
+        match &mut self.kind {
+            AnimationKind::Tween(track) => track.apply(position),
+            AnimationKind::Wait(_) => {},
+            AnimationKind::Parallel(children) => {
+                for child in children {
+                    child.sample(position);
+                }
+            },
+            AnimationKind::Sequence(children) => {
+                let mut starts = Vec::with_capacity(children.len());
+                let mut start = Some(Duration::ZERO);
+                for child in children.iter() {
+                    starts.push(start);
+                    start = start.zip(child.total_duration()).map(|(start, duration)| start + duration);
+                }
+                // Later steps that already played are rewound first (yoyo plays
+                // sequences backwards), then earlier ones settle in order
+                for (child, start) in children.iter_mut().zip(&starts).rev() {
+                    if child.started && !matches!(start, Some(start) if *start <= position) {
+                        child.sample(Duration::ZERO);
+                    }
+                }
+                for (child, start) in children.iter_mut().zip(&starts) {
+                    match start {
+                        Some(start) if *start <= position => {
+                            child.sample(position - *start);
+                        },
+                        _ => break,
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// Lets the code that started an animation stop it or check on it
+#[derive(Clone)]
+pub struct AnimationHandle {
+    cancelled: Arc<AtomicBool>,
+    finished: Arc<AtomicBool>,
+}
+
+impl AnimationHandle {
+    /// Stop where it is; values keep whatever the last frame set
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.cancelled.load(Ordering::Acquire) && !self.finished.load(Ordering::Acquire)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Acquire)
+    }
+}
+
+struct RunningAnimation {
+    animation: Animation,
+    started_at: Duration,
+    cancelled: Arc<AtomicBool>,
+    finished: Arc<AtomicBool>,
+}
+
+/// Plays started animations and moving springs on the frame clock. While
+/// anything is moving it ticks once per frame and keeps widget updates coming;
+/// once everything has finished or come to rest it goes idle.
+pub struct Animator {
+    running: Mutex<Vec<RunningAnimation>>,
+    springs: Mutex<Vec<Arc<dyn SpringStep>>>,
+    tick_scheduled: AtomicBool,
+}
+
+impl Animator {
+    fn new() -> Self {
+        Self {
+            running: Mutex::new(Vec::new()),
+            springs: Mutex::new(Vec::new()),
+            tick_scheduled: AtomicBool::new(false),
+        }
+    }
+
+    pub fn start(&self, animation: Animation) -> AnimationHandle {
+        let handle = AnimationHandle {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            finished: Arc::new(AtomicBool::new(false)),
+        };
+        if let Ok(mut running) = self.running.lock() {
+            running.push(RunningAnimation {
+                animation,
+                started_at: global_frame_scheduler().frame_timestamp(),
+                cancelled: Arc::clone(&handle.cancelled),
+                finished: Arc::clone(&handle.finished),
+            });
+        }
+        self.schedule_tick();
+        handle
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.running.lock().map(|running| !running.is_empty()).unwrap_or(false)
+            || self.springs.lock().map(|springs| !springs.is_empty()).unwrap_or(false)
+    }
+
+    fn add_spring(&self, spring: Arc<dyn SpringStep>) {
+        if let Ok(mut springs) = self.springs.lock() {
+            springs.push(spring);
+        }
+        self.schedule_tick();
+    }
+
+    /// Cancel everything, e.g. when tearing down a view
+    pub fn cancel_all(&self) {
+        if let Ok(running) = self.running.lock() {
+            for entry in running.iter() {
+                entry.cancelled.store(true, Ordering::Release);
+            }
+        }
+    }
+
+    fn schedule_tick(&self) {
+        if !self.tick_scheduled.swap(true, Ordering::AcqRel) {
+            global_frame_scheduler().schedule_for_next_frame(|| global_animator().tick());
+        }
+        global_frame_scheduler().request_update();
+    }
+
+    /// Advance every running animation to the current frame time
+    pub fn tick(&self) {
+        self.tick_scheduled.store(false, Ordering::Release);
+        let now = global_frame_scheduler().frame_timestamp();
+        // Sample outside the lock: completion callbacks may start new animations
+        let mut entries = self.running.lock().map(|mut running| std::mem::take(&mut *running)).unwrap_or_default();
+        entries.retain_mut(|entry| {
+            if entry.cancelled.load(Ordering::Acquire) {
+                return false;
+            }
+            let finished = entry.animation.sample(now.saturating_sub(entry.started_at));
+            entry.finished.store(finished, Ordering::Release);
+            !finished
+        });
+        let any_running = if let Ok(mut running) = self.running.lock() {
+            // Animations started during the tick go after the ones already playing
+            entries.append(&mut running);
+            *running = entries;
+            !running.is_empty()
+        } else {
+            false
+        };
+
+        // Springs drop out once they come to rest
+        let mut springs = self.springs.lock().map(|mut springs| std::mem::take(&mut *springs)).unwrap_or_default();
+        springs.retain(|spring| spring.step(now));
+        let any_moving = if let Ok(mut moving) = self.springs.lock() {
+            springs.append(&mut moving);
+            *moving = springs;
+            !moving.is_empty()
+        } else {
+            false
+        };
+
+        if any_running || any_moving {
+            self.schedule_tick();
+        }
+    }
+}
+
+static GLOBAL_ANIMATOR: OnceLock<Animator> = OnceLock::new();
+
+pub fn global_animator() -> &'static Animator {
+    GLOBAL_ANIMATOR.get_or_init(Animator::new)
+}
+
+/// Tween `target` to `to` and start right away
+pub fn animate<T: Animatable>(target: &Signal<T>, to: T, duration: Duration, easing: Easing) -> AnimationHandle {
+    Animation::tween(target.clone(), to, duration).with_easing(easing).start()
+}
+
+/// How a widget property eases between values instead of jumping
+#[derive(Clone, Copy, Debug)]
+pub struct Transition {
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl Transition {
+    pub fn new(duration: Duration) -> Self {
+        Self { duration, easing: Easing::EaseOut }
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// A property with an implicit transition: every new target is eased to from
+/// wherever the value currently is, cancelling the previous transition
+pub struct Transitioned<T: Animatable + PartialEq> {
+    value: Signal<T>,
+    target: T,
+    transition: Transition,
+    running: Option<AnimationHandle>,
+}
+
+impl<T: Animatable + PartialEq> Transitioned<T> {
+    pub fn new(initial: T, transition: Transition) -> Self {
+        Self {
+            value: Signal::new(initial.clone()),
+            target: initial,
+            transition,
+            running: None,
+        }
+    }
+
+    pub fn get(&self) -> T {
+        self.value.get()
+    }
+
+    /// Signal the eased value is written to, for widgets to redraw on
+    pub fn signal(&self) -> &Signal<T> {
+        &self.value
+    }
+
+    pub fn target(&self) -> &T {
+        &self.target
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.running.as_ref().is_some_and(AnimationHandle::is_running)
+    }
+
+    pub fn animate_to(&mut self, target: T) {
+        if target == self.target {
+            return;
+        }
+        self.target = target.clone();
+        if let Some(running) = self.running.take() {
+            running.cancel();
+        }
+        self.running = Some(
+            Animation::tween(self.value.clone(), target, self.transition.duration)
+                .with_easing(self.transition.easing)
+                .start(),
+        );
+    }
+
+    /// Set the value without a transition
+    pub fn jump_to(&mut self, target: T) {
+        if let Some(running) = self.running.take() {
+            running.cancel();
+        }
+        self.target = target.clone();
+        self.value.set(target);
+    }
+}
+
+/// A value a spring can drive, seen as up to four independent components
+pub trait SpringValue: Animatable {
+    fn to_components(&self) -> [f64; 4];
+    fn from_components(components: [f64; 4]) -> Self;
+}
+
+impl SpringValue for f32 {
+    fn to_components(&self) -> [f64; 4] {
+        [*self as f64, 0.0, 0.0, 0.0]
+    }
+
+    fn from_components(components: [f64; 4]) -> Self {
+        components[0] as f32
+    }
+}
+
+impl SpringValue for f64 {
+    fn to_components(&self) -> [f64; 4] {
+        [*self, 0.0, 0.0, 0.0]
+    }
+
+    fn from_components(components: [f64; 4]) -> Self {
+        components[0]
+    }
+}
+
+impl SpringValue for (f32, f32) {
+    fn to_components(&self) -> [f64; 4] {
+        [self.0 as f64, self.1 as f64, 0.0, 0.0]
+    }
+
+    fn from_components(components: [f64; 4]) -> Self {
+        (components[0] as f32, components[1] as f32)
+    }
+}
+
+impl SpringValue for Point {
+    fn to_components(&self) -> [f64; 4] {
+        [self.x, self.y, 0.0, 0.0]
+    }
+
+    fn from_components(components: [f64; 4]) -> Self {
+        Point::new(components[0], components[1])
+    }
+}
+
+impl SpringValue for Rect {
+    fn to_components(&self) -> [f64; 4] {
+        [self.origin.x, self.origin.y, self.size.width, self.size.height]
+    }
+
+    fn from_components(components: [f64; 4]) -> Self {
+        Rect::new(components[0], components[1], components[2], components[3])
+    }
+}
+
+impl SpringValue for Color {
+    fn to_components(&self) -> [f64; 4] {
+        [self.r as f64, self.g as f64, self.b as f64, self.a as f64]
+    }
+
+    fn from_components(components: [f64; 4]) -> Self {
+        let channel = |c: f64| c.round().clamp(0.0, 255.0) as u8;
+        Color::rgba8(channel(components[0]), channel(components[1]), channel(components[2]), channel(components[3]))
+    }
+}
+
+// Longest frame gap a spring integrates across; after a stall it picks up
+// where it was instead of jumping
+const MAX_SPRING_FRAME: f64 = 1.0 / 15.0;
+// Integration step, small enough to stay stable for stiff springs
+const SPRING_SUBSTEP: f64 = 1.0 / 240.0;
+
+/// Physical parameters of a spring, plus how still it must be to count as settled
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpringConfig {
+    pub stiffness: f64,
+    pub damping: f64,
+    pub mass: f64,
+    /// Largest distance from the target that counts as arrived
+    pub rest_displacement: f64,
+    /// Largest speed, in units per second, that counts as stopped
+    pub rest_velocity: f64,
+}
+
+impl SpringConfig {
+    pub fn new(stiffness: f64, damping: f64, mass: f64) -> Self {
+        Self {
+            stiffness,
+            damping,
+            mass,
+            rest_displacement: 0.01,
+            rest_velocity: 0.01,
+        }
+    }
+
+    pub fn gentle() -> Self {
+        Self::new(120.0, 14.0, 1.0)
+    }
+
+    pub fn wobbly() -> Self {
+        Self::new(180.0, 12.0, 1.0)
+    }
+
+    pub fn stiff() -> Self {
+        Self::new(210.0, 20.0, 1.0)
+    }
+
+    /// Fast with no overshoot, for snapping panels and reordering
+    pub fn snappy() -> Self {
+        Self::new(400.0, 40.0, 1.0)
+    }
+
+    pub fn with_rest_threshold(mut self, displacement: f64, velocity: f64) -> Self {
+        self.rest_displacement = displacement;
+        self.rest_velocity = velocity;
+        self
+    }
+}
+
+impl Default for SpringConfig {
+    fn default() -> Self {
+        Self::new(170.0, 26.0, 1.0)
+    }
+}
+
+// What the animator needs from a spring of any value type
+trait SpringStep: Send + Sync {
+    /// Advance to `now`; false once the spring has come to rest
+    fn step(&self, now: Duration) -> bool;
+}
+
+struct SpringState {
+    position: [f64; 4],
+    velocity: [f64; 4],
+    target: [f64; 4],
+    config: SpringConfig,
+    // Frame time of the last step, or None while at rest
+    last_step: Option<Duration>,
+}
+
+impl SpringState {
+    fn integrate(&mut self, dt: f64) {
+        let SpringConfig { stiffness, damping, mass, .. } = self.config;
+        let steps = (dt / SPRING_SUBSTEP).ceil().max(1.0) as usize;
+        let h = dt / steps as f64;
+        for _ in 0..steps {
+            for i in 0..4 {
+                let force = -stiffness * (self.position[i] - self.target[i]) - damping * self.velocity[i];
+                // Semi-implicit Euler: velocity first, then position from the new velocity
+                self.velocity[i] += force / mass * h;
+                self.position[i] += self.velocity[i] * h;
+            }
+        }
+    }
+
+    fn is_at_rest(&self) -> bool {
+        (0..4).all(|i| {
+            (self.position[i] - self.target[i]).abs() <= self.config.rest_displacement
+                && self.velocity[i].abs() <= self.config.rest_velocity
+        })
+    }
+}
+
+struct SpringInner<T: SpringValue> {
+    state: Mutex<SpringState>,
+    value: Signal<T>,
+}
+
+impl<T: SpringValue> SpringStep for SpringInner<T> {
+    fn step(&self, now: Duration) -> bool {
+        let (value, moving) = {
+            let Ok(mut state) = self.state.lock() else {
+                return false;
+            };
+            let Some(last_step) = state.last_step else {
+                return false;
+            };
+            let dt = now.saturating_sub(last_step).as_secs_f64().min(MAX_SPRING_FRAME);
+            state.integrate(dt);
+            if state.is_at_rest() {
+                state.position = state.target;
+                state.velocity = [0.0; 4];
+                state.last_step = None;
+            } else {
+                state.last_step = Some(now);
+            }
+            (T::from_components(state.position), state.last_step.is_some())
+        };
+        // Written outside the lock so subscribers can read the spring
+        self.value.set(value);
+        moving
+    }
+}
+
+/// A value pulled towards its target by a damped spring. Unlike a tween it
+/// has no fixed duration: retargeting mid-flight keeps the current velocity,
+/// so drags that let go, panels that snap and reordered rows move naturally.
+/// Springs are stepped each frame by the global animator until they settle.
+pub struct Spring<T: SpringValue> {
+    inner: Arc<SpringInner<T>>,
+}
+
+impl<T: SpringValue> Clone for Spring<T> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<T: SpringValue> Spring<T> {
+    pub fn new(initial: T, config: SpringConfig) -> Self {
+        let position = initial.to_components();
+        Self {
+            inner: Arc::new(SpringInner {
+                state: Mutex::new(SpringState {
+                    position,
+                    velocity: [0.0; 4],
+                    target: position,
+                    config,
+                    last_step: None,
+                }),
+                value: Signal::new(initial),
+            }),
+        }
+    }
+
+    pub fn get(&self) -> T {
+        self.inner.value.get()
+    }
+
+    /// Signal the spring's position is written to each frame
+    pub fn signal(&self) -> &Signal<T> {
+        &self.inner.value
+    }
+
+    pub fn target(&self) -> T {
+        self.with_state(|state| T::from_components(state.target))
+    }
+
+    /// Velocity per component, in units per second
+    pub fn velocity(&self) -> T {
+        self.with_state(|state| T::from_components(state.velocity))
+    }
+
+    pub fn is_at_rest(&self) -> bool {
+        self.with_state(|state| state.last_step.is_none())
+    }
+
+    pub fn set_config(&self, config: SpringConfig) {
+        self.update_state(|state| state.config = config);
+    }
+
+    /// Head for `target` from wherever the spring is, keeping its velocity
+    pub fn set_target(&self, target: T) {
+        let target = target.to_components();
+        self.update_state(|state| state.target = target);
+    }
+
+    /// Add to the current velocity, e.g. with the pointer speed when a drag is released
+    pub fn impulse(&self, velocity: T) {
+        let velocity = velocity.to_components();
+        self.update_state(|state| {
+            for (current, added) in state.velocity.iter_mut().zip(velocity) {
+                *current += added;
+            }
+        });
+    }
+
+    /// Move straight to `value` and stop there, e.g. while a drag holds it
+    pub fn jump_to(&self, value: T) {
+        if let Ok(mut state) = self.inner.state.lock() {
+            state.position = value.to_components();
+            state.target = state.position;
+            state.velocity = [0.0; 4];
+            state.last_step = None;
+        }
+        self.inner.value.set(value);
+    }
+
+    fn with_state<R>(&self, read: impl FnOnce(&SpringState) -> R) -> R {
+        let state = self.inner.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        read(&state)
+    }
+
+    // Change the spring and wake it on the animator if it was resting
+    fn update_state(&self, change: impl FnOnce(&mut SpringState)) {
+        let woke = {
+            let mut state = self.inner.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            change(&mut state);
+            let resting = state.last_step.is_none();
+            if resting && !state.is_at_rest() {
+                state.last_step = Some(global_frame_scheduler().frame_timestamp());
+                true
+            } else {
+                false
+            }
+        };
+        if woke {
+            global_animator().add_spring(Arc::clone(&self.inner) as Arc<dyn SpringStep>);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn sequences_delays_and_yoyo_follow_the_clock() {
+        let x = Signal::new(0.0f32);
+        let y = Signal::new(0.0f32);
+        let mut animation = Animation::sequence(vec![
+            Animation::tween(x.clone(), 10.0, ms(100)),
+            Animation::tween(y.clone(), 20.0, ms(100)).with_delay(ms(50)),
+        ])
+        .with_easing(Easing::Linear)
+        .repeat(2)
+        .yoyo();
+        assert_eq!(animation.total_duration(), Some(ms(500)));
+
+        assert!(!animation.sample(ms(50)));
+        assert_eq!((x.get(), y.get()), (5.0, 0.0));
+        // Inside the second tween's delay
+        animation.sample(ms(120));
+        assert_eq!((x.get(), y.get()), (10.0, 0.0));
+        animation.sample(ms(200));
+        assert_eq!((x.get(), y.get()), (10.0, 10.0));
+        // Second repetition plays backwards
+        animation.sample(ms(300));
+        assert_eq!((x.get(), y.get()), (10.0, 10.0));
+        animation.sample(ms(400));
+        assert_eq!((x.get(), y.get()), (10.0, 0.0));
+        animation.sample(ms(450));
+        assert_eq!((x.get(), y.get()), (5.0, 0.0));
+        assert!(animation.sample(ms(600)));
+        assert_eq!((x.get(), y.get()), (0.0, 0.0));
+    }
+
+    #[test]
+    fn parallel_groups_end_with_the_longest_and_complete_once() {
+        let completed = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&completed);
+        let color = Signal::new(Color::rgba8(0, 0, 0, 255));
+        let point = Signal::new(Point::new(0.0, 0.0));
+        let mut animation = Animation::parallel(vec![
+            Animation::tween(color.clone(), Color::rgba8(200, 100, 0, 255), ms(100)),
+            Animation::tween_from(point.clone(), Point::new(10.0, 0.0), Point::new(20.0, 40.0), ms(200)),
+        ])
+        .with_easing(Easing::Linear)
+        .on_complete(move || {
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+
+        assert!(!animation.sample(ms(100)));
+        assert_eq!(color.get(), Color::rgba8(200, 100, 0, 255));
+        assert_eq!(point.get(), Point::new(15.0, 20.0));
+        assert!(animation.sample(ms(250)));
+        assert!(animation.sample(ms(300)));
+        assert_eq!(point.get(), Point::new(20.0, 40.0));
+        assert_eq!(completed.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn springs_keep_velocity_when_retargeted_and_settle() {
+        let spring = Spring::new(0.0f32, SpringConfig::default());
+        spring.set_target(100.0);
+        assert!(!spring.is_at_rest());
+        let start = spring.inner.state.lock().unwrap().last_step.unwrap();
+
+        let mut now = start;
+        for _ in 0..10 {
+            now += ms(16);
+            assert!(spring.inner.step(now));
+        }
+        let (position, velocity) = (spring.get(), spring.velocity());
+        assert!(position > 0.0 && position < 100.0);
+        assert!(velocity > 0.0);
+
+        // Turning back mid-flight keeps the momentum for now
+        spring.set_target(-50.0);
+        assert_eq!(spring.velocity(), velocity);
+        now += ms(4);
+        spring.inner.step(now);
+        assert!(spring.get() > position);
+
+        let mut frames = 0;
+        loop {
+            now += ms(16);
+            if !spring.inner.step(now) {
+                break;
+            }
+            frames += 1;
+            assert!(frames < 600, "spring never settled");
+        }
+        assert!(spring.is_at_rest());
+        assert_eq!(spring.get(), -50.0);
+        assert_eq!(spring.velocity(), 0.0);
+    }
+
+    #[test]
+    fn easings_start_at_zero_and_end_at_one() {
+        for easing in [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut, Easing::EaseOutBack, Easing::EaseOutBounce] {
+            assert!(easing.apply(0.0).abs() < 1e-4, "{:?}", easing);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-4, "{:?}", easing);
+        }
+    }
+}
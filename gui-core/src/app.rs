@@ -14,6 +14,7 @@ use gui_reactive::global_frame_scheduler;
 use gui_render::{VelloRenderer, primitives::TextRenderer};
 use crate::event::{Event, MouseEvent, KeyboardEvent, Point};
 use crate::media_query::ViewportSize;
+use crate::overlay::global_overlay_layer;
 use vello::ExternalResource;
 
 #[derive(Debug)]
@@ -577,7 +578,9 @@ impl App {
         let now = Instant::now();
         let should_full_update = now.duration_since(self.last_full_update) >= Duration::from_secs(1);
         
-        if (should_full_update && self.full_update_count == 0) || needs_immediate_update {
+        let update_requested = global_frame_scheduler().take_update_request();
+        
+        if (should_full_update && self.full_update_count == 0) || needs_immediate_update || update_requested {
             println!("update all");
             // Update all widgets - widgets will mark themselves as dirty when their position/state changes
             if let Err(e) = self.widget_manager.update_all() {
@@ -625,7 +628,9 @@ impl App {
         let now = Instant::now();
         let should_full_update = now.duration_since(self.last_full_update) >= Duration::from_secs(1);
         
-        if (should_full_update && self.full_update_count == 0) || needs_immediate_update {
+        let update_requested = global_frame_scheduler().take_update_request();
+        
+        if (should_full_update && self.full_update_count == 0) || needs_immediate_update || update_requested {
             println!("update all");
             // Update all widgets - widgets will mark themselves as dirty when their position/state changes
             if let Err(e) = self.widget_manager.update_all() {
@@ -739,6 +744,15 @@ impl App {
             }
         }
         
+        // Render overlays (tooltips, popups) above the widget tree
+        if let Some(text_renderer) = &mut self.text_renderer {
+            let device = self.device.as_ref().map(|d| d.as_ref());
+            let queue = self.queue.as_ref().map(|q| q.as_ref());
+            if let Err(e) = global_overlay_layer().render(vello_renderer.scene(), text_renderer, device, queue) {
+                eprintln!("Overlay render error: {:?}", e);
+            }
+        }
+        
         // Render to surface with direct render function
         let width = surface_config.width;
         let height = surface_config.height;
@@ -806,6 +820,15 @@ impl App {
             }
         }
         
+        // Render overlays (tooltips, popups) above the widget tree
+        if let Some(text_renderer) = &mut self.text_renderer {
+            let device = self.device.as_ref().map(|d| d.as_ref());
+            let queue = self.queue.as_ref().map(|q| q.as_ref());
+            if let Err(e) = global_overlay_layer().render(vello_renderer.scene(), text_renderer, device, queue) {
+                eprintln!("Overlay render error: {:?}", e);
+            }
+        }
+        
         // Render to surface with direct render function
         let width = surface_config.width;
         let height = surface_config.height;
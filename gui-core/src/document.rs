@@ -0,0 +1,903 @@
+use crate::element::{Element, IntoElement};
+use crate::style::parse_color;
+use crate::widgets::container::{container, Padding};
+use crate::widgets::interactive::{button, button_signal, checkbox, input, slider, toggle_switch, CheckState};
+use crate::widgets::layout::{column, row, CrossAxisAlignment, MainAxisAlignment};
+use crate::widgets::text::{text, text_signal};
+use gui_reactive::Signal;
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use vello::peniko::Color;
+
+/// A screen described as data, loaded from JSON or RON at runtime:
+///
+/// ```ron
+/// (
+///     root: (
+///         type: "column",
+///         props: { "gap": 12, "size": [320, 240] },
+///         children: [
+///             (type: "text", bind: { "content": "status" }, classes: ["title"]),
+///             (type: "button", props: { "label": "Save" }, on: { "click": "save" }),
+///         ],
+///     ),
+/// )
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UiDocument {
+    pub root: UiNode,
+}
+
+/// One widget: its registered type name, literal properties, style classes,
+/// properties bound to named signals, events wired to named actions, and children
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct UiNode {
+    #[serde(rename = "type")]
+    pub widget: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub props: BTreeMap<String, UiValue>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub classes: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub bind: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub on: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<UiNode>,
+}
+
+impl UiNode {
+    pub fn new(widget: impl Into<String>) -> Self {
+        Self { widget: widget.into(), ..Self::default() }
+    }
+
+    pub fn with_prop(mut self, name: impl Into<String>, value: UiValue) -> Self {
+        self.props.insert(name.into(), value);
+        self
+    }
+
+    pub fn with_class(mut self, class: impl Into<String>) -> Self {
+        self.classes.push(class.into());
+        self
+    }
+
+    pub fn with_binding(mut self, prop: impl Into<String>, signal: impl Into<String>) -> Self {
+        self.bind.insert(prop.into(), signal.into());
+        self
+    }
+
+    pub fn with_action(mut self, event: impl Into<String>, action: impl Into<String>) -> Self {
+        self.on.insert(event.into(), action.into());
+        self
+    }
+
+    pub fn with_child(mut self, child: UiNode) -> Self {
+        self.children.push(child);
+        self
+    }
+}
+
+/// A literal property value
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum UiValue {
+    Bool(bool),
+    Number(f64),
+    String(String),
+    List(Vec<UiValue>),
+}
+
+/// What a property accepts, checked when a document is loaded
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PropKind {
+    Bool,
+    Number,
+    String,
+    /// A string in style sheet color syntax, e.g. `"#ff8800"`
+    Color,
+    /// `[width, height]`
+    Size,
+    /// One of the listed strings
+    Choice(&'static [&'static str]),
+}
+
+impl PropKind {
+    /// Describes what was expected when `value` doesn't fit
+    fn check(&self, value: &UiValue) -> Result<(), String> {
+        let matches = match (self, value) {
+            (PropKind::Bool, UiValue::Bool(_)) | (PropKind::Number, UiValue::Number(_)) | (PropKind::String, UiValue::String(_)) => true,
+            (PropKind::Color, UiValue::String(color)) => parse_color(color).is_ok(),
+            (PropKind::Size, UiValue::List(items)) => matches!(items.as_slice(), [UiValue::Number(_), UiValue::Number(_)]),
+            (PropKind::Choice(options), UiValue::String(choice)) => options.contains(&choice.as_str()),
+            _ => false,
+        };
+        if matches {
+            return Ok(());
+        }
+        Err(match self {
+            PropKind::Bool => "a boolean".to_string(),
+            PropKind::Number => "a number".to_string(),
+            PropKind::String => "a string".to_string(),
+            PropKind::Color => "a color string such as \"#ff8800\"".to_string(),
+            PropKind::Size => "a [width, height] pair".to_string(),
+            PropKind::Choice(options) => format!("one of {}", options.iter().map(|o| format!("\"{}\"", o)).collect::<Vec<_>>().join(", ")),
+        })
+    }
+}
+
+#[derive(Clone)]
+struct PropSpec {
+    name: &'static str,
+    kind: PropKind,
+    // Signal value type the property can be bound to
+    binding: Option<(TypeId, &'static str)>,
+}
+
+/// A named action, called with the new value for change events and `None` for clicks
+pub type UiAction = Arc<dyn Fn(Option<UiValue>) + Send + Sync>;
+
+type BuildFn = Arc<dyn Fn(&mut BuildContext) -> Element + Send + Sync>;
+
+/// How to construct a widget from a document node, and which properties,
+/// events, classes and children the node may have
+#[derive(Clone)]
+pub struct WidgetType {
+    build: BuildFn,
+    props: Vec<PropSpec>,
+    events: Vec<&'static str>,
+    children: bool,
+    classes: bool,
+}
+
+impl WidgetType {
+    pub fn new<F>(build: F) -> Self
+    where
+        F: Fn(&mut BuildContext) -> Element + Send + Sync + 'static,
+    {
+        Self { build: Arc::new(build), props: Vec::new(), events: Vec::new(), children: false, classes: false }
+    }
+
+    pub fn with_prop(mut self, name: &'static str, kind: PropKind) -> Self {
+        self.props.push(PropSpec { name, kind, binding: None });
+        self
+    }
+
+    /// A property that can also be bound to a `Signal<T>`
+    pub fn with_bindable_prop<T: 'static>(mut self, name: &'static str, kind: PropKind) -> Self {
+        self.props.push(PropSpec { name, kind, binding: Some((TypeId::of::<T>(), std::any::type_name::<T>())) });
+        self
+    }
+
+    pub fn with_event(mut self, name: &'static str) -> Self {
+        self.events.push(name);
+        self
+    }
+
+    pub fn with_children(mut self) -> Self {
+        self.children = true;
+        self
+    }
+
+    pub fn with_classes(mut self) -> Self {
+        self.classes = true;
+        self
+    }
+
+    fn with_size_props(self) -> Self {
+        self.with_prop("size", PropKind::Size)
+            .with_prop("width", PropKind::Number)
+            .with_prop("height", PropKind::Number)
+    }
+
+    fn prop(&self, name: &str) -> Option<&PropSpec> {
+        self.props.iter().find(|prop| prop.name == name)
+    }
+}
+
+macro_rules! apply_size {
+    ($ctx:expr, $widget:expr) => {{
+        let mut widget = $widget;
+        if let Some((width, height)) = $ctx.size("size") {
+            widget = widget.with_size(width, height);
+        }
+        if let Some(width) = $ctx.number("width") {
+            widget = widget.with_width(width);
+        }
+        if let Some(height) = $ctx.number("height") {
+            widget = widget.with_height(height);
+        }
+        widget
+    }};
+}
+
+macro_rules! apply_classes {
+    ($ctx:expr, $widget:expr) => {{
+        let mut widget = $widget;
+        for class in $ctx.classes() {
+            widget = widget.with_class(class.clone());
+        }
+        widget
+    }};
+}
+
+/// Widget types a document can name. Starts with the built-in widgets;
+/// register custom ones with `with_widget`.
+#[derive(Clone)]
+pub struct WidgetRegistry {
+    types: HashMap<String, WidgetType>,
+}
+
+impl WidgetRegistry {
+    pub fn new() -> Self {
+        Self::empty()
+            .with_widget("column", layout_type(|ctx| {
+                let mut widget = apply_size!(ctx, column().with_children(ctx.take_children()));
+                if let Some(gap) = ctx.number("gap") {
+                    widget = widget.with_gap(gap);
+                }
+                if let Some(alignment) = ctx.string("main_axis_alignment").and_then(main_axis_alignment) {
+                    widget = widget.with_main_axis_alignment(alignment);
+                }
+                if let Some(alignment) = ctx.string("cross_axis_alignment").and_then(cross_axis_alignment) {
+                    widget = widget.with_cross_axis_alignment(alignment);
+                }
+                widget.into_element()
+            }))
+            .with_widget("row", layout_type(|ctx| {
+                let mut widget = apply_size!(ctx, row().with_children(ctx.take_children()));
+                if let Some(gap) = ctx.number("gap") {
+                    widget = widget.with_gap(gap);
+                }
+                if let Some(alignment) = ctx.string("main_axis_alignment").and_then(main_axis_alignment) {
+                    widget = widget.with_main_axis_alignment(alignment);
+                }
+                if let Some(alignment) = ctx.string("cross_axis_alignment").and_then(cross_axis_alignment) {
+                    widget = widget.with_cross_axis_alignment(alignment);
+                }
+                widget.into_element()
+            }))
+            .with_widget("container", WidgetType::new(|ctx| {
+                let mut widget = apply_classes!(ctx, apply_size!(ctx, container().with_children(ctx.take_children())));
+                if let Some(color) = ctx.color("background") {
+                    widget = widget.with_background_color(color);
+                }
+                if let Some(radius) = ctx.number("border_radius") {
+                    widget = widget.with_border_radius(radius);
+                }
+                if let Some(padding) = ctx.number("padding") {
+                    widget = widget.with_padding(Padding::all(padding));
+                }
+                if let Some(color) = ctx.color("border_color") {
+                    widget = widget.with_border(ctx.number("border_width").unwrap_or(1.0), color);
+                }
+                widget.into_element()
+            })
+            .with_size_props()
+            .with_prop("background", PropKind::Color)
+            .with_prop("border_radius", PropKind::Number)
+            .with_prop("padding", PropKind::Number)
+            .with_prop("border_width", PropKind::Number)
+            .with_prop("border_color", PropKind::Color)
+            .with_children()
+            .with_classes())
+            .with_widget("text", WidgetType::new(|ctx| {
+                let widget = match ctx.signal::<String>("content") {
+                    Some(content) => text_signal(content),
+                    None => text(ctx.string("content").unwrap_or_default()),
+                };
+                let mut widget = apply_classes!(ctx, widget);
+                if let Some(size) = ctx.number("font_size") {
+                    widget = widget.with_font_size(size);
+                }
+                if let Some(weight) = ctx.number("font_weight") {
+                    widget = widget.with_font_weight(weight as u16);
+                }
+                if let Some(color) = ctx.color("color") {
+                    widget = widget.with_color(color);
+                }
+                if let Some(italic) = ctx.bool("italic") {
+                    widget = widget.with_italic(italic);
+                }
+                widget.into_element()
+            })
+            .with_bindable_prop::<String>("content", PropKind::String)
+            .with_prop("font_size", PropKind::Number)
+            .with_prop("font_weight", PropKind::Number)
+            .with_prop("color", PropKind::Color)
+            .with_prop("italic", PropKind::Bool)
+            .with_classes())
+            .with_widget("button", WidgetType::new(|ctx| {
+                let widget = match ctx.signal::<String>("label") {
+                    Some(label) => button_signal(label),
+                    None => button(ctx.string("label").unwrap_or_default()),
+                };
+                let mut widget = apply_classes!(ctx, apply_size!(ctx, widget));
+                if let Some(size) = ctx.number("font_size") {
+                    widget = widget.with_font_size(size);
+                }
+                if let Some(weight) = ctx.number("font_weight") {
+                    widget = widget.with_font_weight(weight as u16);
+                }
+                if let Some(color) = ctx.color("text_color") {
+                    widget = widget.with_text_color(color);
+                }
+                if let Some(radius) = ctx.number("border_radius") {
+                    widget = widget.with_border_radius(radius);
+                }
+                if let Some(action) = ctx.action("click") {
+                    widget = widget.on_click(move || action(None));
+                }
+                widget.into_element()
+            })
+            .with_bindable_prop::<String>("label", PropKind::String)
+            .with_size_props()
+            .with_prop("font_size", PropKind::Number)
+            .with_prop("font_weight", PropKind::Number)
+            .with_prop("text_color", PropKind::Color)
+            .with_prop("border_radius", PropKind::Number)
+            .with_event("click")
+            .with_classes())
+            .with_widget("input", WidgetType::new(|ctx| {
+                let mut widget = apply_classes!(ctx, apply_size!(ctx, input()));
+                if let Some(placeholder) = ctx.string("placeholder") {
+                    widget = widget.with_placeholder(placeholder);
+                }
+                if let Some(signal) = ctx.signal::<String>("text") {
+                    widget = widget.with_signal(signal);
+                } else if let Some(content) = ctx.string("text") {
+                    widget = widget.with_text(content);
+                }
+                if let Some(size) = ctx.number("font_size") {
+                    widget = widget.with_font_size(size);
+                }
+                if let Some(action) = ctx.action("change") {
+                    widget = widget.on_change(move |value| action(Some(UiValue::String(value.to_string()))));
+                }
+                if let Some(action) = ctx.action("submit") {
+                    widget = widget.on_submit(move |value| action(Some(UiValue::String(value.to_string()))));
+                }
+                widget.into_element()
+            })
+            .with_bindable_prop::<String>("text", PropKind::String)
+            .with_prop("placeholder", PropKind::String)
+            .with_size_props()
+            .with_prop("font_size", PropKind::Number)
+            .with_event("change")
+            .with_event("submit")
+            .with_classes())
+            .with_widget("checkbox", WidgetType::new(|ctx| {
+                let mut widget = checkbox(ctx.string("label").unwrap_or_default());
+                if let Some((width, height)) = ctx.size("size") {
+                    widget = widget.with_size(width, height);
+                }
+                if let Some(signal) = ctx.signal::<bool>("checked") {
+                    widget = widget.with_checked_signal(signal);
+                } else if let Some(checked) = ctx.bool("checked") {
+                    widget = widget.with_checked(checked);
+                }
+                if let Some(size) = ctx.number("font_size") {
+                    widget = widget.with_font_size(size);
+                }
+                if let Some(action) = ctx.action("change") {
+                    widget = widget.on_change(move |state| action(Some(UiValue::Bool(state == CheckState::Checked))));
+                }
+                widget.into_element()
+            })
+            .with_prop("label", PropKind::String)
+            .with_bindable_prop::<bool>("checked", PropKind::Bool)
+            .with_prop("size", PropKind::Size)
+            .with_prop("font_size", PropKind::Number)
+            .with_event("change"))
+            .with_widget("slider", WidgetType::new(|ctx| {
+                let widget = slider(ctx.number("min").unwrap_or(0.0), ctx.number("max").unwrap_or(1.0));
+                let mut widget = apply_size!(ctx, widget);
+                if let Some(signal) = ctx.signal::<f32>("value") {
+                    widget = widget.with_value_signal(signal);
+                } else if let Some(value) = ctx.number("value") {
+                    widget = widget.with_value(value);
+                }
+                if let Some(step) = ctx.number("step") {
+                    widget = widget.with_step(step);
+                }
+                if let Some(action) = ctx.action("change") {
+                    widget = widget.on_change(move |value| action(Some(UiValue::Number(value as f64))));
+                }
+                widget.into_element()
+            })
+            .with_prop("min", PropKind::Number)
+            .with_prop("max", PropKind::Number)
+            .with_bindable_prop::<f32>("value", PropKind::Number)
+            .with_prop("step", PropKind::Number)
+            .with_size_props()
+            .with_event("change"))
+            .with_widget("toggle_switch", WidgetType::new(|ctx| {
+                let mut widget = toggle_switch();
+                if let Some(label) = ctx.string("label") {
+                    widget = widget.with_label(label);
+                }
+                if let Some((width, height)) = ctx.size("size") {
+                    widget = widget.with_size(width, height);
+                }
+                if let Some(signal) = ctx.signal::<bool>("on") {
+                    widget = widget.with_signal(signal);
+                } else if let Some(on) = ctx.bool("on") {
+                    widget = widget.with_on(on);
+                }
+                if let Some(action) = ctx.action("change") {
+                    widget = widget.on_change(move |on| action(Some(UiValue::Bool(on))));
+                }
+                widget.into_element()
+            })
+            .with_prop("label", PropKind::String)
+            .with_bindable_prop::<bool>("on", PropKind::Bool)
+            .with_prop("size", PropKind::Size)
+            .with_event("change"))
+    }
+
+    /// A registry without the built-in widgets
+    pub fn empty() -> Self {
+        Self { types: HashMap::new() }
+    }
+
+    pub fn with_widget(mut self, name: impl Into<String>, widget_type: WidgetType) -> Self {
+        self.register(name, widget_type);
+        self
+    }
+
+    pub fn register(&mut self, name: impl Into<String>, widget_type: WidgetType) {
+        self.types.insert(name.into(), widget_type);
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.types.contains_key(name)
+    }
+}
+
+impl Default for WidgetRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const MAIN_AXIS_ALIGNMENTS: &[&str] = &["start", "end", "center", "space_between", "space_around", "space_evenly"];
+const CROSS_AXIS_ALIGNMENTS: &[&str] = &["start", "end", "center", "stretch"];
+
+fn layout_type<F>(build: F) -> WidgetType
+where
+    F: Fn(&mut BuildContext) -> Element + Send + Sync + 'static,
+{
+    WidgetType::new(build)
+        .with_size_props()
+        .with_prop("gap", PropKind::Number)
+        .with_prop("main_axis_alignment", PropKind::Choice(MAIN_AXIS_ALIGNMENTS))
+        .with_prop("cross_axis_alignment", PropKind::Choice(CROSS_AXIS_ALIGNMENTS))
+        .with_children()
+}
+
+fn main_axis_alignment(name: &str) -> Option<MainAxisAlignment> {
+    match name {
+        "start" => Some(MainAxisAlignment::Start),
+        "end" => Some(MainAxisAlignment::End),
+        "center" => Some(MainAxisAlignment::Center),
+        "space_between" => Some(MainAxisAlignment::SpaceBetween),
+        "space_around" => Some(MainAxisAlignment::SpaceAround),
+        "space_evenly" => Some(MainAxisAlignment::SpaceEvenly),
+        _ => None,
+    }
+}
+
+fn cross_axis_alignment(name: &str) -> Option<CrossAxisAlignment> {
+    match name {
+        "start" => Some(CrossAxisAlignment::Start),
+        "end" => Some(CrossAxisAlignment::End),
+        "center" => Some(CrossAxisAlignment::Center),
+        "stretch" => Some(CrossAxisAlignment::Stretch),
+        _ => None,
+    }
+}
+
+#[derive(Clone)]
+struct BoundSignal {
+    signal: Arc<dyn Any + Send + Sync>,
+    type_id: TypeId,
+    type_name: &'static str,
+}
+
+/// Everything a widget type's constructor sees of its node. Properties have
+/// already been checked against the widget type, so accessors only return
+/// `None` for properties the document left out.
+pub struct BuildContext<'a> {
+    node: &'a UiNode,
+    loader: &'a UiLoader,
+    children: Vec<Element>,
+}
+
+impl<'a> BuildContext<'a> {
+    pub fn widget_type(&self) -> &str {
+        &self.node.widget
+    }
+
+    pub fn value(&self, name: &str) -> Option<&'a UiValue> {
+        self.node.props.get(name)
+    }
+
+    pub fn number(&self, name: &str) -> Option<f32> {
+        match self.value(name)? {
+            UiValue::Number(number) => Some(*number as f32),
+            _ => None,
+        }
+    }
+
+    pub fn string(&self, name: &str) -> Option<&'a str> {
+        match self.value(name)? {
+            UiValue::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn bool(&self, name: &str) -> Option<bool> {
+        match self.value(name)? {
+            UiValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn color(&self, name: &str) -> Option<Color> {
+        parse_color(self.string(name)?).ok()
+    }
+
+    pub fn size(&self, name: &str) -> Option<(f32, f32)> {
+        match self.value(name)? {
+            UiValue::List(items) => match items.as_slice() {
+                [UiValue::Number(width), UiValue::Number(height)] => Some((*width as f32, *height as f32)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The signal `prop` is bound to, if the document binds it
+    pub fn signal<T: Clone + Send + Sync + 'static>(&self, prop: &str) -> Option<Signal<T>> {
+        self.loader.signal(self.node.bind.get(prop)?)
+    }
+
+    /// The action `event` is wired to, if the document wires it
+    pub fn action(&self, event: &str) -> Option<UiAction> {
+        self.loader.actions.get(self.node.on.get(event)?).cloned()
+    }
+
+    pub fn classes(&self) -> &'a [String] {
+        &self.node.classes
+    }
+
+    /// The node's children, already built
+    pub fn take_children(&mut self) -> Vec<Element> {
+        std::mem::take(&mut self.children)
+    }
+}
+
+/// File format of a UI document
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocumentFormat {
+    Json,
+    Ron,
+}
+
+impl DocumentFormat {
+    /// Guessed from the file extension: `.json` or `.ron`
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(Self::Json),
+            "ron" => Some(Self::Ron),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum DocumentError {
+    Io(std::io::Error),
+    UnknownFormat(PathBuf),
+    /// Syntax or validation error; validation errors point just past the offending node
+    Parse { line: usize, column: usize, message: String },
+    /// Validation error in a document built in code
+    Invalid(String),
+}
+
+impl std::fmt::Display for DocumentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DocumentError::Io(err) => write!(f, "UI document IO error: {}", err),
+            DocumentError::UnknownFormat(path) => write!(f, "UI document '{}' is neither .json nor .ron", path.display()),
+            DocumentError::Parse { line, column, message } => write!(f, "UI document error at line {}, column {}: {}", line, column, message),
+            DocumentError::Invalid(message) => write!(f, "Invalid UI document: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for DocumentError {}
+
+thread_local! {
+    // Loader whose widget types and bindings nodes are checked against while
+    // a document is being parsed, so errors carry the parser's position
+    static VALIDATING_LOADER: RefCell<Option<UiLoader>> = const { RefCell::new(None) };
+}
+
+/// Builds `Element` trees from UI documents, with the widget types they may
+/// use and the actions and signals they may refer to by name
+#[derive(Clone, Default)]
+pub struct UiLoader {
+    registry: WidgetRegistry,
+    actions: HashMap<String, UiAction>,
+    signals: HashMap<String, BoundSignal>,
+}
+
+impl UiLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_registry(mut self, registry: WidgetRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    pub fn with_widget(mut self, name: impl Into<String>, widget_type: WidgetType) -> Self {
+        self.registry.register(name, widget_type);
+        self
+    }
+
+    /// Make `name` available to `on: { "click": "name" }` and friends
+    pub fn with_action<F>(mut self, name: impl Into<String>, action: F) -> Self
+    where
+        F: Fn(Option<UiValue>) + Send + Sync + 'static,
+    {
+        self.actions.insert(name.into(), Arc::new(action));
+        self
+    }
+
+    /// Make `signal` available to `bind: { "prop": "name" }`
+    pub fn with_signal<T: Clone + Send + Sync + 'static>(mut self, name: impl Into<String>, signal: Signal<T>) -> Self {
+        self.signals.insert(name.into(), BoundSignal { signal: Arc::new(signal), type_id: TypeId::of::<T>(), type_name: std::any::type_name::<T>() });
+        self
+    }
+
+    pub fn signal<T: Clone + Send + Sync + 'static>(&self, name: &str) -> Option<Signal<T>> {
+        self.signals.get(name)?.signal.downcast_ref::<Signal<T>>().cloned()
+    }
+
+    /// Parse and validate a document without building it
+    pub fn parse(&self, source: &str, format: DocumentFormat) -> Result<UiDocument, DocumentError> {
+        let previous = VALIDATING_LOADER.with(|loader| loader.replace(Some(self.clone())));
+        let result = match format {
+            DocumentFormat::Json => serde_json::from_str(source).map_err(json_error),
+            DocumentFormat::Ron => ron::from_str(source).map_err(|err| DocumentError::Parse {
+                line: err.position.line,
+                column: err.position.col,
+                message: err.code.to_string(),
+            }),
+        };
+        VALIDATING_LOADER.with(|loader| *loader.borrow_mut() = previous);
+        result
+    }
+
+    pub fn load_str(&self, source: &str, format: DocumentFormat) -> Result<Element, DocumentError> {
+        let document = self.parse(source, format)?;
+        Ok(self.build_node(&document.root))
+    }
+
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<Element, DocumentError> {
+        let path = path.as_ref();
+        let format = DocumentFormat::from_path(path).ok_or_else(|| DocumentError::UnknownFormat(path.to_path_buf()))?;
+        let source = std::fs::read_to_string(path).map_err(DocumentError::Io)?;
+        self.load_str(&source, format)
+    }
+
+    /// Build a document assembled in code; it is validated first
+    pub fn build(&self, document: &UiDocument) -> Result<Element, DocumentError> {
+        self.check_tree(&document.root).map_err(DocumentError::Invalid)?;
+        Ok(self.build_node(&document.root))
+    }
+
+    fn build_node(&self, node: &UiNode) -> Element {
+        let children = node.children.iter().map(|child| self.build_node(child)).collect();
+        let widget_type = &self.registry.types[&node.widget];
+        let mut context = BuildContext { node, loader: self, children };
+        (widget_type.build)(&mut context)
+    }
+
+    fn check_tree(&self, node: &UiNode) -> Result<(), String> {
+        self.check_node(node)?;
+        node.children.iter().try_for_each(|child| self.check_tree(child))
+    }
+
+    /// Check one node (not its children) against the registry and bindings
+    fn check_node(&self, node: &UiNode) -> Result<(), String> {
+        let name = &node.widget;
+        let widget_type = self.registry.types.get(name).ok_or_else(|| {
+            let mut known: Vec<&str> = self.registry.types.keys().map(String::as_str).collect();
+            known.sort_unstable();
+            format!("unknown widget type `{}` (known types: {})", name, known.join(", "))
+        })?;
+
+        for (prop, value) in &node.props {
+            let spec = widget_type.prop(prop).ok_or_else(|| format!("`{}` has no property `{}`", name, prop))?;
+            spec.kind.check(value).map_err(|expected| format!("property `{}` of `{}` expects {}", prop, name, expected))?;
+        }
+
+        for (prop, signal) in &node.bind {
+            let spec = widget_type.prop(prop).ok_or_else(|| format!("`{}` has no property `{}`", name, prop))?;
+            let (type_id, type_name) = spec.binding.ok_or_else(|| format!("property `{}` of `{}` can't be bound to a signal", prop, name))?;
+            let bound = self.signals.get(signal).ok_or_else(|| format!("no signal named `{}` has been bound", signal))?;
+            if bound.type_id != type_id {
+                return Err(format!(
+                    "signal `{}` is a `Signal<{}>`, but `{}` of `{}` needs a `Signal<{}>`",
+                    signal, bound.type_name, prop, name, type_name
+                ));
+            }
+        }
+
+        for (event, action) in &node.on {
+            if !widget_type.events.contains(&event.as_str()) {
+                return Err(format!("`{}` has no event `{}`", name, event));
+            }
+            if !self.actions.contains_key(action) {
+                return Err(format!("no action named `{}` has been bound", action));
+            }
+        }
+
+        if !node.classes.is_empty() && !widget_type.classes {
+            return Err(format!("`{}` doesn't support style classes", name));
+        }
+        if !node.children.is_empty() && !widget_type.children {
+            return Err(format!("`{}` can't have children", name));
+        }
+        Ok(())
+    }
+}
+
+fn json_error(err: serde_json::Error) -> DocumentError {
+    let (line, column) = (err.line(), err.column());
+    let message = err.to_string();
+    let suffix = format!(" at line {} column {}", line, column);
+    let message = message.strip_suffix(&suffix).unwrap_or(&message).to_string();
+    DocumentError::Parse { line, column, message }
+}
+
+// Nodes are read through `RawUiNode` and then checked while the parser still
+// knows where the node ends
+#[derive(Deserialize)]
+#[serde(rename = "UiNode", deny_unknown_fields)]
+struct RawUiNode {
+    #[serde(rename = "type")]
+    widget: String,
+    #[serde(default)]
+    props: BTreeMap<String, UiValue>,
+    #[serde(default)]
+    classes: Vec<String>,
+    #[serde(default)]
+    bind: BTreeMap<String, String>,
+    #[serde(default)]
+    on: BTreeMap<String, String>,
+    #[serde(default)]
+    children: Vec<UiNode>,
+}
+
+const NODE_FIELDS: &[&str] = &["type", "props", "classes", "bind", "on", "children"];
+
+impl<'de> Deserialize<'de> for UiNode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NodeVisitor;
+
+        impl<'de> Visitor<'de> for NodeVisitor {
+            type Value = UiNode;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a UI node with a `type`")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<UiNode, A::Error> {
+                let RawUiNode { widget, props, classes, bind, on, children } =
+                    RawUiNode::deserialize(MapAccessDeserializer::new(map))?;
+                let node = UiNode { widget, props, classes, bind, on, children };
+                VALIDATING_LOADER.with(|loader| match loader.borrow().as_ref() {
+                    Some(loader) => loader.check_node(&node).map_err(<A::Error as de::Error>::custom),
+                    None => Ok(()),
+                })?;
+                Ok(node)
+            }
+        }
+
+        deserializer.deserialize_struct("UiNode", NODE_FIELDS, NodeVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn child_count(element: &Element) -> usize {
+        match element {
+            Element::Widget(_) => 0,
+            Element::Container { children, .. } | Element::Fragment(children) => children.len(),
+        }
+    }
+
+    #[test]
+    fn json_document_builds_with_bindings() {
+        let loader = UiLoader::new()
+            .with_action("save", |_| {})
+            .with_signal("status", Signal::new("Ready".to_string()));
+        let source = r#"{
+            "root": {
+                "type": "column",
+                "props": { "gap": 12, "size": [320, 240], "main_axis_alignment": "center" },
+                "children": [
+                    { "type": "text", "bind": { "content": "status" }, "classes": ["title"] },
+                    { "type": "button", "props": { "label": "Save" }, "on": { "click": "save" } }
+                ]
+            }
+        }"#;
+
+        let element = loader.load_str(source, DocumentFormat::Json).unwrap();
+        assert_eq!(child_count(&element), 2);
+    }
+
+    #[test]
+    fn validation_errors_point_at_the_node() {
+        let loader = UiLoader::new();
+        let source = "{\n  \"root\": {\n    \"type\": \"column\",\n    \"children\": [\n      { \"type\": \"text\", \"props\": { \"colour\": \"red\" } }\n    ]\n  }\n}";
+
+        match loader.parse(source, DocumentFormat::Json) {
+            Err(DocumentError::Parse { line, message, .. }) => {
+                assert_eq!(line, 5);
+                assert!(message.contains("`text` has no property `colour`"), "{}", message);
+            },
+            other => panic!("expected a validation error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn ron_signal_bindings_are_type_checked() {
+        let loader = UiLoader::new().with_signal("volume", Signal::new("loud".to_string()));
+        let source = r#"(
+    root: (
+        type: "row",
+        children: [
+            (type: "slider", props: { "max": 11 }, bind: { "value": "volume" }),
+        ],
+    ),
+)"#;
+
+        match loader.parse(source, DocumentFormat::Ron) {
+            Err(DocumentError::Parse { line, message, .. }) => {
+                assert_eq!(line, 5);
+                assert!(message.contains("needs a `Signal<f32>`"), "{}", message);
+            },
+            other => panic!("expected a validation error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn documents_round_trip_and_build_from_code() {
+        let document = UiDocument {
+            root: UiNode::new("container")
+                .with_prop("padding", UiValue::Number(8.0))
+                .with_child(UiNode::new("text").with_prop("content", UiValue::String("Hi".into()))),
+        };
+        let json = serde_json::to_string(&document).unwrap();
+        assert_eq!(serde_json::from_str::<UiDocument>(&json).unwrap(), document);
+
+        let loader = UiLoader::new();
+        assert_eq!(child_count(&loader.build(&document).unwrap()), 1);
+        let invalid = UiDocument { root: UiNode::new("text").with_child(UiNode::new("text")) };
+        assert!(matches!(loader.build(&invalid), Err(DocumentError::Invalid(_))));
+    }
+}
@@ -1,11 +1,25 @@
 use crate::{Widget, WidgetId, EventResult, WidgetError, RenderData, WidgetUpdateContext};
 use crate::event::Event;
 use crate::widgets::text::TextWidget;
-use crate::widgets::container::BoxWidget;
-use crate::widgets::interactive::{ButtonWidget, InputWidget, SliderWidget};
-use crate::widgets::layout::{ColumnWidget, RowWidget};
+use crate::widgets::container::{BoxWidget, StackWidget};
+use crate::widgets::interactive::{ButtonWidget, InputWidget, SliderWidget, CheckboxWidget, RadioGroupWidget, ToggleSwitchWidget};
+use crate::widgets::color_picker::{ColorPickerWidget, ColorSwatchWidget};
+use crate::widgets::image_view::ImageWidget;
+use crate::widgets::icon::IconWidget;
+use crate::widgets::progress::{ProgressBarWidget, SpinnerWidget};
+use crate::widgets::splitter::SplitterWidget;
+use crate::widgets::docking::DockSpaceWidget;
+use crate::widgets::number_field::NumberFieldWidget;
+use crate::widgets::multi_select::MultiSelectWidget;
+use crate::widgets::segmented::SegmentedControl;
+use crate::widgets::layout::{ColumnWidget, RowWidget, GridWidget};
 use crate::widgets::canvas::CanvasWidget;
 use crate::widgets::property_inspector::PropertyInspectorWidget;
+use crate::widgets::tooltip::{TooltipWidget, TooltipBubbleWidget};
+use crate::widgets::menu::{MenuWidget, MenuBarWidget, ContextMenuWidget};
+use crate::theme::{Theme, ThemeScopeWidget, apply_theme_to_widget};
+use crate::style::{StyleSheet, apply_style_sheet_to_widget};
+use crate::media_query::ViewportSize;
 
 use vello::Scene;
 
@@ -72,6 +86,9 @@ impl Element {
         match self {
             Element::Widget(widget) => {
                 // println!("widget update");
+                if let Some(dock_space_widget) = widget.as_any_mut().downcast_mut::<DockSpaceWidget>() {
+                    Element::position_children_for_dock_space(dock_space_widget);
+                }
                 widget.update(ctx)?;
 
                 // dont update children here, they are updated recursively in the widget.update() function
@@ -95,6 +112,32 @@ impl Element {
                     Element::position_children_for_box_static(box_widget, children);
                 } else if let Some(inspector_widget) = widget.as_any_mut().downcast_mut::<PropertyInspectorWidget>() {
                     Element::position_children_for_property_inspector(inspector_widget);
+                } else if let Some(tooltip_widget) = widget.as_any_mut().downcast_mut::<TooltipWidget>() {
+                    let (x, y) = tooltip_widget.get_position();
+                    let (width, height) = tooltip_widget.get_size();
+                    if let Some(bounds) = Element::position_wrapped_target(children, x, y, width, height) {
+                        tooltip_widget.set_size(bounds.width, bounds.height);
+                    }
+                } else if let Some(context_menu_widget) = widget.as_any_mut().downcast_mut::<ContextMenuWidget>() {
+                    let (x, y) = context_menu_widget.get_position();
+                    let (width, height) = context_menu_widget.get_size();
+                    if let Some(bounds) = Element::position_wrapped_target(children, x, y, width, height) {
+                        context_menu_widget.set_size(bounds.width, bounds.height);
+                    }
+                } else if let Some(theme_scope_widget) = widget.as_any_mut().downcast_mut::<ThemeScopeWidget>() {
+                    let (x, y) = theme_scope_widget.get_position();
+                    let (width, height) = theme_scope_widget.get_size();
+                    if let Some(bounds) = Element::position_wrapped_target(children, x, y, width, height) {
+                        theme_scope_widget.set_size(bounds.width, bounds.height);
+                    }
+                } else if let Some(splitter_widget) = widget.as_any_mut().downcast_mut::<SplitterWidget>() {
+                    splitter_widget.set_pane_count(children.len());
+                    Element::position_children_for_splitter(splitter_widget, children);
+                } else if let Some(bubble_widget) = widget.as_any().downcast_ref::<TooltipBubbleWidget>() {
+                    let (content_x, content_y, content_width, content_height) = bubble_widget.get_content_area();
+                    for child in children.iter_mut() {
+                        Element::position_child_element_static(child, content_x, content_y, content_width, content_height);
+                    }
                 }
                 
                 for child in children.iter_mut() {
@@ -124,6 +167,25 @@ impl Element {
                     }
                 }
                 
+                // Tooltips observe pointer movement before their target can consume it
+                if let Some(tooltip_widget) = widget.as_any_mut().downcast_mut::<TooltipWidget>() {
+                    tooltip_widget.observe_event(event);
+                }
+                
+                // Right clicks open the context menu instead of reaching the target
+                if let Some(context_menu_widget) = widget.as_any_mut().downcast_mut::<ContextMenuWidget>() {
+                    if let EventResult::Handled = context_menu_widget.observe_event(event) {
+                        return EventResult::Handled;
+                    }
+                }
+                
+                // Splitter dividers take drags and keyboard resizing before the panes
+                if let Some(splitter_widget) = widget.as_any_mut().downcast_mut::<SplitterWidget>() {
+                    if let EventResult::Handled = splitter_widget.observe_event(event) {
+                        return EventResult::Handled;
+                    }
+                }
+                
                 // For normal containers, process children first, then container
                 for child in children.iter_mut() {
                     match child.handle_event(event) {
@@ -147,6 +209,55 @@ impl Element {
         }
     }
     
+    /// Restyle this subtree from `theme`; theme scopes substitute their own for their children
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        match self {
+            Element::Widget(widget) => {
+                apply_theme_to_widget(widget.as_mut(), theme);
+                for child in widget.children_mut() {
+                    child.apply_theme(theme);
+                }
+            },
+            Element::Container { widget, children } => {
+                let scoped = widget.as_any().downcast_ref::<ThemeScopeWidget>().map(|scope| scope.resolve(theme));
+                let theme = scoped.as_ref().unwrap_or(theme);
+                apply_theme_to_widget(widget.as_mut(), theme);
+                for child in children.iter_mut() {
+                    child.apply_theme(theme);
+                }
+            },
+            Element::Fragment(children) => {
+                for child in children.iter_mut() {
+                    child.apply_theme(theme);
+                }
+            }
+        }
+    }
+
+    /// Restyle this subtree from the classes its widgets opted into. Runs right
+    /// after `apply_theme` so sheet rules land on top of theme defaults.
+    pub fn apply_style_sheet(&mut self, sheet: &StyleSheet, viewport: ViewportSize) {
+        match self {
+            Element::Widget(widget) => {
+                apply_style_sheet_to_widget(widget.as_mut(), sheet, viewport);
+                for child in widget.children_mut() {
+                    child.apply_style_sheet(sheet, viewport);
+                }
+            },
+            Element::Container { widget, children } => {
+                apply_style_sheet_to_widget(widget.as_mut(), sheet, viewport);
+                for child in children.iter_mut() {
+                    child.apply_style_sheet(sheet, viewport);
+                }
+            },
+            Element::Fragment(children) => {
+                for child in children.iter_mut() {
+                    child.apply_style_sheet(sheet, viewport);
+                }
+            }
+        }
+    }
+
     pub fn get_widget_by_id(&self, id: WidgetId) -> Option<&dyn Widget> {
         match self {
             Element::Widget(widget) => {
@@ -282,6 +393,49 @@ impl Element {
                         max_z_index = max_z_index.max(child_render_data.z_index);
                     }
                     
+                    Ok(RenderData {
+                        dirty_regions: all_dirty_regions,
+                        z_index: max_z_index,
+                    })
+                } else if let Some(dock_space_widget) = widget.as_any().downcast_ref::<DockSpaceWidget>() {
+                    let mut all_dirty_regions = widget_render_data.dirty_regions;
+                    let mut max_z_index = widget_render_data.z_index;
+                    
+                    for id in dock_space_widget.visible_panels(None) {
+                        if let Some(content) = dock_space_widget.panel_content(&id) {
+                            let child_render_data = content.render(scene, text_renderer, device, queue)?;
+                            all_dirty_regions.extend(child_render_data.dirty_regions);
+                            max_z_index = max_z_index.max(child_render_data.z_index);
+                        }
+                    }
+                    
+                    // Floating groups draw over the docked ones, each with its own content
+                    for index in 0..dock_space_widget.floating_count() {
+                        if let Some(shadow) = dock_space_widget.create_floating_shadow(index) {
+                            shadow.draw(scene);
+                        }
+                        for rectangle in dock_space_widget.create_chrome_rectangles(Some(index)) {
+                            rectangle.draw(scene);
+                        }
+                        for text_primitive in dock_space_widget.create_tab_texts(Some(index)) {
+                            text_primitive.draw(scene, text_renderer);
+                        }
+                        for id in dock_space_widget.visible_panels(Some(index)) {
+                            if let Some(content) = dock_space_widget.panel_content(&id) {
+                                let child_render_data = content.render(scene, text_renderer, device, queue)?;
+                                all_dirty_regions.extend(child_render_data.dirty_regions);
+                                max_z_index = max_z_index.max(child_render_data.z_index);
+                            }
+                        }
+                    }
+                    
+                    for rectangle in dock_space_widget.create_drop_preview() {
+                        rectangle.draw(scene);
+                    }
+                    if let Some(text_primitive) = dock_space_widget.create_drag_text() {
+                        text_primitive.draw(scene, text_renderer);
+                    }
+                    
                     Ok(RenderData {
                         dirty_regions: all_dirty_regions,
                         z_index: max_z_index,
@@ -290,7 +444,7 @@ impl Element {
                     let mut all_dirty_regions = widget_render_data.dirty_regions;
                     let mut max_z_index = widget_render_data.z_index;
                     
-                    for child in inspector_widget.get_children() {
+                    for child in inspector_widget.visible_children() {
                         let child_render_data = child.render(scene, text_renderer, device, queue)?;
                         all_dirty_regions.extend(child_render_data.dirty_regions);
                         max_z_index = max_z_index.max(child_render_data.z_index);
@@ -374,6 +528,12 @@ impl Element {
             let text_primitive = text_widget.create_text_primitive();
             text_primitive.draw(scene, text_renderer);
         } else if let Some(box_widget) = widget.as_any().downcast_ref::<BoxWidget>() {
+            if let Some(shadow) = box_widget.create_shadow() {
+                shadow.draw(scene);
+            }
+            if let Some(border_rect) = box_widget.create_border_rectangle() {
+                border_rect.draw(scene);
+            }
             if let Some(background_rect) = box_widget.create_background_rectangle() {
                 background_rect.draw(scene);
             }
@@ -381,9 +541,20 @@ impl Element {
             // let (x, y) = button_widget.get_position();
             // let (w, h) = button_widget.get_size();
             // println!("Rendering button at x={}, y={}, w={}, h={}", x, y, w, h);
+            if let Some(shadow) = button_widget.create_shadow() {
+                shadow.draw(scene);
+            }
+            if let Some(border_rect) = button_widget.create_border_rectangle() {
+                border_rect.draw(scene);
+            }
             let background_rect = button_widget.create_background_rectangle();
             // println!("Button background color: {:?}", button_widget.get_current_color());
             background_rect.draw(scene);
+            if let Some(icon_widget) = button_widget.get_icon() {
+                for path in icon_widget.create_path_primitives() {
+                    path.draw(scene);
+                }
+            }
             if let Some(text_primitive) = button_widget.create_text_primitive() {
                 text_primitive.draw(scene, text_renderer);
             }
@@ -392,6 +563,9 @@ impl Element {
             if let Some(shadow) = input_widget.create_shadow() {
                 shadow.draw(scene);
             }
+            if let Some(border_rect) = input_widget.create_border_rectangle() {
+                border_rect.draw(scene);
+            }
             // Render background rectangle
             let background_rect = input_widget.create_background_rectangle();
             background_rect.draw(scene);
@@ -410,6 +584,97 @@ impl Element {
             // Render fill
             let fill_rect = slider_widget.create_fill_rectangle();
             fill_rect.draw(scene);
+            for tick in slider_widget.create_tick_rectangles() {
+                tick.draw(scene);
+            }
+            for label in slider_widget.create_tick_texts() {
+                label.draw(scene, text_renderer);
+            }
+            for thumb in slider_widget.create_thumb_rectangles() {
+                thumb.draw(scene);
+            }
+            if let Some((bubble, value_text)) = slider_widget.create_value_tooltip() {
+                bubble.draw(scene);
+                value_text.draw(scene, text_renderer);
+            }
+        } else if let Some(checkbox_widget) = widget.as_any().downcast_ref::<CheckboxWidget>() {
+            for rectangle in checkbox_widget.create_box_rectangles() {
+                rectangle.draw(scene);
+            }
+            for text_primitive in checkbox_widget.create_text_primitives() {
+                text_primitive.draw(scene, text_renderer);
+            }
+        } else if let Some(radio_group_widget) = widget.as_any().downcast_ref::<RadioGroupWidget>() {
+            for rectangle in radio_group_widget.create_option_rectangles() {
+                rectangle.draw(scene);
+            }
+            for text_primitive in radio_group_widget.create_text_primitives() {
+                text_primitive.draw(scene, text_renderer);
+            }
+        } else if let Some(toggle_switch_widget) = widget.as_any().downcast_ref::<ToggleSwitchWidget>() {
+            for rectangle in toggle_switch_widget.create_rectangles() {
+                rectangle.draw(scene);
+            }
+            if let Some(text_primitive) = toggle_switch_widget.create_text_primitive() {
+                text_primitive.draw(scene, text_renderer);
+            }
+        } else if let Some(color_picker_widget) = widget.as_any().downcast_ref::<ColorPickerWidget>() {
+            if let Some(shadow) = color_picker_widget.create_shadow() {
+                shadow.draw(scene);
+            }
+            color_picker_widget.create_background_rectangle().draw(scene);
+            for rectangle in color_picker_widget.create_rectangles() {
+                rectangle.draw(scene);
+            }
+            for text_primitive in color_picker_widget.create_text_primitives() {
+                text_primitive.draw(scene, text_renderer);
+            }
+        } else if let Some(color_swatch_widget) = widget.as_any().downcast_ref::<ColorSwatchWidget>() {
+            for rectangle in color_swatch_widget.create_rectangles() {
+                rectangle.draw(scene);
+            }
+            color_swatch_widget.create_text_primitive().draw(scene, text_renderer);
+        } else if let Some(image_widget) = widget.as_any().downcast_ref::<ImageWidget>() {
+            if let Some(placeholder) = image_widget.create_placeholder_rectangle() {
+                placeholder.draw(scene);
+            }
+            if let Some(image) = image_widget.create_image_primitive() {
+                image.draw(scene);
+            }
+        } else if let Some(icon_widget) = widget.as_any().downcast_ref::<IconWidget>() {
+            for path in icon_widget.create_path_primitives() {
+                path.draw(scene);
+            }
+        } else if let Some(number_field_widget) = widget.as_any().downcast_ref::<NumberFieldWidget>() {
+            for rectangle in number_field_widget.create_rectangles() {
+                rectangle.draw(scene);
+            }
+            for text_primitive in number_field_widget.create_text_primitives() {
+                text_primitive.draw(scene, text_renderer);
+            }
+        } else if let Some(dock_space_widget) = widget.as_any().downcast_ref::<DockSpaceWidget>() {
+            for rectangle in dock_space_widget.create_chrome_rectangles(None) {
+                rectangle.draw(scene);
+            }
+            for text_primitive in dock_space_widget.create_tab_texts(None) {
+                text_primitive.draw(scene, text_renderer);
+            }
+        } else if let Some(splitter_widget) = widget.as_any().downcast_ref::<SplitterWidget>() {
+            for divider in splitter_widget.create_divider_rectangles() {
+                divider.draw(scene);
+            }
+        } else if let Some(progress_bar_widget) = widget.as_any().downcast_ref::<ProgressBarWidget>() {
+            progress_bar_widget.create_track_rectangle().draw(scene);
+            if let Some(fill_rect) = progress_bar_widget.create_fill_rectangle() {
+                fill_rect.draw(scene);
+            }
+            if let Some(text_primitive) = progress_bar_widget.create_text_primitive() {
+                text_primitive.draw(scene, text_renderer);
+            }
+        } else if let Some(spinner_widget) = widget.as_any().downcast_ref::<SpinnerWidget>() {
+            for path in spinner_widget.create_path_primitives() {
+                path.draw(scene);
+            }
         } else if let Some(canvas_widget) = widget.as_any().downcast_ref::<CanvasWidget>() {
             // Render Canvas widget with custom render function
             if let (Some(device), Some(queue)) = (device, queue) {
@@ -420,6 +685,21 @@ impl Element {
             // Render PropertyInspectorWidget background
             let background_rect = inspector_widget.create_background_rectangle();
             background_rect.draw(scene);
+            
+            if let Some(search_input) = inspector_widget.get_search_input() {
+                search_input.create_background_rectangle().draw(scene);
+                if let Some(text_primitive) = search_input.create_text_primitive() {
+                    text_primitive.draw(scene, text_renderer);
+                }
+            }
+            
+            // Match highlights, modified markers, reset buttons and collapse arrows
+            for rectangle in inspector_widget.create_decoration_rectangles() {
+                rectangle.draw(scene);
+            }
+            for text_primitive in inspector_widget.create_decoration_texts() {
+                text_primitive.draw(scene, text_renderer);
+            }
         } else if let Some(dropdown_widget) = widget.as_any().downcast_ref::<crate::widgets::dropdown::DropdownWidget>() {
             // Render DropdownWidget background
             let background_rect = dropdown_widget.create_background_rectangle();
@@ -439,16 +719,102 @@ impl Element {
                 dropdown_background.draw(scene);
             }
             
-            // Render option text
-            for (option_rect, option_text) in dropdown_widget.create_option_primitives() {
+            // Render the combobox filter field
+            if let Some(filter_input) = dropdown_widget.get_filter_input() {
+                filter_input.create_background_rectangle().draw(scene);
+                if let Some(text_primitive) = filter_input.create_text_primitive() {
+                    text_primitive.draw(scene, text_renderer);
+                }
+            }
+            
+            // Render the visible rows, with fuzzy matched characters marked behind the labels
+            let option_primitives = dropdown_widget.create_option_primitives();
+            for (option_rect, _) in &option_primitives {
                 option_rect.draw(scene);
+            }
+            for match_rect in dropdown_widget.create_match_rectangles() {
+                match_rect.draw(scene);
+            }
+            for (_, option_text) in option_primitives {
                 option_text.draw(scene, text_renderer);
             }
+            if let Some(empty_text) = dropdown_widget.create_empty_text() {
+                empty_text.draw(scene, text_renderer);
+            }
+            if let Some(scrollbar) = dropdown_widget.create_scrollbar_rectangle() {
+                scrollbar.draw(scene);
+            }
+        } else if let Some(multi_select_widget) = widget.as_any().downcast_ref::<MultiSelectWidget>() {
+            multi_select_widget.create_background_rectangle().draw(scene);
+            if let Some(summary) = multi_select_widget.create_summary_text() {
+                summary.draw(scene, text_renderer);
+            }
+            for (chip, label) in multi_select_widget.create_chip_primitives() {
+                chip.draw(scene);
+                label.draw(scene, text_renderer);
+            }
+            multi_select_widget.create_arrow_primitive().draw(scene, text_renderer);
+            
+            if let Some(dropdown_background) = multi_select_widget.create_dropdown_background() {
+                dropdown_background.draw(scene);
+            }
+            for rectangle in multi_select_widget.create_option_rectangles() {
+                rectangle.draw(scene);
+            }
+            for text_primitive in multi_select_widget.create_option_texts() {
+                text_primitive.draw(scene, text_renderer);
+            }
+        } else if let Some(segmented_control) = widget.as_any().downcast_ref::<SegmentedControl>() {
+            for rectangle in segmented_control.create_rectangles() {
+                rectangle.draw(scene);
+            }
+            for icon_widget in segmented_control.get_icons() {
+                for path in icon_widget.create_path_primitives() {
+                    path.draw(scene);
+                }
+            }
+            for label in segmented_control.create_label_texts() {
+                label.draw(scene, text_renderer);
+            }
+        } else if let Some(bubble_widget) = widget.as_any().downcast_ref::<TooltipBubbleWidget>() {
+            if let Some(shadow) = bubble_widget.create_shadow() {
+                shadow.draw(scene);
+            }
+            bubble_widget.create_background_rectangle().draw(scene);
+            if let Some(text_primitive) = bubble_widget.create_text_primitive() {
+                text_primitive.draw(scene, text_renderer);
+            }
+        } else if let Some(menu_widget) = widget.as_any().downcast_ref::<MenuWidget>() {
+            Element::render_menu(menu_widget, scene, text_renderer);
+        } else if let Some(menu_bar_widget) = widget.as_any().downcast_ref::<MenuBarWidget>() {
+            menu_bar_widget.create_background_rectangle().draw(scene);
+            for (highlight, title) in menu_bar_widget.create_title_primitives() {
+                if let Some(highlight) = highlight {
+                    highlight.draw(scene);
+                }
+                title.draw(scene, text_renderer);
+            }
         }
         
         Ok(render_data)
     }
     
+    fn render_menu(menu_widget: &MenuWidget, scene: &mut Scene, text_renderer: &mut gui_render::primitives::TextRenderer) {
+        if let Some(shadow) = menu_widget.create_shadow() {
+            shadow.draw(scene);
+        }
+        menu_widget.create_background_rectangle().draw(scene);
+        for rectangle in menu_widget.create_row_rectangles() {
+            rectangle.draw(scene);
+        }
+        for text_primitive in menu_widget.create_text_primitives() {
+            text_primitive.draw(scene, text_renderer);
+        }
+        // Open submenus draw above their parent
+        if let Some(submenu) = menu_widget.open_submenu() {
+            Element::render_menu(submenu, scene, text_renderer);
+        }
+    }
     
     fn position_children_for_box_static(box_widget: &BoxWidget, children: &mut Vec<Element>) {
         if children.is_empty() {
@@ -684,11 +1050,12 @@ impl Element {
         // PropertyInspectorWidget manages its own internal layout
         // We just need to position the children within the inspector's content area
         let content_x = inspector_x + padding.left;
-        let mut current_y = inspector_y + padding.top;
+        let content_y = inspector_y + padding.top;
+        let mut current_y = content_y;
         
-        let header_height = inspector_widget.get_header_height();
         let row_height = inspector_widget.get_row_height();
         let content_width = inspector_width - padding.left - padding.right;
+        let child_offsets = inspector_widget.get_child_offsets().to_vec();
 
         let children = &mut inspector_widget.children;
 
@@ -701,7 +1068,11 @@ impl Element {
         // Position children based on PropertyInspector's internal structure
         // Headers and property rows are arranged vertically
         println!("position child prop insp");
-        for child in children.iter_mut() {
+        for (index, child) in children.iter_mut().enumerate() {
+            // Rows of different heights are laid out where the inspector placed them
+            if let Some(offset) = child_offsets.get(index) {
+                current_y = content_y + offset;
+            }
             Element::position_child_element_static(child, content_x, current_y, content_width, row_height);
             
             // Check if this child is a header or a property row and advance accordingly
@@ -718,6 +1089,74 @@ impl Element {
         }
     }
     
+    /// Size and place each pane of a splitter in its slot
+    fn position_children_for_splitter(splitter_widget: &SplitterWidget, children: &mut [Element]) {
+        for (child, (x, y, width, height)) in children.iter_mut().zip(splitter_widget.pane_rects()) {
+            Element::resize_child_element(child, width, height);
+            Element::position_child_element_static(child, x, y, width, height);
+        }
+    }
+    
+    /// Size and place the active panel of every dock group in its content area
+    fn position_children_for_dock_space(dock_space_widget: &mut DockSpaceWidget) {
+        for (id, (x, y, width, height)) in dock_space_widget.visible_panel_rects() {
+            if let Some(content) = dock_space_widget.panel_content_mut(&id) {
+                Element::resize_child_element(content, width, height);
+                Element::position_child_element_static(content, x, y, width, height);
+            }
+        }
+    }
+    
+    /// Resize widgets that fill the space given to them, such as splitter panes
+    fn resize_child_element(child: &mut Element, width: f32, height: f32) {
+        use crate::widgets::{container::BoxWidget, layout::{ColumnWidget, RowWidget}, canvas::CanvasWidget};
+        
+        let widget = match child {
+            Element::Widget(widget) => widget,
+            Element::Container { widget, .. } => widget,
+            Element::Fragment(_) => return,
+        };
+        if let Some(box_widget) = widget.as_any_mut().downcast_mut::<BoxWidget>() {
+            box_widget.set_size(width, height);
+        } else if let Some(column_widget) = widget.as_any_mut().downcast_mut::<ColumnWidget>() {
+            column_widget.set_size(width, height);
+        } else if let Some(row_widget) = widget.as_any_mut().downcast_mut::<RowWidget>() {
+            row_widget.set_size(width, height);
+        } else if let Some(canvas_widget) = widget.as_any_mut().downcast_mut::<CanvasWidget>() {
+            canvas_widget.set_size(width, height);
+        } else if let Some(splitter_widget) = widget.as_any_mut().downcast_mut::<SplitterWidget>() {
+            splitter_widget.set_size(width, height);
+        } else if let Some(dock_space_widget) = widget.as_any_mut().downcast_mut::<DockSpaceWidget>() {
+            dock_space_widget.set_size(width, height);
+        } else if let Some(number_field_widget) = widget.as_any_mut().downcast_mut::<NumberFieldWidget>() {
+            number_field_widget.set_size(width, height);
+        } else if let Some(multi_select_widget) = widget.as_any_mut().downcast_mut::<MultiSelectWidget>() {
+            multi_select_widget.set_size(width, height);
+        } else if let Some(segmented_control) = widget.as_any_mut().downcast_mut::<SegmentedControl>() {
+            segmented_control.set_size(width, height);
+        }
+    }
+    
+    /// Position the target of a wrapper widget (tooltip, context menu) at the wrapper's
+    /// position and return the target's bounds, which the wrapper adopts so its hit
+    /// tests match what is drawn
+    fn position_wrapped_target(children: &mut [Element], x: f32, y: f32, width: f32, height: f32) -> Option<crate::DirtyRegion> {
+        for child in children.iter_mut() {
+            Element::position_child_element_static(child, x, y, width, height);
+        }
+        children.first().and_then(Element::bounds)
+    }
+    
+    /// Bounds of the element's own widget, taken from the region it reports when rendering
+    pub fn bounds(&self) -> Option<crate::DirtyRegion> {
+        match self {
+            Element::Widget(widget) | Element::Container { widget, .. } => {
+                widget.render().ok().and_then(|render_data| render_data.dirty_regions.into_iter().next())
+            },
+            Element::Fragment(_) => None,
+        }
+    }
+    
     fn position_child_element_static(child: &mut Element, x: f32, y: f32, _width: f32, _height: f32) {
         // println!("Positioning child at x={}, y={}", x, y);
         match child {
@@ -755,6 +1194,48 @@ impl Element {
                 } else if let Some(dropdown_widget) = widget.as_any_mut().downcast_mut::<crate::widgets::dropdown::DropdownWidget>() {
                     dropdown_widget.set_position(x, y);
                     dropdown_widget.dirty = true;
+                } else if let Some(menu_bar_widget) = widget.as_any_mut().downcast_mut::<MenuBarWidget>() {
+                    menu_bar_widget.set_position(x, y);
+                    menu_bar_widget.dirty = true;
+                } else if let Some(checkbox_widget) = widget.as_any_mut().downcast_mut::<CheckboxWidget>() {
+                    checkbox_widget.set_position(x, y);
+                    checkbox_widget.dirty = true;
+                } else if let Some(radio_group_widget) = widget.as_any_mut().downcast_mut::<RadioGroupWidget>() {
+                    radio_group_widget.set_position(x, y);
+                    radio_group_widget.dirty = true;
+                } else if let Some(toggle_switch_widget) = widget.as_any_mut().downcast_mut::<ToggleSwitchWidget>() {
+                    toggle_switch_widget.set_position(x, y);
+                    toggle_switch_widget.dirty = true;
+                } else if let Some(color_picker_widget) = widget.as_any_mut().downcast_mut::<ColorPickerWidget>() {
+                    color_picker_widget.set_position(x, y);
+                    color_picker_widget.dirty = true;
+                } else if let Some(color_swatch_widget) = widget.as_any_mut().downcast_mut::<ColorSwatchWidget>() {
+                    color_swatch_widget.set_position(x, y);
+                    color_swatch_widget.dirty = true;
+                } else if let Some(image_widget) = widget.as_any_mut().downcast_mut::<ImageWidget>() {
+                    image_widget.set_position(x, y);
+                    image_widget.dirty = true;
+                } else if let Some(icon_widget) = widget.as_any_mut().downcast_mut::<IconWidget>() {
+                    icon_widget.set_position(x, y);
+                    icon_widget.dirty = true;
+                } else if let Some(progress_bar_widget) = widget.as_any_mut().downcast_mut::<ProgressBarWidget>() {
+                    progress_bar_widget.set_position(x, y);
+                    progress_bar_widget.dirty = true;
+                } else if let Some(spinner_widget) = widget.as_any_mut().downcast_mut::<SpinnerWidget>() {
+                    spinner_widget.set_position(x, y);
+                    spinner_widget.dirty = true;
+                } else if let Some(dock_space_widget) = widget.as_any_mut().downcast_mut::<DockSpaceWidget>() {
+                    dock_space_widget.set_position(x, y);
+                    dock_space_widget.dirty = true;
+                } else if let Some(number_field_widget) = widget.as_any_mut().downcast_mut::<NumberFieldWidget>() {
+                    number_field_widget.set_position(x, y);
+                    number_field_widget.dirty = true;
+                } else if let Some(multi_select_widget) = widget.as_any_mut().downcast_mut::<MultiSelectWidget>() {
+                    multi_select_widget.set_position(x, y);
+                    multi_select_widget.dirty = true;
+                } else if let Some(segmented_control) = widget.as_any_mut().downcast_mut::<SegmentedControl>() {
+                    segmented_control.set_position(x, y);
+                    segmented_control.dirty = true;
                 }
             },
             Element::Container { widget, .. } => {
@@ -785,6 +1266,18 @@ impl Element {
                 } else if let Some(dropdown_widget) = widget.as_any_mut().downcast_mut::<crate::widgets::dropdown::DropdownWidget>() {
                     dropdown_widget.set_position(x, y);
                     dropdown_widget.dirty = true;
+                } else if let Some(tooltip_widget) = widget.as_any_mut().downcast_mut::<TooltipWidget>() {
+                    tooltip_widget.set_position(x, y);
+                    tooltip_widget.dirty = true;
+                } else if let Some(context_menu_widget) = widget.as_any_mut().downcast_mut::<ContextMenuWidget>() {
+                    context_menu_widget.set_position(x, y);
+                    context_menu_widget.dirty = true;
+                } else if let Some(theme_scope_widget) = widget.as_any_mut().downcast_mut::<ThemeScopeWidget>() {
+                    theme_scope_widget.set_position(x, y);
+                    theme_scope_widget.dirty = true;
+                } else if let Some(splitter_widget) = widget.as_any_mut().downcast_mut::<SplitterWidget>() {
+                    splitter_widget.set_position(x, y);
+                    splitter_widget.dirty = true;
                 }
             },
             Element::Fragment(_) => {
@@ -838,6 +1331,48 @@ impl Element {
                 } else if let Some(dropdown_widget) = widget.as_any_mut().downcast_mut::<crate::widgets::dropdown::DropdownWidget>() {
                     dropdown_widget.set_position(x, y);
                     dropdown_widget.dirty = true;
+                } else if let Some(menu_bar_widget) = widget.as_any_mut().downcast_mut::<MenuBarWidget>() {
+                    menu_bar_widget.set_position(x, y);
+                    menu_bar_widget.dirty = true;
+                } else if let Some(checkbox_widget) = widget.as_any_mut().downcast_mut::<CheckboxWidget>() {
+                    checkbox_widget.set_position(x, y);
+                    checkbox_widget.dirty = true;
+                } else if let Some(radio_group_widget) = widget.as_any_mut().downcast_mut::<RadioGroupWidget>() {
+                    radio_group_widget.set_position(x, y);
+                    radio_group_widget.dirty = true;
+                } else if let Some(toggle_switch_widget) = widget.as_any_mut().downcast_mut::<ToggleSwitchWidget>() {
+                    toggle_switch_widget.set_position(x, y);
+                    toggle_switch_widget.dirty = true;
+                } else if let Some(color_picker_widget) = widget.as_any_mut().downcast_mut::<ColorPickerWidget>() {
+                    color_picker_widget.set_position(x, y);
+                    color_picker_widget.dirty = true;
+                } else if let Some(color_swatch_widget) = widget.as_any_mut().downcast_mut::<ColorSwatchWidget>() {
+                    color_swatch_widget.set_position(x, y);
+                    color_swatch_widget.dirty = true;
+                } else if let Some(image_widget) = widget.as_any_mut().downcast_mut::<ImageWidget>() {
+                    image_widget.set_position(x, y);
+                    image_widget.dirty = true;
+                } else if let Some(icon_widget) = widget.as_any_mut().downcast_mut::<IconWidget>() {
+                    icon_widget.set_position(x, y);
+                    icon_widget.dirty = true;
+                } else if let Some(progress_bar_widget) = widget.as_any_mut().downcast_mut::<ProgressBarWidget>() {
+                    progress_bar_widget.set_position(x, y);
+                    progress_bar_widget.dirty = true;
+                } else if let Some(spinner_widget) = widget.as_any_mut().downcast_mut::<SpinnerWidget>() {
+                    spinner_widget.set_position(x, y);
+                    spinner_widget.dirty = true;
+                } else if let Some(dock_space_widget) = widget.as_any_mut().downcast_mut::<DockSpaceWidget>() {
+                    dock_space_widget.set_position(x, y);
+                    dock_space_widget.dirty = true;
+                } else if let Some(number_field_widget) = widget.as_any_mut().downcast_mut::<NumberFieldWidget>() {
+                    number_field_widget.set_position(x, y);
+                    number_field_widget.dirty = true;
+                } else if let Some(multi_select_widget) = widget.as_any_mut().downcast_mut::<MultiSelectWidget>() {
+                    multi_select_widget.set_position(x, y);
+                    multi_select_widget.dirty = true;
+                } else if let Some(segmented_control) = widget.as_any_mut().downcast_mut::<SegmentedControl>() {
+                    segmented_control.set_position(x, y);
+                    segmented_control.dirty = true;
                 }
             },
             Element::Container { widget, .. } => {
@@ -883,6 +1418,18 @@ impl Element {
                 } else if let Some(dropdown_widget) = widget.as_any_mut().downcast_mut::<crate::widgets::dropdown::DropdownWidget>() {
                     dropdown_widget.set_position(x, y);
                     dropdown_widget.dirty = true;
+                } else if let Some(tooltip_widget) = widget.as_any_mut().downcast_mut::<TooltipWidget>() {
+                    tooltip_widget.set_position(x, y);
+                    tooltip_widget.dirty = true;
+                } else if let Some(context_menu_widget) = widget.as_any_mut().downcast_mut::<ContextMenuWidget>() {
+                    context_menu_widget.set_position(x, y);
+                    context_menu_widget.dirty = true;
+                } else if let Some(theme_scope_widget) = widget.as_any_mut().downcast_mut::<ThemeScopeWidget>() {
+                    theme_scope_widget.set_position(x, y);
+                    theme_scope_widget.dirty = true;
+                } else if let Some(splitter_widget) = widget.as_any_mut().downcast_mut::<SplitterWidget>() {
+                    splitter_widget.set_position(x, y);
+                    splitter_widget.dirty = true;
                 }
             },
             Element::Fragment(_) => {
@@ -940,4 +1487,49 @@ impl Element {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Anything `ui!` can place in the tree: finished elements and the widget builders
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be used as a UI element",
+    label = "not a widget or `Element`",
+    note = "`ui!` children must be widgets or `Element`s; wrap other values in a widget, e.g. `text(value.to_string())`"
+)]
+pub trait IntoElement {
+    fn into_element(self) -> Element;
+}
+
+impl IntoElement for Element {
+    fn into_element(self) -> Element {
+        self
+    }
+}
+
+macro_rules! impl_into_element {
+    (container: $($container:ty),*; widget: $($widget:ty),* $(,)?) => {
+        $(
+            impl IntoElement for $container {
+                fn into_element(self) -> Element {
+                    self.into_container_element()
+                }
+            }
+        )*
+        $(
+            impl IntoElement for $widget {
+                fn into_element(self) -> Element {
+                    Element::new_widget(Box::new(self))
+                }
+            }
+        )*
+    };
+}
+
+impl_into_element!(
+    container: BoxWidget, ColumnWidget, RowWidget, SplitterWidget, PropertyInspectorWidget;
+    widget: TextWidget, ButtonWidget, InputWidget, SliderWidget, CheckboxWidget, RadioGroupWidget,
+        ToggleSwitchWidget, ColorPickerWidget, ColorSwatchWidget, ImageWidget, IconWidget,
+        ProgressBarWidget, SpinnerWidget, DockSpaceWidget, NumberFieldWidget, MultiSelectWidget,
+        SegmentedControl, CanvasWidget, MenuBarWidget, MenuWidget, crate::widgets::dropdown::DropdownWidget,
+        StackWidget, GridWidget,
+);
+
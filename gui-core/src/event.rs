@@ -192,6 +192,93 @@ impl EventDispatcher {
     }
 }
 
+/// A key combination bound to a command, e.g. Ctrl+S
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shortcut {
+    pub key: KeyCode,
+    pub modifiers: ModifiersState,
+}
+
+impl Shortcut {
+    pub fn new(key: KeyCode) -> Self {
+        Self {
+            key,
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    pub fn ctrl(key: KeyCode) -> Self {
+        Self::new(key).with_ctrl()
+    }
+
+    pub fn with_ctrl(mut self) -> Self {
+        self.modifiers |= ModifiersState::CONTROL;
+        self
+    }
+
+    pub fn with_shift(mut self) -> Self {
+        self.modifiers |= ModifiersState::SHIFT;
+        self
+    }
+
+    pub fn with_alt(mut self) -> Self {
+        self.modifiers |= ModifiersState::ALT;
+        self
+    }
+
+    pub fn with_super(mut self) -> Self {
+        self.modifiers |= ModifiersState::SUPER;
+        self
+    }
+
+    pub fn matches(&self, event: &KeyboardEvent) -> bool {
+        event.state == ElementState::Pressed &&
+        event.key_code == Some(self.key) &&
+        event.modifiers == self.modifiers
+    }
+
+    /// Human readable form used for accelerator hints, e.g. "Ctrl+Shift+S"
+    pub fn label(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        if self.modifiers.control_key() {
+            parts.push("Ctrl".to_string());
+        }
+        if self.modifiers.alt_key() {
+            parts.push("Alt".to_string());
+        }
+        if self.modifiers.shift_key() {
+            parts.push("Shift".to_string());
+        }
+        if self.modifiers.super_key() {
+            parts.push("Super".to_string());
+        }
+        parts.push(Self::key_label(self.key));
+        parts.join("+")
+    }
+
+    fn key_label(key: KeyCode) -> String {
+        let name = format!("{:?}", key);
+        match key {
+            KeyCode::ArrowUp => "Up".to_string(),
+            KeyCode::ArrowDown => "Down".to_string(),
+            KeyCode::ArrowLeft => "Left".to_string(),
+            KeyCode::ArrowRight => "Right".to_string(),
+            KeyCode::Escape => "Esc".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Equal => "=".to_string(),
+            KeyCode::Minus => "-".to_string(),
+            KeyCode::Comma => ",".to_string(),
+            KeyCode::Period => ".".to_string(),
+            KeyCode::Slash => "/".to_string(),
+            // KeyA -> A, Digit1 -> 1
+            _ => name.strip_prefix("Key")
+                .or_else(|| name.strip_prefix("Digit"))
+                .map(str::to_string)
+                .unwrap_or(name.clone()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FocusDirection {
     Next,
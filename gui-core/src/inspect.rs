@@ -0,0 +1,241 @@
+use crate::widgets::property_inspector::{PropertyDefinition, PropertyGroup, PropertyType, PropertyValue};
+use vello::peniko::Color;
+
+/// Types that describe themselves to the property inspector.
+///
+/// Usually derived with `#[derive(Inspect)]`:
+///
+/// ```ignore
+/// #[derive(Inspect)]
+/// #[inspect(group = "Transform")]
+/// struct Transform {
+///     position: [f32; 3],
+///     #[inspect(label = "Rotation", range(min = 0.0, max = 360.0), unit = "deg")]
+///     angle: f32,
+///     #[inspect(group = "Rendering", options("opaque", "blend", "additive"))]
+///     blend_mode: String,
+///     #[inspect(hidden)]
+///     cache_key: u64,
+/// }
+/// ```
+///
+/// Field attributes: `label = ".."`, `group = ".."`, `range(min = .., max = ..)`,
+/// `step = ..` (ranges only), `unit = ".."`, `options(".." , ..)` (String fields),
+/// `read_only` and `hidden`. A struct-level `group` names the default group,
+/// which is otherwise the struct name.
+pub trait Inspect {
+    fn inspector_groups(&self) -> Vec<PropertyGroup>;
+
+    /// Write an edited value back onto the matching field. Returns false when the
+    /// key is unknown, the field is not editable or the value has the wrong type.
+    fn apply_property(&mut self, key: &str, value: &PropertyValue) -> bool;
+}
+
+/// Field types the `Inspect` derive knows how to show and edit
+pub trait InspectValue: Sized {
+    fn to_property_value(&self) -> PropertyValue;
+
+    fn from_property_value(value: &PropertyValue) -> Option<Self>;
+
+    fn property_type() -> PropertyType;
+}
+
+/// Append `definition` to the group titled `group`, creating it in order of first use
+#[doc(hidden)]
+pub fn push_property(groups: &mut Vec<PropertyGroup>, group: &str, definition: PropertyDefinition) {
+    match groups.iter_mut().find(|existing| existing.title == group) {
+        Some(existing) => existing.properties.push(definition),
+        None => groups.push(PropertyGroup::new(group).with_property(definition)),
+    }
+}
+
+macro_rules! impl_inspect_number {
+    (@convert $n:expr, f32) => { $n };
+    (@convert $n:expr, f64) => { $n as f64 };
+    (@convert $n:expr, $ty:ident) => { $n.round() as $ty };
+    ($($ty:ident),*) => {
+        $(
+            impl InspectValue for $ty {
+                fn to_property_value(&self) -> PropertyValue {
+                    PropertyValue::Number(*self as f32)
+                }
+
+                fn from_property_value(value: &PropertyValue) -> Option<Self> {
+                    match value {
+                        PropertyValue::Number(n) if n.is_finite() => Some(impl_inspect_number!(@convert *n, $ty)),
+                        _ => None,
+                    }
+                }
+
+                fn property_type() -> PropertyType {
+                    PropertyType::NumberInput
+                }
+            }
+        )*
+    };
+}
+
+impl_inspect_number!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl InspectValue for bool {
+    fn to_property_value(&self) -> PropertyValue {
+        PropertyValue::Boolean(*self)
+    }
+
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    fn property_type() -> PropertyType {
+        PropertyType::Checkbox
+    }
+}
+
+impl InspectValue for String {
+    fn to_property_value(&self) -> PropertyValue {
+        PropertyValue::Text(self.clone())
+    }
+
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Text(s) | PropertyValue::Select(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+
+    fn property_type() -> PropertyType {
+        PropertyType::TextInput
+    }
+}
+
+impl InspectValue for Color {
+    fn to_property_value(&self) -> PropertyValue {
+        PropertyValue::Color(*self)
+    }
+
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Color(c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    fn property_type() -> PropertyType {
+        PropertyType::ColorPicker
+    }
+}
+
+macro_rules! impl_inspect_vector {
+    ($($len:literal => $variant:ident),*) => {
+        $(
+            impl InspectValue for [f32; $len] {
+                fn to_property_value(&self) -> PropertyValue {
+                    PropertyValue::$variant(*self)
+                }
+
+                fn from_property_value(value: &PropertyValue) -> Option<Self> {
+                    match value {
+                        PropertyValue::$variant(v) => Some(*v),
+                        _ => None,
+                    }
+                }
+
+                fn property_type() -> PropertyType {
+                    PropertyType::$variant
+                }
+            }
+        )*
+    };
+}
+
+impl_inspect_vector!(2 => Vec2, 3 => Vec3, 4 => Vec4);
+
+impl InspectValue for Option<String> {
+    fn to_property_value(&self) -> PropertyValue {
+        PropertyValue::Asset(self.clone())
+    }
+
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Asset(path) => Some(path.clone()),
+            _ => None,
+        }
+    }
+
+    fn property_type() -> PropertyType {
+        PropertyType::Asset(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::number_field::NumberUnit;
+
+    #[derive(crate::Inspect)]
+    #[inspect(group = "Transform")]
+    struct Sample {
+        position: [f32; 2],
+        #[inspect(label = "Angle", range(min = 0.0, max = 360.0), step = 15.0, unit = "deg")]
+        rotation: f32,
+        #[inspect(group = "Rendering", options("opaque", "blend"))]
+        blend_mode: String,
+        #[inspect(group = "Rendering")]
+        layer_count: u32,
+        #[inspect(read_only)]
+        name: String,
+        #[inspect(hidden)]
+        #[allow(dead_code)]
+        cache_key: u64,
+    }
+
+    fn sample() -> Sample {
+        Sample {
+            position: [1.0, 2.0],
+            rotation: 90.0,
+            blend_mode: "opaque".to_string(),
+            layer_count: 3,
+            name: "Player".to_string(),
+            cache_key: 7,
+        }
+    }
+
+    #[test]
+    fn derive_builds_groups() {
+        let groups = sample().inspector_groups();
+        let titles: Vec<&str> = groups.iter().map(|group| group.title.as_str()).collect();
+        assert_eq!(titles, ["Transform", "Rendering"]);
+
+        let keys: Vec<&str> = groups[0].properties.iter().map(|property| property.key.as_str()).collect();
+        assert_eq!(keys, ["position", "rotation", "name"]);
+
+        let rotation = &groups[0].properties[1];
+        assert_eq!(rotation.label, "Angle");
+        assert_eq!(rotation.unit, NumberUnit::Degrees);
+        assert!(matches!(rotation.property_type, PropertyType::Range { min, max, step } if min == 0.0 && max == 360.0 && step == 15.0));
+
+        let blend_mode = &groups[1].properties[0];
+        assert_eq!(blend_mode.label, "Blend Mode");
+        assert!(matches!(&blend_mode.value, PropertyValue::Select(s) if s == "opaque"));
+        assert!(matches!(&blend_mode.property_type, PropertyType::Dropdown(options) if options.len() == 2));
+    }
+
+    #[test]
+    fn derive_applies_changes() {
+        let mut target = sample();
+        assert!(target.apply_property("rotation", &PropertyValue::Number(45.0)));
+        assert!(target.apply_property("layer_count", &PropertyValue::Number(4.6)));
+        assert!(target.apply_property("blend_mode", &PropertyValue::Select("blend".to_string())));
+        assert!(!target.apply_property("rotation", &PropertyValue::Boolean(true)));
+        assert!(!target.apply_property("name", &PropertyValue::Text("Enemy".to_string())));
+        assert!(!target.apply_property("cache_key", &PropertyValue::Number(1.0)));
+
+        assert_eq!(target.rotation, 45.0);
+        assert_eq!(target.layer_count, 5);
+        assert_eq!(target.blend_mode, "blend");
+        assert_eq!(target.name, "Player");
+    }
+}
@@ -1,3 +1,6 @@
+// Lets macro-generated `::gui_core::` paths resolve inside this crate too
+extern crate self as gui_core;
+
 pub mod widget;
 pub mod element;
 pub mod widget_manager;
@@ -7,13 +10,26 @@ pub mod event;
 pub mod widgets;
 pub mod media_query;
 pub mod sizing;
+pub mod overlay;
+pub mod inspect;
+pub mod theme;
+pub mod style;
+pub mod animation;
+pub mod document;
 
 pub use widget::{Widget, WidgetId, EventResult, WidgetError, RenderData, DirtyRegion, WidgetUpdateContext};
-pub use element::Element;
+pub use element::{Element, IntoElement};
 pub use widget_manager::WidgetManager;
 pub use widget_state::{WidgetStateManager, StateHandle, ComputedHandle, EffectHandle, StatefulWidget, WidgetStateContext};
 pub use app::App;
-pub use event::Event;
+pub use event::{Event, Shortcut};
 pub use widgets::*;
 pub use media_query::*;
-pub use sizing::*;
\ No newline at end of file
+pub use sizing::*;
+pub use overlay::{OverlayLayer, OverlayId, global_overlay_layer};
+pub use theme::*;
+pub use style::*;
+pub use animation::*;
+pub use document::*;
+pub use inspect::{Inspect, InspectValue};
+pub use gui_macros::{Inspect, ui};
\ No newline at end of file
@@ -0,0 +1,252 @@
+use crate::{WidgetId, EventResult, WidgetError, RenderData, WidgetUpdateContext};
+use crate::event::Event;
+use crate::element::Element;
+use vello::Scene;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+pub type OverlayId = u64;
+
+struct OverlayEntry {
+    id: OverlayId,
+    owner: Option<WidgetId>,
+    z_index: i32,
+    element: Element,
+}
+
+/// Top-level layer rendered after the root element tree.
+///
+/// Popups such as tooltips and menus live here so they draw above (and are never
+/// laid out or clipped by) the widget that opened them. Entries receive events
+/// before the root tree, topmost first.
+pub struct OverlayLayer {
+    entries: Mutex<Vec<OverlayEntry>>,
+    // Ids of the entries checked out for event dispatch or update
+    checked_out: Mutex<Vec<OverlayId>>,
+    // Removals requested for checked-out entries, applied when they are checked back in
+    pending_removals: Mutex<Vec<OverlayId>>,
+    next_id: AtomicU64,
+}
+
+impl OverlayLayer {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(Vec::new()),
+            checked_out: Mutex::new(Vec::new()),
+            pending_removals: Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Add an element to the overlay. The caller owns the element's mount lifecycle.
+    pub fn push(&self, element: Element, z_index: i32) -> OverlayId {
+        self.push_with_owner(element, z_index, None)
+    }
+
+    pub fn push_with_owner(&self, element: Element, z_index: i32, owner: Option<WidgetId>) -> OverlayId {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.push(OverlayEntry { id, owner, z_index, element });
+            // Keep entries sorted so the topmost entry renders last
+            entries.sort_by_key(|entry| entry.z_index);
+        }
+        id
+    }
+
+    /// Remove an entry and hand its element back to the caller.
+    ///
+    /// If the entry is currently being dispatched to (e.g. a menu closing itself from
+    /// its own click handler) it is dropped once dispatch finishes and `None` is returned.
+    /// Unknown ids are ignored.
+    pub fn remove(&self, id: OverlayId) -> Option<Element> {
+        let Ok(mut entries) = self.entries.lock() else {
+            return None;
+        };
+        if let Some(index) = entries.iter().position(|entry| entry.id == id) {
+            return Some(entries.remove(index).element);
+        }
+        // Checked under the entries lock, so a check-in can't slip in between
+        let checked_out = self.checked_out.lock().is_ok_and(|checked_out| checked_out.contains(&id));
+        if checked_out {
+            if let Ok(mut pending) = self.pending_removals.lock() {
+                if !pending.contains(&id) {
+                    pending.push(id);
+                }
+            }
+        }
+        None
+    }
+
+    /// Remove every entry opened by the given widget
+    pub fn remove_owned_by(&self, owner: WidgetId) {
+        let ids: Vec<OverlayId> = match self.entries.lock() {
+            Ok(entries) => entries.iter()
+                .filter(|entry| entry.owner == Some(owner))
+                .map(|entry| entry.id)
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        for id in ids {
+            self.remove(id);
+        }
+    }
+
+    pub fn contains(&self, id: OverlayId) -> bool {
+        self.entries.lock()
+            .map(|entries| entries.iter().any(|entry| entry.id == id))
+            .unwrap_or(false)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().map(|entries| entries.is_empty()).unwrap_or(true)
+    }
+
+    pub fn with_element_mut<F, R>(&self, id: OverlayId, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut Element) -> R,
+    {
+        let mut entries = self.entries.lock().ok()?;
+        entries.iter_mut()
+            .find(|entry| entry.id == id)
+            .map(|entry| f(&mut entry.element))
+    }
+
+    pub fn update(&self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        let mut entries = self.check_out();
+        let mut result = Ok(());
+        for entry in entries.iter_mut() {
+            if let Err(e) = entry.element.update(ctx) {
+                result = Err(e);
+                break;
+            }
+        }
+        self.check_in(entries);
+        result
+    }
+
+    /// Restyle every overlay's element tree from `theme`
+    pub fn apply_theme(&self, theme: &crate::theme::Theme) {
+        let mut entries = self.check_out();
+        for entry in entries.iter_mut() {
+            entry.element.apply_theme(theme);
+        }
+        self.check_in(entries);
+    }
+
+    /// Restyle every overlay's element tree from the style sheet
+    pub fn apply_style_sheet(&self, sheet: &crate::style::StyleSheet, viewport: crate::media_query::ViewportSize) {
+        let mut entries = self.check_out();
+        for entry in entries.iter_mut() {
+            entry.element.apply_style_sheet(sheet, viewport);
+        }
+        self.check_in(entries);
+    }
+
+    pub fn handle_event(&self, event: &Event) -> EventResult {
+        let mut entries = self.check_out();
+        let mut result = EventResult::Ignored;
+        // Topmost entries get the first chance to handle the event
+        for entry in entries.iter_mut().rev() {
+            match entry.element.handle_event(event) {
+                EventResult::Handled => {
+                    result = EventResult::Handled;
+                    break;
+                },
+                EventResult::Propagate => continue,
+                EventResult::Ignored => continue,
+            }
+        }
+        self.check_in(entries);
+        result
+    }
+
+    pub fn render(&self, scene: &mut Scene, text_renderer: &mut gui_render::primitives::TextRenderer, device: Option<&wgpu::Device>, queue: Option<&wgpu::Queue>) -> Result<RenderData, WidgetError> {
+        let mut all_dirty_regions = Vec::new();
+        let mut max_z_index = 0;
+
+        if let Ok(entries) = self.entries.lock() {
+            for entry in entries.iter() {
+                let render_data = entry.element.render(scene, text_renderer, device, queue)?;
+                all_dirty_regions.extend(render_data.dirty_regions);
+                max_z_index = max_z_index.max(entry.z_index.max(render_data.z_index));
+            }
+        }
+
+        Ok(RenderData {
+            dirty_regions: all_dirty_regions,
+            z_index: max_z_index,
+        })
+    }
+
+    // Entries are taken out of the lock while widgets run so that handlers may push or
+    // remove overlay entries without deadlocking
+    fn check_out(&self) -> Vec<OverlayEntry> {
+        let Ok(mut live) = self.entries.lock() else {
+            return Vec::new();
+        };
+        let entries = std::mem::take(&mut *live);
+        if let Ok(mut checked_out) = self.checked_out.lock() {
+            checked_out.extend(entries.iter().map(|entry| entry.id));
+        }
+        entries
+    }
+
+    fn check_in(&self, mut entries: Vec<OverlayEntry>) {
+        let Ok(mut current) = self.entries.lock() else {
+            return;
+        };
+        let returned: Vec<OverlayId> = entries.iter().map(|entry| entry.id).collect();
+        if let Ok(mut checked_out) = self.checked_out.lock() {
+            checked_out.retain(|id| !returned.contains(id));
+        }
+        if let Ok(mut pending) = self.pending_removals.lock() {
+            entries.retain(|entry| !pending.contains(&entry.id));
+            pending.retain(|id| !returned.contains(id));
+        }
+
+        // Anything pushed during dispatch was added to the (empty) live list
+        entries.extend(current.drain(..));
+        entries.sort_by_key(|entry| entry.z_index);
+        *current = entries;
+    }
+}
+
+impl Default for OverlayLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static GLOBAL_OVERLAY_LAYER: std::sync::OnceLock<OverlayLayer> = std::sync::OnceLock::new();
+
+pub fn global_overlay_layer() -> &'static OverlayLayer {
+    GLOBAL_OVERLAY_LAYER.get_or_init(OverlayLayer::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::text::text;
+
+    #[test]
+    fn removal_during_dispatch_is_deferred_and_unknown_ids_are_ignored() {
+        let layer = OverlayLayer::new();
+        let first = layer.push(Element::new_widget(Box::new(text("first"))), 0);
+        let second = layer.push(Element::new_widget(Box::new(text("second"))), 0);
+
+        assert!(layer.remove(first).is_some());
+        assert!(layer.remove(first).is_none());
+        assert!(layer.remove(99).is_none());
+        assert!(layer.pending_removals.lock().unwrap().is_empty());
+
+        let entries = layer.check_out();
+        assert!(layer.remove(second).is_none());
+        assert_eq!(*layer.pending_removals.lock().unwrap(), [second]);
+        layer.check_in(entries);
+
+        assert!(!layer.contains(second));
+        assert!(layer.is_empty());
+        assert!(layer.pending_removals.lock().unwrap().is_empty());
+        assert!(layer.checked_out.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,685 @@
+use crate::Widget;
+use crate::media_query::{MediaQuery, ViewportSize};
+use crate::theme::{invalidate_theme, ShadowToken};
+use crate::widgets::container::{BoxWidget, Padding};
+use crate::widgets::interactive::{ButtonWidget, InputWidget};
+use crate::widgets::text::TextWidget;
+use gui_reactive::Signal;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+use vello::peniko::Color;
+
+/// Interaction state a rule can be limited to, written `.class:hover` etc.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StyleState {
+    Hover,
+    Pressed,
+    Focused,
+    Disabled,
+}
+
+impl StyleState {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "hover" => Some(Self::Hover),
+            "pressed" | "active" => Some(Self::Pressed),
+            "focused" | "focus" => Some(Self::Focused),
+            "disabled" => Some(Self::Disabled),
+            _ => None,
+        }
+    }
+}
+
+/// A set of style properties. Unset properties fall through to less specific
+/// rules, then to the theme.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Style {
+    pub background: Option<Color>,
+    pub border_color: Option<Color>,
+    pub border_width: Option<f32>,
+    pub border_radius: Option<f32>,
+    pub padding: Option<Padding>,
+    pub font_size: Option<f32>,
+    pub font_weight: Option<u16>,
+    pub text_color: Option<Color>,
+    pub shadow: Option<ShadowToken>,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    pub fn with_border(mut self, width: f32, color: Color) -> Self {
+        self.border_width = Some(width);
+        self.border_color = Some(color);
+        self
+    }
+
+    pub fn with_border_radius(mut self, radius: f32) -> Self {
+        self.border_radius = Some(radius);
+        self
+    }
+
+    pub fn with_padding(mut self, padding: Padding) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.font_size = Some(size);
+        self
+    }
+
+    pub fn with_font_weight(mut self, weight: u16) -> Self {
+        self.font_weight = Some(weight);
+        self
+    }
+
+    pub fn with_text_color(mut self, color: Color) -> Self {
+        self.text_color = Some(color);
+        self
+    }
+
+    pub fn with_shadow(mut self, shadow: ShadowToken) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+
+    /// Layer `other` on top: its set properties replace ours
+    pub fn merge(&mut self, other: &Style) {
+        fn layer<T: Clone>(into: &mut Option<T>, from: &Option<T>) {
+            if from.is_some() {
+                *into = from.clone();
+            }
+        }
+        layer(&mut self.background, &other.background);
+        layer(&mut self.border_color, &other.border_color);
+        layer(&mut self.border_width, &other.border_width);
+        layer(&mut self.border_radius, &other.border_radius);
+        layer(&mut self.padding, &other.padding);
+        layer(&mut self.font_size, &other.font_size);
+        layer(&mut self.font_weight, &other.font_weight);
+        layer(&mut self.text_color, &other.text_color);
+        layer(&mut self.shadow, &other.shadow);
+    }
+
+    /// Border as (width, color), when a rule gave it a color
+    pub fn border(&self) -> Option<(f32, Color)> {
+        self.border_color.map(|color| (self.border_width.unwrap_or(1.0), color))
+    }
+}
+
+/// One selector block: a class, optionally narrowed to a state and a viewport
+#[derive(Clone, Debug)]
+pub struct StyleRule {
+    pub class: String,
+    pub state: Option<StyleState>,
+    pub media: Option<MediaQuery>,
+    pub style: Style,
+}
+
+impl StyleRule {
+    pub fn new(class: impl Into<String>, style: Style) -> Self {
+        Self {
+            class: class.into(),
+            state: None,
+            media: None,
+            style,
+        }
+    }
+
+    pub fn with_state(mut self, state: StyleState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    pub fn with_media(mut self, media: MediaQuery) -> Self {
+        self.media = Some(media);
+        self
+    }
+
+    fn applies_to(&self, classes: &[String], viewport: ViewportSize) -> bool {
+        classes.contains(&self.class)
+            && self.media.as_ref().is_none_or(|media| media.matches(viewport))
+    }
+}
+
+/// A class's style resolved for every state a widget can be in
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResolvedStyle {
+    pub normal: Style,
+    pub hover: Style,
+    pub pressed: Style,
+    pub focused: Style,
+    pub disabled: Style,
+}
+
+/// Named styles that widgets opt into with `with_class`
+#[derive(Clone, Debug, Default)]
+pub struct StyleSheet {
+    rules: Vec<StyleRule>,
+}
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rule(mut self, rule: StyleRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn add_rule(&mut self, rule: StyleRule) {
+        self.rules.push(rule);
+    }
+
+    /// Append another sheet's rules, which then win over ours
+    pub fn extend(&mut self, other: StyleSheet) {
+        self.rules.extend(other.rules);
+    }
+
+    pub fn rules(&self) -> &[StyleRule] {
+        &self.rules
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether resolving depends on the viewport at all
+    pub fn has_media_rules(&self) -> bool {
+        self.rules.iter().any(|rule| rule.media.is_some())
+    }
+
+    /// Parse the CSS-like sheet format:
+    ///
+    /// ```text
+    /// .primary { background: #3b82f6; color: #fff; border-radius: 6; }
+    /// .primary:hover { background: #2563eb; }
+    /// @media (max-width: 767) { .primary { font-size: 12; padding: 4 8; } }
+    /// ```
+    pub fn parse(source: &str) -> Result<Self, StyleError> {
+        Parser { source, pos: 0 }.parse_sheet()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, StyleError> {
+        let source = std::fs::read_to_string(path).map_err(StyleError::Io)?;
+        Self::parse(&source)
+    }
+
+    /// Style for `classes` in `state` (None for the resting state). Rules are
+    /// layered in source order, and state rules go on top of plain ones.
+    pub fn resolve(&self, classes: &[String], state: Option<StyleState>, viewport: ViewportSize) -> Style {
+        let mut style = Style::default();
+        if classes.is_empty() {
+            return style;
+        }
+        let matching = move || self.rules.iter().filter(move |rule| rule.applies_to(classes, viewport));
+        for rule in matching().filter(|rule| rule.state.is_none()) {
+            style.merge(&rule.style);
+        }
+        if state.is_some() {
+            for rule in matching().filter(|rule| rule.state == state) {
+                style.merge(&rule.style);
+            }
+        }
+        style
+    }
+
+    pub fn resolve_all(&self, classes: &[String], viewport: ViewportSize) -> ResolvedStyle {
+        ResolvedStyle {
+            normal: self.resolve(classes, None, viewport),
+            hover: self.resolve(classes, Some(StyleState::Hover), viewport),
+            pressed: self.resolve(classes, Some(StyleState::Pressed), viewport),
+            focused: self.resolve(classes, Some(StyleState::Focused), viewport),
+            disabled: self.resolve(classes, Some(StyleState::Disabled), viewport),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum StyleError {
+    Io(std::io::Error),
+    Parse { line: usize, message: String },
+}
+
+impl std::fmt::Display for StyleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StyleError::Io(err) => write!(f, "Style sheet IO error: {}", err),
+            StyleError::Parse { line, message } => write!(f, "Style sheet error on line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for StyleError {}
+
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    fn line(&self) -> usize {
+        self.source[..self.pos].matches('\n').count() + 1
+    }
+
+    fn error(&self, message: impl Into<String>) -> StyleError {
+        StyleError::Parse { line: self.line(), message: message.into() }
+    }
+
+    fn skip_whitespace(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if trimmed.starts_with("/*") {
+                match trimmed.find("*/") {
+                    Some(end) => self.pos += end + 2,
+                    None => self.pos = self.source.len(),
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Text up to `delimiter`, which is consumed
+    fn read_until(&mut self, delimiter: char) -> Result<&'a str, StyleError> {
+        let rest = self.rest();
+        match rest.find(delimiter) {
+            Some(end) => {
+                self.pos += end + delimiter.len_utf8();
+                Ok(rest[..end].trim())
+            },
+            None => Err(self.error(format!("expected '{}'", delimiter))),
+        }
+    }
+
+    fn parse_sheet(&mut self) -> Result<StyleSheet, StyleError> {
+        let mut sheet = StyleSheet::new();
+        loop {
+            self.skip_whitespace();
+            if self.rest().is_empty() {
+                break;
+            }
+            if let Some(query) = self.rest().strip_prefix("@media") {
+                self.pos += self.rest().len() - query.len();
+                let line = self.line();
+                let media = parse_media_query(self.read_until('{')?)
+                    .map_err(|message| StyleError::Parse { line, message })?;
+                loop {
+                    self.skip_whitespace();
+                    if self.rest().is_empty() {
+                        return Err(self.error("unclosed @media block"));
+                    }
+                    if self.rest().starts_with('}') {
+                        self.pos += 1;
+                        break;
+                    }
+                    for rule in self.parse_rule()? {
+                        sheet.add_rule(rule.with_media(media.clone()));
+                    }
+                }
+            } else {
+                for rule in self.parse_rule()? {
+                    sheet.add_rule(rule);
+                }
+            }
+        }
+        Ok(sheet)
+    }
+
+    fn parse_rule(&mut self) -> Result<Vec<StyleRule>, StyleError> {
+        let line = self.line();
+        let at_line = |message: String| StyleError::Parse { line, message };
+        let selectors = self.read_until('{')?;
+        if selectors.contains('}') {
+            return Err(at_line("unexpected '}'".to_string()));
+        }
+        let style = parse_declarations(self.read_until('}')?).map_err(at_line)?;
+        selectors
+            .split(',')
+            .map(|selector| -> Result<StyleRule, StyleError> {
+                let (class, state) = parse_selector(selector.trim()).map_err(at_line)?;
+                let rule = StyleRule::new(class, style.clone());
+                Ok(match state {
+                    Some(state) => rule.with_state(state),
+                    None => rule,
+                })
+            })
+            .collect()
+    }
+}
+
+fn parse_selector(selector: &str) -> Result<(String, Option<StyleState>), String> {
+    let body = selector
+        .strip_prefix('.')
+        .ok_or_else(|| format!("selector '{}' must be a class like .name", selector))?;
+    let (class, state) = match body.split_once(':') {
+        Some((class, state)) => {
+            let state = StyleState::from_name(state.trim())
+                .ok_or_else(|| format!("unknown state ':{}'", state.trim()))?;
+            (class, Some(state))
+        },
+        None => (body, None),
+    };
+    let valid = !class.is_empty() && class.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("invalid class name '{}'", class));
+    }
+    Ok((class.to_string(), state))
+}
+
+fn parse_media_query(query: &str) -> Result<MediaQuery, String> {
+    let mut media = MediaQuery::new();
+    for condition in query.split(" and ") {
+        let condition = condition.trim().trim_start_matches('(').trim_end_matches(')');
+        let (feature, value) = condition
+            .split_once(':')
+            .ok_or_else(|| format!("expected (feature: value) in @media, got '{}'", condition))?;
+        let value = parse_number(value)? as u32;
+        media = match feature.trim() {
+            "min-width" => media.min_width(value),
+            "max-width" => media.max_width(value),
+            "min-height" => media.min_height(value),
+            "max-height" => media.max_height(value),
+            other => return Err(format!("unknown media feature '{}'", other)),
+        };
+    }
+    Ok(media)
+}
+
+fn parse_declarations(body: &str) -> Result<Style, String> {
+    let mut style = Style::default();
+    for declaration in body.split(';').map(str::trim).filter(|d| !d.is_empty()) {
+        let (property, value) = declaration
+            .split_once(':')
+            .ok_or_else(|| format!("expected 'property: value', got '{}'", declaration))?;
+        let value = value.trim();
+        match property.trim() {
+            "background" | "background-color" => style.background = Some(parse_color(value)?),
+            "border" => {
+                let (width, color) = value
+                    .split_once(char::is_whitespace)
+                    .ok_or_else(|| format!("border needs a width and a color, got '{}'", value))?;
+                style.border_width = Some(parse_number(width)?);
+                style.border_color = Some(parse_color(color.trim())?);
+            },
+            "border-color" => style.border_color = Some(parse_color(value)?),
+            "border-width" => style.border_width = Some(parse_number(value)?),
+            "border-radius" => style.border_radius = Some(parse_number(value)?),
+            "padding" => style.padding = Some(parse_padding(value)?),
+            "font-size" => style.font_size = Some(parse_number(value)?),
+            "font-weight" => style.font_weight = Some(match value {
+                "normal" => 400,
+                "bold" => 700,
+                _ => parse_number(value)? as u16,
+            }),
+            "color" | "text-color" => style.text_color = Some(parse_color(value)?),
+            "shadow" | "box-shadow" => style.shadow = Some(parse_shadow(value)?),
+            other => return Err(format!("unknown property '{}'", other)),
+        }
+    }
+    Ok(style)
+}
+
+fn parse_number(value: &str) -> Result<f32, String> {
+    let value = value.trim();
+    value
+        .strip_suffix("px")
+        .unwrap_or(value)
+        .parse::<f32>()
+        .map_err(|_| format!("expected a number, got '{}'", value))
+}
+
+/// One to four values, in CSS order
+fn parse_padding(value: &str) -> Result<Padding, String> {
+    let values = value.split_whitespace().map(parse_number).collect::<Result<Vec<_>, _>>()?;
+    match values.as_slice() {
+        [all] => Ok(Padding::all(*all)),
+        [vertical, horizontal] => Ok(Padding::symmetric(*vertical, *horizontal)),
+        [top, horizontal, bottom] => Ok(Padding::only(*top, *horizontal, *bottom, *horizontal)),
+        [top, right, bottom, left] => Ok(Padding::only(*top, *right, *bottom, *left)),
+        _ => Err(format!("padding takes one to four values, got '{}'", value)),
+    }
+}
+
+/// `offset-x offset-y blur color`
+fn parse_shadow(value: &str) -> Result<ShadowToken, String> {
+    let mut rest = value.trim();
+    let mut numbers = [0.0; 3];
+    for number in numbers.iter_mut() {
+        let (head, tail) = rest
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| format!("shadow needs 'offset-x offset-y blur color', got '{}'", value))?;
+        *number = parse_number(head)?;
+        rest = tail.trim_start();
+    }
+    let [offset_x, offset_y, blur_radius] = numbers;
+    Ok(ShadowToken { offset_x, offset_y, blur_radius, color: parse_color(rest)? })
+}
+
+/// `#rgb`, `#rrggbb`, `#rrggbbaa`, `rgb(r, g, b)`, `rgba(r, g, b, a)` with
+/// alpha in 0..1, or `transparent`
+pub(crate) fn parse_color(value: &str) -> Result<Color, String> {
+    let invalid = || format!("invalid color '{}'", value);
+    if value == "transparent" {
+        return Ok(Color::rgba8(0, 0, 0, 0));
+    }
+    if let Some(hex) = value.strip_prefix('#') {
+        let channel = |i: usize, len: usize| {
+            hex.get(i * len..(i + 1) * len)
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                .map(|c| if len == 1 { c * 17 } else { c })
+                .ok_or_else(invalid)
+        };
+        return match hex.len() {
+            3 => Ok(Color::rgba8(channel(0, 1)?, channel(1, 1)?, channel(2, 1)?, 255)),
+            6 => Ok(Color::rgba8(channel(0, 2)?, channel(1, 2)?, channel(2, 2)?, 255)),
+            8 => Ok(Color::rgba8(channel(0, 2)?, channel(1, 2)?, channel(2, 2)?, channel(3, 2)?)),
+            _ => Err(invalid()),
+        };
+    }
+    let (args, has_alpha) = if let Some(args) = value.strip_prefix("rgba(") {
+        (args, true)
+    } else if let Some(args) = value.strip_prefix("rgb(") {
+        (args, false)
+    } else {
+        return Err(invalid());
+    };
+    let components = args
+        .strip_suffix(')')
+        .ok_or_else(invalid)?
+        .split(',')
+        .map(|c| c.trim().parse::<f32>().map_err(|_| invalid()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let byte = |c: f32| c.clamp(0.0, 255.0).round() as u8;
+    match (components.as_slice(), has_alpha) {
+        ([r, g, b], false) => Ok(Color::rgba8(byte(*r), byte(*g), byte(*b), 255)),
+        ([r, g, b, a], true) => Ok(Color::rgba8(byte(*r), byte(*g), byte(*b), byte(a * 255.0))),
+        _ => Err(invalid()),
+    }
+}
+
+static GLOBAL_STYLE_SHEET: OnceLock<Signal<StyleSheet>> = OnceLock::new();
+
+/// App-wide style sheet that `with_class` names are looked up in
+pub fn global_style_sheet() -> &'static Signal<StyleSheet> {
+    GLOBAL_STYLE_SHEET.get_or_init(|| {
+        let sheet = Signal::new(StyleSheet::new());
+        // Restyling rides on the theme pass
+        sheet.subscribe_fn(|_| invalidate_theme());
+        sheet
+    })
+}
+
+pub fn current_style_sheet() -> StyleSheet {
+    global_style_sheet().get()
+}
+
+pub fn set_style_sheet(sheet: StyleSheet) {
+    global_style_sheet().set(sheet);
+}
+
+/// Parse `path` and make it the global style sheet
+pub fn load_style_sheet(path: impl AsRef<Path>) -> Result<(), StyleError> {
+    set_style_sheet(StyleSheet::load(path)?);
+    Ok(())
+}
+
+// Stop flags of the running style sheet watchers, one per file
+static STYLE_SHEET_WATCHERS: OnceLock<Mutex<HashMap<PathBuf, Arc<AtomicBool>>>> = OnceLock::new();
+
+fn style_sheet_watchers() -> &'static Mutex<HashMap<PathBuf, Arc<AtomicBool>>> {
+    STYLE_SHEET_WATCHERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn watcher_key(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Load `path`, then reload it whenever the file changes on disk so styles
+/// can be tweaked while the app runs. A sheet that fails to parse is reported
+/// and the previous one stays in place. Watching a file that is already
+/// watched only reloads it.
+pub fn watch_style_sheet(path: impl Into<PathBuf>) -> Result<(), StyleError> {
+    let path = path.into();
+    load_style_sheet(&path)?;
+    let stop = Arc::new(AtomicBool::new(false));
+    match style_sheet_watchers().lock() {
+        Ok(mut watchers) if !watchers.contains_key(&watcher_key(&path)) => {
+            watchers.insert(watcher_key(&path), stop.clone());
+        },
+        _ => return Ok(()),
+    }
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|meta| meta.modified()).ok();
+    let mut last_modified: Option<SystemTime> = modified(&path);
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_millis(500));
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        let current = modified(&path);
+        if current.is_none() || current == last_modified {
+            continue;
+        }
+        last_modified = current;
+        if let Err(e) = load_style_sheet(&path) {
+            eprintln!("Failed to reload style sheet {}: {}", path.display(), e);
+        }
+    });
+    Ok(())
+}
+
+/// Stop reloading `path`; the sheet it last loaded stays in place
+pub fn unwatch_style_sheet(path: impl AsRef<Path>) {
+    if let Ok(mut watchers) = style_sheet_watchers().lock() {
+        if let Some(stop) = watchers.remove(&watcher_key(path.as_ref())) {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Restyle a single widget from the classes it opted into
+///
+/// Only buttons, inputs, text and boxes take classes (`with_class`) so far;
+/// every other widget is styled by the theme alone and is skipped here.
+pub fn apply_style_sheet_to_widget(widget: &mut dyn Widget, sheet: &StyleSheet, viewport: ViewportSize) {
+    let any = widget.as_any_mut();
+    if let Some(button_widget) = any.downcast_mut::<ButtonWidget>() {
+        button_widget.apply_style_sheet(sheet, viewport);
+    } else if let Some(input_widget) = any.downcast_mut::<InputWidget>() {
+        input_widget.apply_style_sheet(sheet, viewport);
+    } else if let Some(text_widget) = any.downcast_mut::<TextWidget>() {
+        text_widget.apply_style_sheet(sheet, viewport);
+    } else if let Some(box_widget) = any.downcast_mut::<BoxWidget>() {
+        box_widget.apply_style_sheet(sheet, viewport);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHEET: &str = "
+        /* buttons */
+        .primary, .accent { background: #3b82f6; color: #fff; border: 1 #1d4ed8; padding: 4 8; }
+        .primary:hover { background: rgba(37, 99, 235, 1); }
+        @media (max-width: 767px) {
+            .primary { font-size: 12; font-weight: bold; }
+        }
+    ";
+
+    fn classes(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_rules_states_and_media() {
+        let sheet = StyleSheet::parse(SHEET).unwrap();
+        assert_eq!(sheet.rules().len(), 4);
+
+        let wide = ViewportSize { width: 1024.0, height: 768.0 };
+        let narrow = ViewportSize { width: 600.0, height: 800.0 };
+        let primary = classes(&["primary"]);
+
+        let normal = sheet.resolve(&primary, None, wide);
+        assert_eq!(normal.background, Some(Color::rgba8(0x3b, 0x82, 0xf6, 255)));
+        assert_eq!(normal.text_color, Some(Color::rgba8(255, 255, 255, 255)));
+        assert_eq!(normal.border(), Some((1.0, Color::rgba8(0x1d, 0x4e, 0xd8, 255))));
+        assert_eq!(normal.padding.map(|p| (p.top, p.left)), Some((4.0, 8.0)));
+        assert_eq!(normal.font_size, None);
+
+        let hover = sheet.resolve(&primary, Some(StyleState::Hover), narrow);
+        assert_eq!(hover.background, Some(Color::rgba8(37, 99, 235, 255)));
+        assert_eq!(hover.font_size, Some(12.0));
+        assert_eq!(hover.font_weight, Some(700));
+
+        // `.accent` shares the first rule only
+        let accent = sheet.resolve(&classes(&["accent"]), Some(StyleState::Hover), narrow);
+        assert_eq!(accent.background, Some(Color::rgba8(0x3b, 0x82, 0xf6, 255)));
+        assert_eq!(accent.font_size, None);
+    }
+
+    #[test]
+    fn reports_errors_with_line_numbers() {
+        match StyleSheet::parse(".ok { color: #000; }\n.bad { colour: red; }") {
+            Err(StyleError::Parse { line, .. }) => assert_eq!(line, 2),
+            other => panic!("expected a parse error, got {:?}", other.map(|sheet| sheet.rules().len())),
+        }
+        assert!(StyleSheet::parse(".x:wiggle { color: #000; }").is_err());
+        assert!(StyleSheet::parse("@media (max-width: 10) { .x { color: #000; }").is_err());
+    }
+
+    #[test]
+    fn watching_a_file_twice_keeps_one_watcher() {
+        let path = std::env::temp_dir().join(format!("commonui-watch-{}.css", std::process::id()));
+        std::fs::write(&path, "").unwrap();
+        let watching = || style_sheet_watchers().lock().unwrap().contains_key(&watcher_key(&path));
+
+        watch_style_sheet(&path).unwrap();
+        let stop = style_sheet_watchers().lock().unwrap()[&watcher_key(&path)].clone();
+        watch_style_sheet(&path).unwrap();
+        assert!(Arc::ptr_eq(&stop, &style_sheet_watchers().lock().unwrap()[&watcher_key(&path)]));
+
+        unwatch_style_sheet(&path);
+        assert!(!watching());
+        assert!(stop.load(Ordering::Relaxed));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,522 @@
+use crate::{Widget, WidgetId, EventResult, WidgetError, RenderData, DirtyRegion, WidgetUpdateContext};
+use crate::event::Event;
+use crate::widgets::interactive::{ButtonWidget, InputWidget, SliderWidget, CheckboxWidget, RadioGroupWidget, ToggleSwitchWidget};
+use crate::widgets::text::TextWidget;
+use crate::widgets::dropdown::DropdownWidget;
+use crate::widgets::multi_select::MultiSelectWidget;
+use crate::widgets::segmented::SegmentedControl;
+use crate::widgets::number_field::NumberFieldWidget;
+use crate::widgets::menu::{MenuWidget, MenuBarWidget, ContextMenuWidget};
+use crate::widgets::tooltip::{TooltipWidget, TooltipBubbleWidget};
+use crate::widgets::color_picker::{ColorPickerWidget, ColorSwatchWidget};
+use crate::widgets::image_view::ImageWidget;
+use crate::widgets::icon::IconWidget;
+use crate::widgets::progress::{ProgressBarWidget, SpinnerWidget};
+use crate::widgets::splitter::SplitterWidget;
+use crate::widgets::docking::DockSpaceWidget;
+use crate::widgets::property_inspector::PropertyInspectorWidget;
+use gui_reactive::Signal;
+use gui_render::primitives::Shadow;
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use vello::peniko::Color;
+
+static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(17000);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThemeMode {
+    Light,
+    Dark,
+}
+
+/// Color tokens. Widgets map their parts onto these rather than picking colors.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Palette {
+    pub primary: Color,
+    pub primary_hover: Color,
+    pub primary_pressed: Color,
+    /// Text and icons drawn on `primary`
+    pub on_primary: Color,
+    pub background: Color,
+    /// Fields, boxes and popups
+    pub surface: Color,
+    pub surface_hover: Color,
+    pub border: Color,
+    pub text: Color,
+    pub text_muted: Color,
+    pub disabled: Color,
+    /// Highlighted rows and selected text
+    pub selection: Color,
+    pub danger: Color,
+    pub warning: Color,
+    pub success: Color,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Typography {
+    pub font_size_small: f32,
+    pub font_size: f32,
+    pub font_size_large: f32,
+    pub font_size_heading: f32,
+    pub font_weight: u16,
+    pub font_weight_bold: u16,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spacing {
+    pub xs: f32,
+    pub small: f32,
+    pub medium: f32,
+    pub large: f32,
+    pub xl: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Radii {
+    pub small: f32,
+    pub medium: f32,
+    pub large: f32,
+    /// Pills and round thumbs
+    pub full: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShadowToken {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub blur_radius: f32,
+    pub color: Color,
+}
+
+impl ShadowToken {
+    /// A shadow with this token's offset, blur and color. Widgets place it over
+    /// their own bounds when drawing.
+    pub fn to_shadow(&self) -> Shadow {
+        Shadow::new(0.0, 0.0, 0.0, 0.0, self.offset_x, self.offset_y, self.blur_radius, self.color)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shadows {
+    pub small: ShadowToken,
+    pub medium: ShadowToken,
+    pub large: ShadowToken,
+}
+
+/// Design tokens every themed widget takes its default look from
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    pub mode: ThemeMode,
+    pub palette: Palette,
+    pub typography: Typography,
+    pub spacing: Spacing,
+    pub radii: Radii,
+    pub shadows: Shadows,
+}
+
+impl Theme {
+    pub fn light() -> Self {
+        Self {
+            mode: ThemeMode::Light,
+            palette: Palette {
+                primary: Color::rgba8(100, 150, 255, 255),
+                primary_hover: Color::rgba8(120, 170, 255, 255),
+                primary_pressed: Color::rgba8(80, 130, 235, 255),
+                on_primary: Color::rgba8(255, 255, 255, 255),
+                background: Color::rgba8(250, 250, 250, 255),
+                surface: Color::rgba8(255, 255, 255, 255),
+                surface_hover: Color::rgba8(245, 245, 245, 255),
+                border: Color::rgba8(200, 200, 200, 255),
+                text: Color::rgba8(0, 0, 0, 255),
+                text_muted: Color::rgba8(150, 150, 150, 255),
+                disabled: Color::rgba8(180, 180, 180, 255),
+                selection: Color::rgba8(225, 235, 255, 255),
+                danger: Color::rgba8(220, 60, 60, 255),
+                warning: Color::rgba8(235, 165, 40, 255),
+                success: Color::rgba8(60, 170, 90, 255),
+            },
+            typography: Typography::default(),
+            spacing: Spacing::default(),
+            radii: Radii::default(),
+            shadows: Shadows {
+                small: ShadowToken { offset_x: 0.0, offset_y: 1.0, blur_radius: 2.0, color: Color::rgba8(0, 0, 0, 40) },
+                medium: ShadowToken { offset_x: 0.0, offset_y: 2.0, blur_radius: 6.0, color: Color::rgba8(0, 0, 0, 50) },
+                large: ShadowToken { offset_x: 0.0, offset_y: 6.0, blur_radius: 16.0, color: Color::rgba8(0, 0, 0, 60) },
+            },
+        }
+    }
+
+    pub fn dark() -> Self {
+        Self {
+            mode: ThemeMode::Dark,
+            palette: Palette {
+                primary: Color::rgba8(90, 140, 240, 255),
+                primary_hover: Color::rgba8(110, 160, 250, 255),
+                primary_pressed: Color::rgba8(70, 115, 215, 255),
+                on_primary: Color::rgba8(255, 255, 255, 255),
+                background: Color::rgba8(30, 30, 34, 255),
+                surface: Color::rgba8(45, 45, 50, 255),
+                surface_hover: Color::rgba8(58, 58, 64, 255),
+                border: Color::rgba8(75, 75, 82, 255),
+                text: Color::rgba8(230, 230, 230, 255),
+                text_muted: Color::rgba8(150, 150, 158, 255),
+                disabled: Color::rgba8(90, 90, 96, 255),
+                selection: Color::rgba8(55, 75, 115, 255),
+                danger: Color::rgba8(235, 90, 90, 255),
+                warning: Color::rgba8(240, 180, 70, 255),
+                success: Color::rgba8(80, 190, 110, 255),
+            },
+            typography: Typography::default(),
+            spacing: Spacing::default(),
+            radii: Radii::default(),
+            shadows: Shadows {
+                small: ShadowToken { offset_x: 0.0, offset_y: 1.0, blur_radius: 2.0, color: Color::rgba8(0, 0, 0, 90) },
+                medium: ShadowToken { offset_x: 0.0, offset_y: 2.0, blur_radius: 6.0, color: Color::rgba8(0, 0, 0, 110) },
+                large: ShadowToken { offset_x: 0.0, offset_y: 6.0, blur_radius: 16.0, color: Color::rgba8(0, 0, 0, 130) },
+            },
+        }
+    }
+
+    pub fn for_mode(mode: ThemeMode) -> Self {
+        match mode {
+            ThemeMode::Light => Self::light(),
+            ThemeMode::Dark => Self::dark(),
+        }
+    }
+
+    pub fn is_dark(&self) -> bool {
+        self.mode == ThemeMode::Dark
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+impl Default for Typography {
+    fn default() -> Self {
+        Self {
+            font_size_small: 12.0,
+            font_size: 14.0,
+            font_size_large: 16.0,
+            font_size_heading: 20.0,
+            font_weight: 400,
+            font_weight_bold: 600,
+        }
+    }
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Self { xs: 2.0, small: 4.0, medium: 8.0, large: 16.0, xl: 24.0 }
+    }
+}
+
+impl Default for Radii {
+    fn default() -> Self {
+        Self { small: 2.0, medium: 4.0, large: 8.0, full: 9999.0 }
+    }
+}
+
+/// Style properties set explicitly on a widget. Theme changes leave these alone.
+#[derive(Clone, Debug, Default)]
+pub struct StyleOverrides {
+    properties: Vec<&'static str>,
+}
+
+impl StyleOverrides {
+    pub fn mark(&mut self, property: &'static str) {
+        if !self.properties.contains(&property) {
+            self.properties.push(property);
+        }
+    }
+
+    pub fn is_set(&self, property: &str) -> bool {
+        self.properties.contains(&property)
+    }
+}
+
+static GLOBAL_THEME: OnceLock<Signal<Theme>> = OnceLock::new();
+// Bumped whenever the global theme or any scope's theme changes, so the
+// widget manager knows to restyle the tree
+static THEME_REVISION: AtomicU64 = AtomicU64::new(0);
+
+/// App-wide theme. Widgets not under a `ThemeScopeWidget` follow it.
+pub fn global_theme() -> &'static Signal<Theme> {
+    GLOBAL_THEME.get_or_init(|| {
+        let theme = Signal::new(Theme::light());
+        theme.subscribe_fn(|_| invalidate_theme());
+        theme
+    })
+}
+
+/// Snapshot of the global theme, which constructors take their defaults from
+pub fn current_theme() -> Theme {
+    global_theme().get()
+}
+
+pub fn set_theme(theme: Theme) {
+    global_theme().set(theme);
+}
+
+/// Switch the global theme between `light` and `dark` whenever `mode` changes
+pub fn follow_theme_mode(mode: Signal<ThemeMode>, light: Theme, dark: Theme) {
+    let pick = move |mode: ThemeMode| match mode {
+        ThemeMode::Light => light.clone(),
+        ThemeMode::Dark => dark.clone(),
+    };
+    set_theme(pick(mode.get()));
+    mode.subscribe_fn(move |mode| set_theme(pick(*mode)));
+}
+
+pub fn theme_revision() -> u64 {
+    THEME_REVISION.load(Ordering::Relaxed)
+}
+
+pub fn invalidate_theme() {
+    THEME_REVISION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Restyle a single widget from `theme`, for the widget types that are themed.
+/// Layout widgets (rows, columns, stacks, grids, canvases, boxes) draw no theme
+/// colors of their own; the tree walk themes the children they hold.
+pub fn apply_theme_to_widget(widget: &mut dyn Widget, theme: &Theme) {
+    let any = widget.as_any_mut();
+    if let Some(button_widget) = any.downcast_mut::<ButtonWidget>() {
+        button_widget.apply_theme(theme);
+    } else if let Some(input_widget) = any.downcast_mut::<InputWidget>() {
+        input_widget.apply_theme(theme);
+    } else if let Some(slider_widget) = any.downcast_mut::<SliderWidget>() {
+        slider_widget.apply_theme(theme);
+    } else if let Some(checkbox_widget) = any.downcast_mut::<CheckboxWidget>() {
+        checkbox_widget.apply_theme(theme);
+    } else if let Some(radio_group_widget) = any.downcast_mut::<RadioGroupWidget>() {
+        radio_group_widget.apply_theme(theme);
+    } else if let Some(toggle_switch_widget) = any.downcast_mut::<ToggleSwitchWidget>() {
+        toggle_switch_widget.apply_theme(theme);
+    } else if let Some(text_widget) = any.downcast_mut::<TextWidget>() {
+        text_widget.apply_theme(theme);
+    } else if let Some(dropdown_widget) = any.downcast_mut::<DropdownWidget>() {
+        dropdown_widget.apply_theme(theme);
+    } else if let Some(multi_select_widget) = any.downcast_mut::<MultiSelectWidget>() {
+        multi_select_widget.apply_theme(theme);
+    } else if let Some(segmented_control) = any.downcast_mut::<SegmentedControl>() {
+        segmented_control.apply_theme(theme);
+    } else if let Some(number_field_widget) = any.downcast_mut::<NumberFieldWidget>() {
+        number_field_widget.apply_theme(theme);
+    } else if let Some(menu_widget) = any.downcast_mut::<MenuWidget>() {
+        menu_widget.apply_theme(theme);
+    } else if let Some(menu_bar_widget) = any.downcast_mut::<MenuBarWidget>() {
+        menu_bar_widget.apply_theme(theme);
+    } else if let Some(context_menu_widget) = any.downcast_mut::<ContextMenuWidget>() {
+        context_menu_widget.apply_theme(theme);
+    } else if let Some(tooltip_widget) = any.downcast_mut::<TooltipWidget>() {
+        tooltip_widget.apply_theme(theme);
+    } else if let Some(tooltip_bubble_widget) = any.downcast_mut::<TooltipBubbleWidget>() {
+        tooltip_bubble_widget.apply_theme(theme);
+    } else if let Some(color_picker_widget) = any.downcast_mut::<ColorPickerWidget>() {
+        color_picker_widget.apply_theme(theme);
+    } else if let Some(color_swatch_widget) = any.downcast_mut::<ColorSwatchWidget>() {
+        color_swatch_widget.apply_theme(theme);
+    } else if let Some(image_widget) = any.downcast_mut::<ImageWidget>() {
+        image_widget.apply_theme(theme);
+    } else if let Some(icon_widget) = any.downcast_mut::<IconWidget>() {
+        icon_widget.apply_theme(theme);
+    } else if let Some(progress_bar_widget) = any.downcast_mut::<ProgressBarWidget>() {
+        progress_bar_widget.apply_theme(theme);
+    } else if let Some(spinner_widget) = any.downcast_mut::<SpinnerWidget>() {
+        spinner_widget.apply_theme(theme);
+    } else if let Some(splitter_widget) = any.downcast_mut::<SplitterWidget>() {
+        splitter_widget.apply_theme(theme);
+    } else if let Some(dock_space_widget) = any.downcast_mut::<DockSpaceWidget>() {
+        dock_space_widget.apply_theme(theme);
+    } else if let Some(property_inspector_widget) = any.downcast_mut::<PropertyInspectorWidget>() {
+        property_inspector_widget.apply_theme(theme);
+    }
+}
+
+#[derive(Clone)]
+enum ScopeTheme {
+    Replace(Signal<Theme>),
+    Derive(Arc<dyn Fn(&Theme) -> Theme + Send + Sync>),
+}
+
+/// Gives its subtree a different theme: a fixed one, one held in a signal, or
+/// a tweak of the surrounding theme. Draws nothing itself; it takes the
+/// position and size of its child.
+pub struct ThemeScopeWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    theme: ScopeTheme,
+    pub dirty: bool,
+}
+
+impl ThemeScopeWidget {
+    fn with_scope_theme(theme: ScopeTheme) -> Self {
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+            theme,
+            dirty: true,
+        }
+    }
+
+    pub fn new(theme: Theme) -> Self {
+        Self::with_scope_theme(ScopeTheme::Replace(Signal::new(theme)))
+    }
+
+    /// Follow a theme signal, e.g. a dark sidebar in a light app
+    pub fn with_signal(theme: Signal<Theme>) -> Self {
+        Self::with_scope_theme(ScopeTheme::Replace(theme))
+    }
+
+    /// Adjust the surrounding theme, e.g. `|theme| { let mut t = theme.clone(); t.typography.font_size = 12.0; t }`
+    pub fn derive<F>(modify: F) -> Self
+    where
+        F: Fn(&Theme) -> Theme + Send + Sync + 'static,
+    {
+        Self::with_scope_theme(ScopeTheme::Derive(Arc::new(modify)))
+    }
+
+    /// The theme for the subtree, given the one around the scope
+    pub fn resolve(&self, parent: &Theme) -> Theme {
+        match &self.theme {
+            ScopeTheme::Replace(theme) => theme.get(),
+            ScopeTheme::Derive(modify) => modify(parent),
+        }
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        if self.width != width || self.height != height {
+            self.width = width;
+            self.height = height;
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+}
+
+impl Widget for ThemeScopeWidget {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        if let ScopeTheme::Replace(theme) = &self.theme {
+            theme.subscribe_fn(|_| invalidate_theme());
+        }
+        // A scope added to a live tree needs its subtree restyled
+        invalidate_theme();
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> EventResult {
+        EventResult::Ignored
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        // The children decide their own rendering; the scope draws nothing
+        true
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            }],
+            z_index: 0,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+// Convenience functions for scoping themes
+pub fn theme_scope(theme: Theme, child: crate::Element) -> crate::Element {
+    crate::Element::new_container(Box::new(ThemeScopeWidget::new(theme)), vec![child])
+}
+
+pub fn theme_scope_signal(theme: Signal<Theme>, child: crate::Element) -> crate::Element {
+    crate::Element::new_container(Box::new(ThemeScopeWidget::with_signal(theme)), vec![child])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_styles_survive_theme_changes() {
+        let mut themed = ButtonWidget::new("Themed");
+        let mut custom = ButtonWidget::new("Custom").with_colors(
+            Color::rgba8(200, 0, 0, 255),
+            Color::rgba8(220, 0, 0, 255),
+            Color::rgba8(180, 0, 0, 255),
+        );
+        let dark = Theme::dark();
+        apply_theme_to_widget(&mut themed, &dark);
+        apply_theme_to_widget(&mut custom, &dark);
+
+        let color_of = |button: &ButtonWidget| match button.get_current_background() {
+            crate::widgets::container::Background::Color(color) => *color,
+            _ => panic!("expected a solid background"),
+        };
+        assert_eq!(color_of(&themed), dark.palette.primary);
+        assert_eq!(color_of(&custom), Color::rgba8(200, 0, 0, 255));
+    }
+
+    #[test]
+    fn derived_scope_adjusts_the_parent_theme() {
+        let scope = ThemeScopeWidget::derive(|theme| {
+            let mut small = theme.clone();
+            small.typography.font_size = 11.0;
+            small
+        });
+        let resolved = scope.resolve(&Theme::dark());
+        assert_eq!(resolved.typography.font_size, 11.0);
+        assert_eq!(resolved.palette, Theme::dark().palette);
+    }
+}
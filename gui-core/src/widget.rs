@@ -1,3 +1,4 @@
+use crate::element::Element;
 use crate::event::Event;
 use crate::media_query::{ViewportSize, MediaQueryManager};
 use std::any::Any;
@@ -39,6 +40,12 @@ pub trait Widget: Send + Sync {
     
     fn as_any_mut(&mut self) -> &mut dyn Any;
     
+    /// Children the widget holds itself rather than through the element tree,
+    /// so tree-wide passes such as theming can still reach them
+    fn children_mut(&mut self) -> Vec<&mut Element> {
+        Vec::new()
+    }
+    
     fn get_id(&self) -> WidgetId;
 }
 
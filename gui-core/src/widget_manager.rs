@@ -1,6 +1,9 @@
 use crate::{Element, Widget, WidgetId, WidgetError, EventResult, WidgetUpdateContext};
 use crate::event::Event;
 use crate::media_query::{MediaQueryManager, ViewportSize};
+use crate::overlay::global_overlay_layer;
+use crate::theme::{current_theme, theme_revision};
+use crate::style::{current_style_sheet, global_style_sheet};
 use std::collections::HashMap;
 use gui_reactive::{Signal, ReactiveWidgetRegistry};
 use gui_reactive::widget_registry::WidgetDirtyNotifier;
@@ -13,6 +16,9 @@ pub struct WidgetManager {
     dirty_widgets: Signal<Vec<WidgetId>>,
     reactive_registry: ReactiveWidgetRegistry,
     media_query_manager: MediaQueryManager,
+    // Theme revision and viewport the tree was last styled for
+    applied_theme_revision: Option<u64>,
+    applied_style_viewport: Option<ViewportSize>,
 }
 
 struct WidgetManagerUpdateContext<'a> {
@@ -29,6 +35,8 @@ impl WidgetManager {
             dirty_widgets: Signal::new(Vec::new()),
             reactive_registry: ReactiveWidgetRegistry::new(),
             media_query_manager: MediaQueryManager::new(ViewportSize { width: 800.0, height: 600.0 }),
+            applied_theme_revision: None,
+            applied_style_viewport: None,
         }
     }
 
@@ -43,6 +51,8 @@ impl WidgetManager {
         
         self.mount_element(&mut element)?;
         self.root = Some(element);
+        // Style the new tree on the next update
+        self.applied_theme_revision = None;
         Ok(())
     }
     
@@ -113,19 +123,51 @@ impl WidgetManager {
     }
     
     pub fn update_all(&mut self) -> Result<(), WidgetError> {
-        if self.root.is_some() {
+        self.apply_theme_if_changed();
+        if let Some(root) = self.root.as_mut() {
             // Create a temporary context that implements WidgetUpdateContext
             let mut context = WidgetManagerUpdateContext {
                 dirty_widgets: &self.dirty_widgets,
                 media_query_manager: &mut self.media_query_manager,
             };
-            self.root.as_mut().unwrap().update(&mut context)
+            root.update(&mut context)?;
+            global_overlay_layer().update(&mut context)
         } else {
             Ok(())
         }
     }
     
+    /// Restyle the tree and overlays when the global theme, a scoped theme or
+    /// the style sheet changed, or when the viewport moved under a sheet with
+    /// media rules
+    fn apply_theme_if_changed(&mut self) {
+        let revision = theme_revision();
+        let viewport = self.media_query_manager.viewport();
+        // Runs every frame, so look at the sheet in place and only clone it to restyle
+        let viewport_changed = self.applied_style_viewport != Some(viewport)
+            && global_style_sheet().with(|sheet| sheet.has_media_rules());
+        if self.applied_theme_revision == Some(revision) && !viewport_changed {
+            return;
+        }
+        // The sheet layers on top of the theme, so both passes always run together
+        let theme = current_theme();
+        let sheet = current_style_sheet();
+        if let Some(root) = self.root.as_mut() {
+            root.apply_theme(&theme);
+            root.apply_style_sheet(&sheet, viewport);
+        }
+        global_overlay_layer().apply_theme(&theme);
+        global_overlay_layer().apply_style_sheet(&sheet, viewport);
+        self.applied_theme_revision = Some(revision);
+        self.applied_style_viewport = Some(viewport);
+    }
+    
     pub fn handle_event(&mut self, event: &Event) -> EventResult {
+        // Overlays sit above the widget tree, so they see events first
+        if let EventResult::Handled = global_overlay_layer().handle_event(event) {
+            return EventResult::Handled;
+        }
+        
         if let Some(ref mut root) = self.root {
             root.handle_event(event)
         } else {
@@ -262,7 +304,7 @@ mod tests {
             Ok(())
         }
         
-        fn update(&mut self, _ctx: &dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        fn update(&mut self, _ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
             Ok(())
         }
         
@@ -0,0 +1,1155 @@
+use crate::{Widget, WidgetId, EventResult, WidgetError, RenderData, DirtyRegion, WidgetUpdateContext};
+use crate::event::Event;
+use crate::element::Element;
+use crate::overlay::{global_overlay_layer, OverlayId};
+use crate::theme::{current_theme, StyleOverrides, Theme};
+use winit::event::ElementState;
+use winit::keyboard::KeyCode;
+use gui_reactive::Signal;
+use gui_render::primitives::{Rectangle, Shadow, Text};
+use std::any::Any;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use vello::peniko::{Color, Gradient};
+use super::container::Background;
+
+static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(8000);
+
+const COLOR_PICKER_Z_INDEX: i32 = 150;
+const MAX_RECENT_COLORS: usize = 8;
+
+/// Hue in degrees (0..360), saturation, value and alpha in 0..1
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hsva {
+    pub h: f32,
+    pub s: f32,
+    pub v: f32,
+    pub a: f32,
+}
+
+impl Hsva {
+    pub fn from_color(color: Color) -> Self {
+        let r = color.r as f32 / 255.0;
+        let g = color.g as f32 / 255.0;
+        let b = color.b as f32 / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+
+        let h = if delta <= f32::EPSILON {
+            0.0
+        } else if max == r {
+            60.0 * (((g - b) / delta).rem_euclid(6.0))
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        let s = if max <= f32::EPSILON { 0.0 } else { delta / max };
+
+        Self { h, s, v: max, a: color.a as f32 / 255.0 }
+    }
+
+    pub fn to_color(&self) -> Color {
+        let h = self.h.rem_euclid(360.0) / 60.0;
+        let c = self.v * self.s;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let m = self.v - c;
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let to_u8 = |channel: f32| ((channel + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+        Color::rgba8(to_u8(r), to_u8(g), to_u8(b), (self.a * 255.0).round().clamp(0.0, 255.0) as u8)
+    }
+}
+
+/// Format as `#rrggbb`, or `#rrggbbaa` when not fully opaque
+pub fn color_to_hex(color: Color) -> String {
+    if color.a == 255 {
+        format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", color.r, color.g, color.b, color.a)
+    }
+}
+
+/// Parse `#rgb`, `#rrggbb` or `#rrggbbaa`, with or without the leading `#`
+pub fn parse_hex_color(text: &str) -> Option<Color> {
+    let hex = text.trim().trim_start_matches('#');
+    let channel = |index: usize| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok();
+    match hex.len() {
+        3 => {
+            let short = |index: usize| u8::from_str_radix(hex.get(index..index + 1)?, 16).ok().map(|value| value * 17);
+            Some(Color::rgba8(short(0)?, short(1)?, short(2)?, 255))
+        },
+        6 => Some(Color::rgba8(channel(0)?, channel(2)?, channel(4)?, 255)),
+        8 => Some(Color::rgba8(channel(0)?, channel(2)?, channel(4)?, channel(6)?)),
+        _ => None,
+    }
+}
+
+static RECENT_COLORS: OnceLock<Signal<Vec<Color>>> = OnceLock::new();
+
+/// Colors recently committed in any color picker, most recent first
+pub fn recent_colors() -> Signal<Vec<Color>> {
+    RECENT_COLORS.get_or_init(|| Signal::new(Vec::new())).clone()
+}
+
+fn remember_color(color: Color) {
+    recent_colors().update(|colors| {
+        colors.retain(|existing| *existing != color);
+        colors.insert(0, color);
+        colors.truncate(MAX_RECENT_COLORS);
+    });
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColorFieldMode {
+    Rgba,
+    Hsva,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ColorField {
+    Hex,
+    // One of the four numeric channel fields
+    Channel(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DragTarget {
+    SaturationValue,
+    Hue,
+    Alpha,
+}
+
+pub type EyedropperHook = Arc<dyn Fn(Signal<Color>) + Send + Sync>;
+
+/// Saturation/value square with hue and alpha strips, hex and numeric fields and
+/// a row of recently used colors, bound to a `Signal<Color>`.
+pub struct ColorPickerWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    color: Signal<Color>,
+    // Kept separately so hue survives while saturation or value is zero
+    hsva: Hsva,
+    mode: ColorFieldMode,
+    drag: Option<DragTarget>,
+    focused_field: Option<ColorField>,
+    field_text: String,
+    eyedropper: Option<EyedropperHook>,
+    on_change: Option<Arc<dyn Fn(Color) + Send + Sync>>,
+    background: Background,
+    text_color: Color,
+    field_background: Color,
+    border_color: Color,
+    focused_border_color: Color,
+    font_size: f32,
+    padding: f32,
+    square_height: f32,
+    strip_height: f32,
+    row_height: f32,
+    shadow: Option<Shadow>,
+    overrides: StyleOverrides,
+    // Set when shown as a popup; outside clicks and Escape then close it
+    overlay_id: Option<OverlayId>,
+    pub dirty: bool,
+    // Shared dirty flag that reactive signals can set
+    reactive_dirty: Arc<RwLock<bool>>,
+}
+
+impl ColorPickerWidget {
+    pub fn new(color: Signal<Color>) -> Self {
+        let hsva = Hsva::from_color(color.get());
+        let theme = current_theme();
+        let mut picker = Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 240.0,
+            height: 0.0,
+            color,
+            hsva,
+            mode: ColorFieldMode::Rgba,
+            drag: None,
+            focused_field: None,
+            field_text: String::new(),
+            eyedropper: None,
+            on_change: None,
+            background: Background::Color(theme.palette.surface),
+            text_color: theme.palette.text,
+            field_background: theme.palette.background,
+            border_color: theme.palette.border,
+            focused_border_color: theme.palette.primary,
+            font_size: 12.0,
+            padding: 10.0,
+            square_height: 150.0,
+            strip_height: 12.0,
+            row_height: 24.0,
+            shadow: Some(theme.shadows.large.to_shadow()),
+            overrides: StyleOverrides::default(),
+            overlay_id: None,
+            dirty: true,
+            reactive_dirty: Arc::new(RwLock::new(false)),
+        };
+        picker.fit_height();
+        picker
+    }
+
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: ColorFieldMode) -> Self {
+        self.mode = mode;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_background(mut self, background: Background) -> Self {
+        self.background = background;
+        self.overrides.mark("background");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_text_color(mut self, color: Color) -> Self {
+        self.text_color = color;
+        self.overrides.mark("text_color");
+        self.dirty = true;
+        self
+    }
+
+    /// Take every style property not set through a builder from `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let palette = &theme.palette;
+        if !self.overrides.is_set("background") {
+            self.background = Background::Color(palette.surface);
+        }
+        if !self.overrides.is_set("text_color") {
+            self.text_color = palette.text;
+        }
+        self.field_background = palette.background;
+        self.border_color = palette.border;
+        self.focused_border_color = palette.primary;
+        self.shadow = Some(theme.shadows.large.to_shadow());
+        self.dirty = true;
+    }
+
+    /// Show an eyedropper button. The app receives the picker's signal and sets it
+    /// once the user has sampled a color, e.g. from the viewport under the pointer.
+    pub fn with_eyedropper<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(Signal<Color>) + Send + Sync + 'static,
+    {
+        self.eyedropper = Some(Arc::new(hook));
+        self.dirty = true;
+        self
+    }
+
+    fn with_eyedropper_hook(mut self, hook: Option<EyedropperHook>) -> Self {
+        self.eyedropper = hook;
+        self
+    }
+
+    pub fn on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(Color) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Arc::new(callback));
+        self
+    }
+
+    fn with_change_callback(mut self, callback: Option<Arc<dyn Fn(Color) + Send + Sync>>) -> Self {
+        self.on_change = callback;
+        self
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn get_color(&self) -> Color {
+        self.color.get()
+    }
+
+    pub fn get_hsva(&self) -> Hsva {
+        self.hsva
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    /// Show the picker on the overlay layer; it closes itself on an outside click or Escape
+    pub fn popup(mut self, x: f32, y: f32) -> OverlayId {
+        self.set_position(x, y);
+        let overlay = global_overlay_layer();
+        let overlay_id = overlay.push(Element::new_widget(Box::new(self)), COLOR_PICKER_Z_INDEX);
+        overlay.with_element_mut(overlay_id, |element| {
+            if let Element::Widget(widget) = element {
+                if let Some(picker) = widget.as_any_mut().downcast_mut::<ColorPickerWidget>() {
+                    picker.overlay_id = Some(overlay_id);
+                }
+            }
+        });
+        overlay_id
+    }
+
+    pub fn close(&mut self) {
+        self.commit_field();
+        if let Some(overlay_id) = self.overlay_id.take() {
+            remember_color(self.color.get());
+            global_overlay_layer().remove(overlay_id);
+        }
+    }
+
+    fn fit_height(&mut self) {
+        // square, hue, alpha, preview/hex row, channel row, recent row
+        self.height = self.padding * 2.0
+            + self.square_height + 8.0
+            + self.strip_height + 8.0
+            + self.strip_height + 10.0
+            + self.row_height + 8.0
+            + self.row_height + 8.0
+            + 18.0;
+    }
+
+    fn inner_width(&self) -> f32 {
+        self.width - self.padding * 2.0
+    }
+
+    fn square_bounds(&self) -> (f32, f32, f32, f32) {
+        (self.x + self.padding, self.y + self.padding, self.inner_width(), self.square_height)
+    }
+
+    fn hue_bounds(&self) -> (f32, f32, f32, f32) {
+        let (x, y, width, height) = self.square_bounds();
+        (x, y + height + 8.0, width, self.strip_height)
+    }
+
+    fn alpha_bounds(&self) -> (f32, f32, f32, f32) {
+        let (x, y, width, height) = self.hue_bounds();
+        (x, y + height + 8.0, width, self.strip_height)
+    }
+
+    fn preview_row_y(&self) -> f32 {
+        let (_, y, _, height) = self.alpha_bounds();
+        y + height + 10.0
+    }
+
+    fn swatch_bounds(&self) -> (f32, f32, f32, f32) {
+        (self.x + self.padding, self.preview_row_y(), self.row_height, self.row_height)
+    }
+
+    fn hex_bounds(&self) -> (f32, f32, f32, f32) {
+        let (x, y, width, _) = self.swatch_bounds();
+        (x + width + 6.0, y, 90.0, self.row_height)
+    }
+
+    fn mode_button_bounds(&self) -> (f32, f32, f32, f32) {
+        let (x, y, width, _) = self.hex_bounds();
+        (x + width + 6.0, y, 44.0, self.row_height)
+    }
+
+    fn eyedropper_bounds(&self) -> Option<(f32, f32, f32, f32)> {
+        self.eyedropper.as_ref().map(|_| {
+            let y = self.preview_row_y();
+            (self.x + self.width - self.padding - self.row_height, y, self.row_height, self.row_height)
+        })
+    }
+
+    fn channel_bounds(&self, index: usize) -> (f32, f32, f32, f32) {
+        let y = self.preview_row_y() + self.row_height + 8.0;
+        let gap = 6.0;
+        let width = (self.inner_width() - gap * 3.0) / 4.0;
+        (self.x + self.padding + index as f32 * (width + gap), y, width, self.row_height)
+    }
+
+    fn recent_bounds(&self, index: usize) -> (f32, f32, f32, f32) {
+        let (_, channel_y, _, channel_height) = self.channel_bounds(0);
+        let size = 18.0;
+        (self.x + self.padding + index as f32 * (size + 6.0), channel_y + channel_height + 8.0, size, size)
+    }
+
+    fn contains((x, y, width, height): (f32, f32, f32, f32), px: f32, py: f32) -> bool {
+        px >= x && px <= x + width && py >= y && py <= y + height
+    }
+
+    fn channel_labels(&self) -> [&'static str; 4] {
+        match self.mode {
+            ColorFieldMode::Rgba => ["R", "G", "B", "A"],
+            ColorFieldMode::Hsva => ["H", "S", "V", "A"],
+        }
+    }
+
+    fn channel_value(&self, index: usize) -> String {
+        let color = self.color.get();
+        match (self.mode, index) {
+            (ColorFieldMode::Rgba, 0) => color.r.to_string(),
+            (ColorFieldMode::Rgba, 1) => color.g.to_string(),
+            (ColorFieldMode::Rgba, 2) => color.b.to_string(),
+            (ColorFieldMode::Rgba, _) => color.a.to_string(),
+            (ColorFieldMode::Hsva, 0) => format!("{:.0}", self.hsva.h),
+            (ColorFieldMode::Hsva, 1) => format!("{:.0}", self.hsva.s * 100.0),
+            (ColorFieldMode::Hsva, 2) => format!("{:.0}", self.hsva.v * 100.0),
+            (ColorFieldMode::Hsva, _) => format!("{:.0}", self.hsva.a * 100.0),
+        }
+    }
+
+    fn field_value(&self, field: ColorField) -> String {
+        match field {
+            ColorField::Hex => color_to_hex(self.color.get()),
+            ColorField::Channel(index) => self.channel_value(index),
+        }
+    }
+
+    /// Set the color from HSVA, keeping the stored hue
+    pub fn set_hsva(&mut self, hsva: Hsva) {
+        self.hsva = Hsva {
+            h: hsva.h.rem_euclid(360.0),
+            s: hsva.s.clamp(0.0, 1.0),
+            v: hsva.v.clamp(0.0, 1.0),
+            a: hsva.a.clamp(0.0, 1.0),
+        };
+        let color = self.hsva.to_color();
+        if self.color.get() != color {
+            self.color.set(color);
+            if let Some(ref callback) = self.on_change {
+                callback(color);
+            }
+        }
+        self.dirty = true;
+    }
+
+    pub fn set_color(&mut self, color: Color) {
+        let mut hsva = Hsva::from_color(color);
+        // Grays carry no hue; keep the one the user had
+        if hsva.s <= f32::EPSILON || hsva.v <= f32::EPSILON {
+            hsva.h = self.hsva.h;
+        }
+        self.set_hsva(hsva);
+    }
+
+    fn drag_to(&mut self, target: DragTarget, px: f32, py: f32) {
+        let mut hsva = self.hsva;
+        match target {
+            DragTarget::SaturationValue => {
+                let (x, y, width, height) = self.square_bounds();
+                hsva.s = ((px - x) / width).clamp(0.0, 1.0);
+                hsva.v = 1.0 - ((py - y) / height).clamp(0.0, 1.0);
+            },
+            DragTarget::Hue => {
+                let (x, _, width, _) = self.hue_bounds();
+                hsva.h = ((px - x) / width).clamp(0.0, 1.0) * 359.9;
+            },
+            DragTarget::Alpha => {
+                let (x, _, width, _) = self.alpha_bounds();
+                hsva.a = ((px - x) / width).clamp(0.0, 1.0);
+            },
+        }
+        self.set_hsva(hsva);
+    }
+
+    fn focus_field(&mut self, field: Option<ColorField>) {
+        if self.focused_field == field {
+            return;
+        }
+        self.commit_field();
+        self.focused_field = field;
+        self.field_text = field.map(|field| self.field_value(field)).unwrap_or_default();
+        self.dirty = true;
+    }
+
+    fn commit_field(&mut self) {
+        let Some(field) = self.focused_field.take() else {
+            return;
+        };
+        let text = std::mem::take(&mut self.field_text);
+        self.dirty = true;
+
+        match field {
+            ColorField::Hex => {
+                if let Some(color) = parse_hex_color(&text) {
+                    self.set_color(color);
+                    remember_color(color);
+                }
+            },
+            ColorField::Channel(index) => {
+                let Ok(value) = text.trim().parse::<f32>() else {
+                    return;
+                };
+                match self.mode {
+                    ColorFieldMode::Rgba => {
+                        let mut color = self.color.get();
+                        let value = value.round().clamp(0.0, 255.0) as u8;
+                        match index {
+                            0 => color.r = value,
+                            1 => color.g = value,
+                            2 => color.b = value,
+                            _ => color.a = value,
+                        }
+                        self.set_color(color);
+                    },
+                    ColorFieldMode::Hsva => {
+                        let mut hsva = self.hsva;
+                        match index {
+                            0 => hsva.h = value.clamp(0.0, 359.9),
+                            1 => hsva.s = value / 100.0,
+                            2 => hsva.v = value / 100.0,
+                            _ => hsva.a = value / 100.0,
+                        }
+                        self.set_hsva(hsva);
+                    },
+                }
+                remember_color(self.color.get());
+            },
+        }
+    }
+
+    fn handle_press(&mut self, px: f32, py: f32) -> EventResult {
+        if !self.is_point_inside(px, py) {
+            self.focus_field(None);
+            if self.overlay_id.is_some() {
+                // Outside click closes the popup without reaching the widgets below
+                self.close();
+                return EventResult::Handled;
+            }
+            return EventResult::Ignored;
+        }
+
+        if Self::contains(self.square_bounds(), px, py) {
+            self.focus_field(None);
+            self.drag = Some(DragTarget::SaturationValue);
+        } else if Self::contains(self.hue_bounds(), px, py) {
+            self.focus_field(None);
+            self.drag = Some(DragTarget::Hue);
+        } else if Self::contains(self.alpha_bounds(), px, py) {
+            self.focus_field(None);
+            self.drag = Some(DragTarget::Alpha);
+        } else if Self::contains(self.hex_bounds(), px, py) {
+            self.focus_field(Some(ColorField::Hex));
+        } else if let Some(index) = (0..4).find(|&index| Self::contains(self.channel_bounds(index), px, py)) {
+            self.focus_field(Some(ColorField::Channel(index)));
+        } else if Self::contains(self.mode_button_bounds(), px, py) {
+            self.focus_field(None);
+            self.mode = match self.mode {
+                ColorFieldMode::Rgba => ColorFieldMode::Hsva,
+                ColorFieldMode::Hsva => ColorFieldMode::Rgba,
+            };
+        } else if self.eyedropper_bounds().is_some_and(|bounds| Self::contains(bounds, px, py)) {
+            self.focus_field(None);
+            if let Some(hook) = &self.eyedropper {
+                hook(self.color.clone());
+            }
+        } else {
+            self.focus_field(None);
+            let recent = recent_colors().get();
+            if let Some(index) = (0..recent.len()).find(|&index| Self::contains(self.recent_bounds(index), px, py)) {
+                self.set_color(recent[index]);
+            }
+        }
+
+        if let Some(target) = self.drag {
+            self.drag_to(target, px, py);
+        }
+        self.dirty = true;
+        EventResult::Handled
+    }
+
+    fn handle_field_key(&mut self, key_code: Option<KeyCode>, character: Option<char>) -> EventResult {
+        let Some(field) = self.focused_field else {
+            return EventResult::Ignored;
+        };
+        match key_code {
+            Some(KeyCode::Enter) => self.commit_field(),
+            Some(KeyCode::Escape) => {
+                self.focused_field = None;
+                self.field_text.clear();
+            },
+            Some(KeyCode::Backspace) => {
+                self.field_text.pop();
+            },
+            Some(KeyCode::Tab) => {
+                // Step through hex and the four channel fields
+                let next = match field {
+                    ColorField::Hex => ColorField::Channel(0),
+                    ColorField::Channel(index) if index < 3 => ColorField::Channel(index + 1),
+                    ColorField::Channel(_) => ColorField::Hex,
+                };
+                self.focus_field(Some(next));
+            },
+            _ => {
+                let accepted = character.filter(|c| match field {
+                    ColorField::Hex => c.is_ascii_hexdigit() || *c == '#',
+                    ColorField::Channel(_) => c.is_ascii_digit() || *c == '.',
+                });
+                match accepted {
+                    Some(c) => self.field_text.push(c),
+                    None => return EventResult::Handled,
+                }
+            },
+        }
+        self.dirty = true;
+        EventResult::Handled
+    }
+
+    fn strip_gradient(x: f32, width: f32, stops: &[Color]) -> Gradient {
+        Gradient::new_linear((x as f64, 0.0), ((x + width) as f64, 0.0)).with_stops(stops)
+    }
+
+    fn cursor_rectangles(center_x: f32, center_y: f32, size: f32, fill: Color) -> [Rectangle; 2] {
+        let half = size / 2.0;
+        [
+            Rectangle::new(center_x - half, center_y - half, size, size, Color::rgba8(255, 255, 255, 255))
+                .with_border_radius(half),
+            Rectangle::new(center_x - half + 2.0, center_y - half + 2.0, size - 4.0, size - 4.0, fill)
+                .with_border_radius(half - 2.0),
+        ]
+    }
+
+    fn field_rectangles(&self, bounds: (f32, f32, f32, f32), focused: bool) -> [Rectangle; 2] {
+        let (x, y, width, height) = bounds;
+        let border = if focused { self.focused_border_color } else { self.border_color };
+        [
+            Rectangle::new(x, y, width, height, border).with_border_radius(3.0),
+            Rectangle::new(x + 1.0, y + 1.0, width - 2.0, height - 2.0, self.field_background).with_border_radius(3.0),
+        ]
+    }
+
+    pub fn create_background_rectangle(&self) -> Rectangle {
+        Rectangle::new_with_brush(self.x, self.y, self.width, self.height, self.background.to_brush())
+            .with_border_radius(6.0)
+    }
+
+    pub fn create_shadow(&self) -> Option<Shadow> {
+        self.shadow.as_ref().map(|shadow| {
+            Shadow::new(self.x, self.y, self.width, self.height,
+                       shadow.offset_x, shadow.offset_y, shadow.blur_radius, shadow.color)
+        })
+    }
+
+    /// Square, strips, cursors, preview swatch, fields, buttons and recent swatches
+    pub fn create_rectangles(&self) -> Vec<Rectangle> {
+        let color = self.color.get();
+        let opaque = Color::rgba8(color.r, color.g, color.b, 255);
+        let hue_color = Hsva { h: self.hsva.h, s: 1.0, v: 1.0, a: 1.0 }.to_color();
+        let mut rectangles = Vec::new();
+
+        // Saturation runs white to the hue left to right, value fades to black downwards
+        let (square_x, square_y, square_width, square_height) = self.square_bounds();
+        rectangles.push(Rectangle::new(square_x, square_y, square_width, square_height, hue_color));
+        rectangles.push(Rectangle::new_with_brush(square_x, square_y, square_width, square_height,
+            Background::Gradient(Self::strip_gradient(square_x, square_width,
+                &[Color::rgba8(255, 255, 255, 255), Color::rgba8(255, 255, 255, 0)])).to_brush()));
+        rectangles.push(Rectangle::new_with_brush(square_x, square_y, square_width, square_height,
+            Background::Gradient(Gradient::new_linear((0.0, square_y as f64), (0.0, (square_y + square_height) as f64))
+                .with_stops([Color::rgba8(0, 0, 0, 0), Color::rgba8(0, 0, 0, 255)].as_slice())).to_brush()));
+        rectangles.extend(Self::cursor_rectangles(
+            square_x + self.hsva.s * square_width,
+            square_y + (1.0 - self.hsva.v) * square_height,
+            12.0,
+            opaque,
+        ));
+
+        let (hue_x, hue_y, hue_width, hue_height) = self.hue_bounds();
+        let hue_stops: Vec<Color> = (0..=6)
+            .map(|step| Hsva { h: (step as f32 * 60.0).min(359.9), s: 1.0, v: 1.0, a: 1.0 }.to_color())
+            .collect();
+        rectangles.push(Rectangle::new_with_brush(hue_x, hue_y, hue_width, hue_height,
+            Background::Gradient(Self::strip_gradient(hue_x, hue_width, &hue_stops)).to_brush())
+            .with_border_radius(hue_height / 2.0));
+        rectangles.extend(Self::cursor_rectangles(hue_x + self.hsva.h / 360.0 * hue_width, hue_y + hue_height / 2.0, 14.0, hue_color));
+
+        // Alpha strip over a mid gray so transparency is visible
+        let (alpha_x, alpha_y, alpha_width, alpha_height) = self.alpha_bounds();
+        rectangles.push(Rectangle::new(alpha_x, alpha_y, alpha_width, alpha_height, Color::rgba8(128, 128, 128, 255))
+            .with_border_radius(alpha_height / 2.0));
+        rectangles.push(Rectangle::new_with_brush(alpha_x, alpha_y, alpha_width, alpha_height,
+            Background::Gradient(Self::strip_gradient(alpha_x, alpha_width,
+                &[Color::rgba8(color.r, color.g, color.b, 0), opaque])).to_brush())
+            .with_border_radius(alpha_height / 2.0));
+        rectangles.extend(Self::cursor_rectangles(alpha_x + self.hsva.a * alpha_width, alpha_y + alpha_height / 2.0, 14.0, color));
+
+        let (swatch_x, swatch_y, swatch_width, swatch_height) = self.swatch_bounds();
+        rectangles.push(Rectangle::new(swatch_x, swatch_y, swatch_width, swatch_height, Color::rgba8(128, 128, 128, 255))
+            .with_border_radius(3.0));
+        rectangles.push(Rectangle::new(swatch_x, swatch_y, swatch_width, swatch_height, color).with_border_radius(3.0));
+
+        rectangles.extend(self.field_rectangles(self.hex_bounds(), self.focused_field == Some(ColorField::Hex)));
+        for index in 0..4 {
+            rectangles.extend(self.field_rectangles(self.channel_bounds(index), self.focused_field == Some(ColorField::Channel(index))));
+        }
+
+        let (mode_x, mode_y, mode_width, mode_height) = self.mode_button_bounds();
+        rectangles.push(Rectangle::new(mode_x, mode_y, mode_width, mode_height, self.border_color).with_border_radius(3.0));
+        if let Some((eye_x, eye_y, eye_width, eye_height)) = self.eyedropper_bounds() {
+            rectangles.push(Rectangle::new(eye_x, eye_y, eye_width, eye_height, self.border_color).with_border_radius(3.0));
+        }
+
+        for (index, recent) in recent_colors().get().into_iter().enumerate() {
+            let (recent_x, recent_y, recent_width, recent_height) = self.recent_bounds(index);
+            rectangles.push(Rectangle::new(recent_x, recent_y, recent_width, recent_height, recent).with_border_radius(3.0));
+        }
+        rectangles
+    }
+
+    /// Field contents and button labels
+    pub fn create_text_primitives(&self) -> Vec<Text> {
+        let mut texts = Vec::new();
+        let text_y = |y: f32, height: f32| y + (height / 2.0) + (self.font_size * 0.25);
+
+        let (hex_x, hex_y, _, hex_height) = self.hex_bounds();
+        let hex_text = if self.focused_field == Some(ColorField::Hex) {
+            format!("{}|", self.field_text)
+        } else {
+            color_to_hex(self.color.get())
+        };
+        texts.push(Text::new(hex_x + 6.0, text_y(hex_y, hex_height), hex_text, self.text_color, self.font_size));
+
+        for (index, label) in self.channel_labels().iter().enumerate() {
+            let (field_x, field_y, _, field_height) = self.channel_bounds(index);
+            let value = if self.focused_field == Some(ColorField::Channel(index)) {
+                format!("{}|", self.field_text)
+            } else {
+                self.channel_value(index)
+            };
+            texts.push(Text::new(field_x + 6.0, text_y(field_y, field_height), format!("{} {}", label, value), self.text_color, self.font_size));
+        }
+
+        let (mode_x, mode_y, _, mode_height) = self.mode_button_bounds();
+        let mode_label = match self.mode {
+            ColorFieldMode::Rgba => "RGBA",
+            ColorFieldMode::Hsva => "HSVA",
+        };
+        texts.push(Text::new(mode_x + 6.0, text_y(mode_y, mode_height), mode_label.to_string(), self.text_color, self.font_size));
+
+        if let Some((eye_x, eye_y, _, eye_height)) = self.eyedropper_bounds() {
+            texts.push(Text::new(eye_x + 6.0, text_y(eye_y, eye_height), "⊙".to_string(), self.text_color, self.font_size));
+        }
+        texts
+    }
+}
+
+impl Widget for ColorPickerWidget {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        self.dirty = true;
+
+        let reactive_dirty = self.reactive_dirty.clone();
+        self.color.subscribe_fn(move |_| {
+            if let Ok(mut dirty) = reactive_dirty.write() {
+                *dirty = true;
+            }
+        });
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        self.drag = None;
+        self.focused_field = None;
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        if let Ok(mut reactive_dirty) = self.reactive_dirty.write() {
+            if *reactive_dirty {
+                *reactive_dirty = false;
+                self.dirty = true;
+            }
+        }
+
+        // Follow changes made elsewhere, e.g. by the eyedropper
+        let color = self.color.get();
+        if self.hsva.to_color() != color {
+            let mut hsva = Hsva::from_color(color);
+            if hsva.s <= f32::EPSILON || hsva.v <= f32::EPSILON {
+                hsva.h = self.hsva.h;
+            }
+            self.hsva = hsva;
+            self.dirty = true;
+        }
+
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        match event {
+            Event::Mouse(mouse_event) => {
+                let x = mouse_event.position.x as f32;
+                let y = mouse_event.position.y as f32;
+
+                if mouse_event.button.is_none() {
+                    // Mouse move
+                    match self.drag {
+                        Some(target) => {
+                            self.drag_to(target, x, y);
+                            EventResult::Handled
+                        },
+                        None => EventResult::Ignored,
+                    }
+                } else if mouse_event.state == ElementState::Pressed {
+                    self.handle_press(x, y)
+                } else if self.drag.take().is_some() {
+                    remember_color(self.color.get());
+                    self.dirty = true;
+                    EventResult::Handled
+                } else {
+                    EventResult::Ignored
+                }
+            },
+            Event::Keyboard(keyboard_event) if keyboard_event.state == ElementState::Pressed => {
+                if self.focused_field.is_some() {
+                    return self.handle_field_key(keyboard_event.key_code, keyboard_event.character);
+                }
+                if self.overlay_id.is_some() {
+                    if keyboard_event.key_code == Some(KeyCode::Escape) {
+                        self.close();
+                    }
+                    // An open popup keeps keyboard focus
+                    return EventResult::Handled;
+                }
+                EventResult::Ignored
+            },
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            }],
+            z_index: if self.overlay_id.is_some() { COLOR_PICKER_Z_INDEX } else { 1 },
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+/// Compact color field: a swatch and hex value that opens a `ColorPickerWidget`
+/// popup when clicked
+pub struct ColorSwatchWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    color: Signal<Color>,
+    eyedropper: Option<EyedropperHook>,
+    on_change: Option<Arc<dyn Fn(Color) + Send + Sync>>,
+    popup: Option<OverlayId>,
+    viewport: (f32, f32),
+    background: Color,
+    border_color: Color,
+    text_color: Color,
+    font_size: f32,
+    overrides: StyleOverrides,
+    // Handed to the picker the swatch opens
+    theme: Theme,
+    pub dirty: bool,
+    // Shared dirty flag that reactive signals can set
+    reactive_dirty: Arc<RwLock<bool>>,
+}
+
+impl ColorSwatchWidget {
+    pub fn new(color: Signal<Color>) -> Self {
+        let theme = current_theme();
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 120.0,
+            height: 28.0,
+            color,
+            eyedropper: None,
+            on_change: None,
+            popup: None,
+            viewport: (800.0, 600.0),
+            background: theme.palette.background,
+            border_color: theme.palette.border,
+            text_color: theme.palette.text,
+            font_size: 12.0,
+            overrides: StyleOverrides::default(),
+            theme,
+            dirty: true,
+            reactive_dirty: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_colors(mut self, background: Color, border_color: Color, text_color: Color) -> Self {
+        self.background = background;
+        self.border_color = border_color;
+        self.text_color = text_color;
+        self.overrides.mark("colors");
+        self.dirty = true;
+        self
+    }
+
+    /// Take the colors from `theme` unless `with_colors` set them; the picker
+    /// popup uses it too
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        if !self.overrides.is_set("colors") {
+            self.background = theme.palette.background;
+            self.border_color = theme.palette.border;
+            self.text_color = theme.palette.text;
+        }
+        self.theme = theme.clone();
+        self.dirty = true;
+    }
+
+    pub fn with_eyedropper<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(Signal<Color>) + Send + Sync + 'static,
+    {
+        self.eyedropper = Some(Arc::new(hook));
+        self
+    }
+
+    /// Called with each color picked in the popup
+    pub fn on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(Color) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.popup.is_some_and(|popup| global_overlay_layer().contains(popup))
+    }
+
+    fn open(&mut self) {
+        let mut picker = ColorPickerWidget::new(self.color.clone())
+            .with_eyedropper_hook(self.eyedropper.clone())
+            .with_change_callback(self.on_change.clone());
+        picker.apply_theme(&self.theme);
+        let (picker_width, picker_height) = picker.get_size();
+
+        // Open below the field, or above it when there is no room
+        let mut y = self.y + self.height + 4.0;
+        if y + picker_height > self.viewport.1 {
+            y = (self.y - picker_height - 4.0).max(0.0);
+        }
+        let x = self.x.min(self.viewport.0 - picker_width).max(0.0);
+        self.popup = Some(picker.popup(x, y));
+    }
+
+    pub fn create_rectangles(&self) -> Vec<Rectangle> {
+        let swatch_size = self.height - 8.0;
+        vec![
+            Rectangle::new(self.x, self.y, self.width, self.height, self.border_color).with_border_radius(4.0),
+            Rectangle::new(self.x + 1.0, self.y + 1.0, self.width - 2.0, self.height - 2.0, self.background).with_border_radius(4.0),
+            Rectangle::new(self.x + 4.0, self.y + 4.0, swatch_size, swatch_size, self.color.get()).with_border_radius(3.0),
+        ]
+    }
+
+    pub fn create_text_primitive(&self) -> Text {
+        let text_x = self.x + self.height + 2.0;
+        let text_y = self.y + (self.height / 2.0) + (self.font_size * 0.25);
+        Text::new(text_x, text_y, color_to_hex(self.color.get()), self.text_color, self.font_size)
+    }
+}
+
+impl Widget for ColorSwatchWidget {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        self.dirty = true;
+
+        let reactive_dirty = self.reactive_dirty.clone();
+        self.color.subscribe_fn(move |_| {
+            if let Ok(mut dirty) = reactive_dirty.write() {
+                *dirty = true;
+            }
+        });
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        if let Some(popup) = self.popup.take() {
+            global_overlay_layer().remove(popup);
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        let viewport = ctx.viewport_size();
+        self.viewport = (viewport.width, viewport.height);
+
+        if let Ok(mut reactive_dirty) = self.reactive_dirty.write() {
+            if *reactive_dirty {
+                *reactive_dirty = false;
+                self.dirty = true;
+            }
+        }
+
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        let Event::Mouse(mouse_event) = event else {
+            return EventResult::Ignored;
+        };
+        if mouse_event.button.is_none() || mouse_event.state != ElementState::Pressed {
+            return EventResult::Ignored;
+        }
+        if !self.is_point_inside(mouse_event.position.x as f32, mouse_event.position.y as f32) {
+            return EventResult::Ignored;
+        }
+        // An open popup closes itself on this click before it reaches us
+        if !self.is_open() {
+            self.open();
+        }
+        self.dirty = true;
+        EventResult::Handled
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            }],
+            z_index: 1,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+// Convenience functions for creating color pickers
+pub fn color_picker(color: Signal<Color>) -> ColorPickerWidget {
+    ColorPickerWidget::new(color)
+}
+
+pub fn color_swatch(color: Signal<Color>) -> ColorSwatchWidget {
+    ColorSwatchWidget::new(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hsva_round_trips_primary_colors() {
+        for color in [
+            Color::rgba8(255, 0, 0, 255),
+            Color::rgba8(0, 255, 0, 128),
+            Color::rgba8(0, 0, 255, 0),
+            Color::rgba8(12, 200, 99, 255),
+        ] {
+            assert_eq!(Hsva::from_color(color).to_color(), color);
+        }
+    }
+
+    #[test]
+    fn hex_parsing_accepts_short_long_and_alpha_forms() {
+        assert_eq!(parse_hex_color("#fff"), Some(Color::rgba8(255, 255, 255, 255)));
+        assert_eq!(parse_hex_color("336699"), Some(Color::rgba8(0x33, 0x66, 0x99, 255)));
+        assert_eq!(parse_hex_color("#33669980"), Some(Color::rgba8(0x33, 0x66, 0x99, 0x80)));
+        assert_eq!(parse_hex_color("#12"), None);
+        assert_eq!(color_to_hex(Color::rgba8(0x33, 0x66, 0x99, 0x80)), "#33669980");
+    }
+
+    #[test]
+    fn gray_keeps_previous_hue() {
+        let mut picker = ColorPickerWidget::new(Signal::new(Color::rgba8(0, 0, 255, 255)));
+        picker.set_color(Color::rgba8(128, 128, 128, 255));
+        assert!((picker.get_hsva().h - 240.0).abs() < 0.5);
+    }
+}
@@ -11,6 +11,9 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::collections::HashMap;
 use vello::peniko::{Color, Gradient, Brush};
 use gui_layout::Position;
+use crate::theme::StyleOverrides;
+use crate::style::StyleSheet;
+use crate::media_query::ViewportSize;
 
 static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(1000);
 
@@ -63,10 +66,14 @@ pub struct BoxWidget {
     height: f32,
     background: Option<Background>,
     border_radius: f32,
+    border: Option<(f32, Color)>,
     padding: Padding,
     shadow: Option<Shadow>,
     children: Vec<Element>,
     pub dirty: bool,
+    // Style sheet classes and the properties builders set, which win over them
+    classes: Vec<String>,
+    overrides: StyleOverrides,
     // Responsive styling
     responsive_styles: HashMap<MediaQuery, ResponsiveStyle>,
     // Display control signal
@@ -77,7 +84,7 @@ pub struct BoxWidget {
     reactive_children: Arc<RwLock<Vec<Element>>>,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Padding {
     pub top: f32,
     pub right: f32,
@@ -139,10 +146,13 @@ impl BoxWidget {
             height: 0.0,
             background: None,
             border_radius: 0.0,
+            border: None,
             padding: Padding::default(),
             shadow: None,
             children: Vec::new(),
             dirty: true,
+            classes: Vec::new(),
+            overrides: StyleOverrides::default(),
             responsive_styles: HashMap::new(),
             display_signal: None,
             position: Position::Relative,
@@ -197,42 +207,63 @@ impl BoxWidget {
 
     pub fn with_background_color(mut self, color: Color) -> Self {
         self.background = Some(Background::Color(color));
+        self.overrides.mark("background");
         self.dirty = true;
         self
     }
 
     pub fn with_background(mut self, background: Background) -> Self {
         self.background = Some(background);
+        self.overrides.mark("background");
         self.dirty = true;
         self
     }
 
     pub fn with_linear_gradient(mut self, gradient: Gradient) -> Self {
         self.background = Some(Background::Gradient(gradient));
+        self.overrides.mark("background");
         self.dirty = true;
         self
     }
 
     pub fn with_radial_gradient(mut self, gradient: Gradient) -> Self {
         self.background = Some(Background::Gradient(gradient));
+        self.overrides.mark("background");
         self.dirty = true;
         self
     }
 
     pub fn with_border_radius(mut self, radius: f32) -> Self {
         self.border_radius = radius;
+        self.overrides.mark("border_radius");
         self.dirty = true;
         self
     }
 
     pub fn with_padding(mut self, padding: Padding) -> Self {
         self.padding = padding;
+        self.overrides.mark("padding");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_border(mut self, width: f32, color: Color) -> Self {
+        self.border = Some((width, color));
+        self.overrides.mark("border");
         self.dirty = true;
         self
     }
 
     pub fn with_shadow(mut self, offset_x: f32, offset_y: f32, blur_radius: f32, color: Color) -> Self {
         self.shadow = Some(Shadow::new(self.x, self.y, self.width, self.height, offset_x, offset_y, blur_radius, color));
+        self.overrides.mark("shadow");
+        self.dirty = true;
+        self
+    }
+
+    /// Style this box from a style sheet class; can be called repeatedly
+    pub fn with_class(mut self, class: impl Into<String>) -> Self {
+        self.classes.push(class.into());
         self.dirty = true;
         self
     }
@@ -346,6 +377,38 @@ impl BoxWidget {
         }
     }
 
+    /// Take background, border, padding and shadow from this box's classes.
+    /// Boxes aren't themed, so properties the sheet leaves unset go back to
+    /// their defaults.
+    pub fn apply_style_sheet(&mut self, sheet: &StyleSheet, viewport: ViewportSize) {
+        if self.classes.is_empty() {
+            return;
+        }
+        let style = sheet.resolve(&self.classes, None, viewport);
+        if !self.overrides.is_set("background") {
+            self.background = style.background.map(Background::Color);
+        }
+        if !self.overrides.is_set("border_radius") {
+            self.border_radius = style.border_radius.unwrap_or(0.0);
+        }
+        if !self.overrides.is_set("border") {
+            self.border = style.border();
+        }
+        if !self.overrides.is_set("padding") {
+            self.padding = style.padding.unwrap_or_default();
+        }
+        if !self.overrides.is_set("shadow") {
+            self.shadow = style.shadow.as_ref().map(|shadow| {
+                Shadow::new(self.x, self.y, self.width, self.height, shadow.offset_x, shadow.offset_y, shadow.blur_radius, shadow.color)
+            });
+        }
+        self.dirty = true;
+    }
+
+    pub fn get_classes(&self) -> &[String] {
+        &self.classes
+    }
+
     pub fn get_content_area(&self) -> (f32, f32, f32, f32) {
         (
             self.x + self.padding.left,
@@ -363,6 +426,14 @@ impl BoxWidget {
         })
     }
 
+    /// Border drawn as a larger rectangle behind the background
+    pub fn create_border_rectangle(&self) -> Option<Rectangle> {
+        self.border.map(|(width, color)| {
+            Rectangle::new(self.x - width, self.y - width, self.width + width * 2.0, self.height + width * 2.0, color)
+                .with_border_radius(self.border_radius + width)
+        })
+    }
+
     pub fn create_shadow(&self) -> Option<Shadow> {
         self.shadow.as_ref().map(|shadow| {
             Shadow::new(self.x, self.y, self.width, self.height, 
@@ -480,6 +551,10 @@ impl Widget for BoxWidget {
         self
     }
 
+    fn children_mut(&mut self) -> Vec<&mut Element> {
+        self.children.iter_mut().collect()
+    }
+
     fn get_id(&self) -> WidgetId {
         self.id
     }
@@ -616,6 +691,14 @@ impl StackWidget {
         self.dirty = true;
     }
 
+    pub fn get_children_mut(&mut self) -> &mut Vec<Element> {
+        &mut self.children
+    }
+
+    pub fn get_children(&self) -> &Vec<Element> {
+        &self.children
+    }
+
     pub fn with_shadow(mut self, offset_x: f32, offset_y: f32, blur_radius: f32, color: Color) -> Self {
         self.shadow = Some(Shadow::new(self.x, self.y, self.width, self.height, offset_x, offset_y, blur_radius, color));
         self.dirty = true;
@@ -710,6 +793,10 @@ impl Widget for StackWidget {
         self
     }
 
+    fn children_mut(&mut self) -> Vec<&mut Element> {
+        self.children.iter_mut().collect()
+    }
+
     fn get_id(&self) -> WidgetId {
         self.id
     }
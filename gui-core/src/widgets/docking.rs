@@ -0,0 +1,1215 @@
+use crate::{Widget, WidgetId, EventResult, WidgetError, RenderData, DirtyRegion, WidgetUpdateContext};
+use crate::event::Event;
+use crate::element::Element;
+use crate::theme::{current_theme, ShadowToken, StyleOverrides, Theme};
+use winit::event::{ElementState, MouseButton};
+use gui_reactive::Signal;
+use gui_render::primitives::{Rectangle, Shadow, Text};
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use vello::peniko::Color;
+use super::splitter::SplitDirection;
+
+static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(13000);
+
+// Pointer travel before a pressed tab turns into a drag
+const DRAG_THRESHOLD: f32 = 6.0;
+// Band along the dock space edges that docks against the whole layout
+const ROOT_EDGE_BAND: f32 = 24.0;
+const MIN_PANE_SIZE: f32 = 40.0;
+
+/// A node of the docked layout: a group of tabbed panels or a split between nodes
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DockNode {
+    Tabs {
+        panels: Vec<String>,
+        active: usize,
+    },
+    Split {
+        direction: SplitDirection,
+        // Fractions of the space left after dividers
+        sizes: Vec<f32>,
+        children: Vec<DockNode>,
+    },
+}
+
+impl DockNode {
+    pub fn tabs<S: Into<String>>(panels: impl IntoIterator<Item = S>) -> Self {
+        DockNode::Tabs {
+            panels: panels.into_iter().map(Into::into).collect(),
+            active: 0,
+        }
+    }
+
+    pub fn split(direction: SplitDirection, children: Vec<DockNode>) -> Self {
+        let count = children.len().max(1);
+        DockNode::Split {
+            direction,
+            sizes: vec![1.0 / count as f32; children.len()],
+            children,
+        }
+    }
+
+    pub fn with_sizes(mut self, new_sizes: Vec<f32>) -> Self {
+        if let DockNode::Split { sizes, children, .. } = &mut self {
+            if new_sizes.len() == children.len() {
+                *sizes = new_sizes;
+            }
+        }
+        self
+    }
+
+    fn collect_panels(&self, panels: &mut Vec<String>) {
+        match self {
+            DockNode::Tabs { panels: tabs, .. } => panels.extend(tabs.iter().cloned()),
+            DockNode::Split { children, .. } => {
+                for child in children {
+                    child.collect_panels(panels);
+                }
+            },
+        }
+    }
+
+    fn remove_panel(&mut self, id: &str) -> bool {
+        match self {
+            DockNode::Tabs { panels, active } => {
+                let Some(position) = panels.iter().position(|panel| panel == id) else {
+                    return false;
+                };
+                panels.remove(position);
+                if position < *active || *active >= panels.len() {
+                    *active = active.saturating_sub(1);
+                }
+                true
+            },
+            DockNode::Split { children, .. } => children.iter_mut().any(|child| child.remove_panel(id)),
+        }
+    }
+
+    /// Drop empty tab groups and splits left with a single child
+    fn pruned(self) -> Option<DockNode> {
+        match self {
+            DockNode::Tabs { ref panels, .. } if panels.is_empty() => None,
+            DockNode::Tabs { .. } => Some(self),
+            DockNode::Split { direction, sizes, children } => {
+                let (mut sizes, mut children): (Vec<f32>, Vec<DockNode>) = sizes.into_iter()
+                    .zip(children)
+                    .filter_map(|(size, child)| child.pruned().map(|child| (size, child)))
+                    .unzip();
+                match children.len() {
+                    0 => None,
+                    1 => children.pop(),
+                    _ => {
+                        let total: f32 = sizes.iter().sum();
+                        if total > 0.0 {
+                            sizes.iter_mut().for_each(|size| *size /= total);
+                        }
+                        Some(DockNode::Split { direction, sizes, children })
+                    },
+                }
+            },
+        }
+    }
+
+    /// Path of child indices to the tab group holding `id`
+    fn find_group(&self, id: &str) -> Option<Vec<usize>> {
+        match self {
+            DockNode::Tabs { panels, .. } => panels.iter().any(|panel| panel == id).then(Vec::new),
+            DockNode::Split { children, .. } => children.iter().enumerate().find_map(|(index, child)| {
+                child.find_group(id).map(|mut path| {
+                    path.insert(0, index);
+                    path
+                })
+            }),
+        }
+    }
+
+    fn node_at_mut(&mut self, path: &[usize]) -> Option<&mut DockNode> {
+        match path.split_first() {
+            None => Some(self),
+            Some((index, rest)) => match self {
+                DockNode::Split { children, .. } => children.get_mut(*index)?.node_at_mut(rest),
+                DockNode::Tabs { .. } => None,
+            },
+        }
+    }
+
+    fn first_group_mut(&mut self) -> &mut DockNode {
+        // Decided before borrowing a child so that `self` can still be returned
+        if !matches!(self, DockNode::Split { children, .. } if !children.is_empty()) {
+            return self;
+        }
+        match self {
+            DockNode::Split { children, .. } => children[0].first_group_mut(),
+            node => node,
+        }
+    }
+
+    fn activate(&mut self, id: &str) -> bool {
+        match self {
+            DockNode::Tabs { panels, active } => match panels.iter().position(|panel| panel == id) {
+                Some(position) => {
+                    *active = position;
+                    true
+                },
+                None => false,
+            },
+            DockNode::Split { children, .. } => children.iter_mut().any(|child| child.activate(id)),
+        }
+    }
+}
+
+/// Panels torn off into an in-app floating window
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FloatingGroup {
+    pub panels: Vec<String>,
+    pub active: usize,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Where a dragged panel lands relative to a group or the whole layout
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DockZone {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    Center,
+}
+
+impl DockZone {
+    fn split(&self) -> Option<(SplitDirection, bool)> {
+        match self {
+            DockZone::Left => Some((SplitDirection::Horizontal, true)),
+            DockZone::Right => Some((SplitDirection::Horizontal, false)),
+            DockZone::Top => Some((SplitDirection::Vertical, true)),
+            DockZone::Bottom => Some((SplitDirection::Vertical, false)),
+            DockZone::Center => None,
+        }
+    }
+}
+
+/// Whole docking arrangement; serializes to JSON so editors can restore it
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DockLayout {
+    pub root: Option<DockNode>,
+    pub floating: Vec<FloatingGroup>,
+}
+
+impl DockLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_root(mut self, root: DockNode) -> Self {
+        self.root = Some(root);
+        self
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn panel_ids(&self) -> Vec<String> {
+        let mut panels = Vec::new();
+        if let Some(root) = &self.root {
+            root.collect_panels(&mut panels);
+        }
+        for group in &self.floating {
+            panels.extend(group.panels.iter().cloned());
+        }
+        panels
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.panel_ids().iter().any(|panel| panel == id)
+    }
+
+    pub fn remove_panel(&mut self, id: &str) -> bool {
+        let mut removed = false;
+        if let Some(root) = &mut self.root {
+            removed = root.remove_panel(id);
+        }
+        self.root = self.root.take().and_then(DockNode::pruned);
+
+        for group in &mut self.floating {
+            if let Some(position) = group.panels.iter().position(|panel| panel == id) {
+                group.panels.remove(position);
+                if position < group.active || group.active >= group.panels.len() {
+                    group.active = group.active.saturating_sub(1);
+                }
+                removed = true;
+            }
+        }
+        self.floating.retain(|group| !group.panels.is_empty());
+        removed
+    }
+
+    /// Dock `panel` next to, or as a tab beside, the group holding `anchor`.
+    /// Floating groups only take tabs.
+    pub fn dock_panel(&mut self, panel: &str, anchor: &str, zone: DockZone) -> bool {
+        if panel == anchor {
+            return false;
+        }
+        if let Some(group) = self.floating.iter_mut().find(|group| group.panels.iter().any(|id| id == anchor)) {
+            group.panels.push(panel.to_string());
+            group.active = group.panels.len() - 1;
+            return true;
+        }
+
+        let Some(root) = &mut self.root else {
+            return false;
+        };
+        let Some(path) = root.find_group(anchor) else {
+            return false;
+        };
+
+        let Some((direction, before)) = zone.split() else {
+            if let Some(DockNode::Tabs { panels, active }) = root.node_at_mut(&path) {
+                panels.push(panel.to_string());
+                *active = panels.len() - 1;
+            }
+            return true;
+        };
+        let new_group = DockNode::tabs([panel]);
+
+        // Join a parent split running the same way rather than nesting another one
+        if let Some((&index, parent_path)) = path.split_last() {
+            if let Some(DockNode::Split { direction: parent_direction, sizes, children }) = root.node_at_mut(parent_path) {
+                if *parent_direction == direction {
+                    let half = sizes[index] / 2.0;
+                    sizes[index] = half;
+                    let insert_at = if before { index } else { index + 1 };
+                    sizes.insert(insert_at, half);
+                    children.insert(insert_at, new_group);
+                    return true;
+                }
+            }
+        }
+
+        if let Some(node) = root.node_at_mut(&path) {
+            let existing = std::mem::replace(node, DockNode::tabs(Vec::<String>::new()));
+            let children = if before { vec![new_group, existing] } else { vec![existing, new_group] };
+            *node = DockNode::split(direction, children);
+        }
+        true
+    }
+
+    /// Dock `panel` along an edge of the whole layout, taking a quarter of it
+    pub fn dock_at_root(&mut self, panel: &str, zone: DockZone) {
+        let new_group = DockNode::tabs([panel]);
+        let Some(root) = self.root.take() else {
+            self.root = Some(new_group);
+            return;
+        };
+
+        let Some((direction, before)) = zone.split() else {
+            let mut root = root;
+            if let DockNode::Tabs { panels, active } = root.first_group_mut() {
+                panels.push(panel.to_string());
+                *active = panels.len() - 1;
+            }
+            self.root = Some(root);
+            return;
+        };
+
+        self.root = Some(match root {
+            DockNode::Split { direction: root_direction, mut sizes, mut children } if root_direction == direction => {
+                sizes.iter_mut().for_each(|size| *size *= 0.75);
+                let insert_at = if before { 0 } else { children.len() };
+                sizes.insert(insert_at, 0.25);
+                children.insert(insert_at, new_group);
+                DockNode::Split { direction, sizes, children }
+            },
+            root => {
+                let (sizes, children) = if before {
+                    (vec![0.25, 0.75], vec![new_group, root])
+                } else {
+                    (vec![0.75, 0.25], vec![root, new_group])
+                };
+                DockNode::Split { direction, sizes, children }
+            },
+        });
+    }
+
+    pub fn float_panel(&mut self, panel: &str, x: f32, y: f32, width: f32, height: f32) {
+        self.floating.push(FloatingGroup {
+            panels: vec![panel.to_string()],
+            active: 0,
+            x,
+            y,
+            width,
+            height,
+        });
+    }
+
+    pub fn activate(&mut self, panel: &str) {
+        if let Some(root) = &mut self.root {
+            if root.activate(panel) {
+                return;
+            }
+        }
+        for group in &mut self.floating {
+            if let Some(position) = group.panels.iter().position(|id| id == panel) {
+                group.active = position;
+            }
+        }
+    }
+
+    /// Drop panels that are not registered and add registered ones the layout lacks,
+    /// so a restored layout always matches the panels the app provides
+    pub fn reconcile(&mut self, known: &[String]) {
+        for id in self.panel_ids() {
+            if !known.contains(&id) {
+                self.remove_panel(&id);
+            }
+        }
+        for id in known {
+            if !self.contains(id) {
+                self.dock_at_root(id, DockZone::Center);
+            }
+        }
+    }
+}
+
+struct DockPanel {
+    id: String,
+    title: String,
+    content: Element,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum GroupRef {
+    Docked(Vec<usize>),
+    Floating(usize),
+}
+
+#[derive(Clone, Debug)]
+struct GroupRegion {
+    group: GroupRef,
+    panels: Vec<String>,
+    active: usize,
+    rect: (f32, f32, f32, f32),
+}
+
+#[derive(Clone, Debug)]
+struct DividerRegion {
+    // Path to the split node
+    path: Vec<usize>,
+    index: usize,
+    direction: SplitDirection,
+    // Length of the split along its axis
+    length: f32,
+    rect: (f32, f32, f32, f32),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum DropKind {
+    Dock { anchor: String, zone: DockZone },
+    Root(DockZone),
+    Float,
+}
+
+#[derive(Clone, Debug)]
+struct DropTarget {
+    kind: DropKind,
+    preview: (f32, f32, f32, f32),
+}
+
+enum DockDrag {
+    Panel {
+        id: String,
+        start: (f32, f32),
+        position: (f32, f32),
+        active: bool,
+    },
+    Divider {
+        path: Vec<usize>,
+        index: usize,
+        direction: SplitDirection,
+        last: f32,
+    },
+    Floating {
+        index: usize,
+        offset: (f32, f32),
+    },
+}
+
+// The drop preview is the accent color, faint enough to see the panels under it
+fn preview_color(accent: Color) -> Color {
+    Color::rgba8(accent.r, accent.g, accent.b, 70)
+}
+
+/// Editor-style docking area. Panels live in tab groups that can be dragged to
+/// dock beside other groups, joined as tabs, or torn off into floating groups.
+pub struct DockSpaceWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    panels: Vec<DockPanel>,
+    layout: Signal<DockLayout>,
+    drag: Option<DockDrag>,
+    drop_target: Option<DropTarget>,
+    tab_height: f32,
+    divider_thickness: f32,
+    font_size: f32,
+    background_color: Color,
+    header_color: Color,
+    tab_color: Color,
+    active_tab_color: Color,
+    divider_color: Color,
+    text_color: Color,
+    preview_color: Color,
+    floating_shadow: ShadowToken,
+    overrides: StyleOverrides,
+    pub dirty: bool,
+    // Shared dirty flag that reactive signals can set
+    reactive_dirty: Arc<RwLock<bool>>,
+}
+
+impl DockSpaceWidget {
+    pub fn new() -> Self {
+        let theme = current_theme();
+        let palette = &theme.palette;
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 800.0,
+            height: 600.0,
+            panels: Vec::new(),
+            layout: Signal::new(DockLayout::new()),
+            drag: None,
+            drop_target: None,
+            tab_height: 26.0,
+            divider_thickness: 4.0,
+            font_size: 12.0,
+            background_color: palette.surface,
+            header_color: palette.background,
+            tab_color: palette.surface_hover,
+            active_tab_color: palette.surface,
+            divider_color: palette.border,
+            text_color: palette.text,
+            preview_color: preview_color(palette.primary),
+            floating_shadow: theme.shadows.large.clone(),
+            overrides: StyleOverrides::default(),
+            dirty: true,
+            reactive_dirty: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self.dirty = true;
+        self
+    }
+
+    /// Register a panel. Panels missing from the layout are added as tabs of the first group.
+    pub fn with_panel(mut self, id: impl Into<String>, title: impl Into<String>, content: Element) -> Self {
+        self.panels.push(DockPanel {
+            id: id.into(),
+            title: title.into(),
+            content,
+        });
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_layout(self, layout: DockLayout) -> Self {
+        self.layout.set(layout);
+        self
+    }
+
+    /// Share the layout with the app, e.g. to save it on exit and restore it later
+    pub fn with_layout_signal(mut self, layout: Signal<DockLayout>) -> Self {
+        self.layout = layout;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_tab_height(mut self, height: f32) -> Self {
+        self.tab_height = height;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_colors(mut self, background: Color, header: Color, active_tab: Color, text: Color) -> Self {
+        self.background_color = background;
+        self.header_color = header;
+        self.active_tab_color = active_tab;
+        self.text_color = text;
+        self.overrides.mark("colors");
+        self.dirty = true;
+        self
+    }
+
+    /// Take every color not set through `with_colors` from `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let palette = &theme.palette;
+        if !self.overrides.is_set("colors") {
+            self.background_color = palette.surface;
+            self.header_color = palette.background;
+            self.active_tab_color = palette.surface;
+            self.text_color = palette.text;
+        }
+        self.tab_color = palette.surface_hover;
+        self.divider_color = palette.border;
+        self.preview_color = preview_color(palette.primary);
+        self.floating_shadow = theme.shadows.large.clone();
+        self.dirty = true;
+    }
+
+    pub fn get_layout_signal(&self) -> Signal<DockLayout> {
+        self.layout.clone()
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        self.width = width;
+        self.height = height;
+        self.dirty = true;
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    fn panel_ids(&self) -> Vec<String> {
+        self.panels.iter().map(|panel| panel.id.clone()).collect()
+    }
+
+    fn reconcile_layout(&mut self) {
+        let known = self.panel_ids();
+        let needs_fix = self.layout.with(|layout| {
+            let ids = layout.panel_ids();
+            ids.len() != known.len() || known.iter().any(|id| !ids.contains(id))
+        });
+        if needs_fix {
+            self.layout.update(|layout| layout.reconcile(&known));
+        }
+    }
+
+    fn title_of<'a>(&'a self, id: &'a str) -> &'a str {
+        self.panels.iter().find(|panel| panel.id == id).map_or(id, |panel| panel.title.as_str())
+    }
+
+    fn content_rect(&self, rect: (f32, f32, f32, f32)) -> (f32, f32, f32, f32) {
+        let (x, y, width, height) = rect;
+        (x, y + self.tab_height, width, (height - self.tab_height).max(0.0))
+    }
+
+    fn layout_node(&self, node: &DockNode, path: Vec<usize>, rect: (f32, f32, f32, f32), groups: &mut Vec<GroupRegion>, dividers: &mut Vec<DividerRegion>) {
+        match node {
+            DockNode::Tabs { panels, active } => groups.push(GroupRegion {
+                group: GroupRef::Docked(path),
+                panels: panels.clone(),
+                active: *active,
+                rect,
+            }),
+            DockNode::Split { direction, sizes, children } => {
+                let (x, y, width, height) = rect;
+                let axis = match direction {
+                    SplitDirection::Horizontal => width,
+                    SplitDirection::Vertical => height,
+                };
+                let available = (axis - children.len().saturating_sub(1) as f32 * self.divider_thickness).max(0.0);
+                let total: f32 = sizes.iter().sum::<f32>().max(f32::EPSILON);
+                let mut offset = 0.0;
+                for (index, child) in children.iter().enumerate() {
+                    let size = sizes.get(index).copied().unwrap_or(0.0) / total * available;
+                    let child_rect = match direction {
+                        SplitDirection::Horizontal => (x + offset, y, size, height),
+                        SplitDirection::Vertical => (x, y + offset, width, size),
+                    };
+                    let mut child_path = path.clone();
+                    child_path.push(index);
+                    self.layout_node(child, child_path, child_rect, groups, dividers);
+                    offset += size;
+
+                    if index + 1 < children.len() {
+                        let divider_rect = match direction {
+                            SplitDirection::Horizontal => (x + offset, y, self.divider_thickness, height),
+                            SplitDirection::Vertical => (x, y + offset, width, self.divider_thickness),
+                        };
+                        dividers.push(DividerRegion { path: path.clone(), index, direction: *direction, length: axis, rect: divider_rect });
+                        offset += self.divider_thickness;
+                    }
+                }
+            },
+        }
+    }
+
+    /// Docked groups in layout order followed by floating groups, back to front
+    fn regions(&self) -> (Vec<GroupRegion>, Vec<DividerRegion>) {
+        let mut groups = Vec::new();
+        let mut dividers = Vec::new();
+        self.layout.with(|layout| {
+            if let Some(root) = &layout.root {
+                self.layout_node(root, Vec::new(), (self.x, self.y, self.width, self.height), &mut groups, &mut dividers);
+            }
+            for (index, group) in layout.floating.iter().enumerate() {
+                groups.push(GroupRegion {
+                    group: GroupRef::Floating(index),
+                    panels: group.panels.clone(),
+                    active: group.active,
+                    rect: (group.x, group.y, group.width, group.height),
+                });
+            }
+        });
+        (groups, dividers)
+    }
+
+    fn tab_rects(&self, region: &GroupRegion) -> Vec<(f32, f32, f32, f32)> {
+        let (x, y, width, _) = region.rect;
+        let mut offset = x;
+        region.panels.iter().map(|id| {
+            let tab_width = (self.title_of(id).len() as f32 * self.font_size * 0.6 + 20.0).min(x + width - offset).max(0.0);
+            let rect = (offset, y, tab_width, self.tab_height);
+            offset += tab_width + 1.0;
+            rect
+        }).collect()
+    }
+
+    fn contains((x, y, width, height): (f32, f32, f32, f32), px: f32, py: f32) -> bool {
+        px >= x && px <= x + width && py >= y && py <= y + height
+    }
+
+    /// Panels currently shown, with the content rectangle each should fill
+    pub fn visible_panel_rects(&self) -> Vec<(String, (f32, f32, f32, f32))> {
+        let (groups, _) = self.regions();
+        groups.iter().filter_map(|region| {
+            region.panels.get(region.active).map(|id| (id.clone(), self.content_rect(region.rect)))
+        }).collect()
+    }
+
+    pub fn panel_content_mut(&mut self, id: &str) -> Option<&mut Element> {
+        self.panels.iter_mut().find(|panel| panel.id == id).map(|panel| &mut panel.content)
+    }
+
+    /// Content of every panel, shown or not
+    pub fn panel_contents_mut(&mut self) -> impl Iterator<Item = &mut Element> {
+        self.panels.iter_mut().map(|panel| &mut panel.content)
+    }
+
+    pub fn panel_content(&self, id: &str) -> Option<&Element> {
+        self.panels.iter().find(|panel| panel.id == id).map(|panel| &panel.content)
+    }
+
+    /// `None` when the panel would land where it already is
+    fn drop_target_at(&self, px: f32, py: f32, dragged: &str) -> Option<DropTarget> {
+        let (groups, _) = self.regions();
+
+        // Edges of the whole dock space
+        let band = ROOT_EDGE_BAND;
+        let root_zone = if !self.is_point_inside(px, py) {
+            None
+        } else if px < self.x + band {
+            Some(DockZone::Left)
+        } else if px > self.x + self.width - band {
+            Some(DockZone::Right)
+        } else if py < self.y + band {
+            Some(DockZone::Top)
+        } else if py > self.y + self.height - band {
+            Some(DockZone::Bottom)
+        } else {
+            None
+        };
+        if let Some(zone) = root_zone {
+            let quarter_width = self.width / 4.0;
+            let quarter_height = self.height / 4.0;
+            let preview = match zone {
+                DockZone::Left => (self.x, self.y, quarter_width, self.height),
+                DockZone::Right => (self.x + self.width - quarter_width, self.y, quarter_width, self.height),
+                DockZone::Top => (self.x, self.y, self.width, quarter_height),
+                _ => (self.x, self.y + self.height - quarter_height, self.width, quarter_height),
+            };
+            return Some(DropTarget { kind: DropKind::Root(zone), preview });
+        }
+
+        // Topmost group under the pointer, floating groups first
+        for region in groups.iter().rev() {
+            if !Self::contains(region.rect, px, py) {
+                continue;
+            }
+            let Some(anchor) = region.panels.iter().find(|id| id.as_str() != dragged).cloned() else {
+                // Only the dragged panel lives here; dropping back is a no-op
+                return None;
+            };
+            let (x, y, width, height) = region.rect;
+            let floating = matches!(region.group, GroupRef::Floating(_));
+            let relative_x = (px - x) / width.max(1.0);
+            let relative_y = (py - y - self.tab_height) / (height - self.tab_height).max(1.0);
+            let zone = if floating || py < y + self.tab_height {
+                DockZone::Center
+            } else if relative_x < 0.25 {
+                DockZone::Left
+            } else if relative_x > 0.75 {
+                DockZone::Right
+            } else if relative_y < 0.25 {
+                DockZone::Top
+            } else if relative_y > 0.75 {
+                DockZone::Bottom
+            } else {
+                DockZone::Center
+            };
+            let preview = match zone {
+                DockZone::Left => (x, y, width / 2.0, height),
+                DockZone::Right => (x + width / 2.0, y, width / 2.0, height),
+                DockZone::Top => (x, y, width, height / 2.0),
+                DockZone::Bottom => (x, y + height / 2.0, width, height / 2.0),
+                DockZone::Center => region.rect,
+            };
+            return Some(DropTarget { kind: DropKind::Dock { anchor, zone }, preview });
+        }
+
+        Some(DropTarget {
+            kind: DropKind::Float,
+            preview: (px - 40.0, py - self.tab_height / 2.0, 320.0, 240.0),
+        })
+    }
+
+    fn apply_drop(&mut self, panel: &str, target: DropTarget) {
+        self.layout.update(|layout| {
+            let before = layout.clone();
+            layout.remove_panel(panel);
+            let applied = match &target.kind {
+                DropKind::Dock { anchor, zone } => layout.dock_panel(panel, anchor, *zone),
+                DropKind::Root(zone) => {
+                    layout.dock_at_root(panel, *zone);
+                    true
+                },
+                DropKind::Float => {
+                    let (x, y, width, height) = target.preview;
+                    layout.float_panel(panel, x, y, width, height);
+                    true
+                },
+            };
+            if !applied {
+                *layout = before;
+            }
+        });
+        self.dirty = true;
+    }
+
+    fn move_divider(&mut self, path: &[usize], index: usize, delta: f32) {
+        let (_, dividers) = self.regions();
+        let Some(axis) = dividers.iter()
+            .find(|divider| divider.path == path && divider.index == index)
+            .map(|divider| divider.length) else {
+            return;
+        };
+
+        self.layout.update(|layout| {
+            let Some(root) = &mut layout.root else {
+                return;
+            };
+            if let Some(DockNode::Split { sizes, children, .. }) = root.node_at_mut(path) {
+                let available = (axis - children.len().saturating_sub(1) as f32 * self.divider_thickness).max(1.0);
+                let total: f32 = sizes.iter().sum::<f32>().max(f32::EPSILON);
+                let first = sizes[index] / total * available;
+                let second = sizes[index + 1] / total * available;
+                let pair = first + second;
+                let new_first = (first + delta).clamp(MIN_PANE_SIZE.min(pair / 2.0), (pair - MIN_PANE_SIZE).max(pair / 2.0));
+                sizes[index] = new_first / available * total;
+                sizes[index + 1] = (pair - new_first) / available * total;
+            }
+        });
+        self.dirty = true;
+    }
+
+    fn handle_press(&mut self, px: f32, py: f32) -> EventResult {
+        let (groups, dividers) = self.regions();
+
+        // Floating groups sit on top, so test them first
+        for region in groups.iter().rev() {
+            if !Self::contains(region.rect, px, py) {
+                continue;
+            }
+            let tabs = self.tab_rects(region);
+            if let Some(tab) = tabs.iter().position(|rect| Self::contains(*rect, px, py)) {
+                let id = region.panels[tab].clone();
+                self.layout.update(|layout| layout.activate(&id));
+                self.drag = Some(DockDrag::Panel { id, start: (px, py), position: (px, py), active: false });
+                self.dirty = true;
+                return EventResult::Handled;
+            }
+            if let GroupRef::Floating(index) = region.group {
+                let (x, y, _, _) = region.rect;
+                if py < y + self.tab_height {
+                    // Empty header space moves the floating group
+                    self.drag = Some(DockDrag::Floating { index, offset: (px - x, py - y) });
+                    return EventResult::Handled;
+                }
+            }
+            return EventResult::Ignored;
+        }
+
+        if let Some(divider) = dividers.iter().find(|divider| Self::contains(divider.rect, px, py)) {
+            let last = match divider.direction {
+                SplitDirection::Horizontal => px,
+                SplitDirection::Vertical => py,
+            };
+            self.drag = Some(DockDrag::Divider {
+                path: divider.path.clone(),
+                index: divider.index,
+                direction: divider.direction,
+                last,
+            });
+            return EventResult::Handled;
+        }
+        EventResult::Ignored
+    }
+
+    fn handle_drag_move(&mut self, px: f32, py: f32) -> EventResult {
+        match self.drag.take() {
+            Some(DockDrag::Panel { id, start, active, .. }) => {
+                let active = active || (px - start.0).hypot(py - start.1) > DRAG_THRESHOLD;
+                if active {
+                    self.drop_target = self.drop_target_at(px, py, &id);
+                    self.dirty = true;
+                }
+                self.drag = Some(DockDrag::Panel { id, start, position: (px, py), active });
+                EventResult::Handled
+            },
+            Some(DockDrag::Divider { path, index, direction, last }) => {
+                let position = match direction {
+                    SplitDirection::Horizontal => px,
+                    SplitDirection::Vertical => py,
+                };
+                self.move_divider(&path, index, position - last);
+                self.drag = Some(DockDrag::Divider { path, index, direction, last: position });
+                EventResult::Handled
+            },
+            Some(DockDrag::Floating { index, offset }) => {
+                self.layout.update(|layout| {
+                    if let Some(group) = layout.floating.get_mut(index) {
+                        group.x = px - offset.0;
+                        group.y = py - offset.1;
+                    }
+                });
+                self.drag = Some(DockDrag::Floating { index, offset });
+                self.dirty = true;
+                EventResult::Handled
+            },
+            None => EventResult::Ignored,
+        }
+    }
+
+    fn handle_release(&mut self) -> EventResult {
+        let Some(drag) = self.drag.take() else {
+            return EventResult::Ignored;
+        };
+        if let DockDrag::Panel { id, active: true, .. } = drag {
+            if let Some(target) = self.drop_target.take() {
+                self.apply_drop(&id, target);
+            }
+        }
+        self.drop_target = None;
+        self.dirty = true;
+        EventResult::Handled
+    }
+
+    fn in_layer(region: &GroupRegion, layer: Option<usize>) -> bool {
+        match (&region.group, layer) {
+            (GroupRef::Docked(_), None) => true,
+            (GroupRef::Floating(index), Some(layer)) => *index == layer,
+            _ => false,
+        }
+    }
+
+    pub fn floating_count(&self) -> usize {
+        self.layout.with(|layout| layout.floating.len())
+    }
+
+    /// Group backgrounds, tab strips and dividers of a layer: `None` for the docked
+    /// groups, `Some(index)` for a floating group
+    pub fn create_chrome_rectangles(&self, layer: Option<usize>) -> Vec<Rectangle> {
+        let (groups, dividers) = self.regions();
+        let mut rectangles = Vec::new();
+        if layer.is_none() {
+            rectangles.push(Rectangle::new(self.x, self.y, self.width, self.height, self.divider_color));
+        }
+        for region in groups.iter().filter(|region| Self::in_layer(region, layer)) {
+            let (x, y, width, height) = region.rect;
+            rectangles.push(Rectangle::new(x, y, width, height, self.background_color));
+            rectangles.push(Rectangle::new(x, y, width, self.tab_height, self.header_color));
+            for (index, (tab_x, tab_y, tab_width, tab_height)) in self.tab_rects(region).into_iter().enumerate() {
+                let color = if index == region.active { self.active_tab_color } else { self.tab_color };
+                rectangles.push(Rectangle::new(tab_x, tab_y, tab_width, tab_height, color));
+            }
+        }
+        if layer.is_none() {
+            for divider in dividers {
+                let (x, y, width, height) = divider.rect;
+                rectangles.push(Rectangle::new(x, y, width, height, self.divider_color));
+            }
+        }
+        rectangles
+    }
+
+    pub fn create_floating_shadow(&self, index: usize) -> Option<Shadow> {
+        self.layout.with(|layout| {
+            layout.floating.get(index).map(|group| {
+                let shadow = &self.floating_shadow;
+                Shadow::new(group.x, group.y, group.width, group.height,
+                           shadow.offset_x, shadow.offset_y, shadow.blur_radius, shadow.color)
+            })
+        })
+    }
+
+    pub fn create_tab_texts(&self, layer: Option<usize>) -> Vec<Text> {
+        let (groups, _) = self.regions();
+        let mut texts = Vec::new();
+        for region in groups.iter().filter(|region| Self::in_layer(region, layer)) {
+            for (id, (tab_x, tab_y, _, tab_height)) in region.panels.iter().zip(self.tab_rects(region)) {
+                let text_y = tab_y + (tab_height / 2.0) + (self.font_size * 0.25);
+                texts.push(Text::new(tab_x + 10.0, text_y, self.title_of(id).to_string(), self.text_color, self.font_size));
+            }
+        }
+        texts
+    }
+
+    /// Ids of the active panels of a layer
+    pub fn visible_panels(&self, layer: Option<usize>) -> Vec<String> {
+        let (groups, _) = self.regions();
+        groups.iter()
+            .filter(|region| Self::in_layer(region, layer))
+            .filter_map(|region| region.panels.get(region.active).cloned())
+            .collect()
+    }
+
+    /// Highlight where the dragged panel would land, plus a ghost tab under the pointer
+    pub fn create_drop_preview(&self) -> Vec<Rectangle> {
+        let mut rectangles = Vec::new();
+        if let Some(target) = &self.drop_target {
+            let (x, y, width, height) = target.preview;
+            rectangles.push(Rectangle::new(x, y, width, height, self.preview_color).with_border_radius(4.0));
+        }
+        if let Some(DockDrag::Panel { id, position, active: true, .. }) = &self.drag {
+            let tab_width = self.title_of(id).len() as f32 * self.font_size * 0.6 + 20.0;
+            rectangles.push(Rectangle::new(position.0 - tab_width / 2.0, position.1 - self.tab_height / 2.0,
+                tab_width, self.tab_height, self.active_tab_color).with_border_radius(3.0));
+        }
+        rectangles
+    }
+
+    pub fn create_drag_text(&self) -> Option<Text> {
+        let Some(DockDrag::Panel { id, position, active: true, .. }) = &self.drag else {
+            return None;
+        };
+        let title = self.title_of(id);
+        let tab_width = title.len() as f32 * self.font_size * 0.6 + 20.0;
+        Some(Text::new(position.0 - tab_width / 2.0 + 10.0, position.1 + self.font_size * 0.25,
+            title.to_string(), self.text_color, self.font_size))
+    }
+}
+
+impl Default for DockSpaceWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for DockSpaceWidget {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        self.reconcile_layout();
+        for panel in &mut self.panels {
+            panel.content.mount()?;
+        }
+        self.dirty = true;
+
+        let reactive_dirty = self.reactive_dirty.clone();
+        self.layout.subscribe_fn(move |_| {
+            if let Ok(mut dirty) = reactive_dirty.write() {
+                *dirty = true;
+            }
+        });
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        for panel in &mut self.panels {
+            panel.content.unmount()?;
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        if let Ok(mut reactive_dirty) = self.reactive_dirty.write() {
+            if *reactive_dirty {
+                *reactive_dirty = false;
+                self.dirty = true;
+            }
+        }
+        // A layout restored from disk may name other panels than the app registered
+        self.reconcile_layout();
+
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+
+        for panel in &mut self.panels {
+            panel.content.update(ctx)?;
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        if let Event::Mouse(mouse_event) = event {
+            let x = mouse_event.position.x as f32;
+            let y = mouse_event.position.y as f32;
+
+            if mouse_event.button.is_none() {
+                // Mouse move
+                if let EventResult::Handled = self.handle_drag_move(x, y) {
+                    return EventResult::Handled;
+                }
+            } else if mouse_event.button == Some(MouseButton::Left) {
+                let result = match mouse_event.state {
+                    ElementState::Pressed => self.handle_press(x, y),
+                    ElementState::Released => self.handle_release(),
+                };
+                if let EventResult::Handled = result {
+                    return EventResult::Handled;
+                }
+            }
+        }
+
+        // Floating panels are on top, so they see events first
+        let mut order: Vec<String> = (0..self.floating_count()).rev()
+            .flat_map(|index| self.visible_panels(Some(index)))
+            .collect();
+        order.extend(self.visible_panels(None));
+        for id in order {
+            if let Some(content) = self.panel_content_mut(&id) {
+                if let EventResult::Handled = content.handle_event(event) {
+                    return EventResult::Handled;
+                }
+            }
+        }
+        EventResult::Ignored
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            }],
+            z_index: 0,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Element> {
+        self.panel_contents_mut().collect()
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+// Convenience functions for creating dock spaces
+pub fn dock_space() -> DockSpaceWidget {
+    DockSpaceWidget::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editor_layout() -> DockLayout {
+        DockLayout::new().with_root(DockNode::split(SplitDirection::Horizontal, vec![
+            DockNode::tabs(["scene"]),
+            DockNode::tabs(["viewport", "console"]),
+        ]))
+    }
+
+    #[test]
+    fn docking_beside_joins_parent_split_of_same_direction() {
+        let mut layout = editor_layout();
+        assert!(layout.remove_panel("console"));
+        assert!(layout.dock_panel("console", "viewport", DockZone::Right));
+
+        let Some(DockNode::Split { children, sizes, .. }) = &layout.root else {
+            panic!("expected split root");
+        };
+        assert_eq!(children.len(), 3);
+        assert_eq!(children[2], DockNode::tabs(["console"]));
+        assert!((sizes.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn docking_across_nests_split_and_removal_prunes_it() {
+        let mut layout = editor_layout();
+        layout.remove_panel("console");
+        layout.dock_panel("console", "viewport", DockZone::Bottom);
+        assert_eq!(layout.root.as_ref().and_then(|root| root.find_group("console")), Some(vec![1, 1]));
+
+        layout.remove_panel("console");
+        assert_eq!(layout, DockLayout::new().with_root(DockNode::split(SplitDirection::Horizontal, vec![
+            DockNode::tabs(["scene"]),
+            DockNode::tabs(["viewport"]),
+        ])));
+    }
+
+    #[test]
+    fn layout_round_trips_through_json_and_reconciles_panels() {
+        let mut layout = editor_layout();
+        layout.remove_panel("scene");
+        layout.float_panel("scene", 10.0, 20.0, 300.0, 200.0);
+
+        let restored = DockLayout::from_json(&layout.to_json().unwrap()).unwrap();
+        assert_eq!(restored, layout);
+
+        let mut reconciled = restored;
+        reconciled.reconcile(&["viewport".to_string(), "inspector".to_string()]);
+        assert_eq!(reconciled.panel_ids(), vec!["viewport".to_string(), "inspector".to_string()]);
+    }
+}
@@ -3,16 +3,87 @@ use crate::event::Event;
 use crate::element::Element;
 use crate::sizing::{Unit, Size};
 use winit::event::ElementState;
+use winit::keyboard::KeyCode;
 use gui_reactive::Signal;
 use gui_render::primitives::{Rectangle, Shadow, Text};
 use std::any::Any;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use vello::peniko::Color;
 use super::container::{Background, BoxWidget, container};
+use super::interactive::InputWidget;
 use super::text::text;
+use crate::theme::{current_theme, StyleOverrides, Theme};
 
 static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(4000);
 
+// Typed characters within this window extend the type-ahead prefix
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// Where a fuzzy pattern matched a label
+#[derive(Clone, Debug, PartialEq)]
+pub struct FuzzyMatch {
+    pub score: i32,
+    /// Char indices of the matched characters, ascending
+    pub positions: Vec<usize>,
+}
+
+/// Case-insensitive subsequence match of `pattern` in `candidate`. Matches at
+/// word starts and runs of consecutive characters score higher; gaps cost a little.
+/// Whitespace in the pattern is ignored.
+pub fn fuzzy_match(pattern: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let pattern: Vec<char> = pattern.chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let chars: Vec<char> = candidate.chars().collect();
+    let lower: Vec<char> = chars.iter().map(|c| c.to_lowercase().next().unwrap_or(*c)).collect();
+
+    let is_word_start = |index: usize| {
+        index == 0
+            || !chars[index - 1].is_alphanumeric()
+            || (chars[index - 1].is_lowercase() && chars[index].is_uppercase())
+    };
+
+    let mut positions = Vec::with_capacity(pattern.len());
+    let mut score = 0;
+    let mut next = 0;
+    for wanted in pattern {
+        // Prefer the next word start holding the character over an earlier mid-word hit,
+        // unless the earlier one continues the current run
+        let first = (next..lower.len()).find(|&index| lower[index] == wanted)?;
+        let continues_run = positions.last().is_some_and(|last| last + 1 == first);
+        let index = if continues_run || is_word_start(first) {
+            first
+        } else {
+            (first..lower.len())
+                .find(|&index| lower[index] == wanted && is_word_start(index))
+                .unwrap_or(first)
+        };
+
+        score += 1;
+        if is_word_start(index) {
+            score += 8;
+        }
+        match positions.last() {
+            Some(last) if last + 1 == index => score += 5,
+            Some(last) => score -= ((index - last - 1) as i32).min(3),
+            None => score -= (index as i32).min(3),
+        }
+        positions.push(index);
+        next = index + 1;
+    }
+    Some(FuzzyMatch { score, positions })
+}
+
+// An option shown in the open list, with the characters the filter matched
+#[derive(Clone, Debug)]
+struct OptionMatch {
+    index: usize,
+    positions: Vec<usize>,
+}
+
 #[derive(Clone, Debug)]
 pub struct DropdownOption {
     pub value: String,
@@ -49,11 +120,36 @@ pub struct DropdownWidget {
     max_height: f32,
     shadow: Option<Shadow>,
     on_change: Option<Box<dyn Fn(&str) + Send + Sync>>,
+    // Combobox mode: a filter field above the list narrows it by fuzzy match
+    searchable: bool,
+    allow_free_text: bool,
+    filter: Signal<String>,
+    filter_input: InputWidget,
+    applied_filter: String,
+    // Options currently listed, best match first
+    matches: Vec<OptionMatch>,
+    // Index into `matches` of the keyboard/hover highlight
+    highlighted: Option<usize>,
+    // First listed row; only rows that fit below it are laid out and drawn
+    scroll_row: usize,
+    type_ahead: String,
+    last_type_time: Option<Instant>,
+    highlight_color: Color,
+    match_color: Color,
+    row_color: Color,
+    selected_row_color: Color,
+    options_changed: Arc<AtomicBool>,
+    // Style set through builders, which the theme leaves alone
+    overrides: StyleOverrides,
     pub dirty: bool,
+    // Shared dirty flag that reactive signals can set
+    reactive_dirty: Arc<RwLock<bool>>,
 }
 
 impl DropdownWidget {
     pub fn new() -> Self {
+        let filter = Signal::new(String::new());
+        let theme = current_theme();
         Self {
             id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
             x: 0.0,
@@ -65,22 +161,43 @@ impl DropdownWidget {
             options_signal: None,
             is_open: false,
             is_hovering: false,
-            background: Background::Color(Color::rgba8(255, 255, 255, 255)),
-            hover_background: Background::Color(Color::rgba8(245, 245, 245, 255)),
-            border_color: Color::rgba8(200, 200, 200, 255),
-            text_color: Color::rgba8(0, 0, 0, 255),
-            arrow_color: Color::rgba8(100, 100, 100, 255),
-            border_radius: 4.0,
-            font_size: 14.0,
+            background: Background::Color(theme.palette.surface),
+            hover_background: Background::Color(theme.palette.surface_hover),
+            border_color: theme.palette.border,
+            text_color: theme.palette.text,
+            arrow_color: theme.palette.text_muted,
+            border_radius: theme.radii.medium,
+            font_size: theme.typography.font_size,
             max_height: 200.0,
             shadow: None,
             on_change: None,
+            searchable: false,
+            allow_free_text: false,
+            filter: filter.clone(),
+            filter_input: InputWidget::new()
+                .with_size(200.0, 32.0)
+                .with_placeholder("Search...")
+                .with_signal(filter),
+            applied_filter: String::new(),
+            matches: Vec::new(),
+            highlighted: None,
+            scroll_row: 0,
+            type_ahead: String::new(),
+            last_type_time: None,
+            highlight_color: theme.palette.selection,
+            match_color: Color::rgba8(255, 214, 102, 160),
+            row_color: theme.palette.surface,
+            selected_row_color: theme.palette.surface_hover,
+            options_changed: Arc::new(AtomicBool::new(false)),
+            overrides: StyleOverrides::default(),
             dirty: true,
+            reactive_dirty: Arc::new(RwLock::new(false)),
         }
     }
 
     pub fn with_font_size(mut self, font_size: f32) -> Self {
         self.font_size = font_size;
+        self.overrides.mark("font_size");
         self.dirty = true;
         self
     }
@@ -88,10 +205,64 @@ impl DropdownWidget {
     pub fn with_size(mut self, width: f32, height: f32) -> Self {
         self.width = width;
         self.height = height;
+        self.filter_input.set_size(width, height);
+        self.dirty = true;
+        self
+    }
+
+    /// Height the open list may take; longer lists scroll
+    pub fn with_max_height(mut self, max_height: f32) -> Self {
+        self.max_height = max_height;
+        self.dirty = true;
+        self
+    }
+
+    /// Combobox mode: the open list gets a filter field at the top that fuzzy
+    /// matches option labels, best matches first
+    pub fn searchable(mut self) -> Self {
+        self.searchable = true;
         self.dirty = true;
         self
     }
 
+    /// Let Enter in the filter field pick the typed text when it names no option.
+    /// Implies combobox mode.
+    pub fn with_free_text(mut self, allow: bool) -> Self {
+        self.allow_free_text = allow;
+        self.searchable |= allow;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_highlight_colors(mut self, row: Color, matched: Color) -> Self {
+        self.highlight_color = row;
+        self.match_color = matched;
+        self.overrides.mark("highlight_color");
+        self.dirty = true;
+        self
+    }
+
+    /// Take every style property not set through a builder from `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let palette = &theme.palette;
+        if !self.overrides.is_set("font_size") {
+            self.font_size = theme.typography.font_size;
+        }
+        if !self.overrides.is_set("highlight_color") {
+            self.highlight_color = palette.selection;
+        }
+        self.background = Background::Color(palette.surface);
+        self.hover_background = Background::Color(palette.surface_hover);
+        self.border_color = palette.border;
+        self.text_color = palette.text;
+        self.arrow_color = palette.text_muted;
+        self.border_radius = theme.radii.medium;
+        self.row_color = palette.surface;
+        self.selected_row_color = palette.surface_hover;
+        self.filter_input.apply_theme(theme);
+        self.dirty = true;
+    }
+
     pub fn with_options(mut self, options: Vec<DropdownOption>) -> Self {
         self.options = options;
         self.dirty = true;
@@ -110,6 +281,14 @@ impl DropdownWidget {
         self
     }
 
+    /// Bind the selection to a signal. Picking an option writes through to it and
+    /// changes made elsewhere update the displayed choice.
+    pub fn with_selected_signal(mut self, selected: Signal<String>) -> Self {
+        self.selected_value = selected;
+        self.dirty = true;
+        self
+    }
+
     pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
         if self.selected_value.get().is_empty() {
             self.selected_value = Signal::new(placeholder.into());
@@ -138,10 +317,19 @@ impl DropdownWidget {
         if self.x != x || self.y != y {
             self.x = x;
             self.y = y;
+            self.filter_input.set_position(x, y + self.height);
             self.dirty = true;
         }
     }
 
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn get_filter_signal(&self) -> Signal<String> {
+        self.filter.clone()
+    }
+
     fn get_effective_options(&self) -> Vec<DropdownOption> {
         if let Some(signal) = &self.options_signal {
             signal.get()
@@ -150,6 +338,128 @@ impl DropdownWidget {
         }
     }
 
+    /// Options currently listed, in display order
+    pub fn get_listed_options(&self) -> Vec<DropdownOption> {
+        let options = self.get_effective_options();
+        self.matches.iter().filter_map(|matched| options.get(matched.index).cloned()).collect()
+    }
+
+    pub fn get_highlighted_option(&self) -> Option<DropdownOption> {
+        let matched = self.matches.get(self.highlighted?)?;
+        self.get_effective_options().get(matched.index).cloned()
+    }
+
+    // Re-run the filter over all options, keeping the highlight on the same option
+    fn refresh_matches(&mut self) {
+        let previous = self.highlighted
+            .and_then(|highlighted| self.matches.get(highlighted))
+            .map(|matched| matched.index);
+        let options = self.get_effective_options();
+        let filter = self.applied_filter.trim().to_string();
+
+        self.matches = if self.searchable && !filter.is_empty() {
+            let mut scored: Vec<(i32, OptionMatch)> = options.iter().enumerate()
+                .filter_map(|(index, option)| {
+                    fuzzy_match(&filter, &option.label).map(|found| (found.score, OptionMatch { index, positions: found.positions }))
+                })
+                .collect();
+            // Stable, so equally good matches keep their order
+            scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+            scored.into_iter().map(|(_, matched)| matched).collect()
+        } else {
+            (0..options.len()).map(|index| OptionMatch { index, positions: Vec::new() }).collect()
+        };
+
+        self.highlighted = if self.searchable && !filter.is_empty() {
+            (!self.matches.is_empty()).then_some(0)
+        } else {
+            previous.and_then(|index| self.matches.iter().position(|matched| matched.index == index))
+        };
+        self.scroll_row = 0;
+        self.scroll_to_highlight();
+        self.dirty = true;
+    }
+
+    fn visible_row_count(&self) -> usize {
+        ((self.max_height / self.height).floor() as usize).max(1)
+    }
+
+    fn listed_row_count(&self) -> usize {
+        let rows = self.matches.len().min(self.visible_row_count());
+        // An empty filtered list still shows a row saying so
+        if self.searchable { rows.max(1) } else { rows }
+    }
+
+    fn filter_height(&self) -> f32 {
+        if self.searchable { self.height } else { 0.0 }
+    }
+
+    fn list_top(&self) -> f32 {
+        self.y + self.height + self.filter_height()
+    }
+
+    fn popup_height(&self) -> f32 {
+        self.filter_height() + self.listed_row_count() as f32 * self.height
+    }
+
+    fn scroll_to_highlight(&mut self) {
+        let Some(highlighted) = self.highlighted else {
+            return;
+        };
+        let rows = self.visible_row_count();
+        if highlighted < self.scroll_row {
+            self.scroll_row = highlighted;
+        } else if highlighted >= self.scroll_row + rows {
+            self.scroll_row = highlighted + 1 - rows;
+        }
+    }
+
+    fn scroll_by(&mut self, rows: isize) {
+        let max_scroll = self.matches.len().saturating_sub(self.visible_row_count());
+        let scroll_row = (self.scroll_row as isize + rows).clamp(0, max_scroll as isize) as usize;
+        if scroll_row != self.scroll_row {
+            self.scroll_row = scroll_row;
+            self.dirty = true;
+        }
+    }
+
+    /// Move the highlight by `delta` rows, stopping at either end
+    pub fn move_highlight(&mut self, delta: isize) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let last = self.matches.len() as isize - 1;
+        let next = match self.highlighted {
+            Some(current) => (current as isize + delta).clamp(0, last),
+            None if delta < 0 => last,
+            None => 0,
+        } as usize;
+        self.highlighted = Some(next);
+        self.scroll_to_highlight();
+        self.dirty = true;
+    }
+
+    // Jump to the first option whose label starts with the characters typed so far
+    fn type_ahead(&mut self, character: char) {
+        let now = Instant::now();
+        if self.last_type_time.is_none_or(|last| now.duration_since(last) > TYPE_AHEAD_TIMEOUT) {
+            self.type_ahead.clear();
+        }
+        self.last_type_time = Some(now);
+        self.type_ahead.extend(character.to_lowercase());
+
+        let options = self.get_effective_options();
+        let found = self.matches.iter().position(|matched| {
+            options.get(matched.index)
+                .is_some_and(|option| option.label.to_lowercase().starts_with(&self.type_ahead))
+        });
+        if let Some(found) = found {
+            self.highlighted = Some(found);
+            self.scroll_to_highlight();
+            self.dirty = true;
+        }
+    }
+
     pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
         x >= self.x && x <= self.x + self.width &&
         y >= self.y && y <= self.y + self.height
@@ -161,10 +471,12 @@ impl DropdownWidget {
         }
         
         let dropdown_y = self.y + self.height;
-        let dropdown_height = (self.get_effective_options().len() as f32 * self.height).min(self.max_height);
-        
         x >= self.x && x <= self.x + self.width &&
-        y >= dropdown_y && y <= dropdown_y + dropdown_height
+        y >= dropdown_y && y <= dropdown_y + self.popup_height()
+    }
+
+    fn is_point_in_filter(&self, x: f32, y: f32) -> bool {
+        self.is_open && self.searchable && self.filter_input.is_point_inside(x, y)
     }
 
     pub fn get_selected_option(&self) -> Option<DropdownOption> {
@@ -172,14 +484,19 @@ impl DropdownWidget {
         self.get_effective_options().into_iter().find(|opt| opt.value == selected)
     }
 
-    pub fn get_option_at_position(&self, x: f32, y: f32) -> Option<DropdownOption> {
-        if !self.is_point_in_dropdown(x, y) {
+    // Index into `matches` of the listed row under a point
+    fn row_at_position(&self, x: f32, y: f32) -> Option<usize> {
+        if !self.is_point_in_dropdown(x, y) || y < self.list_top() {
             return None;
         }
-        
-        let dropdown_y = self.y + self.height;
-        let option_index = ((y - dropdown_y) / self.height) as usize;
-        self.get_effective_options().get(option_index).cloned()
+        let row = ((y - self.list_top()) / self.height) as usize;
+        let index = self.scroll_row + row;
+        (row < self.visible_row_count() && index < self.matches.len()).then_some(index)
+    }
+
+    pub fn get_option_at_position(&self, x: f32, y: f32) -> Option<DropdownOption> {
+        let matched = self.matches.get(self.row_at_position(x, y)?)?;
+        self.get_effective_options().get(matched.index).cloned()
     }
 
     pub fn create_background_rectangle(&self) -> Rectangle {
@@ -200,19 +517,23 @@ impl DropdownWidget {
         }
         
         let dropdown_y = self.y + self.height;
-        let dropdown_height = (self.get_effective_options().len() as f32 * self.height).min(self.max_height);
         
         Some(Rectangle::new_with_brush(
             self.x, 
             dropdown_y, 
             self.width, 
-            dropdown_height, 
+            self.popup_height(), 
             self.background.to_brush()
         )
         .with_border_radius(self.border_radius)
         .with_stroke_width(1.0))
     }
 
+    /// The filter field at the top of the open list, in combobox mode
+    pub fn get_filter_input(&self) -> Option<&InputWidget> {
+        (self.is_open && self.searchable).then_some(&self.filter_input)
+    }
+
     pub fn create_text_primitive(&self) -> Option<Text> {
         let display_text = if let Some(option) = self.get_selected_option() {
             option.label
@@ -239,22 +560,39 @@ impl DropdownWidget {
         Text::new(arrow_x, arrow_y, arrow_char.to_string(), self.arrow_color, self.font_size)
     }
 
+    /// Background and label of each row that fits the open list. Only these rows
+    /// are built, so lists with thousands of options stay cheap.
     pub fn create_option_primitives(&self) -> Vec<(Rectangle, Text)> {
         if !self.is_open {
             return Vec::new();
         }
         
+        let options = self.get_effective_options();
+        let selected = self.selected_value.get();
         let mut primitives = Vec::new();
-        let dropdown_y = self.y + self.height;
+        let list_top = self.list_top();
+        let visible = self.matches.iter().enumerate()
+            .skip(self.scroll_row)
+            .take(self.visible_row_count());
         
-        for (i, option) in self.get_effective_options().iter().enumerate() {
-            let option_y = dropdown_y + (i as f32 * self.height);
+        for (row, (index, matched)) in visible.enumerate() {
+            let Some(option) = options.get(matched.index) else {
+                continue;
+            };
+            let option_y = list_top + (row as f32 * self.height);
+            let background = if self.highlighted == Some(index) {
+                self.highlight_color
+            } else if option.value == selected {
+                self.selected_row_color
+            } else {
+                self.row_color
+            };
             let option_rect = Rectangle::new(
                 self.x, 
                 option_y, 
                 self.width, 
                 self.height, 
-                Color::rgba8(255, 255, 255, 255)
+                background
             );
             
             let padding = 8.0;
@@ -268,35 +606,202 @@ impl DropdownWidget {
         primitives
     }
 
+    /// Marks behind the characters the filter matched in each visible row
+    pub fn create_match_rectangles(&self) -> Vec<Rectangle> {
+        if !self.is_open {
+            return Vec::new();
+        }
+        
+        let char_width = self.font_size * 0.6;
+        let mut rectangles = Vec::new();
+        let visible = self.matches.iter().skip(self.scroll_row).take(self.visible_row_count());
+        for (row, matched) in visible.enumerate() {
+            let option_y = self.list_top() + (row as f32 * self.height);
+            for position in &matched.positions {
+                rectangles.push(
+                    Rectangle::new(
+                        self.x + 8.0 + *position as f32 * char_width,
+                        option_y + self.height / 2.0 - self.font_size * 0.6,
+                        char_width,
+                        self.font_size * 1.2,
+                        self.match_color,
+                    ).with_border_radius(2.0)
+                );
+            }
+        }
+        rectangles
+    }
+
+    /// Thumb showing where the visible rows sit in a list that scrolls
+    pub fn create_scrollbar_rectangle(&self) -> Option<Rectangle> {
+        let rows = self.visible_row_count();
+        if !self.is_open || self.matches.len() <= rows {
+            return None;
+        }
+        let track_height = rows as f32 * self.height;
+        let thumb_height = (track_height * rows as f32 / self.matches.len() as f32).max(12.0);
+        let max_scroll = (self.matches.len() - rows) as f32;
+        let thumb_y = self.list_top() + (track_height - thumb_height) * self.scroll_row as f32 / max_scroll;
+        Some(
+            Rectangle::new(self.x + self.width - 6.0, thumb_y, 4.0, thumb_height, Color::rgba8(0, 0, 0, 70))
+                .with_border_radius(2.0)
+        )
+    }
+
+    /// "No matches" (or the free text Enter would pick) when the filter lists nothing
+    pub fn create_empty_text(&self) -> Option<Text> {
+        if !self.is_open || !self.searchable || !self.matches.is_empty() {
+            return None;
+        }
+        let filter = self.applied_filter.trim();
+        let message = if self.allow_free_text && !filter.is_empty() {
+            format!("Use \"{}\"", filter)
+        } else {
+            "No matches".to_string()
+        };
+        let text_y = self.list_top() + (self.height / 2.0) + (self.font_size * 0.25);
+        Some(Text::new(self.x + 8.0, text_y, message, self.arrow_color, self.font_size))
+    }
+
     pub fn select_option(&mut self, option: &DropdownOption) {
-        if self.selected_value.get() != option.value {
-            self.selected_value.set(option.value.clone());
+        self.select_value(option.value.clone());
+    }
+
+    fn select_value(&mut self, value: String) {
+        if self.selected_value.get() != value {
+            self.selected_value.set(value.clone());
             if let Some(ref callback) = self.on_change {
-                callback(&option.value);
+                callback(&value);
             }
             self.dirty = true;
         }
+        self.close();
+    }
+
+    // Enter: pick the highlighted row, or the typed text when free text is allowed
+    fn commit_highlight(&mut self) {
+        if let Some(option) = self.get_highlighted_option() {
+            self.select_option(&option);
+            return;
+        }
+        let filter = self.applied_filter.trim().to_string();
+        if self.allow_free_text && !filter.is_empty() {
+            self.select_value(filter);
+        }
+    }
+
+    pub fn open(&mut self) {
+        if self.is_open {
+            return;
+        }
+        self.is_open = true;
+        self.type_ahead.clear();
+        if self.searchable {
+            self.filter.set(String::new());
+            self.applied_filter.clear();
+            self.filter_input.set_position(self.x, self.y + self.height);
+            self.filter_input.set_size(self.width, self.height);
+            self.filter_input.set_focus(true);
+        }
+        self.highlighted = None;
+        self.refresh_matches();
+        // Start on the current choice
+        let selected = self.selected_value.get();
+        let options = self.get_effective_options();
+        self.highlighted = self.matches.iter()
+            .position(|matched| options.get(matched.index).is_some_and(|option| option.value == selected));
+        self.scroll_to_highlight();
+        self.dirty = true;
+    }
+
+    pub fn close(&mut self) {
+        if !self.is_open {
+            return;
+        }
         self.is_open = false;
+        self.filter_input.set_focus(false);
+        self.dirty = true;
     }
 
     pub fn toggle_dropdown(&mut self) {
-        self.is_open = !self.is_open;
-        self.dirty = true;
+        if self.is_open {
+            self.close();
+        } else {
+            self.open();
+        }
+    }
+
+    // Pick up text typed into the filter field
+    fn sync_filter(&mut self) {
+        let filter = self.filter.get();
+        if filter != self.applied_filter {
+            self.applied_filter = filter;
+            self.refresh_matches();
+        }
+    }
+
+    fn handle_key(&mut self, key_code: Option<KeyCode>, character: Option<char>) -> bool {
+        match key_code {
+            Some(KeyCode::ArrowDown) => self.move_highlight(1),
+            Some(KeyCode::ArrowUp) => self.move_highlight(-1),
+            Some(KeyCode::PageDown) => self.move_highlight(self.visible_row_count() as isize),
+            Some(KeyCode::PageUp) => self.move_highlight(-(self.visible_row_count() as isize)),
+            Some(KeyCode::Home) if !self.searchable => self.move_highlight(-(self.matches.len() as isize)),
+            Some(KeyCode::End) if !self.searchable => self.move_highlight(self.matches.len() as isize),
+            Some(KeyCode::Enter) => self.commit_highlight(),
+            Some(KeyCode::Escape) => self.close(),
+            _ => match character {
+                Some(character) if !self.searchable && !character.is_control() => self.type_ahead(character),
+                _ => return false,
+            },
+        }
+        true
     }
 }
 
 impl Widget for DropdownWidget {
     fn mount(&mut self) -> Result<(), WidgetError> {
         self.dirty = true;
+        self.filter_input.mount()?;
+        
+        let reactive_dirty = self.reactive_dirty.clone();
+        self.selected_value.subscribe_fn(move |_| {
+            if let Ok(mut dirty) = reactive_dirty.write() {
+                *dirty = true;
+            }
+        });
+        
+        if let Some(options_signal) = &self.options_signal {
+            let options_changed = self.options_changed.clone();
+            options_signal.subscribe_fn(move |_| {
+                options_changed.store(true, Ordering::SeqCst);
+            });
+        }
+        
         Ok(())
     }
 
     fn unmount(&mut self) -> Result<(), WidgetError> {
         self.is_open = false;
-        Ok(())
+        self.filter_input.unmount()
     }
 
     fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        if let Ok(mut reactive_dirty) = self.reactive_dirty.write() {
+            if *reactive_dirty {
+                *reactive_dirty = false;
+                self.dirty = true;
+            }
+        }
+        
+        if self.options_changed.swap(false, Ordering::SeqCst) && self.is_open {
+            self.refresh_matches();
+        }
+        if self.is_open && self.searchable {
+            self.sync_filter();
+            self.filter_input.update(ctx)?;
+        }
+        
         if self.dirty {
             ctx.mark_dirty(self.id);
         }
@@ -318,29 +823,33 @@ impl Widget for DropdownWidget {
                         self.dirty = true;
                     }
                     
+                    if let Some(row) = self.row_at_position(x, y) {
+                        if self.highlighted != Some(row) {
+                            self.highlighted = Some(row);
+                            self.dirty = true;
+                        }
+                    }
+                    
                     EventResult::Ignored
                 } else if mouse_event.state == ElementState::Pressed {
                     // Mouse down
                     if self.is_point_inside(x, y) {
                         self.toggle_dropdown();
                         EventResult::Handled
+                    } else if self.is_point_in_filter(x, y) {
+                        self.filter_input.handle_event(event);
+                        EventResult::Handled
                     } else if self.is_point_in_dropdown(x, y) {
                         if let Some(option) = self.get_option_at_position(x, y) {
-                            let option_value = option.value.clone();
-                            if self.selected_value.get() != option_value {
-                                self.selected_value.set(option_value.clone());
-                                if let Some(ref callback) = self.on_change {
-                                    callback(&option_value);
-                                }
-                                self.is_open = false;
-                                self.dirty = true;
-                            }
+                            self.select_option(&option);
+                        } else if self.matches.is_empty() {
+                            // The "Use ..." row of free text entry
+                            self.commit_highlight();
                         }
                         EventResult::Handled
                     } else if self.is_open {
                         // Click outside closes dropdown
-                        self.is_open = false;
-                        self.dirty = true;
+                        self.close();
                         EventResult::Handled
                     } else {
                         EventResult::Ignored
@@ -349,6 +858,25 @@ impl Widget for DropdownWidget {
                     EventResult::Ignored
                 }
             },
+            Event::Scroll(scroll_event) => {
+                let (x, y) = (scroll_event.position.x as f32, scroll_event.position.y as f32);
+                if !self.is_point_in_dropdown(x, y) || scroll_event.delta.y == 0.0 {
+                    return EventResult::Ignored;
+                }
+                self.scroll_by(if scroll_event.delta.y > 0.0 { -1 } else { 1 });
+                EventResult::Handled
+            },
+            Event::Keyboard(keyboard_event) if self.is_open && keyboard_event.state == ElementState::Pressed => {
+                if self.handle_key(keyboard_event.key_code, keyboard_event.character) {
+                    return EventResult::Handled;
+                }
+                if self.searchable {
+                    let result = self.filter_input.handle_event(event);
+                    self.sync_filter();
+                    return result;
+                }
+                EventResult::Ignored
+            },
             _ => EventResult::Ignored,
         }
     }
@@ -371,12 +899,11 @@ impl Widget for DropdownWidget {
         
         // Add dropdown area if open
         if self.is_open {
-            let dropdown_height = (self.get_effective_options().len() as f32 * self.height).min(self.max_height);
             dirty_regions.push(DirtyRegion {
                 x: self.x,
                 y: self.y + self.height,
                 width: self.width,
-                height: dropdown_height,
+                height: self.popup_height(),
             });
         }
 
@@ -402,4 +929,77 @@ impl Widget for DropdownWidget {
 // Convenience function for creating dropdowns
 pub fn dropdown() -> DropdownWidget {
     DropdownWidget::new()
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::KeyboardEvent;
+
+    fn key_press(key: KeyCode, character: Option<char>) -> Event {
+        Event::Keyboard(KeyboardEvent {
+            key_code: Some(key),
+            character,
+            scancode: 0,
+            state: ElementState::Pressed,
+            modifiers: Default::default(),
+        })
+    }
+
+    fn fonts() -> Vec<DropdownOption> {
+        ["Arial", "Courier New", "Fira Code", "Roboto Mono", "Source Code Pro"]
+            .iter()
+            .map(|name| DropdownOption::new(*name, *name))
+            .collect()
+    }
+
+    #[test]
+    fn fuzzy_match_prefers_word_starts() {
+        let found = fuzzy_match("scp", "Source Code Pro").unwrap();
+        assert_eq!(found.positions, [0, 7, 12]);
+        assert!(fuzzy_match("xyz", "Source Code Pro").is_none());
+
+        let word_starts = fuzzy_match("fc", "Fira Code").unwrap();
+        let mid_word = fuzzy_match("fc", "Office").unwrap();
+        assert!(word_starts.score > mid_word.score);
+    }
+
+    #[test]
+    fn filter_and_keyboard_selection() {
+        let selected = Signal::new(String::new());
+        let mut combobox = dropdown()
+            .with_options(fonts())
+            .with_selected_signal(selected.clone())
+            .searchable();
+        combobox.open();
+
+        combobox.filter.set("code".to_string());
+        combobox.sync_filter();
+        let listed: Vec<String> = combobox.get_listed_options().into_iter().map(|option| option.value).collect();
+        assert_eq!(listed, ["Fira Code", "Source Code Pro"]);
+
+        combobox.handle_event(&key_press(KeyCode::ArrowDown, None));
+        combobox.handle_event(&key_press(KeyCode::Enter, None));
+        assert_eq!(selected.get(), "Source Code Pro");
+        assert!(!combobox.is_open());
+    }
+
+    #[test]
+    fn free_text_and_type_ahead() {
+        let selected = Signal::new(String::new());
+        let mut combobox = dropdown()
+            .with_options(fonts())
+            .with_selected_signal(selected.clone())
+            .with_free_text(true);
+        combobox.open();
+        combobox.filter.set("Comic Sans".to_string());
+        combobox.sync_filter();
+        combobox.handle_event(&key_press(KeyCode::Enter, None));
+        assert_eq!(selected.get(), "Comic Sans");
+
+        let mut plain = dropdown().with_options(fonts());
+        plain.open();
+        plain.handle_event(&key_press(KeyCode::KeyR, Some('r')));
+        assert_eq!(plain.get_highlighted_option().map(|option| option.value), Some("Roboto Mono".to_string()));
+    }
+}
@@ -0,0 +1,518 @@
+use crate::{Widget, WidgetId, EventResult, WidgetError, RenderData, DirtyRegion, WidgetUpdateContext};
+use crate::event::Event;
+use crate::theme::{current_theme, StyleOverrides, Theme};
+use gui_reactive::Signal;
+use gui_render::primitives::VectorPath;
+use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use vello::kurbo::{Affine, BezPath};
+use vello::peniko::{Color, Fill};
+
+static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(10000);
+
+#[derive(Clone, Debug)]
+enum SvgPaint {
+    Color(Color),
+    // Gradients and patterns; drawn with the icon color or black
+    Other(f32),
+}
+
+impl SvgPaint {
+    fn resolve(&self, recolor: Option<Color>) -> Color {
+        let (color, opacity) = match self {
+            SvgPaint::Color(color) => (recolor.unwrap_or(*color), color.a as f32 / 255.0),
+            SvgPaint::Other(opacity) => (recolor.unwrap_or(Color::rgba8(0, 0, 0, 255)), *opacity),
+        };
+        Color::rgba8(color.r, color.g, color.b, (color.a as f32 * opacity).round() as u8)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct SvgPath {
+    // Already in document coordinates
+    path: BezPath,
+    fill: Option<(SvgPaint, Fill)>,
+    stroke: Option<(SvgPaint, f32)>,
+}
+
+/// Parsed SVG reduced to filled and stroked outlines
+#[derive(Clone, Debug)]
+pub struct SvgDocument {
+    pub width: f32,
+    pub height: f32,
+    paths: Vec<SvgPath>,
+}
+
+impl SvgDocument {
+    pub fn parse(data: &[u8]) -> Result<Self, String> {
+        let tree = usvg::Tree::from_data(data, &usvg::Options::default()).map_err(|e| e.to_string())?;
+        let mut paths = Vec::new();
+        Self::collect_paths(tree.root(), &mut paths);
+        Ok(Self {
+            width: tree.size().width(),
+            height: tree.size().height(),
+            paths,
+        })
+    }
+
+    pub fn path_count(&self) -> usize {
+        self.paths.len()
+    }
+
+    fn collect_paths(group: &usvg::Group, paths: &mut Vec<SvgPath>) {
+        for node in group.children() {
+            match node {
+                usvg::Node::Group(group) => Self::collect_paths(group, paths),
+                usvg::Node::Path(path) if path.is_visible() => {
+                    let transform = path.abs_transform();
+                    let mut bez_path = Self::to_bez_path(path.data());
+                    bez_path.apply_affine(Affine::new([
+                        transform.sx as f64, transform.ky as f64,
+                        transform.kx as f64, transform.sy as f64,
+                        transform.tx as f64, transform.ty as f64,
+                    ]));
+
+                    let fill = path.fill().map(|fill| {
+                        let rule = match fill.rule() {
+                            usvg::FillRule::NonZero => Fill::NonZero,
+                            usvg::FillRule::EvenOdd => Fill::EvenOdd,
+                        };
+                        (Self::to_paint(fill.paint(), fill.opacity().get()), rule)
+                    });
+                    let stroke = path.stroke().map(|stroke| {
+                        (Self::to_paint(stroke.paint(), stroke.opacity().get()), stroke.width().get())
+                    });
+                    paths.push(SvgPath { path: bez_path, fill, stroke });
+                },
+                // Embedded images and text are not supported in icons
+                _ => {},
+            }
+        }
+    }
+
+    fn to_paint(paint: &usvg::Paint, opacity: f32) -> SvgPaint {
+        match paint {
+            usvg::Paint::Color(color) => SvgPaint::Color(Color::rgba8(
+                color.red, color.green, color.blue, (opacity * 255.0).round() as u8,
+            )),
+            _ => SvgPaint::Other(opacity),
+        }
+    }
+
+    fn to_bez_path(data: &usvg::tiny_skia_path::Path) -> BezPath {
+        use usvg::tiny_skia_path::PathSegment;
+
+        let mut bez_path = BezPath::new();
+        for segment in data.segments() {
+            match segment {
+                PathSegment::MoveTo(p) => bez_path.move_to((p.x as f64, p.y as f64)),
+                PathSegment::LineTo(p) => bez_path.line_to((p.x as f64, p.y as f64)),
+                PathSegment::QuadTo(p1, p) => bez_path.quad_to((p1.x as f64, p1.y as f64), (p.x as f64, p.y as f64)),
+                PathSegment::CubicTo(p1, p2, p) => bez_path.curve_to(
+                    (p1.x as f64, p1.y as f64),
+                    (p2.x as f64, p2.y as f64),
+                    (p.x as f64, p.y as f64),
+                ),
+                PathSegment::Close => bez_path.close_path(),
+            }
+        }
+        bez_path
+    }
+}
+
+static SVG_CACHE: OnceLock<Mutex<HashMap<u64, Arc<SvgDocument>>>> = OnceLock::new();
+
+/// Parse SVG data, reusing the document if the same bytes were parsed before
+pub fn load_svg(data: &[u8]) -> Result<Arc<SvgDocument>, String> {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    let key = hasher.finish();
+
+    let cache = SVG_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(document) = cache.lock().ok().and_then(|cache| cache.get(&key).cloned()) {
+        return Ok(document);
+    }
+    let document = Arc::new(SvgDocument::parse(data)?);
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(key, document.clone());
+    }
+    Ok(document)
+}
+
+/// Named icon sets. Icons are looked up as `"set:name"`, or by bare name across
+/// sets in registration order.
+#[derive(Default)]
+pub struct IconRegistry {
+    sets: Vec<(String, HashMap<String, Arc<[u8]>>)>,
+}
+
+impl IconRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register_set<N, D>(&mut self, set: impl Into<String>, icons: impl IntoIterator<Item = (N, D)>)
+    where
+        N: Into<String>,
+        D: Into<Arc<[u8]>>,
+    {
+        let set = set.into();
+        let icons = icons.into_iter().map(|(name, data)| (name.into(), data.into()));
+        match self.sets.iter_mut().find(|(existing, _)| *existing == set) {
+            Some((_, existing)) => existing.extend(icons),
+            None => self.sets.push((set, icons.collect())),
+        }
+    }
+
+    pub fn register(&mut self, set: impl Into<String>, name: impl Into<String>, data: impl Into<Arc<[u8]>>) {
+        self.register_set(set, [(name.into(), data.into())]);
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<Arc<[u8]>> {
+        if let Some((set, icon)) = name.split_once(':') {
+            return self.sets.iter()
+                .find(|(existing, _)| existing == set)
+                .and_then(|(_, icons)| icons.get(icon).cloned());
+        }
+        self.sets.iter().find_map(|(_, icons)| icons.get(name).cloned())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.lookup(name).is_some()
+    }
+}
+
+static ICON_REGISTRY: OnceLock<RwLock<IconRegistry>> = OnceLock::new();
+
+pub fn global_icon_registry() -> &'static RwLock<IconRegistry> {
+    ICON_REGISTRY.get_or_init(|| RwLock::new(IconRegistry::new()))
+}
+
+/// Register a named icon set with the global registry
+pub fn register_icon_set<N, D>(set: impl Into<String>, icons: impl IntoIterator<Item = (N, D)>)
+where
+    N: Into<String>,
+    D: Into<Arc<[u8]>>,
+{
+    if let Ok(mut registry) = global_icon_registry().write() {
+        registry.register_set(set, icons);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum IconSource {
+    Named(String),
+    Svg(Arc<[u8]>),
+}
+
+pub struct IconWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    source: IconSource,
+    document: Option<Arc<SvgDocument>>,
+    // None keeps the SVG's own colors
+    color: Option<Color>,
+    color_signal: Option<Signal<Color>>,
+    reported_missing: bool,
+    overrides: StyleOverrides,
+    pub dirty: bool,
+    // Shared dirty flag that reactive signals can set
+    reactive_dirty: Arc<RwLock<bool>>,
+}
+
+impl IconWidget {
+    pub fn new(source: IconSource) -> Self {
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 16.0,
+            height: 16.0,
+            source,
+            document: None,
+            color: Some(current_theme().palette.text),
+            color_signal: None,
+            reported_missing: false,
+            overrides: StyleOverrides::default(),
+            dirty: true,
+            reactive_dirty: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.width = size;
+        self.height = size;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_dimensions(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self.dirty = true;
+        self
+    }
+
+    /// Recolor every path, keeping its opacity
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self.overrides.mark("color");
+        self.dirty = true;
+        self
+    }
+
+    /// Follow a color signal, e.g. the text color of the surrounding button
+    pub fn with_color_signal(mut self, color: Signal<Color>) -> Self {
+        self.color = Some(color.get());
+        self.color_signal = Some(color);
+        self.overrides.mark("color");
+        self.dirty = true;
+        self
+    }
+
+    /// Draw with the colors from the SVG itself
+    pub fn original_colors(mut self) -> Self {
+        self.color = None;
+        self.color_signal = None;
+        self.overrides.mark("color");
+        self.dirty = true;
+        self
+    }
+
+    pub fn set_color(&mut self, color: Option<Color>) {
+        self.overrides.mark("color");
+        if self.color != color {
+            self.color = color;
+            self.dirty = true;
+        }
+    }
+
+    /// Follow the theme's text color unless a color was set explicitly
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        if !self.overrides.is_set("color") && self.color != Some(theme.palette.text) {
+            self.color = Some(theme.palette.text);
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    fn resolve(&mut self) {
+        if self.document.is_some() {
+            return;
+        }
+        let data = match &self.source {
+            IconSource::Svg(data) => Some(data.clone()),
+            IconSource::Named(name) => global_icon_registry().read().ok().and_then(|registry| registry.lookup(name)),
+        };
+        let Some(data) = data else {
+            if let IconSource::Named(name) = &self.source {
+                if !self.reported_missing {
+                    eprintln!("Icon '{}' is not registered", name);
+                    self.reported_missing = true;
+                }
+            }
+            return;
+        };
+        match load_svg(&data) {
+            Ok(document) => {
+                self.document = Some(document);
+                self.dirty = true;
+            },
+            Err(error) => {
+                eprintln!("Failed to parse icon SVG: {}", error);
+                // Keep the empty icon rather than reparsing every frame
+                self.document = Some(Arc::new(SvgDocument { width: 0.0, height: 0.0, paths: Vec::new() }));
+            },
+        }
+    }
+
+    /// Paths scaled to fit the widget bounds, centered and keeping aspect ratio
+    pub fn create_path_primitives(&self) -> Vec<VectorPath> {
+        let Some(document) = &self.document else {
+            return Vec::new();
+        };
+        if document.width <= 0.0 || document.height <= 0.0 {
+            return Vec::new();
+        }
+        let scale = (self.width / document.width).min(self.height / document.height);
+        let offset_x = self.x + (self.width - document.width * scale) / 2.0;
+        let offset_y = self.y + (self.height - document.height * scale) / 2.0;
+        let transform = Affine::translate((offset_x as f64, offset_y as f64)) * Affine::scale(scale as f64);
+
+        let mut primitives = Vec::new();
+        for path in &document.paths {
+            if let Some((paint, rule)) = &path.fill {
+                primitives.push(VectorPath::new_fill(path.path.clone(), paint.resolve(self.color))
+                    .with_fill_rule(*rule)
+                    .with_transform(transform));
+            }
+            if let Some((paint, width)) = &path.stroke {
+                primitives.push(VectorPath::new_stroke(path.path.clone(), paint.resolve(self.color), *width)
+                    .with_transform(transform));
+            }
+        }
+        primitives
+    }
+}
+
+impl Widget for IconWidget {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        self.resolve();
+        self.dirty = true;
+
+        if let Some(ref color_signal) = self.color_signal {
+            let reactive_dirty = self.reactive_dirty.clone();
+            color_signal.subscribe_fn(move |_| {
+                if let Ok(mut dirty) = reactive_dirty.write() {
+                    *dirty = true;
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        // Icon sets may be registered after the widget was built
+        self.resolve();
+
+        if let Ok(mut reactive_dirty) = self.reactive_dirty.write() {
+            if *reactive_dirty {
+                *reactive_dirty = false;
+                if let Some(ref color_signal) = self.color_signal {
+                    self.color = Some(color_signal.get());
+                }
+                self.dirty = true;
+            }
+        }
+
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> EventResult {
+        EventResult::Ignored
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            }],
+            z_index: 1,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+// Convenience functions for creating icons
+pub fn icon(name: impl Into<String>) -> IconWidget {
+    IconWidget::new(IconSource::Named(name.into()))
+}
+
+pub fn svg(data: impl Into<Arc<[u8]>>) -> IconWidget {
+    IconWidget::new(IconSource::Svg(data.into())).original_colors()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: &[u8] = br##"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="24"><path d="M2 2h20v20H2z" fill="#ff0000"/></svg>"##;
+
+    #[test]
+    fn registry_resolves_qualified_and_bare_names() {
+        let mut registry = IconRegistry::new();
+        registry.register("material", "save", SQUARE);
+        registry.register("custom", "save", &b"<svg/>"[..]);
+        registry.register("custom", "open", &b"<svg/>"[..]);
+
+        assert_eq!(registry.lookup("save").as_deref(), Some(SQUARE));
+        assert_eq!(registry.lookup("custom:save").as_deref(), Some(&b"<svg/>"[..]));
+        assert!(registry.contains("open"));
+        assert!(!registry.contains("material:open"));
+    }
+
+    #[test]
+    fn parsed_icon_scales_to_widget_size() {
+        let document = load_svg(SQUARE).unwrap();
+        assert_eq!(document.path_count(), 1);
+        assert_eq!(document.width, 24.0);
+
+        let mut widget = IconWidget::new(IconSource::Svg(SQUARE.into())).with_size(48.0);
+        widget.resolve();
+        let primitives = widget.create_path_primitives();
+        assert_eq!(primitives.len(), 1);
+        assert_eq!(primitives[0].transform, Affine::scale(2.0));
+    }
+
+    #[test]
+    fn theme_colors_icons_unless_set_explicitly() {
+        let dark = Theme::dark();
+        let mut themed = IconWidget::new(IconSource::Svg(SQUARE.into()));
+        let mut explicit = IconWidget::new(IconSource::Svg(SQUARE.into())).with_color(Color::rgba8(255, 0, 0, 255));
+        let mut original = IconWidget::new(IconSource::Svg(SQUARE.into())).original_colors();
+
+        themed.apply_theme(&dark);
+        explicit.apply_theme(&dark);
+        original.apply_theme(&dark);
+
+        assert_eq!(themed.color, Some(dark.palette.text));
+        assert_eq!(explicit.color, Some(Color::rgba8(255, 0, 0, 255)));
+        assert_eq!(original.color, None);
+    }
+}
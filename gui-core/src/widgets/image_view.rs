@@ -0,0 +1,557 @@
+use crate::{Widget, WidgetId, EventResult, WidgetError, RenderData, DirtyRegion, WidgetUpdateContext};
+use crate::event::Event;
+use crate::theme::{current_theme, StyleOverrides, Theme};
+use gui_reactive::global_frame_scheduler;
+use gui_render::primitives::{Image, ImageFormat, Rectangle};
+use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use vello::peniko::{Blob, Color};
+
+static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(9000);
+
+// Default budget for decoded pixels held by the shared cache
+const DEFAULT_CACHE_BYTES: usize = 64 * 1024 * 1024;
+
+#[derive(Clone, Debug)]
+pub enum ImageSource {
+    Path(PathBuf),
+    Bytes(Arc<[u8]>),
+}
+
+impl ImageSource {
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        ImageSource::Path(path.into())
+    }
+
+    pub fn bytes(bytes: impl Into<Arc<[u8]>>) -> Self {
+        ImageSource::Bytes(bytes.into())
+    }
+
+    /// Cache key: the path itself, or a hash of the encoded bytes
+    pub fn cache_key(&self) -> String {
+        match self {
+            ImageSource::Path(path) => format!("path:{}", path.display()),
+            ImageSource::Bytes(bytes) => {
+                let mut hasher = DefaultHasher::new();
+                bytes.hash(&mut hasher);
+                format!("bytes:{:016x}:{}", hasher.finish(), bytes.len())
+            },
+        }
+    }
+
+    fn decode(&self) -> Result<DecodedImage, String> {
+        let decoded = match self {
+            ImageSource::Path(path) => {
+                let bytes = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+                image::load_from_memory(&bytes)
+            },
+            ImageSource::Bytes(bytes) => image::load_from_memory(bytes),
+        }
+        .map_err(|e| e.to_string())?;
+
+        let rgba = decoded.into_rgba8();
+        Ok(DecodedImage {
+            width: rgba.width(),
+            height: rgba.height(),
+            pixels: Blob::from(rgba.into_raw()),
+        })
+    }
+}
+
+/// RGBA8 pixels of a decoded image, shared between widgets showing it
+#[derive(Clone, Debug)]
+pub struct DecodedImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Blob<u8>,
+}
+
+#[derive(Clone, Debug)]
+pub enum ImageLoadState {
+    Loading,
+    Ready(DecodedImage),
+    Failed(String),
+}
+
+struct ImageCacheInner {
+    entries: HashMap<String, DecodedImage>,
+    // Least recently used first
+    order: VecDeque<String>,
+    // Decodes in flight, so concurrent requests for one source share a worker
+    loading: HashMap<String, Arc<Mutex<ImageLoadState>>>,
+    bytes: usize,
+    capacity_bytes: usize,
+}
+
+/// LRU cache of decoded images, bounded by the size of their pixel data
+pub struct ImageCache {
+    inner: Mutex<ImageCacheInner>,
+}
+
+impl ImageCache {
+    pub fn new(capacity_bytes: usize) -> Self {
+        Self {
+            inner: Mutex::new(ImageCacheInner {
+                entries: HashMap::new(),
+                order: VecDeque::new(),
+                loading: HashMap::new(),
+                bytes: 0,
+                capacity_bytes,
+            }),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<DecodedImage> {
+        let mut inner = self.inner.lock().ok()?;
+        let image = inner.entries.get(key).cloned()?;
+        inner.order.retain(|existing| existing != key);
+        inner.order.push_back(key.to_string());
+        Some(image)
+    }
+
+    pub fn insert(&self, key: impl Into<String>, image: DecodedImage) {
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        let key = key.into();
+        if let Some(previous) = inner.entries.remove(&key) {
+            inner.bytes -= previous.pixels.len();
+            inner.order.retain(|existing| *existing != key);
+        }
+        inner.bytes += image.pixels.len();
+        inner.entries.insert(key.clone(), image);
+        inner.order.push_back(key);
+
+        // Evict least recently used, always keeping the newest entry
+        while inner.bytes > inner.capacity_bytes && inner.order.len() > 1 {
+            if let Some(oldest) = inner.order.pop_front() {
+                if let Some(evicted) = inner.entries.remove(&oldest) {
+                    inner.bytes -= evicted.pixels.len();
+                }
+            }
+        }
+    }
+
+    pub fn set_capacity(&self, capacity_bytes: usize) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.capacity_bytes = capacity_bytes;
+        }
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.entries.clear();
+            inner.order.clear();
+            inner.bytes = 0;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().map(|inner| inner.entries.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Load state for a source, starting a background decode on a cache miss
+    pub fn load(&'static self, source: &ImageSource) -> Arc<Mutex<ImageLoadState>> {
+        let key = source.cache_key();
+        if let Some(image) = self.get(&key) {
+            return Arc::new(Mutex::new(ImageLoadState::Ready(image)));
+        }
+
+        let state = {
+            let Ok(mut inner) = self.inner.lock() else {
+                return Arc::new(Mutex::new(ImageLoadState::Failed("image cache poisoned".to_string())));
+            };
+            if let Some(state) = inner.loading.get(&key) {
+                return state.clone();
+            }
+            let state = Arc::new(Mutex::new(ImageLoadState::Loading));
+            inner.loading.insert(key.clone(), state.clone());
+            state
+        };
+
+        let source = source.clone();
+        let worker_state = state.clone();
+        std::thread::spawn(move || {
+            let result = match source.decode() {
+                Ok(image) => {
+                    self.insert(key.clone(), image.clone());
+                    ImageLoadState::Ready(image)
+                },
+                Err(error) => ImageLoadState::Failed(error),
+            };
+            if let Ok(mut state) = worker_state.lock() {
+                *state = result;
+            }
+            if let Ok(mut inner) = self.inner.lock() {
+                inner.loading.remove(&key);
+            }
+            global_frame_scheduler().request_update();
+        });
+        state
+    }
+}
+
+static IMAGE_CACHE: OnceLock<ImageCache> = OnceLock::new();
+
+/// Decoded-image cache shared by all image widgets
+pub fn global_image_cache() -> &'static ImageCache {
+    IMAGE_CACHE.get_or_init(|| ImageCache::new(DEFAULT_CACHE_BYTES))
+}
+
+/// How the image is sized within the widget bounds, as in CSS `object-fit`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ObjectFit {
+    Contain,
+    Cover,
+    Fill,
+    None,
+    ScaleDown,
+}
+
+impl ObjectFit {
+    /// Drawn rectangle (x, y, width, height) for an image of the given size,
+    /// centered in the box; may extend past the box for `Cover` and `None`
+    pub fn fit_rect(&self, image_width: f32, image_height: f32, x: f32, y: f32, width: f32, height: f32) -> (f32, f32, f32, f32) {
+        if image_width <= 0.0 || image_height <= 0.0 {
+            return (x, y, width, height);
+        }
+        let contain = (width / image_width).min(height / image_height);
+        let scale = match self {
+            ObjectFit::Fill => return (x, y, width, height),
+            ObjectFit::Contain => contain,
+            ObjectFit::Cover => (width / image_width).max(height / image_height),
+            ObjectFit::None => 1.0,
+            ObjectFit::ScaleDown => contain.min(1.0),
+        };
+        let drawn_width = image_width * scale;
+        let drawn_height = image_height * scale;
+        (
+            x + (width - drawn_width) / 2.0,
+            y + (height - drawn_height) / 2.0,
+            drawn_width,
+            drawn_height,
+        )
+    }
+}
+
+pub struct ImageWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    source: ImageSource,
+    state: Option<Arc<Mutex<ImageLoadState>>>,
+    image: Option<DecodedImage>,
+    failed: bool,
+    fit: ObjectFit,
+    tint: Option<Color>,
+    opacity: f32,
+    placeholder_color: Color,
+    error_color: Color,
+    border_radius: f32,
+    overrides: StyleOverrides,
+    pub dirty: bool,
+}
+
+impl ImageWidget {
+    pub fn new(source: ImageSource) -> Self {
+        let theme = current_theme();
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 100.0,
+            height: 100.0,
+            source,
+            state: None,
+            image: None,
+            failed: false,
+            fit: ObjectFit::Contain,
+            tint: None,
+            opacity: 1.0,
+            placeholder_color: theme.palette.surface_hover,
+            error_color: theme.palette.danger,
+            border_radius: 0.0,
+            overrides: StyleOverrides::default(),
+            dirty: true,
+        }
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_fit(mut self, fit: ObjectFit) -> Self {
+        self.fit = fit;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_tint(mut self, tint: Color) -> Self {
+        self.tint = Some(tint);
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self.dirty = true;
+        self
+    }
+
+    /// Shown while the image decodes
+    pub fn with_placeholder_color(mut self, color: Color) -> Self {
+        self.placeholder_color = color;
+        self.overrides.mark("placeholder_color");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_error_color(mut self, color: Color) -> Self {
+        self.error_color = color;
+        self.overrides.mark("error_color");
+        self.dirty = true;
+        self
+    }
+
+    /// Take the placeholder and error colors from `theme` unless set through a builder
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        if !self.overrides.is_set("placeholder_color") {
+            self.placeholder_color = theme.palette.surface_hover;
+        }
+        if !self.overrides.is_set("error_color") {
+            self.error_color = theme.palette.danger;
+        }
+        self.dirty = true;
+    }
+
+    pub fn with_border_radius(mut self, radius: f32) -> Self {
+        self.border_radius = radius;
+        self.dirty = true;
+        self
+    }
+
+    pub fn set_source(&mut self, source: ImageSource) {
+        self.source = source;
+        self.state = None;
+        self.image = None;
+        self.failed = false;
+        self.load();
+        self.dirty = true;
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.image.is_some()
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.failed
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    fn load(&mut self) {
+        if self.image.is_none() && self.state.is_none() {
+            self.state = Some(global_image_cache().load(&self.source));
+            self.poll();
+        }
+    }
+
+    // Pick up the worker's result once decoding finishes
+    fn poll(&mut self) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let finished = match state.lock() {
+            Ok(state) => match &*state {
+                ImageLoadState::Loading => None,
+                ImageLoadState::Ready(image) => Some(Ok(image.clone())),
+                ImageLoadState::Failed(error) => Some(Err(error.clone())),
+            },
+            Err(_) => Some(Err("image load state poisoned".to_string())),
+        };
+        match finished {
+            Some(Ok(image)) => {
+                self.image = Some(image);
+                self.state = None;
+                self.dirty = true;
+            },
+            Some(Err(error)) => {
+                eprintln!("Failed to load image: {}", error);
+                self.failed = true;
+                self.state = None;
+                self.dirty = true;
+            },
+            None => {},
+        }
+    }
+
+    /// Placeholder while loading, or an error block if decoding failed
+    pub fn create_placeholder_rectangle(&self) -> Option<Rectangle> {
+        if self.image.is_some() {
+            return None;
+        }
+        let color = if self.failed { self.error_color } else { self.placeholder_color };
+        Some(Rectangle::new(self.x, self.y, self.width, self.height, color).with_border_radius(self.border_radius))
+    }
+
+    pub fn create_image_primitive(&self) -> Option<Image> {
+        let image = self.image.as_ref()?;
+        let (draw_x, draw_y, draw_width, draw_height) = self.fit.fit_rect(
+            image.width as f32, image.height as f32,
+            self.x, self.y, self.width, self.height,
+        );
+
+        let mut primitive = Image::new(draw_x, draw_y, draw_width, draw_height, image.pixels.clone(), ImageFormat::Rgba8)
+            .with_source_size(image.width, image.height)
+            .with_opacity(self.opacity);
+        if draw_width > self.width || draw_height > self.height {
+            primitive = primitive.with_clip(self.x, self.y, self.width, self.height);
+        }
+        if let Some(tint) = self.tint {
+            primitive = primitive.with_tint(tint);
+        }
+        Some(primitive)
+    }
+}
+
+impl Widget for ImageWidget {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        self.load();
+        self.dirty = true;
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        // The decode keeps running and still lands in the cache
+        self.state = None;
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        self.poll();
+
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> EventResult {
+        EventResult::Ignored
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            }],
+            z_index: 1,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+// Convenience functions for creating images
+pub fn image_from_path(path: impl Into<PathBuf>) -> ImageWidget {
+    ImageWidget::new(ImageSource::path(path))
+}
+
+pub fn image_from_bytes(bytes: impl Into<Arc<[u8]>>) -> ImageWidget {
+    ImageWidget::new(ImageSource::bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32) -> DecodedImage {
+        DecodedImage { width, height, pixels: Blob::from(vec![0; (width * height * 4) as usize]) }
+    }
+
+    #[test]
+    fn fit_modes_place_image_in_box() {
+        // 200x100 image in a 100x100 box
+        assert_eq!(ObjectFit::Contain.fit_rect(200.0, 100.0, 0.0, 0.0, 100.0, 100.0), (0.0, 25.0, 100.0, 50.0));
+        assert_eq!(ObjectFit::Cover.fit_rect(200.0, 100.0, 0.0, 0.0, 100.0, 100.0), (-50.0, 0.0, 200.0, 100.0));
+        assert_eq!(ObjectFit::Fill.fit_rect(200.0, 100.0, 0.0, 0.0, 100.0, 100.0), (0.0, 0.0, 100.0, 100.0));
+        assert_eq!(ObjectFit::None.fit_rect(50.0, 50.0, 0.0, 0.0, 100.0, 100.0), (25.0, 25.0, 50.0, 50.0));
+        assert_eq!(ObjectFit::ScaleDown.fit_rect(50.0, 50.0, 0.0, 0.0, 100.0, 100.0), (25.0, 25.0, 50.0, 50.0));
+        assert_eq!(ObjectFit::ScaleDown.fit_rect(200.0, 100.0, 0.0, 0.0, 100.0, 100.0), (0.0, 25.0, 100.0, 50.0));
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used() {
+        // Room for two 4x4 images
+        let cache = ImageCache::new(2 * 4 * 4 * 4);
+        cache.insert("a", solid(4, 4));
+        cache.insert("b", solid(4, 4));
+        assert!(cache.get("a").is_some());
+        cache.insert("c", solid(4, 4));
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn byte_sources_share_a_key_by_content() {
+        let first = ImageSource::bytes(vec![1u8, 2, 3]);
+        let second = ImageSource::bytes(vec![1u8, 2, 3]);
+        assert_eq!(first.cache_key(), second.cache_key());
+        assert_ne!(first.cache_key(), ImageSource::bytes(vec![3u8, 2, 1]).cache_key());
+    }
+}
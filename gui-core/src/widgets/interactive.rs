@@ -1,15 +1,20 @@
 use crate::{Widget, WidgetId, EventResult, WidgetError, RenderData, DirtyRegion, WidgetUpdateContext};
 use crate::event::Event;
 use crate::sizing::{Unit, Size};
-use winit::event::ElementState;
+use winit::event::{ElementState, MouseButton};
 use gui_reactive::Signal;
 use gui_render::primitives::{Rectangle, Shadow};
 use std::any::Any;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use gui_render::primitives::Text;
-use vello::peniko::Color;
+use vello::peniko::{Brush, Color};
 use super::container::Background;
+use super::icon::IconWidget;
+use crate::theme::{current_theme, StyleOverrides, Theme};
+use crate::style::StyleSheet;
+use crate::media_query::ViewportSize;
+use crate::animation::{Transition, Transitioned};
 
 static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(3000);
 
@@ -21,6 +26,15 @@ pub enum ButtonState {
     Disabled,
 }
 
+/// Where a button draws its icon relative to the label
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IconPlacement {
+    Left,
+    Right,
+    /// Icon alone, centered; the label is not drawn
+    Only,
+}
+
 pub struct ButtonWidget {
     id: WidgetId,
     x: f32,
@@ -35,8 +49,25 @@ pub struct ButtonWidget {
     disabled_background: Background,
     border_radius: f32,
     font_size: f32,
+    font_weight: u16,
+    text_color: Color,
+    border: Option<(f32, Color)>,
     shadow: Option<Shadow>,
+    // Implicit transition for background changes, and the eased color it drives
+    transition: Option<Transition>,
+    background_transition: Option<Transitioned<Color>>,
+    icon: Option<IconWidget>,
+    icon_placement: IconPlacement,
+    icon_gap: f32,
+    // Toggle mode: each click flips the pressed state
+    toggled: Option<Signal<bool>>,
+    toggled_background: Background,
     on_click: Option<Box<dyn Fn() + Send + Sync>>,
+    on_toggle: Option<Box<dyn Fn(bool) + Send + Sync>>,
+    // Style sheet classes, applied between the theme and explicit overrides
+    classes: Vec<String>,
+    // Style set through builders, which the theme leaves alone
+    overrides: StyleOverrides,
     pub dirty: bool,
     // Shared dirty flag that reactive signals can set
     reactive_dirty: Arc<RwLock<bool>>,
@@ -44,6 +75,7 @@ pub struct ButtonWidget {
 
 impl ButtonWidget {
     pub fn new(label: impl Into<String>) -> Self {
+        let theme = current_theme();
         Self {
             id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
             x: 0.0,
@@ -52,14 +84,27 @@ impl ButtonWidget {
             height: 40.0,
             label: Signal::new(label.into()),
             state: ButtonState::Normal,
-            background: Background::Color(Color::rgba8(100, 150, 255, 255)), // Blue
-            hover_background: Background::Color(Color::rgba8(120, 170, 255, 255)),      // Lighter blue
-            pressed_background: Background::Color(Color::rgba8(80, 130, 235, 255)),     // Darker blue
-            disabled_background: Background::Color(Color::rgba8(150, 150, 150, 255)),   // Gray
-            border_radius: 4.0,
-            font_size: 14.0,
+            background: Background::Color(theme.palette.primary),
+            hover_background: Background::Color(theme.palette.primary_hover),
+            pressed_background: Background::Color(theme.palette.primary_pressed),
+            disabled_background: Background::Color(theme.palette.disabled),
+            border_radius: theme.radii.medium,
+            font_size: theme.typography.font_size,
+            font_weight: theme.typography.font_weight,
+            text_color: theme.palette.on_primary,
+            border: None,
             shadow: None,
+            transition: None,
+            background_transition: None,
+            icon: None,
+            icon_placement: IconPlacement::Left,
+            icon_gap: 6.0,
+            toggled: None,
+            toggled_background: Background::Color(theme.palette.primary_pressed),
             on_click: None,
+            on_toggle: None,
+            classes: Vec::new(),
+            overrides: StyleOverrides::default(),
             dirty: true,
             reactive_dirty: Arc::new(RwLock::new(false)),
         }
@@ -110,60 +155,140 @@ impl ButtonWidget {
         self
     }
 
-    pub fn with_colors(mut self, normal: Color, hover: Color, pressed: Color) -> Self {
-        self.background = Background::Color(normal);
-        self.hover_background = Background::Color(hover);
-        self.pressed_background = Background::Color(pressed);
-        self.dirty = true;
-        self
+    pub fn with_colors(self, normal: Color, hover: Color, pressed: Color) -> Self {
+        self.with_backgrounds(Background::Color(normal), Background::Color(hover), Background::Color(pressed))
     }
 
-    pub fn with_backgrounds(mut self, normal: Background, hover: Background, pressed: Background) -> Self {
-        self.background = normal;
-        self.hover_background = hover;
-        self.pressed_background = pressed;
-        self.dirty = true;
-        self
+    pub fn with_backgrounds(self, normal: Background, hover: Background, pressed: Background) -> Self {
+        self.with_background(normal)
+            .with_hover_background(hover)
+            .with_pressed_background(pressed)
     }
 
     pub fn with_background(mut self, background: Background) -> Self {
         self.background = background;
+        self.overrides.mark("background");
         self.dirty = true;
         self
     }
 
     pub fn with_hover_background(mut self, background: Background) -> Self {
         self.hover_background = background;
+        self.overrides.mark("hover_background");
         self.dirty = true;
         self
     }
 
     pub fn with_pressed_background(mut self, background: Background) -> Self {
         self.pressed_background = background;
+        self.overrides.mark("pressed_background");
         self.dirty = true;
         self
     }
 
     pub fn with_disabled_background(mut self, background: Background) -> Self {
         self.disabled_background = background;
+        self.overrides.mark("disabled_background");
         self.dirty = true;
         self
     }
 
     pub fn with_border_radius(mut self, radius: f32) -> Self {
         self.border_radius = radius;
+        self.overrides.mark("border_radius");
         self.dirty = true;
         self
     }
 
     pub fn with_font_size(mut self, size: f32) -> Self {
         self.font_size = size;
+        self.overrides.mark("font_size");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_font_weight(mut self, weight: u16) -> Self {
+        self.font_weight = weight;
+        self.overrides.mark("font_weight");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_text_color(mut self, color: Color) -> Self {
+        self.text_color = color;
+        self.overrides.mark("text_color");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_border(mut self, width: f32, color: Color) -> Self {
+        self.border = Some((width, color));
+        self.overrides.mark("border");
         self.dirty = true;
         self
     }
 
     pub fn with_shadow(mut self, offset_x: f32, offset_y: f32, blur_radius: f32, color: Color) -> Self {
         self.shadow = Some(Shadow::new(self.x, self.y, self.width, self.height, offset_x, offset_y, blur_radius, color));
+        self.overrides.mark("shadow");
+        self.dirty = true;
+        self
+    }
+
+    /// Fade between backgrounds (hover, press, toggle, theme changes) instead
+    /// of switching instantly. Gradient backgrounds still switch instantly.
+    pub fn with_transition(mut self, transition: Transition) -> Self {
+        self.transition = Some(transition);
+        self
+    }
+
+    /// Style this button from a style sheet class; can be called repeatedly
+    pub fn with_class(mut self, class: impl Into<String>) -> Self {
+        self.classes.push(class.into());
+        self.dirty = true;
+        self
+    }
+
+    /// Icon beside the label, e.g. `icon("tools/brush").with_size(16.0)`
+    pub fn with_icon(mut self, icon: IconWidget) -> Self {
+        self.icon = Some(icon);
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_icon_placement(mut self, placement: IconPlacement) -> Self {
+        self.icon_placement = placement;
+        self.dirty = true;
+        self
+    }
+
+    /// Draw only the icon; the label still names the button
+    pub fn icon_only(self) -> Self {
+        self.with_icon_placement(IconPlacement::Only)
+    }
+
+    pub fn with_icon_gap(mut self, gap: f32) -> Self {
+        self.icon_gap = gap;
+        self.dirty = true;
+        self
+    }
+
+    /// Toggle mode: clicks flip `toggled` and the button stays pressed while it is true
+    pub fn with_toggle_signal(mut self, toggled: Signal<bool>) -> Self {
+        self.toggled = Some(toggled);
+        self.dirty = true;
+        self
+    }
+
+    /// Toggle mode with its own state, starting released
+    pub fn toggle(self) -> Self {
+        self.with_toggle_signal(Signal::new(false))
+    }
+
+    /// Background while toggled on
+    pub fn with_toggled_background(mut self, background: Background) -> Self {
+        self.toggled_background = background;
+        self.overrides.mark("toggled_background");
         self.dirty = true;
         self
     }
@@ -176,14 +301,164 @@ impl ButtonWidget {
         self
     }
 
+    /// Called with the new state when a click toggles the button
+    pub fn on_toggle<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(bool) + Send + Sync + 'static,
+    {
+        self.on_toggle = Some(Box::new(callback));
+        self
+    }
+
     pub fn set_position(&mut self, x: f32, y: f32) {
         if self.x != x || self.y != y {
             self.x = x;
             self.y = y;
+            self.layout_icon();
             self.dirty = true;
         }
     }
 
+    pub fn is_toggle(&self) -> bool {
+        self.toggled.is_some()
+    }
+
+    pub fn is_toggled(&self) -> bool {
+        self.toggled.as_ref().is_some_and(|toggled| toggled.get())
+    }
+
+    pub fn get_toggle_signal(&self) -> Option<Signal<bool>> {
+        self.toggled.clone()
+    }
+
+    pub fn set_toggled(&mut self, toggled: bool) {
+        if let Some(signal) = &self.toggled {
+            if signal.get() != toggled {
+                signal.set(toggled);
+                self.dirty = true;
+            }
+        }
+    }
+
+    pub fn get_icon(&self) -> Option<&IconWidget> {
+        self.icon.as_ref()
+    }
+
+    /// Take every style property not set through a builder from `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let palette = &theme.palette;
+        let overrides = &self.overrides;
+        if !overrides.is_set("background") {
+            self.background = Background::Color(palette.primary);
+        }
+        if !overrides.is_set("hover_background") {
+            self.hover_background = Background::Color(palette.primary_hover);
+        }
+        if !overrides.is_set("pressed_background") {
+            self.pressed_background = Background::Color(palette.primary_pressed);
+        }
+        if !overrides.is_set("disabled_background") {
+            self.disabled_background = Background::Color(palette.disabled);
+        }
+        if !overrides.is_set("toggled_background") {
+            self.toggled_background = Background::Color(palette.primary_pressed);
+        }
+        if !overrides.is_set("border_radius") {
+            self.border_radius = theme.radii.medium;
+        }
+        if !overrides.is_set("font_size") {
+            self.font_size = theme.typography.font_size;
+        }
+        if !overrides.is_set("text_color") {
+            self.text_color = palette.on_primary;
+        }
+        if !overrides.is_set("font_weight") {
+            self.font_weight = theme.typography.font_weight;
+        }
+        self.dirty = true;
+    }
+
+    /// Layer this button's classes over the theme. Runs after `apply_theme`,
+    /// so properties the sheet leaves unset keep their theme value.
+    pub fn apply_style_sheet(&mut self, sheet: &StyleSheet, viewport: ViewportSize) {
+        if self.classes.is_empty() {
+            return;
+        }
+        let resolved = sheet.resolve_all(&self.classes, viewport);
+        let overrides = &self.overrides;
+        let states = [
+            ("background", &resolved.normal, &mut self.background),
+            ("hover_background", &resolved.hover, &mut self.hover_background),
+            ("pressed_background", &resolved.pressed, &mut self.pressed_background),
+            ("disabled_background", &resolved.disabled, &mut self.disabled_background),
+        ];
+        for (property, style, background) in states {
+            if let (false, Some(color)) = (overrides.is_set(property), style.background) {
+                *background = Background::Color(color);
+            }
+        }
+        let normal = &resolved.normal;
+        if let (false, Some(radius)) = (overrides.is_set("border_radius"), normal.border_radius) {
+            self.border_radius = radius;
+        }
+        if let (false, Some(size)) = (overrides.is_set("font_size"), normal.font_size) {
+            self.font_size = size;
+        }
+        if let (false, Some(weight)) = (overrides.is_set("font_weight"), normal.font_weight) {
+            self.font_weight = weight;
+        }
+        if let (false, Some(color)) = (overrides.is_set("text_color"), normal.text_color) {
+            self.text_color = color;
+        }
+        // The theme has no say in borders and shadows, so the sheet owns them outright
+        if !overrides.is_set("border") {
+            self.border = normal.border();
+        }
+        if !overrides.is_set("shadow") {
+            self.shadow = normal.shadow.as_ref().map(|shadow| {
+                Shadow::new(self.x, self.y, self.width, self.height, shadow.offset_x, shadow.offset_y, shadow.blur_radius, shadow.color)
+            });
+        }
+        self.dirty = true;
+    }
+
+    pub fn get_classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    fn label_width(&self) -> f32 {
+        self.label.with(|label| label.chars().count()) as f32 * self.font_size * 0.6
+    }
+
+    fn shows_label(&self) -> bool {
+        self.icon.is_none() || (self.icon_placement != IconPlacement::Only && self.label.with(|label| !label.is_empty()))
+    }
+
+    // Left edge of the centered icon + label group
+    fn content_start(&self) -> f32 {
+        let icon_width = self.icon.as_ref().map_or(0.0, |icon| icon.get_size().0);
+        let content_width = match (&self.icon, self.shows_label()) {
+            (Some(_), true) => icon_width + self.icon_gap + self.label_width(),
+            (Some(_), false) => icon_width,
+            (None, _) => self.label_width(),
+        };
+        self.x + (self.width - content_width) / 2.0
+    }
+
+    fn layout_icon(&mut self) {
+        let start = self.content_start();
+        let icon_x = if self.shows_label() && self.icon_placement == IconPlacement::Right {
+            start + self.label_width() + self.icon_gap
+        } else {
+            start
+        };
+        let center_y = self.y + self.height / 2.0;
+        if let Some(icon) = &mut self.icon {
+            let (_, icon_height) = icon.get_size();
+            icon.set_position(icon_x, center_y - icon_height / 2.0);
+        }
+    }
+
     pub fn set_enabled(&mut self, enabled: bool) {
         self.state = if enabled && self.state == ButtonState::Disabled {
             ButtonState::Normal
@@ -208,6 +483,7 @@ impl ButtonWidget {
 
     pub fn get_current_background(&self) -> &Background {
         match self.state {
+            ButtonState::Normal | ButtonState::Hovered if self.is_toggled() => &self.toggled_background,
             ButtonState::Normal => &self.background,
             ButtonState::Hovered => &self.hover_background,
             ButtonState::Pressed => &self.pressed_background,
@@ -216,11 +492,47 @@ impl ButtonWidget {
     }
 
     pub fn create_background_rectangle(&self) -> Rectangle {
-        let brush = self.get_current_background().to_brush();
+        let brush = match (&self.background_transition, self.get_current_background()) {
+            (Some(animated), Background::Color(_)) => Brush::Solid(animated.get()),
+            (_, background) => background.to_brush(),
+        };
         Rectangle::new_with_brush(self.x, self.y, self.width, self.height, brush)
             .with_border_radius(self.border_radius)
     }
 
+    // Point the background transition at the background for the current state
+    fn sync_background_transition(&mut self) {
+        let Some(transition) = self.transition else {
+            return;
+        };
+        let target = match self.get_current_background() {
+            Background::Color(color) => *color,
+            Background::Gradient(_) => return,
+        };
+        match &mut self.background_transition {
+            Some(animated) => animated.animate_to(target),
+            None => {
+                // First sync starts at the target; only later changes fade
+                let animated = Transitioned::new(target, transition);
+                let reactive_dirty = self.reactive_dirty.clone();
+                animated.signal().subscribe_fn(move |_| {
+                    if let Ok(mut dirty) = reactive_dirty.write() {
+                        *dirty = true;
+                    }
+                });
+                self.background_transition = Some(animated);
+            },
+        }
+    }
+
+    /// Border drawn as a larger rectangle behind the background
+    pub fn create_border_rectangle(&self) -> Option<Rectangle> {
+        self.border.map(|(width, color)| {
+            Rectangle::new(self.x - width, self.y - width, self.width + width * 2.0, self.height + width * 2.0, color)
+                .with_border_radius(self.border_radius + width)
+        })
+    }
+
     pub fn create_shadow(&self) -> Option<Shadow> {
         self.shadow.as_ref().map(|shadow| {
             Shadow::new(self.x, self.y, self.width, self.height, 
@@ -238,16 +550,18 @@ impl ButtonWidget {
     
     pub fn create_text_primitive(&self) -> Option<gui_render::primitives::Text> {
         let label_text = self.label.get();
-        if !label_text.is_empty() {
-            // Position text in the center of the button
-            let text_x = self.x + (self.width / 2.0) - (label_text.len() as f32 * self.font_size * 0.3); // Rough centering
+        if !label_text.is_empty() && self.shows_label() {
+            // Center the label, or the icon + label group
+            let text_x = match &self.icon {
+                Some(icon) if self.icon_placement == IconPlacement::Left => self.content_start() + icon.get_size().0 + self.icon_gap,
+                _ => self.content_start(),
+            };
             // Proper vertical centering: position at center and adjust by font baseline offset
             let text_y = self.y + (self.height / 2.0) + (self.font_size * 0.25);
             
-            // Use white text color for good contrast against button background
-            let text_color = Color::rgba8(255, 255, 255, 255);
-            
-            Some(Text::new(text_x, text_y, label_text, text_color, self.font_size))
+            let mut text = Text::new(text_x, text_y, label_text, self.text_color, self.font_size);
+            text.font_weight = self.font_weight;
+            Some(text)
         } else {
             None
         }
@@ -265,11 +579,26 @@ impl Widget for ButtonWidget {
                 *dirty = true;
             }
         });
+        if let Some(ref toggled) = self.toggled {
+            let reactive_dirty = self.reactive_dirty.clone();
+            toggled.subscribe_fn(move |_| {
+                if let Ok(mut dirty) = reactive_dirty.write() {
+                    *dirty = true;
+                }
+            });
+        }
+        if let Some(icon) = &mut self.icon {
+            icon.mount()?;
+        }
+        self.layout_icon();
         
         Ok(())
     }
 
     fn unmount(&mut self) -> Result<(), WidgetError> {
+        if let Some(icon) = &mut self.icon {
+            icon.unmount()?;
+        }
         Ok(())
     }
 
@@ -282,7 +611,14 @@ impl Widget for ButtonWidget {
             }
         }
         
+        if let Some(icon) = &mut self.icon {
+            icon.update(ctx)?;
+        }
+        // Catches theme, style sheet and toggle changes to the background
+        self.sync_background_transition();
         if self.dirty {
+            // Label or size may have changed
+            self.layout_icon();
             ctx.mark_dirty(self.id);
         }
         Ok(())
@@ -329,6 +665,13 @@ impl Widget for ButtonWidget {
                     // Mouse up
                     if self.state == ButtonState::Pressed {
                         self.state = if inside {
+                            if let Some(ref toggled) = self.toggled {
+                                let now_toggled = !toggled.get();
+                                toggled.set(now_toggled);
+                                if let Some(ref callback) = self.on_toggle {
+                                    callback(now_toggled);
+                                }
+                            }
                             if let Some(ref callback) = self.on_click {
                                 callback();
                             }
@@ -340,6 +683,7 @@ impl Widget for ButtonWidget {
                 }
 
                 if old_state != self.state {
+                    self.sync_background_transition();
                     self.dirty = true;
                     EventResult::Handled
                 } else {
@@ -398,12 +742,19 @@ pub struct InputWidget {
     background_color: Color,
     border_color: Color,
     focused_border_color: Color,
+    border_width: f32,
     text_color: Color,
     placeholder_color: Color,
     border_radius: f32,
+    font_size: f32,
+    padding: f32,
     shadow: Option<Shadow>,
     on_change: Option<Box<dyn Fn(&str) + Send + Sync>>,
     on_submit: Option<Box<dyn Fn(&str) + Send + Sync>>,
+    // Style sheet classes, applied between the theme and explicit overrides
+    classes: Vec<String>,
+    // Style set through builders, which the theme leaves alone
+    overrides: StyleOverrides,
     pub dirty: bool,
     // Shared dirty flag that reactive signals can set
     reactive_dirty: Arc<RwLock<bool>>,
@@ -411,6 +762,7 @@ pub struct InputWidget {
 
 impl InputWidget {
     pub fn new() -> Self {
+        let theme = current_theme();
         Self {
             id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
             x: 0.0,
@@ -421,15 +773,20 @@ impl InputWidget {
             placeholder: String::new(),
             is_focused: false,
             cursor_position: 0,
-            background_color: Color::rgba8(255, 255, 255, 255), // White
-            border_color: Color::rgba8(200, 200, 200, 255),     // Light gray
-            focused_border_color: Color::rgba8(100, 150, 255, 255), // Blue
-            text_color: Color::rgba8(0, 0, 0, 255),             // Black
-            placeholder_color: Color::rgba8(150, 150, 150, 255), // Gray
-            border_radius: 4.0,
+            background_color: theme.palette.surface,
+            border_color: theme.palette.border,
+            focused_border_color: theme.palette.primary,
+            border_width: 0.0,
+            text_color: theme.palette.text,
+            placeholder_color: theme.palette.text_muted,
+            border_radius: theme.radii.medium,
+            font_size: 14.0,
+            padding: 8.0,
             shadow: None,
             on_change: None,
             on_submit: None,
+            classes: Vec::new(),
+            overrides: StyleOverrides::default(),
             dirty: true,
             reactive_dirty: Arc::new(RwLock::new(false)),
         }
@@ -519,10 +876,125 @@ impl InputWidget {
 
     pub fn with_shadow(mut self, offset_x: f32, offset_y: f32, blur_radius: f32, color: Color) -> Self {
         self.shadow = Some(Shadow::new(self.x, self.y, self.width, self.height, offset_x, offset_y, blur_radius, color));
+        self.overrides.mark("shadow");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_colors(mut self, background: Color, border: Color, text: Color) -> Self {
+        self.background_color = background;
+        self.border_color = border;
+        self.text_color = text;
+        self.overrides.mark("background_color");
+        self.overrides.mark("border_color");
+        self.overrides.mark("text_color");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_border_radius(mut self, radius: f32) -> Self {
+        self.border_radius = radius;
+        self.overrides.mark("border_radius");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_border_width(mut self, width: f32) -> Self {
+        self.border_width = width;
+        self.overrides.mark("border_width");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self.overrides.mark("font_size");
+        self.dirty = true;
+        self
+    }
+
+    /// Horizontal inset of the text from the edges
+    pub fn with_padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self.overrides.mark("padding");
+        self.dirty = true;
+        self
+    }
+
+    /// Style this input from a style sheet class; can be called repeatedly
+    pub fn with_class(mut self, class: impl Into<String>) -> Self {
+        self.classes.push(class.into());
         self.dirty = true;
         self
     }
 
+    /// Take every style property not set through a builder from `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let palette = &theme.palette;
+        let overrides = &self.overrides;
+        if !overrides.is_set("background_color") {
+            self.background_color = palette.surface;
+        }
+        if !overrides.is_set("border_color") {
+            self.border_color = palette.border;
+        }
+        if !overrides.is_set("text_color") {
+            self.text_color = palette.text;
+        }
+        if !overrides.is_set("border_radius") {
+            self.border_radius = theme.radii.medium;
+        }
+        self.focused_border_color = palette.primary;
+        self.placeholder_color = palette.text_muted;
+        self.dirty = true;
+    }
+
+    /// Layer this input's classes over the theme; `:focused` rules style the
+    /// focused border
+    pub fn apply_style_sheet(&mut self, sheet: &StyleSheet, viewport: ViewportSize) {
+        if self.classes.is_empty() {
+            return;
+        }
+        let resolved = sheet.resolve_all(&self.classes, viewport);
+        let overrides = &self.overrides;
+        let normal = &resolved.normal;
+        if let (false, Some(color)) = (overrides.is_set("background_color"), normal.background) {
+            self.background_color = color;
+        }
+        if let (false, Some(color)) = (overrides.is_set("border_color"), normal.border_color) {
+            self.border_color = color;
+        }
+        if let Some(color) = resolved.focused.border_color {
+            self.focused_border_color = color;
+        }
+        if let (false, Some(color)) = (overrides.is_set("text_color"), normal.text_color) {
+            self.text_color = color;
+        }
+        if let (false, Some(radius)) = (overrides.is_set("border_radius"), normal.border_radius) {
+            self.border_radius = radius;
+        }
+        // Not theme tokens, so unset values go back to the built-in defaults
+        if !overrides.is_set("border_width") {
+            self.border_width = normal.border().map_or(0.0, |(width, _)| width);
+        }
+        if !overrides.is_set("font_size") {
+            self.font_size = normal.font_size.unwrap_or(14.0);
+        }
+        if !overrides.is_set("padding") {
+            self.padding = normal.padding.map_or(8.0, |padding| padding.left);
+        }
+        if !overrides.is_set("shadow") {
+            self.shadow = normal.shadow.as_ref().map(|shadow| {
+                Shadow::new(self.x, self.y, self.width, self.height, shadow.offset_x, shadow.offset_y, shadow.blur_radius, shadow.color)
+            });
+        }
+        self.dirty = true;
+    }
+
+    pub fn get_classes(&self) -> &[String] {
+        &self.classes
+    }
+
     pub fn set_position(&mut self, x: f32, y: f32) {
         if self.x != x || self.y != y {
             self.x = x;
@@ -531,6 +1003,14 @@ impl InputWidget {
         }
     }
 
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        if self.width != width || self.height != height {
+            self.width = width;
+            self.height = height;
+            self.dirty = true;
+        }
+    }
+
     pub fn set_focus(&mut self, focused: bool) {
         if self.is_focused != focused {
             self.is_focused = focused;
@@ -608,6 +1088,16 @@ impl InputWidget {
             .with_stroke_width(2.0)
     }
 
+    /// Border drawn as a larger rectangle behind the background
+    pub fn create_border_rectangle(&self) -> Option<Rectangle> {
+        if self.border_width <= 0.0 {
+            return None;
+        }
+        let width = self.border_width;
+        Some(Rectangle::new(self.x - width, self.y - width, self.width + width * 2.0, self.height + width * 2.0, self.get_border_color())
+            .with_border_radius(self.border_radius + width))
+    }
+
     pub fn create_shadow(&self) -> Option<Shadow> {
         self.shadow.as_ref().map(|shadow| {
             Shadow::new(self.x, self.y, self.width, self.height, 
@@ -625,9 +1115,8 @@ impl InputWidget {
         
         if !display_text.is_empty() {
             // Position text with some padding from the left edge
-            let text_size = 14.0; // Default font size for input fields
-            let padding = 8.0;
-            let text_x = self.x + padding;
+            let text_size = self.font_size;
+            let text_x = self.x + self.padding;
             // Better vertical centering: baseline position is roughly 3/4 down from the top of the text height
             let text_y = self.y + (self.height / 2.0) + (text_size * 0.25);
             
@@ -800,6 +1289,51 @@ impl Widget for InputWidget {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SliderOrientation {
+    Horizontal,
+    /// Minimum at the bottom
+    Vertical,
+}
+
+/// How thumb position maps to value
+#[derive(Clone)]
+pub enum SliderScale {
+    Linear,
+    /// Equal travel per ratio, e.g. for frequencies and zoom. Needs a positive minimum.
+    Logarithmic,
+    /// `to_value` maps a position fraction (0..1) to a value fraction (0..1);
+    /// `to_position` is its inverse
+    Curve {
+        to_value: Arc<dyn Fn(f32) -> f32 + Send + Sync>,
+        to_position: Arc<dyn Fn(f32) -> f32 + Send + Sync>,
+    },
+}
+
+/// Mark drawn along the track, optionally labelled
+#[derive(Clone, Debug, PartialEq)]
+pub struct SliderTick {
+    pub value: f32,
+    pub label: Option<String>,
+}
+
+impl SliderTick {
+    pub fn new(value: f32) -> Self {
+        Self { value, label: None }
+    }
+
+    pub fn labeled(value: f32, label: impl Into<String>) -> Self {
+        Self { value, label: Some(label.into()) }
+    }
+}
+
+// Which thumb a drag moves; single-value sliders only use `Low`
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SliderThumb {
+    Low,
+    High,
+}
+
 pub struct SliderWidget {
     id: WidgetId,
     x: f32,
@@ -807,23 +1341,39 @@ pub struct SliderWidget {
     width: f32,
     height: f32,
     value: Signal<f32>,
+    // Two-thumb mode: the selected interval
+    range: Option<Signal<(f32, f32)>>,
     min_value: f32,
     max_value: f32,
     step: f32,
+    orientation: SliderOrientation,
+    scale: SliderScale,
+    ticks: Vec<SliderTick>,
+    snap_to_ticks: bool,
     is_dragging: bool,
+    active_thumb: SliderThumb,
+    show_value_tooltip: bool,
+    value_format: Option<Box<dyn Fn(f32) -> String + Send + Sync>>,
     track_color: Color,
     fill_color: Color,
     thumb_color: Color,
     thumb_hover_color: Color,
+    tick_color: Color,
     thumb_radius: f32,
     shadow: Option<Shadow>,
     on_change: Option<Box<dyn Fn(f32) + Send + Sync>>,
+    on_range_change: Option<Box<dyn Fn(f32, f32) + Send + Sync>>,
+    // Style set through builders, which the theme leaves alone
+    overrides: StyleOverrides,
     pub dirty: bool,
+    // Shared dirty flag that reactive signals can set
+    reactive_dirty: Arc<RwLock<bool>>,
 }
 
 impl SliderWidget {
     pub fn new(min_value: f32, max_value: f32) -> Self {
         let initial_value = (min_value + max_value) / 2.0;
+        let theme = current_theme();
         Self {
             id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
             x: 0.0,
@@ -831,18 +1381,30 @@ impl SliderWidget {
             width: 200.0,
             height: 20.0,
             value: Signal::new(initial_value),
+            range: None,
             min_value,
             max_value,
             step: 0.0, // 0.0 means continuous
+            orientation: SliderOrientation::Horizontal,
+            scale: SliderScale::Linear,
+            ticks: Vec::new(),
+            snap_to_ticks: false,
             is_dragging: false,
-            track_color: Color::rgba8(200, 200, 200, 255),      // Light gray
-            fill_color: Color::rgba8(100, 150, 255, 255),       // Blue
-            thumb_color: Color::rgba8(255, 255, 255, 255),      // White
-            thumb_hover_color: Color::rgba8(240, 240, 240, 255), // Light gray
+            active_thumb: SliderThumb::Low,
+            show_value_tooltip: true,
+            value_format: None,
+            track_color: theme.palette.border,
+            fill_color: theme.palette.primary,
+            thumb_color: theme.palette.on_primary,
+            thumb_hover_color: theme.palette.surface_hover,
+            tick_color: theme.palette.text_muted,
             thumb_radius: 10.0,
             shadow: None,
             on_change: None,
+            on_range_change: None,
+            overrides: StyleOverrides::default(),
             dirty: true,
+            reactive_dirty: Arc::new(RwLock::new(false)),
         }
     }
 
@@ -898,97 +1460,478 @@ impl SliderWidget {
         self
     }
 
-    pub fn with_step(mut self, step: f32) -> Self {
-        self.step = step;
+    /// Bind the value to a signal. Dragging writes through to it and changes
+    /// made elsewhere move the thumb.
+    pub fn with_value_signal(mut self, value: Signal<f32>) -> Self {
+        self.value = value;
+        self.dirty = true;
         self
     }
 
-    pub fn on_change<F>(mut self, callback: F) -> Self
-    where
-        F: Fn(f32) + Send + Sync + 'static,
-    {
-        self.on_change = Some(Box::new(callback));
+    /// Two-thumb mode selecting the interval held in `range` as (low, high)
+    pub fn with_range_signal(mut self, range: Signal<(f32, f32)>) -> Self {
+        self.range = Some(range);
+        self.dirty = true;
         self
     }
 
-    pub fn set_position(&mut self, x: f32, y: f32) {
-        if self.x != x || self.y != y {
-            self.x = x;
-            self.y = y;
-            self.dirty = true;
-        }
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
     }
 
-    pub fn set_value(&mut self, value: f32) {
-        let new_value = if self.step > 0.0 {
-            ((value - self.min_value) / self.step).round() * self.step + self.min_value
-        } else {
-            value
-        };
-        
-        let clamped_value = new_value.clamp(self.min_value, self.max_value);
-        
-        if (self.value.get() - clamped_value).abs() > f32::EPSILON {
-            self.value.set(clamped_value);
-            if let Some(ref callback) = self.on_change {
-                callback(clamped_value);
-            }
-            self.dirty = true;
+    /// Vertical slider with the minimum at the bottom. Swaps a horizontal default size.
+    pub fn vertical(mut self) -> Self {
+        if self.orientation == SliderOrientation::Horizontal && self.width > self.height {
+            std::mem::swap(&mut self.width, &mut self.height);
         }
+        self.orientation = SliderOrientation::Vertical;
+        self.dirty = true;
+        self
     }
 
-    pub fn get_value(&self) -> f32 {
-        self.value.get()
+    pub fn with_scale(mut self, scale: SliderScale) -> Self {
+        self.scale = scale;
+        self.dirty = true;
+        self
     }
 
-    pub fn value_to_position(&self, value: f32) -> f32 {
-        let progress = (value - self.min_value) / (self.max_value - self.min_value);
-        self.x + progress * self.width
+    /// Logarithmic mapping, so each ratio (e.g. each octave) gets the same travel
+    pub fn logarithmic(self) -> Self {
+        self.with_scale(SliderScale::Logarithmic)
     }
 
-    pub fn position_to_value(&self, x: f32) -> f32 {
-        let progress = ((x - self.x) / self.width).clamp(0.0, 1.0);
-        self.min_value + progress * (self.max_value - self.min_value)
+    /// Custom mapping from position fraction to value fraction, with its inverse
+    pub fn with_curve<F, G>(self, to_value: F, to_position: G) -> Self
+    where
+        F: Fn(f32) -> f32 + Send + Sync + 'static,
+        G: Fn(f32) -> f32 + Send + Sync + 'static,
+    {
+        self.with_scale(SliderScale::Curve {
+            to_value: Arc::new(to_value),
+            to_position: Arc::new(to_position),
+        })
     }
 
-    pub fn get_thumb_position(&self) -> f32 {
-        self.value_to_position(self.value.get())
+    pub fn with_ticks(mut self, ticks: Vec<SliderTick>) -> Self {
+        self.ticks = ticks;
+        self.dirty = true;
+        self
     }
 
-    pub fn is_point_on_thumb(&self, x: f32, y: f32) -> bool {
-        let thumb_x = self.get_thumb_position();
-        let thumb_y = self.y + self.height / 2.0;
-        let dx = x - thumb_x;
-        let dy = y - thumb_y;
-        (dx * dx + dy * dy).sqrt() <= self.thumb_radius
+    /// Unlabelled ticks every `interval` from the minimum
+    pub fn with_tick_interval(mut self, interval: f32) -> Self {
+        if interval > 0.0 {
+            let count = ((self.max_value - self.min_value) / interval).floor() as usize;
+            self.ticks = (0..=count).map(|index| SliderTick::new(self.min_value + index as f32 * interval)).collect();
+            self.dirty = true;
+        }
+        self
     }
 
-    pub fn is_point_on_track(&self, x: f32, y: f32) -> bool {
-        x >= self.x && x <= self.x + self.width &&
-        y >= self.y && y <= self.y + self.height
+    /// Values only land on ticks
+    pub fn with_snap_to_ticks(mut self, snap: bool) -> Self {
+        self.snap_to_ticks = snap;
+        self
     }
 
-    pub fn create_track_rectangle(&self) -> Rectangle {
-        let track_height = 4.0;
-        Rectangle::new(
-            self.x,
-            self.y + (self.height - track_height) / 2.0,
-            self.width,
-            track_height,
-            self.track_color,
-        ).with_border_radius(2.0)
+    /// Show the value above the thumb while it is dragged (on by default)
+    pub fn with_value_tooltip(mut self, show: bool) -> Self {
+        self.show_value_tooltip = show;
+        self
+    }
+
+    /// Text of the drag tooltip, e.g. `|db| format!("{:.1} dB", db)`
+    pub fn with_value_format<F>(mut self, format: F) -> Self
+    where
+        F: Fn(f32) -> String + Send + Sync + 'static,
+    {
+        self.value_format = Some(Box::new(format));
+        self
+    }
+
+    pub fn on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(f32) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Box::new(callback));
+        self
+    }
+
+    pub fn on_range_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(f32, f32) + Send + Sync + 'static,
+    {
+        self.on_range_change = Some(Box::new(callback));
+        self
+    }
+
+    pub fn with_colors(mut self, track: Color, fill: Color, thumb: Color) -> Self {
+        self.track_color = track;
+        self.fill_color = fill;
+        self.thumb_color = thumb;
+        self.overrides.mark("track_color");
+        self.overrides.mark("fill_color");
+        self.overrides.mark("thumb_color");
+        self.dirty = true;
+        self
+    }
+
+    /// Take every style property not set through a builder from `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let palette = &theme.palette;
+        let overrides = &self.overrides;
+        if !overrides.is_set("track_color") {
+            self.track_color = palette.border;
+        }
+        if !overrides.is_set("fill_color") {
+            self.fill_color = palette.primary;
+        }
+        if !overrides.is_set("thumb_color") {
+            self.thumb_color = palette.on_primary;
+        }
+        self.thumb_hover_color = palette.surface_hover;
+        self.tick_color = palette.text_muted;
+        self.dirty = true;
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.orientation == SliderOrientation::Vertical
+    }
+
+    pub fn is_range(&self) -> bool {
+        self.range.is_some()
+    }
+
+    // Step or tick snapping, then clamping
+    fn snap_value(&self, value: f32) -> f32 {
+        let snapped = if self.snap_to_ticks && !self.ticks.is_empty() {
+            self.ticks.iter()
+                .map(|tick| tick.value)
+                .min_by(|a, b| (a - value).abs().total_cmp(&(b - value).abs()))
+                .unwrap_or(value)
+        } else if self.step > 0.0 {
+            ((value - self.min_value) / self.step).round() * self.step + self.min_value
+        } else {
+            value
+        };
+        snapped.clamp(self.min_value, self.max_value)
+    }
+
+    pub fn set_value(&mut self, value: f32) {
+        let clamped_value = self.snap_value(value);
+        
+        if (self.value.get() - clamped_value).abs() > f32::EPSILON {
+            self.value.set(clamped_value);
+            if let Some(ref callback) = self.on_change {
+                callback(clamped_value);
+            }
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_value(&self) -> f32 {
+        self.value.get()
     }
 
+    pub fn get_range(&self) -> Option<(f32, f32)> {
+        self.range.as_ref().map(|range| range.get())
+    }
+
+    /// Set both ends of a range slider; they are snapped and put in order
+    pub fn set_range(&mut self, low: f32, high: f32) {
+        let Some(range) = &self.range else {
+            return;
+        };
+        let (low, high) = (self.snap_value(low), self.snap_value(high));
+        let new_range = (low.min(high), low.max(high));
+        if range.get() != new_range {
+            range.set(new_range);
+            if let Some(ref callback) = self.on_range_change {
+                callback(new_range.0, new_range.1);
+            }
+            self.dirty = true;
+        }
+    }
+
+    /// Where `value` sits along the track, 0 at the minimum and 1 at the maximum
+    pub fn value_to_fraction(&self, value: f32) -> f32 {
+        let span = self.max_value - self.min_value;
+        if span == 0.0 {
+            return 0.0;
+        }
+        let linear = ((value - self.min_value) / span).clamp(0.0, 1.0);
+        match &self.scale {
+            SliderScale::Linear => linear,
+            SliderScale::Logarithmic if self.min_value > 0.0 => {
+                ((value.max(self.min_value) / self.min_value).ln() / (self.max_value / self.min_value).ln()).clamp(0.0, 1.0)
+            },
+            SliderScale::Logarithmic => linear,
+            SliderScale::Curve { to_position, .. } => to_position(linear).clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn fraction_to_value(&self, fraction: f32) -> f32 {
+        let fraction = fraction.clamp(0.0, 1.0);
+        let span = self.max_value - self.min_value;
+        match &self.scale {
+            SliderScale::Linear => self.min_value + fraction * span,
+            SliderScale::Logarithmic if self.min_value > 0.0 => {
+                self.min_value * (self.max_value / self.min_value).powf(fraction)
+            },
+            SliderScale::Logarithmic => self.min_value + fraction * span,
+            SliderScale::Curve { to_value, .. } => self.min_value + to_value(fraction).clamp(0.0, 1.0) * span,
+        }
+    }
+
+    /// Coordinate along the track (x, or y when vertical) of `value`
+    pub fn value_to_position(&self, value: f32) -> f32 {
+        let fraction = self.value_to_fraction(value);
+        match self.orientation {
+            SliderOrientation::Horizontal => self.x + fraction * self.width,
+            SliderOrientation::Vertical => self.y + self.height - fraction * self.height,
+        }
+    }
+
+    /// Value under a coordinate along the track (x, or y when vertical)
+    pub fn position_to_value(&self, position: f32) -> f32 {
+        let fraction = match self.orientation {
+            SliderOrientation::Horizontal => (position - self.x) / self.width,
+            SliderOrientation::Vertical => (self.y + self.height - position) / self.height,
+        };
+        self.fraction_to_value(fraction)
+    }
+
+    fn main_axis(&self, x: f32, y: f32) -> f32 {
+        match self.orientation {
+            SliderOrientation::Horizontal => x,
+            SliderOrientation::Vertical => y,
+        }
+    }
+
+    // Centre of the track across its length
+    fn cross_center(&self) -> f32 {
+        match self.orientation {
+            SliderOrientation::Horizontal => self.y + self.height / 2.0,
+            SliderOrientation::Vertical => self.x + self.width / 2.0,
+        }
+    }
+
+    fn thumb_values(&self) -> Vec<(SliderThumb, f32)> {
+        match self.get_range() {
+            Some((low, high)) => vec![(SliderThumb::Low, low), (SliderThumb::High, high)],
+            None => vec![(SliderThumb::Low, self.value.get())],
+        }
+    }
+
+    // Centre of a thumb showing `value`
+    fn thumb_center(&self, value: f32) -> (f32, f32) {
+        let along = self.value_to_position(value);
+        match self.orientation {
+            SliderOrientation::Horizontal => (along, self.cross_center()),
+            SliderOrientation::Vertical => (self.cross_center(), along),
+        }
+    }
+
+    pub fn get_thumb_position(&self) -> f32 {
+        self.value_to_position(self.value.get())
+    }
+
+    pub fn is_point_on_thumb(&self, x: f32, y: f32) -> bool {
+        self.thumb_values().iter().any(|(_, value)| {
+            let (thumb_x, thumb_y) = self.thumb_center(*value);
+            let dx = x - thumb_x;
+            let dy = y - thumb_y;
+            (dx * dx + dy * dy).sqrt() <= self.thumb_radius
+        })
+    }
+
+    pub fn is_point_on_track(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    // The thumb a press at `position` grabs: the closer one, or the one on the
+    // pointer's side when both sit together
+    fn nearest_thumb(&self, position: f32) -> SliderThumb {
+        let Some((low, high)) = self.get_range() else {
+            return SliderThumb::Low;
+        };
+        let (low_position, high_position) = (self.value_to_position(low), self.value_to_position(high));
+        let (low_distance, high_distance) = ((position - low_position).abs(), (position - high_position).abs());
+        if (low_distance - high_distance).abs() < 0.5 {
+            let towards_max = match self.orientation {
+                SliderOrientation::Horizontal => position > high_position,
+                SliderOrientation::Vertical => position < high_position,
+            };
+            if towards_max { SliderThumb::High } else { SliderThumb::Low }
+        } else if low_distance < high_distance {
+            SliderThumb::Low
+        } else {
+            SliderThumb::High
+        }
+    }
+
+    fn drag_to(&mut self, position: f32) {
+        let value = self.position_to_value(position);
+        match (self.get_range(), self.active_thumb) {
+            (Some((_, high)), SliderThumb::Low) => self.set_range(value.min(high), high),
+            (Some((low, _)), SliderThumb::High) => self.set_range(low, value.max(low)),
+            (None, _) => self.set_value(value),
+        }
+    }
+
+    fn format_value(&self, value: f32) -> String {
+        match &self.value_format {
+            Some(format) => format(value),
+            None => {
+                let text = format!("{:.2}", value);
+                text.trim_end_matches('0').trim_end_matches('.').to_string()
+            },
+        }
+    }
+
+    pub fn create_track_rectangle(&self) -> Rectangle {
+        let track_thickness = 4.0;
+        match self.orientation {
+            SliderOrientation::Horizontal => Rectangle::new(
+                self.x,
+                self.y + (self.height - track_thickness) / 2.0,
+                self.width,
+                track_thickness,
+                self.track_color,
+            ),
+            SliderOrientation::Vertical => Rectangle::new(
+                self.x + (self.width - track_thickness) / 2.0,
+                self.y,
+                track_thickness,
+                self.height,
+                self.track_color,
+            ),
+        }.with_border_radius(2.0)
+    }
+
+    /// The filled part of the track: up to the thumb, or between the two thumbs
     pub fn create_fill_rectangle(&self) -> Rectangle {
-        let track_height = 4.0;
-        let fill_width = self.get_thumb_position() - self.x;
-        Rectangle::new(
-            self.x,
-            self.y + (self.height - track_height) / 2.0,
-            fill_width,
-            track_height,
-            self.fill_color,
-        ).with_border_radius(2.0)
+        let track_thickness = 4.0;
+        let (start, end) = match self.get_range() {
+            Some((low, high)) => (self.value_to_fraction(low), self.value_to_fraction(high)),
+            None => (0.0, self.value_to_fraction(self.value.get())),
+        };
+        match self.orientation {
+            SliderOrientation::Horizontal => Rectangle::new(
+                self.x + start * self.width,
+                self.y + (self.height - track_thickness) / 2.0,
+                (end - start) * self.width,
+                track_thickness,
+                self.fill_color,
+            ),
+            SliderOrientation::Vertical => Rectangle::new(
+                self.x + (self.width - track_thickness) / 2.0,
+                self.y + self.height - end * self.height,
+                track_thickness,
+                (end - start) * self.height,
+                self.fill_color,
+            ),
+        }.with_border_radius(2.0)
+    }
+
+    /// Each thumb as an outline disc with the thumb disc on top
+    pub fn create_thumb_rectangles(&self) -> Vec<Rectangle> {
+        self.thumb_values().into_iter().flat_map(|(thumb, value)| {
+            let (center_x, center_y) = self.thumb_center(value);
+            let color = if self.is_dragging && thumb == self.active_thumb {
+                self.thumb_hover_color
+            } else {
+                self.thumb_color
+            };
+            let outline_radius = self.thumb_radius + 1.0;
+            [
+                Rectangle::new(
+                    center_x - outline_radius,
+                    center_y - outline_radius,
+                    outline_radius * 2.0,
+                    outline_radius * 2.0,
+                    self.track_color,
+                ).with_border_radius(outline_radius),
+                Rectangle::new(
+                    center_x - self.thumb_radius,
+                    center_y - self.thumb_radius,
+                    self.thumb_radius * 2.0,
+                    self.thumb_radius * 2.0,
+                    color,
+                ).with_border_radius(self.thumb_radius),
+            ]
+        }).collect()
+    }
+
+    /// Tick marks beside the track (below it, or right of it when vertical)
+    pub fn create_tick_rectangles(&self) -> Vec<Rectangle> {
+        let offset = self.thumb_radius + 2.0;
+        self.ticks.iter().map(|tick| {
+            let along = self.value_to_position(tick.value);
+            match self.orientation {
+                SliderOrientation::Horizontal => Rectangle::new(along - 0.5, self.cross_center() + offset, 1.0, 5.0, self.tick_color),
+                SliderOrientation::Vertical => Rectangle::new(self.cross_center() + offset, along - 0.5, 5.0, 1.0, self.tick_color),
+            }
+        }).collect()
+    }
+
+    pub fn create_tick_texts(&self) -> Vec<Text> {
+        let font_size = 11.0;
+        let offset = self.thumb_radius + 9.0;
+        self.ticks.iter().filter_map(|tick| {
+            let label = tick.label.as_ref()?;
+            let along = self.value_to_position(tick.value);
+            let label_width = label.chars().count() as f32 * font_size * 0.6;
+            Some(match self.orientation {
+                SliderOrientation::Horizontal => Text::new(
+                    along - label_width / 2.0,
+                    self.cross_center() + offset + font_size,
+                    label.clone(),
+                    self.tick_color,
+                    font_size,
+                ),
+                SliderOrientation::Vertical => Text::new(
+                    self.cross_center() + offset,
+                    along + font_size * 0.25,
+                    label.clone(),
+                    self.tick_color,
+                    font_size,
+                ),
+            })
+        }).collect()
+    }
+
+    /// Bubble with the dragged thumb's value, above it (or left of it when vertical)
+    pub fn create_value_tooltip(&self) -> Option<(Rectangle, Text)> {
+        if !self.is_dragging || !self.show_value_tooltip {
+            return None;
+        }
+        let value = self.thumb_values().into_iter()
+            .find(|(thumb, _)| *thumb == self.active_thumb)
+            .map(|(_, value)| value)?;
+        let label = self.format_value(value);
+        let font_size = 12.0;
+        let bubble_width = label.chars().count() as f32 * font_size * 0.6 + 12.0;
+        let bubble_height = font_size + 10.0;
+        let (center_x, center_y) = self.thumb_center(value);
+        let (bubble_x, bubble_y) = match self.orientation {
+            SliderOrientation::Horizontal => (center_x - bubble_width / 2.0, center_y - self.thumb_radius - bubble_height - 6.0),
+            SliderOrientation::Vertical => (center_x - self.thumb_radius - bubble_width - 6.0, center_y - bubble_height / 2.0),
+        };
+        Some((
+            Rectangle::new(bubble_x, bubble_y, bubble_width, bubble_height, Color::rgba8(40, 40, 45, 235)).with_border_radius(4.0),
+            Text::new(bubble_x + 6.0, bubble_y + bubble_height / 2.0 + font_size * 0.25, label, Color::rgba8(255, 255, 255, 255), font_size),
+        ))
     }
 
     pub fn create_shadow(&self) -> Option<Shadow> {
@@ -1002,6 +1945,22 @@ impl SliderWidget {
 impl Widget for SliderWidget {
     fn mount(&mut self) -> Result<(), WidgetError> {
         self.dirty = true;
+        
+        let reactive_dirty = self.reactive_dirty.clone();
+        self.value.subscribe_fn(move |_| {
+            if let Ok(mut dirty) = reactive_dirty.write() {
+                *dirty = true;
+            }
+        });
+        if let Some(range) = &self.range {
+            let reactive_dirty = self.reactive_dirty.clone();
+            range.subscribe_fn(move |_| {
+                if let Ok(mut dirty) = reactive_dirty.write() {
+                    *dirty = true;
+                }
+            });
+        }
+        
         Ok(())
     }
 
@@ -1011,6 +1970,13 @@ impl Widget for SliderWidget {
     }
 
     fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        if let Ok(mut reactive_dirty) = self.reactive_dirty.write() {
+            if *reactive_dirty {
+                *reactive_dirty = false;
+                self.dirty = true;
+            }
+        }
+        
         if self.dirty {
             ctx.mark_dirty(self.id);
         }
@@ -1022,12 +1988,12 @@ impl Widget for SliderWidget {
             Event::Mouse(mouse_event) => {
                 let x_f32 = mouse_event.position.x as f32;
                 let y_f32 = mouse_event.position.y as f32;
+                let position = self.main_axis(x_f32, y_f32);
 
                 if mouse_event.button.is_none() {
                     // Mouse move
                     if self.is_dragging {
-                        let new_value = self.position_to_value(x_f32);
-                        self.set_value(new_value);
+                        self.drag_to(position);
                         EventResult::Handled
                     } else {
                         EventResult::Ignored
@@ -1036,8 +2002,9 @@ impl Widget for SliderWidget {
                     // Mouse down
                     if self.is_point_on_thumb(x_f32, y_f32) || self.is_point_on_track(x_f32, y_f32) {
                         self.is_dragging = true;
-                        let new_value = self.position_to_value(x_f32);
-                        self.set_value(new_value);
+                        self.active_thumb = self.nearest_thumb(position);
+                        self.drag_to(position);
+                        self.dirty = true;
                         EventResult::Handled
                     } else {
                         EventResult::Ignored
@@ -1046,6 +2013,7 @@ impl Widget for SliderWidget {
                     // Mouse up
                     if self.is_dragging {
                         self.is_dragging = false;
+                        self.dirty = true;
                         EventResult::Handled
                     } else {
                         EventResult::Ignored
@@ -1074,7 +2042,8 @@ impl Widget for SliderWidget {
 
         Ok(RenderData {
             dirty_regions: vec![dirty_region],
-            z_index: 1,
+            // Above neighbours while the value tooltip shows
+            z_index: if self.is_dragging { 10 } else { 1 },
         })
     }
 
@@ -1091,23 +2060,1381 @@ impl Widget for SliderWidget {
     }
 }
 
-// Convenience functions for creating interactive widgets
-pub fn button(label: impl Into<String>) -> ButtonWidget {
-    ButtonWidget::new(label)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CheckState {
+    Unchecked,
+    Checked,
+    /// Neither checked nor unchecked, e.g. a "select all" box over a partial selection
+    Indeterminate,
 }
 
-pub fn button_signal(label_signal: Signal<String>) -> ButtonWidget {
-    let mut widget = ButtonWidget::new(label_signal.get());
-    widget.label = label_signal;
-    widget.dirty = true;
-    widget
+impl CheckState {
+    pub fn is_checked(&self) -> bool {
+        *self == CheckState::Checked
+    }
 }
 
-pub fn input() -> InputWidget {
-    InputWidget::new()
+impl From<bool> for CheckState {
+    fn from(checked: bool) -> Self {
+        if checked { CheckState::Checked } else { CheckState::Unchecked }
+    }
 }
 
-pub fn slider(min: f32, max: f32) -> SliderWidget {
-    SliderWidget::new(min, max)
+// Focus ring drawn around a focused control
+fn focus_ring_color() -> Color {
+    Color::rgba8(100, 150, 255, 120)
+}
+
+pub struct CheckboxWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    label: String,
+    state: Signal<CheckState>,
+    // Optional two-state binding kept in sync with `state`
+    checked: Option<Signal<bool>>,
+    tri_state: bool,
+    enabled: bool,
+    is_focused: bool,
+    is_hovered: bool,
+    box_size: f32,
+    box_color: Color,
+    border_color: Color,
+    checked_color: Color,
+    mark_color: Color,
+    text_color: Color,
+    disabled_color: Color,
+    font_size: f32,
+    on_change: Option<Box<dyn Fn(CheckState) + Send + Sync>>,
+    // Style set through builders, which the theme leaves alone
+    overrides: StyleOverrides,
+    pub dirty: bool,
+    // Shared dirty flag that reactive signals can set
+    reactive_dirty: Arc<RwLock<bool>>,
 }
 
+impl CheckboxWidget {
+    pub fn new(label: impl Into<String>) -> Self {
+        let theme = current_theme();
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 200.0,
+            height: 24.0,
+            label: label.into(),
+            state: Signal::new(CheckState::Unchecked),
+            checked: None,
+            tri_state: false,
+            enabled: true,
+            is_focused: false,
+            is_hovered: false,
+            box_size: 16.0,
+            box_color: theme.palette.surface,
+            border_color: theme.palette.text_muted,
+            checked_color: theme.palette.primary,
+            mark_color: theme.palette.on_primary,
+            text_color: theme.palette.text,
+            disabled_color: theme.palette.disabled,
+            font_size: theme.typography.font_size,
+            on_change: None,
+            overrides: StyleOverrides::default(),
+            dirty: true,
+            reactive_dirty: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        self.state = Signal::new(checked.into());
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_state(mut self, state: CheckState) -> Self {
+        self.state = Signal::new(state);
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_state_signal(mut self, state: Signal<CheckState>) -> Self {
+        self.state = state;
+        self.dirty = true;
+        self
+    }
+
+    /// Bind to a boolean signal. Clicking writes through to it, and changes made
+    /// elsewhere are picked up on the next update.
+    pub fn with_checked_signal(mut self, checked: Signal<bool>) -> Self {
+        self.state = Signal::new(checked.get().into());
+        self.checked = Some(checked);
+        self.dirty = true;
+        self
+    }
+
+    /// Let clicks cycle through the indeterminate state as well
+    pub fn with_tri_state(mut self) -> Self {
+        self.tri_state = true;
+        self
+    }
+
+    pub fn with_colors(mut self, box_color: Color, checked_color: Color, mark_color: Color) -> Self {
+        self.box_color = box_color;
+        self.checked_color = checked_color;
+        self.mark_color = mark_color;
+        self.overrides.mark("box_color");
+        self.overrides.mark("checked_color");
+        self.overrides.mark("mark_color");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_text_color(mut self, color: Color) -> Self {
+        self.text_color = color;
+        self.overrides.mark("text_color");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self.overrides.mark("font_size");
+        self.dirty = true;
+        self
+    }
+
+    /// Take every style property not set through a builder from `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let palette = &theme.palette;
+        let overrides = &self.overrides;
+        if !overrides.is_set("box_color") {
+            self.box_color = palette.surface;
+        }
+        if !overrides.is_set("checked_color") {
+            self.checked_color = palette.primary;
+        }
+        if !overrides.is_set("mark_color") {
+            self.mark_color = palette.on_primary;
+        }
+        if !overrides.is_set("text_color") {
+            self.text_color = palette.text;
+        }
+        if !overrides.is_set("font_size") {
+            self.font_size = theme.typography.font_size;
+        }
+        self.border_color = palette.text_muted;
+        self.disabled_color = palette.disabled;
+        self.dirty = true;
+    }
+
+    pub fn on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(CheckState) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Box::new(callback));
+        self
+    }
+
+    pub fn disabled(mut self) -> Self {
+        self.enabled = false;
+        self.dirty = true;
+        self
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.is_focused = false;
+        }
+        self.dirty = true;
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn get_state(&self) -> CheckState {
+        self.state.get()
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.get_state().is_checked()
+    }
+
+    pub fn set_state(&mut self, state: CheckState) {
+        if self.state.get() == state {
+            return;
+        }
+        self.state.set(state);
+        if let Some(checked) = &self.checked {
+            checked.set(state.is_checked());
+        }
+        if let Some(ref callback) = self.on_change {
+            callback(state);
+        }
+        self.dirty = true;
+    }
+
+    pub fn toggle(&mut self) {
+        let next = match (self.state.get(), self.tri_state) {
+            (CheckState::Unchecked, _) => CheckState::Checked,
+            (CheckState::Checked, true) => CheckState::Indeterminate,
+            (CheckState::Checked, false) => CheckState::Unchecked,
+            // Clicking a partial "select all" box selects everything
+            (CheckState::Indeterminate, false) => CheckState::Checked,
+            (CheckState::Indeterminate, true) => CheckState::Unchecked,
+        };
+        self.set_state(next);
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    fn box_origin(&self) -> (f32, f32) {
+        (self.x, self.y + (self.height - self.box_size) / 2.0)
+    }
+
+    /// Focus ring, border, fill and (for the indeterminate state) the dash
+    pub fn create_box_rectangles(&self) -> Vec<Rectangle> {
+        let (box_x, box_y) = self.box_origin();
+        let size = self.box_size;
+        let state = self.state.get();
+        let mut rectangles = Vec::new();
+
+        if self.is_focused {
+            rectangles.push(Rectangle::new(box_x - 3.0, box_y - 3.0, size + 6.0, size + 6.0, focus_ring_color())
+                .with_border_radius(5.0));
+        }
+
+        let filled = state != CheckState::Unchecked;
+        let (border, fill) = if !self.enabled {
+            (self.disabled_color, if filled { self.disabled_color } else { self.box_color })
+        } else if filled {
+            (self.checked_color, self.checked_color)
+        } else if self.is_hovered {
+            (self.checked_color, self.box_color)
+        } else {
+            (self.border_color, self.box_color)
+        };
+        rectangles.push(Rectangle::new(box_x, box_y, size, size, border).with_border_radius(3.0));
+        rectangles.push(Rectangle::new(box_x + 1.5, box_y + 1.5, size - 3.0, size - 3.0, fill).with_border_radius(2.0));
+
+        if state == CheckState::Indeterminate {
+            rectangles.push(Rectangle::new(box_x + 4.0, box_y + size / 2.0 - 1.0, size - 8.0, 2.0, self.mark_color));
+        }
+        rectangles
+    }
+
+    /// Check mark and label
+    pub fn create_text_primitives(&self) -> Vec<Text> {
+        let (box_x, box_y) = self.box_origin();
+        let mut texts = Vec::new();
+
+        if self.state.get() == CheckState::Checked {
+            let mark_size = self.box_size * 0.8;
+            texts.push(Text::new(box_x + self.box_size * 0.2, box_y + self.box_size * 0.5 + mark_size * 0.35,
+                "✓".to_string(), self.mark_color, mark_size));
+        }
+
+        if !self.label.is_empty() {
+            let color = if self.enabled { self.text_color } else { self.disabled_color };
+            let text_y = self.y + (self.height / 2.0) + (self.font_size * 0.25);
+            texts.push(Text::new(box_x + self.box_size + 8.0, text_y, self.label.clone(), color, self.font_size));
+        }
+        texts
+    }
+}
+
+impl Widget for CheckboxWidget {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        self.dirty = true;
+
+        let reactive_dirty = self.reactive_dirty.clone();
+        self.state.subscribe_fn(move |_| {
+            if let Ok(mut dirty) = reactive_dirty.write() {
+                *dirty = true;
+            }
+        });
+        if let Some(checked) = &self.checked {
+            let reactive_dirty = self.reactive_dirty.clone();
+            checked.subscribe_fn(move |_| {
+                if let Ok(mut dirty) = reactive_dirty.write() {
+                    *dirty = true;
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        self.is_focused = false;
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        if let Ok(mut reactive_dirty) = self.reactive_dirty.write() {
+            if *reactive_dirty {
+                *reactive_dirty = false;
+                self.dirty = true;
+            }
+        }
+
+        // Pick up changes made to the bound boolean elsewhere
+        if let Some(checked) = &self.checked {
+            let bound_state: CheckState = checked.get().into();
+            if self.state.get() != bound_state && self.state.get() != CheckState::Indeterminate {
+                self.state.set(bound_state);
+                self.dirty = true;
+            }
+        }
+
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
+        match event {
+            Event::Mouse(mouse_event) => {
+                let inside = self.is_point_inside(mouse_event.position.x as f32, mouse_event.position.y as f32);
+
+                if mouse_event.button.is_none() {
+                    // Mouse move
+                    if inside != self.is_hovered {
+                        self.is_hovered = inside;
+                        self.dirty = true;
+                    }
+                    EventResult::Ignored
+                } else if mouse_event.button == Some(MouseButton::Left) && mouse_event.state == ElementState::Pressed {
+                    if self.is_focused != inside {
+                        self.is_focused = inside;
+                        self.dirty = true;
+                    }
+                    if inside {
+                        self.toggle();
+                        EventResult::Handled
+                    } else {
+                        EventResult::Ignored
+                    }
+                } else {
+                    EventResult::Ignored
+                }
+            },
+            Event::Keyboard(keyboard_event) if keyboard_event.state == ElementState::Pressed && self.is_focused => {
+                use winit::keyboard::KeyCode;
+                match keyboard_event.key_code {
+                    Some(KeyCode::Space) => {
+                        self.toggle();
+                        EventResult::Handled
+                    },
+                    _ => EventResult::Ignored,
+                }
+            },
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        let dirty_region = DirtyRegion {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        };
+
+        Ok(RenderData {
+            dirty_regions: vec![dirty_region],
+            z_index: 1,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RadioOption {
+    pub value: String,
+    pub label: String,
+    pub enabled: bool,
+}
+
+impl RadioOption {
+    pub fn new(value: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            label: label.into(),
+            enabled: true,
+        }
+    }
+
+    pub fn disabled(mut self) -> Self {
+        self.enabled = false;
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RadioDirection {
+    Vertical,
+    Horizontal,
+}
+
+pub struct RadioGroupWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    options: Vec<RadioOption>,
+    selected: Signal<String>,
+    direction: RadioDirection,
+    item_height: f32,
+    item_spacing: f32,
+    enabled: bool,
+    is_focused: bool,
+    focused_index: usize,
+    hovered_index: Option<usize>,
+    circle_size: f32,
+    circle_color: Color,
+    border_color: Color,
+    selected_color: Color,
+    text_color: Color,
+    disabled_color: Color,
+    font_size: f32,
+    on_change: Option<Box<dyn Fn(&str) + Send + Sync>>,
+    // Style set through builders, which the theme leaves alone
+    overrides: StyleOverrides,
+    pub dirty: bool,
+    // Shared dirty flag that reactive signals can set
+    reactive_dirty: Arc<RwLock<bool>>,
+}
+
+impl RadioGroupWidget {
+    pub fn new(options: Vec<RadioOption>) -> Self {
+        let selected = options.first().map(|option| option.value.clone()).unwrap_or_default();
+        let theme = current_theme();
+        let mut widget = Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 200.0,
+            height: 0.0,
+            options,
+            selected: Signal::new(selected),
+            direction: RadioDirection::Vertical,
+            item_height: 24.0,
+            item_spacing: 4.0,
+            enabled: true,
+            is_focused: false,
+            focused_index: 0,
+            hovered_index: None,
+            circle_size: 16.0,
+            circle_color: theme.palette.surface,
+            border_color: theme.palette.text_muted,
+            selected_color: theme.palette.primary,
+            text_color: theme.palette.text,
+            disabled_color: theme.palette.disabled,
+            font_size: theme.typography.font_size,
+            on_change: None,
+            overrides: StyleOverrides::default(),
+            dirty: true,
+            reactive_dirty: Arc::new(RwLock::new(false)),
+        };
+        widget.fit_height();
+        widget
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_selected(mut self, value: impl Into<String>) -> Self {
+        self.selected = Signal::new(value.into());
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_selected_signal(mut self, selected: Signal<String>) -> Self {
+        self.selected = selected;
+        self.dirty = true;
+        self
+    }
+
+    pub fn horizontal(mut self) -> Self {
+        self.direction = RadioDirection::Horizontal;
+        self.fit_height();
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_item_height(mut self, item_height: f32) -> Self {
+        self.item_height = item_height;
+        self.fit_height();
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_selected_color(mut self, color: Color) -> Self {
+        self.selected_color = color;
+        self.overrides.mark("selected_color");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_text_color(mut self, color: Color) -> Self {
+        self.text_color = color;
+        self.overrides.mark("text_color");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self.overrides.mark("font_size");
+        self.dirty = true;
+        self
+    }
+
+    /// Take every style property not set through a builder from `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let palette = &theme.palette;
+        let overrides = &self.overrides;
+        if !overrides.is_set("selected_color") {
+            self.selected_color = palette.primary;
+        }
+        if !overrides.is_set("text_color") {
+            self.text_color = palette.text;
+        }
+        if !overrides.is_set("font_size") {
+            self.font_size = theme.typography.font_size;
+        }
+        self.circle_color = palette.surface;
+        self.border_color = palette.text_muted;
+        self.disabled_color = palette.disabled;
+        self.dirty = true;
+    }
+
+    pub fn on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Box::new(callback));
+        self
+    }
+
+    pub fn disabled(mut self) -> Self {
+        self.enabled = false;
+        self.dirty = true;
+        self
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.is_focused = false;
+        }
+        self.dirty = true;
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn get_selected(&self) -> String {
+        self.selected.get()
+    }
+
+    pub fn select(&mut self, index: usize) {
+        let Some(option) = self.options.get(index) else {
+            return;
+        };
+        if !option.enabled {
+            return;
+        }
+        self.focused_index = index;
+        self.dirty = true;
+        if self.selected.get() != option.value {
+            self.selected.set(option.value.clone());
+            if let Some(ref callback) = self.on_change {
+                callback(&option.value);
+            }
+        }
+    }
+
+    fn fit_height(&mut self) {
+        let count = self.options.len() as f32;
+        self.height = match self.direction {
+            RadioDirection::Vertical => count * self.item_height + (count - 1.0).max(0.0) * self.item_spacing,
+            RadioDirection::Horizontal => self.item_height,
+        };
+    }
+
+    fn option_bounds(&self, index: usize) -> (f32, f32, f32, f32) {
+        match self.direction {
+            RadioDirection::Vertical => {
+                let y = self.y + index as f32 * (self.item_height + self.item_spacing);
+                (self.x, y, self.width, self.item_height)
+            },
+            RadioDirection::Horizontal => {
+                let count = self.options.len().max(1) as f32;
+                let option_width = (self.width - (count - 1.0) * self.item_spacing) / count;
+                let x = self.x + index as f32 * (option_width + self.item_spacing);
+                (x, self.y, option_width, self.item_height)
+            },
+        }
+    }
+
+    fn option_at(&self, x: f32, y: f32) -> Option<usize> {
+        (0..self.options.len()).find(|&index| {
+            let (option_x, option_y, option_width, option_height) = self.option_bounds(index);
+            x >= option_x && x <= option_x + option_width &&
+            y >= option_y && y <= option_y + option_height
+        })
+    }
+
+    // Next enabled option in the given direction, wrapping around
+    fn step_focus(&self, forward: bool) -> Option<usize> {
+        let count = self.options.len();
+        let mut index = self.focused_index;
+        for _ in 0..count {
+            index = if forward { (index + 1) % count } else { (index + count - 1) % count };
+            if self.options[index].enabled {
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    /// Focus ring, outer circle, inner circle and selection dot for each option
+    pub fn create_option_rectangles(&self) -> Vec<Rectangle> {
+        let selected = self.selected.get();
+        let size = self.circle_size;
+        let mut rectangles = Vec::new();
+
+        for (index, option) in self.options.iter().enumerate() {
+            let (option_x, option_y, _, option_height) = self.option_bounds(index);
+            let circle_y = option_y + (option_height - size) / 2.0;
+            let is_selected = option.value == selected;
+            let enabled = self.enabled && option.enabled;
+
+            if self.is_focused && self.focused_index == index {
+                rectangles.push(Rectangle::new(option_x - 3.0, circle_y - 3.0, size + 6.0, size + 6.0, focus_ring_color())
+                    .with_border_radius((size + 6.0) / 2.0));
+            }
+
+            let border = if !enabled {
+                self.disabled_color
+            } else if is_selected || self.hovered_index == Some(index) {
+                self.selected_color
+            } else {
+                self.border_color
+            };
+            rectangles.push(Rectangle::new(option_x, circle_y, size, size, border).with_border_radius(size / 2.0));
+            rectangles.push(Rectangle::new(option_x + 1.5, circle_y + 1.5, size - 3.0, size - 3.0, self.circle_color)
+                .with_border_radius((size - 3.0) / 2.0));
+
+            if is_selected {
+                let dot = size * 0.5;
+                let dot_color = if enabled { self.selected_color } else { self.disabled_color };
+                rectangles.push(Rectangle::new(option_x + (size - dot) / 2.0, circle_y + (size - dot) / 2.0, dot, dot, dot_color)
+                    .with_border_radius(dot / 2.0));
+            }
+        }
+        rectangles
+    }
+
+    pub fn create_text_primitives(&self) -> Vec<Text> {
+        self.options.iter().enumerate().map(|(index, option)| {
+            let (option_x, option_y, _, option_height) = self.option_bounds(index);
+            let color = if self.enabled && option.enabled { self.text_color } else { self.disabled_color };
+            let text_y = option_y + (option_height / 2.0) + (self.font_size * 0.25);
+            Text::new(option_x + self.circle_size + 8.0, text_y, option.label.clone(), color, self.font_size)
+        }).collect()
+    }
+}
+
+impl Widget for RadioGroupWidget {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        self.dirty = true;
+
+        let reactive_dirty = self.reactive_dirty.clone();
+        self.selected.subscribe_fn(move |_| {
+            if let Ok(mut dirty) = reactive_dirty.write() {
+                *dirty = true;
+            }
+        });
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        self.is_focused = false;
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        if let Ok(mut reactive_dirty) = self.reactive_dirty.write() {
+            if *reactive_dirty {
+                *reactive_dirty = false;
+                self.dirty = true;
+            }
+        }
+
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
+        match event {
+            Event::Mouse(mouse_event) => {
+                let x = mouse_event.position.x as f32;
+                let y = mouse_event.position.y as f32;
+                let option = self.option_at(x, y);
+
+                if mouse_event.button.is_none() {
+                    // Mouse move
+                    if option != self.hovered_index {
+                        self.hovered_index = option;
+                        self.dirty = true;
+                    }
+                    EventResult::Ignored
+                } else if mouse_event.button == Some(MouseButton::Left) && mouse_event.state == ElementState::Pressed {
+                    let inside = self.is_point_inside(x, y);
+                    if self.is_focused != inside {
+                        self.is_focused = inside;
+                        self.dirty = true;
+                    }
+                    match option {
+                        Some(index) => {
+                            self.select(index);
+                            EventResult::Handled
+                        },
+                        None if inside => EventResult::Handled,
+                        None => EventResult::Ignored,
+                    }
+                } else {
+                    EventResult::Ignored
+                }
+            },
+            Event::Keyboard(keyboard_event) if keyboard_event.state == ElementState::Pressed && self.is_focused => {
+                use winit::keyboard::KeyCode;
+                // Arrow keys move the selection, like native radio groups
+                let next = match keyboard_event.key_code {
+                    Some(KeyCode::ArrowDown) | Some(KeyCode::ArrowRight) => self.step_focus(true),
+                    Some(KeyCode::ArrowUp) | Some(KeyCode::ArrowLeft) => self.step_focus(false),
+                    Some(KeyCode::Space) => Some(self.focused_index),
+                    _ => return EventResult::Ignored,
+                };
+                if let Some(index) = next {
+                    self.select(index);
+                }
+                EventResult::Handled
+            },
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        let dirty_region = DirtyRegion {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        };
+
+        Ok(RenderData {
+            dirty_regions: vec![dirty_region],
+            z_index: 1,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+pub struct ToggleSwitchWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    label: String,
+    on: Signal<bool>,
+    enabled: bool,
+    is_focused: bool,
+    is_hovered: bool,
+    track_width: f32,
+    track_height: f32,
+    on_color: Color,
+    off_color: Color,
+    thumb_color: Color,
+    text_color: Color,
+    disabled_color: Color,
+    font_size: f32,
+    on_change: Option<Box<dyn Fn(bool) + Send + Sync>>,
+    // Style set through builders, which the theme leaves alone
+    overrides: StyleOverrides,
+    pub dirty: bool,
+    // Shared dirty flag that reactive signals can set
+    reactive_dirty: Arc<RwLock<bool>>,
+}
+
+impl ToggleSwitchWidget {
+    pub fn new() -> Self {
+        let theme = current_theme();
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 36.0,
+            height: 24.0,
+            label: String::new(),
+            on: Signal::new(false),
+            enabled: true,
+            is_focused: false,
+            is_hovered: false,
+            track_width: 36.0,
+            track_height: 20.0,
+            on_color: theme.palette.primary,
+            off_color: theme.palette.border,
+            thumb_color: theme.palette.on_primary,
+            text_color: theme.palette.text,
+            disabled_color: theme.palette.disabled,
+            font_size: theme.typography.font_size,
+            on_change: None,
+            overrides: StyleOverrides::default(),
+            dirty: true,
+            reactive_dirty: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self.width = self.track_width + 8.0 + self.label.len() as f32 * self.font_size * 0.6;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_on(mut self, on: bool) -> Self {
+        self.on = Signal::new(on);
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_signal(mut self, on: Signal<bool>) -> Self {
+        self.on = on;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_colors(mut self, on_color: Color, off_color: Color, thumb_color: Color) -> Self {
+        self.on_color = on_color;
+        self.off_color = off_color;
+        self.thumb_color = thumb_color;
+        self.overrides.mark("on_color");
+        self.overrides.mark("off_color");
+        self.overrides.mark("thumb_color");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_text_color(mut self, color: Color) -> Self {
+        self.text_color = color;
+        self.overrides.mark("text_color");
+        self.dirty = true;
+        self
+    }
+
+    /// Take every style property not set through a builder from `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let palette = &theme.palette;
+        let overrides = &self.overrides;
+        if !overrides.is_set("on_color") {
+            self.on_color = palette.primary;
+        }
+        if !overrides.is_set("off_color") {
+            self.off_color = palette.border;
+        }
+        if !overrides.is_set("thumb_color") {
+            self.thumb_color = palette.on_primary;
+        }
+        if !overrides.is_set("text_color") {
+            self.text_color = palette.text;
+        }
+        self.disabled_color = palette.disabled;
+        self.font_size = theme.typography.font_size;
+        self.dirty = true;
+    }
+
+    pub fn on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(bool) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Box::new(callback));
+        self
+    }
+
+    pub fn disabled(mut self) -> Self {
+        self.enabled = false;
+        self.dirty = true;
+        self
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.is_focused = false;
+        }
+        self.dirty = true;
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.on.get()
+    }
+
+    pub fn toggle(&mut self) {
+        let on = !self.on.get();
+        self.on.set(on);
+        if let Some(ref callback) = self.on_change {
+            callback(on);
+        }
+        self.dirty = true;
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    /// Focus ring, track and thumb
+    pub fn create_rectangles(&self) -> Vec<Rectangle> {
+        let track_y = self.y + (self.height - self.track_height) / 2.0;
+        let radius = self.track_height / 2.0;
+        let on = self.on.get();
+        let mut rectangles = Vec::new();
+
+        if self.is_focused {
+            rectangles.push(Rectangle::new(self.x - 3.0, track_y - 3.0, self.track_width + 6.0, self.track_height + 6.0, focus_ring_color())
+                .with_border_radius(radius + 3.0));
+        }
+
+        let track_color = if !self.enabled {
+            self.disabled_color
+        } else if on {
+            self.on_color
+        } else {
+            self.off_color
+        };
+        rectangles.push(Rectangle::new(self.x, track_y, self.track_width, self.track_height, track_color)
+            .with_border_radius(radius));
+
+        let thumb_size = self.track_height - 4.0;
+        let thumb_x = if on {
+            self.x + self.track_width - thumb_size - 2.0
+        } else {
+            self.x + 2.0
+        };
+        let thumb_size = if self.is_hovered && self.enabled { thumb_size + 1.0 } else { thumb_size };
+        rectangles.push(Rectangle::new(thumb_x, track_y + 2.0, thumb_size, thumb_size, self.thumb_color)
+            .with_border_radius(thumb_size / 2.0));
+        rectangles
+    }
+
+    pub fn create_text_primitive(&self) -> Option<Text> {
+        if self.label.is_empty() {
+            return None;
+        }
+        let color = if self.enabled { self.text_color } else { self.off_color };
+        let text_y = self.y + (self.height / 2.0) + (self.font_size * 0.25);
+        Some(Text::new(self.x + self.track_width + 8.0, text_y, self.label.clone(), color, self.font_size))
+    }
+}
+
+impl Widget for ToggleSwitchWidget {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        self.dirty = true;
+
+        let reactive_dirty = self.reactive_dirty.clone();
+        self.on.subscribe_fn(move |_| {
+            if let Ok(mut dirty) = reactive_dirty.write() {
+                *dirty = true;
+            }
+        });
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        self.is_focused = false;
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        if let Ok(mut reactive_dirty) = self.reactive_dirty.write() {
+            if *reactive_dirty {
+                *reactive_dirty = false;
+                self.dirty = true;
+            }
+        }
+
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
+        match event {
+            Event::Mouse(mouse_event) => {
+                let inside = self.is_point_inside(mouse_event.position.x as f32, mouse_event.position.y as f32);
+
+                if mouse_event.button.is_none() {
+                    // Mouse move
+                    if inside != self.is_hovered {
+                        self.is_hovered = inside;
+                        self.dirty = true;
+                    }
+                    EventResult::Ignored
+                } else if mouse_event.button == Some(MouseButton::Left) && mouse_event.state == ElementState::Pressed {
+                    if self.is_focused != inside {
+                        self.is_focused = inside;
+                        self.dirty = true;
+                    }
+                    if inside {
+                        self.toggle();
+                        EventResult::Handled
+                    } else {
+                        EventResult::Ignored
+                    }
+                } else {
+                    EventResult::Ignored
+                }
+            },
+            Event::Keyboard(keyboard_event) if keyboard_event.state == ElementState::Pressed && self.is_focused => {
+                use winit::keyboard::KeyCode;
+                match keyboard_event.key_code {
+                    Some(KeyCode::Space) | Some(KeyCode::Enter) => {
+                        self.toggle();
+                        EventResult::Handled
+                    },
+                    _ => EventResult::Ignored,
+                }
+            },
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        let dirty_region = DirtyRegion {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        };
+
+        Ok(RenderData {
+            dirty_regions: vec![dirty_region],
+            z_index: 1,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+// Convenience functions for creating interactive widgets
+pub fn button(label: impl Into<String>) -> ButtonWidget {
+    ButtonWidget::new(label)
+}
+
+/// Square button showing only the named icon; `label` names it
+pub fn icon_button(icon_name: impl Into<String>, label: impl Into<String>) -> ButtonWidget {
+    ButtonWidget::new(label)
+        .with_icon(super::icon::icon(icon_name).with_size(18.0))
+        .icon_only()
+        .with_size(32.0, 32.0)
+}
+
+/// Button that stays pressed while `toggled` is true
+pub fn toggle_button(label: impl Into<String>, toggled: Signal<bool>) -> ButtonWidget {
+    ButtonWidget::new(label).with_toggle_signal(toggled)
+}
+
+pub fn button_signal(label_signal: Signal<String>) -> ButtonWidget {
+    let mut widget = ButtonWidget::new(label_signal.get());
+    widget.label = label_signal;
+    widget.dirty = true;
+    widget
+}
+
+pub fn input() -> InputWidget {
+    InputWidget::new()
+}
+
+pub fn slider(min: f32, max: f32) -> SliderWidget {
+    SliderWidget::new(min, max)
+}
+
+/// Two-thumb slider selecting an interval of `min..=max`
+pub fn range_slider(min: f32, max: f32, range: Signal<(f32, f32)>) -> SliderWidget {
+    SliderWidget::new(min, max).with_range_signal(range)
+}
+
+pub fn checkbox(label: impl Into<String>) -> CheckboxWidget {
+    CheckboxWidget::new(label)
+}
+
+pub fn radio_group(options: Vec<RadioOption>) -> RadioGroupWidget {
+    RadioGroupWidget::new(options)
+}
+
+pub fn toggle_switch() -> ToggleSwitchWidget {
+    ToggleSwitchWidget::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WidgetManager;
+    use crate::event::{MouseEvent, Point};
+    use winit::keyboard::ModifiersState;
+
+    fn press(x: f64, y: f64, button: MouseButton) -> Event {
+        Event::Mouse(MouseEvent {
+            position: Point::new(x, y),
+            button: Some(button),
+            state: ElementState::Pressed,
+            modifiers: ModifiersState::empty(),
+        })
+    }
+
+    #[test]
+    fn logarithmic_scale_round_trips() {
+        let slider = slider(20.0, 20000.0).logarithmic();
+        let middle = slider.fraction_to_value(0.5);
+        assert!((middle - 632.455).abs() < 0.01);
+        assert!((slider.value_to_fraction(middle) - 0.5).abs() < 1e-5);
+        assert!((slider.value_to_fraction(200.0) - 1.0 / 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn range_stays_ordered_and_snaps_to_ticks() {
+        let range = Signal::new((20.0, 80.0));
+        let mut slider = range_slider(0.0, 100.0, range.clone())
+            .with_ticks(vec![SliderTick::new(0.0), SliderTick::labeled(25.0, "25"), SliderTick::new(50.0), SliderTick::new(100.0)])
+            .with_snap_to_ticks(true);
+
+        slider.set_range(90.0, 40.0);
+        assert_eq!(range.get(), (50.0, 100.0));
+
+        // Dragging the low thumb past the high one stops at it
+        slider.active_thumb = SliderThumb::Low;
+        slider.drag_to(slider.value_to_position(100.0) + 10.0);
+        assert_eq!(range.get(), (100.0, 100.0));
+    }
+
+    #[test]
+    fn checkbox_toggles_on_left_click_only() {
+        let mut checkbox = checkbox("Enabled").with_size(100.0, 20.0);
+        assert!(matches!(checkbox.handle_event(&press(5.0, 5.0, MouseButton::Right)), EventResult::Ignored));
+        assert_eq!(checkbox.get_state(), CheckState::Unchecked);
+
+        checkbox.handle_event(&press(5.0, 5.0, MouseButton::Left));
+        assert_eq!(checkbox.get_state(), CheckState::Checked);
+        checkbox.handle_event(&press(5.0, 5.0, MouseButton::Left));
+        assert_eq!(checkbox.get_state(), CheckState::Unchecked);
+    }
+
+    #[test]
+    fn indeterminate_checkbox_becomes_checked() {
+        let mut select_all = checkbox("Select all").with_size(100.0, 20.0).with_state(CheckState::Indeterminate);
+        select_all.handle_event(&press(5.0, 5.0, MouseButton::Left));
+        assert_eq!(select_all.get_state(), CheckState::Checked);
+
+        // Tri-state boxes cycle through indeterminate back to unchecked
+        let mut tri_state = checkbox("Mixed").with_tri_state().with_state(CheckState::Checked);
+        tri_state.toggle();
+        assert_eq!(tri_state.get_state(), CheckState::Indeterminate);
+        tri_state.toggle();
+        assert_eq!(tri_state.get_state(), CheckState::Unchecked);
+    }
+
+    #[test]
+    fn checkbox_stays_in_sync_with_bound_signal() {
+        let checked = Signal::new(false);
+        let mut checkbox = checkbox("Visible").with_size(100.0, 20.0).with_checked_signal(checked.clone());
+        checkbox.mount().unwrap();
+
+        checkbox.handle_event(&press(5.0, 5.0, MouseButton::Left));
+        assert!(checked.get());
+
+        checked.set(false);
+        checkbox.update(&mut WidgetManager::new()).unwrap();
+        assert_eq!(checkbox.get_state(), CheckState::Unchecked);
+    }
+
+    #[test]
+    fn radio_group_selects_one_option_on_left_click() {
+        let selected = Signal::new("a".to_string());
+        let mut group = radio_group(vec![
+            RadioOption::new("a", "A"),
+            RadioOption::new("b", "B"),
+            RadioOption::new("c", "C"),
+        ]).with_selected_signal(selected.clone());
+
+        // Options are 24px high with 4px between them
+        group.handle_event(&press(10.0, 40.0, MouseButton::Left));
+        assert_eq!(selected.get(), "b");
+        group.handle_event(&press(10.0, 60.0, MouseButton::Left));
+        assert_eq!(selected.get(), "c");
+
+        group.handle_event(&press(10.0, 10.0, MouseButton::Right));
+        assert_eq!(group.get_selected(), "c");
+    }
+
+    #[test]
+    fn toggle_switch_follows_left_clicks_and_its_signal() {
+        let on = Signal::new(false);
+        let mut switch = toggle_switch().with_signal(on.clone());
+        let (width, height) = switch.get_size();
+        let (x, y) = ((width / 2.0) as f64, (height / 2.0) as f64);
+
+        switch.handle_event(&press(x, y, MouseButton::Middle));
+        assert!(!on.get());
+        switch.handle_event(&press(x, y, MouseButton::Left));
+        assert!(on.get());
+
+        on.set(false);
+        assert!(!switch.is_on());
+    }
+}
@@ -65,6 +65,12 @@ impl RowWidget {
     pub fn get_main_axis_alignment(&self) -> MainAxisAlignment { self.main_axis_alignment }
     pub fn get_cross_axis_alignment(&self) -> CrossAxisAlignment { self.cross_axis_alignment }
 
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        self.width = width;
+        self.height = height;
+        self.dirty = true;
+    }
+
     pub fn with_size(mut self, width: f32, height: f32) -> Self {
         self.width = width;
         self.height = height;
@@ -473,6 +479,10 @@ impl Widget for RowWidget {
         self
     }
 
+    fn children_mut(&mut self) -> Vec<&mut Element> {
+        self.children.iter_mut().collect()
+    }
+
     fn get_id(&self) -> WidgetId {
         self.id
     }
@@ -516,6 +526,12 @@ impl ColumnWidget {
     pub fn get_main_axis_alignment(&self) -> MainAxisAlignment { self.main_axis_alignment }
     pub fn get_cross_axis_alignment(&self) -> CrossAxisAlignment { self.cross_axis_alignment }
 
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        self.width = width;
+        self.height = height;
+        self.dirty = true;
+    }
+
     pub fn with_size(mut self, width: f32, height: f32) -> Self {
         self.width = width;
         self.height = height;
@@ -851,6 +867,10 @@ impl Widget for ColumnWidget {
         self
     }
 
+    fn children_mut(&mut self) -> Vec<&mut Element> {
+        self.children.iter_mut().collect()
+    }
+
     fn get_id(&self) -> WidgetId {
         self.id
     }
@@ -961,6 +981,14 @@ impl GridWidget {
         self.dirty = true;
     }
 
+    pub fn get_children_mut(&mut self) -> &mut Vec<Element> {
+        &mut self.children
+    }
+
+    pub fn get_children(&self) -> &Vec<Element> {
+        &self.children
+    }
+
     pub fn layout_children(&mut self) {
         if self.children.is_empty() || self.rows == 0 || self.columns == 0 {
             return;
@@ -1067,6 +1095,10 @@ impl Widget for GridWidget {
         self
     }
 
+    fn children_mut(&mut self) -> Vec<&mut Element> {
+        self.children.iter_mut().collect()
+    }
+
     fn get_id(&self) -> WidgetId {
         self.id
     }
@@ -0,0 +1,1411 @@
+use crate::{Widget, WidgetId, EventResult, WidgetError, RenderData, DirtyRegion, WidgetUpdateContext};
+use crate::event::{Event, KeyboardEvent, Shortcut};
+use crate::element::Element;
+use crate::overlay::{global_overlay_layer, OverlayId};
+use winit::event::{ElementState, MouseButton};
+use winit::keyboard::KeyCode;
+use gui_reactive::Signal;
+use gui_render::primitives::{Rectangle, Shadow, Text};
+use std::any::Any;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use vello::peniko::Color;
+use super::container::Background;
+use crate::theme::{current_theme, StyleOverrides, Theme};
+
+static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(7000);
+
+const MENU_Z_INDEX: i32 = 200;
+const SEPARATOR_HEIGHT: f32 = 9.0;
+// Space on the left of each row for check and radio marks
+const MARK_GUTTER: f32 = 28.0;
+// Space on the right of each row for the submenu arrow
+const ARROW_GUTTER: f32 = 24.0;
+
+pub type MenuCallback = Arc<dyn Fn() + Send + Sync>;
+
+#[derive(Clone)]
+pub enum MenuItemKind {
+    Action,
+    /// Toggles the signal when selected
+    Check(Signal<bool>),
+    /// Sets the group signal to `value` when selected
+    Radio { group: Signal<String>, value: String },
+    Submenu(Vec<MenuItem>),
+    Separator,
+}
+
+#[derive(Clone)]
+pub struct MenuItem {
+    label: String,
+    kind: MenuItemKind,
+    shortcut: Option<Shortcut>,
+    enabled: bool,
+    on_select: Option<MenuCallback>,
+}
+
+impl MenuItem {
+    fn new(label: impl Into<String>, kind: MenuItemKind) -> Self {
+        Self {
+            label: label.into(),
+            kind,
+            shortcut: None,
+            enabled: true,
+            on_select: None,
+        }
+    }
+
+    pub fn action(label: impl Into<String>) -> Self {
+        Self::new(label, MenuItemKind::Action)
+    }
+
+    pub fn checkbox(label: impl Into<String>, checked: Signal<bool>) -> Self {
+        Self::new(label, MenuItemKind::Check(checked))
+    }
+
+    pub fn radio(label: impl Into<String>, group: Signal<String>, value: impl Into<String>) -> Self {
+        Self::new(label, MenuItemKind::Radio { group, value: value.into() })
+    }
+
+    pub fn submenu(label: impl Into<String>, items: Vec<MenuItem>) -> Self {
+        Self::new(label, MenuItemKind::Submenu(items))
+    }
+
+    pub fn separator() -> Self {
+        Self::new("", MenuItemKind::Separator)
+    }
+
+    /// Bind a shortcut. It is shown as the accelerator hint and, for items in a
+    /// `MenuBarWidget`, triggers the item while no menu is open.
+    pub fn with_shortcut(mut self, shortcut: Shortcut) -> Self {
+        self.shortcut = Some(shortcut);
+        self
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn disabled(self) -> Self {
+        self.with_enabled(false)
+    }
+
+    pub fn on_select<F>(mut self, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_select = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn kind(&self) -> &MenuItemKind {
+        &self.kind
+    }
+
+    pub fn shortcut(&self) -> Option<Shortcut> {
+        self.shortcut
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn is_separator(&self) -> bool {
+        matches!(self.kind, MenuItemKind::Separator)
+    }
+
+    pub fn is_submenu(&self) -> bool {
+        matches!(self.kind, MenuItemKind::Submenu(_))
+    }
+
+    pub fn is_checked(&self) -> bool {
+        match &self.kind {
+            MenuItemKind::Check(checked) => checked.get(),
+            MenuItemKind::Radio { group, value } => group.with(|current| current == value),
+            _ => false,
+        }
+    }
+
+    /// Run the item's effect: toggle checks, select radios, then call `on_select`
+    pub fn trigger(&self) {
+        if !self.enabled {
+            return;
+        }
+        match &self.kind {
+            MenuItemKind::Check(checked) => checked.update(|value| *value = !*value),
+            MenuItemKind::Radio { group, value } => group.set(value.clone()),
+            MenuItemKind::Submenu(_) | MenuItemKind::Separator => return,
+            MenuItemKind::Action => {},
+        }
+        if let Some(callback) = &self.on_select {
+            callback();
+        }
+    }
+
+    fn selectable(&self) -> bool {
+        self.enabled && !self.is_separator()
+    }
+
+    /// Find an enabled item bound to the shortcut, searching submenus too
+    fn find_shortcut<'a>(items: &'a [MenuItem], event: &KeyboardEvent) -> Option<&'a MenuItem> {
+        for item in items {
+            if !item.enabled {
+                continue;
+            }
+            if let MenuItemKind::Submenu(children) = &item.kind {
+                if let Some(found) = Self::find_shortcut(children, event) {
+                    return Some(found);
+                }
+            } else if item.shortcut.is_some_and(|shortcut| shortcut.matches(event)) {
+                return Some(item);
+            }
+        }
+        None
+    }
+}
+
+// Outcome of a menu level handling an event, reported to its parent level
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum MenuResponse {
+    Ignored,
+    Consumed,
+    // Close only this submenu and return focus to the parent
+    CloseSubmenu,
+    // An item was chosen or the menu was dismissed; close the whole menu tree
+    Close,
+}
+
+/// A popup menu shown on the overlay layer.
+///
+/// Open submenus are owned by their parent menu, so a whole menu tree is a single
+/// overlay entry and is dismissed as one.
+pub struct MenuWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    items: Vec<MenuItem>,
+    highlighted: Option<usize>,
+    submenu: Option<Box<MenuWidget>>,
+    submenu_index: Option<usize>,
+    viewport: (f32, f32),
+    background: Background,
+    highlight_color: Color,
+    // Label color on the highlighted row
+    highlight_text_color: Color,
+    text_color: Color,
+    disabled_text_color: Color,
+    shortcut_color: Color,
+    separator_color: Color,
+    border_radius: f32,
+    font_size: f32,
+    item_height: f32,
+    min_width: f32,
+    padding: f32,
+    shadow: Option<Shadow>,
+    overrides: StyleOverrides,
+    overlay_id: Option<OverlayId>,
+    // Clicks in this region do not dismiss the menu (e.g. the menu bar that opened it)
+    passthrough: Option<DirtyRegion>,
+    // Opened by a context menu: a right click elsewhere closes it and still reaches
+    // the tree, so the menu reopens where the pointer is
+    context: bool,
+    pub dirty: bool,
+}
+
+impl MenuWidget {
+    pub fn new(items: Vec<MenuItem>) -> Self {
+        let theme = current_theme();
+        let mut menu = Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+            items,
+            highlighted: None,
+            submenu: None,
+            submenu_index: None,
+            viewport: (800.0, 600.0),
+            background: Background::Color(theme.palette.surface),
+            highlight_color: theme.palette.primary,
+            highlight_text_color: theme.palette.on_primary,
+            text_color: theme.palette.text,
+            disabled_text_color: theme.palette.disabled,
+            shortcut_color: theme.palette.text_muted,
+            separator_color: theme.palette.border,
+            border_radius: 6.0,
+            font_size: 13.0,
+            item_height: 24.0,
+            min_width: 180.0,
+            padding: 4.0,
+            shadow: Some(theme.shadows.large.to_shadow()),
+            overrides: StyleOverrides::default(),
+            overlay_id: None,
+            passthrough: None,
+            context: false,
+            dirty: true,
+        };
+        menu.measure();
+        menu
+    }
+
+    pub fn with_background(mut self, background: Background) -> Self {
+        self.background = background;
+        self.overrides.mark("background");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_highlight_color(mut self, color: Color) -> Self {
+        self.highlight_color = color;
+        self.overrides.mark("highlight_color");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_text_color(mut self, color: Color) -> Self {
+        self.text_color = color;
+        self.overrides.mark("text_color");
+        self.dirty = true;
+        self
+    }
+
+    /// Style the menu from `theme` instead of the global one, e.g. the theme
+    /// around whatever opened it
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.apply_theme(theme);
+        self
+    }
+
+    /// Take every color not set through a builder from `theme`, including the
+    /// open submenu's
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let palette = &theme.palette;
+        if !self.overrides.is_set("background") {
+            self.background = Background::Color(palette.surface);
+        }
+        if !self.overrides.is_set("highlight_color") {
+            self.highlight_color = palette.primary;
+        }
+        if !self.overrides.is_set("text_color") {
+            self.text_color = palette.text;
+        }
+        self.highlight_text_color = palette.on_primary;
+        self.disabled_text_color = palette.disabled;
+        self.shortcut_color = palette.text_muted;
+        self.separator_color = palette.border;
+        self.shadow = Some(theme.shadows.large.to_shadow());
+        if let Some(submenu) = self.submenu.as_mut() {
+            submenu.apply_theme(theme);
+        }
+        self.dirty = true;
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self.measure();
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_item_height(mut self, item_height: f32) -> Self {
+        self.item_height = item_height;
+        self.measure();
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_min_width(mut self, min_width: f32) -> Self {
+        self.min_width = min_width;
+        self.measure();
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_viewport(mut self, width: f32, height: f32) -> Self {
+        self.viewport = (width, height);
+        self
+    }
+
+    /// Region where a click leaves the menu open and falls through to the tree below
+    pub fn with_passthrough(mut self, region: DirtyRegion) -> Self {
+        self.passthrough = Some(region);
+        self
+    }
+
+    fn into_context_menu(mut self) -> Self {
+        self.context = true;
+        self
+    }
+
+    /// Place the menu at the given point, flipping or clamping so it stays in the window
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        let (viewport_width, viewport_height) = self.viewport;
+        let x = x.min(viewport_width - self.width).max(0.0);
+        let y = y.min(viewport_height - self.height).max(0.0);
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+
+    pub fn highlighted(&self) -> Option<usize> {
+        self.highlighted
+    }
+
+    pub fn open_submenu(&self) -> Option<&MenuWidget> {
+        self.submenu.as_deref()
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    /// Show the menu on the overlay layer at the given point
+    pub fn popup(mut self, x: f32, y: f32) -> OverlayId {
+        self.set_position(x, y);
+        let overlay = global_overlay_layer();
+        let overlay_id = overlay.push(Element::new_widget(Box::new(self)), MENU_Z_INDEX);
+        // The menu removes its own entry when it is dismissed
+        overlay.with_element_mut(overlay_id, |element| {
+            if let Element::Widget(widget) = element {
+                if let Some(menu) = widget.as_any_mut().downcast_mut::<MenuWidget>() {
+                    menu.overlay_id = Some(overlay_id);
+                }
+            }
+        });
+        overlay_id
+    }
+
+    /// Close the menu tree and remove it from the overlay layer
+    pub fn dismiss(&mut self) {
+        self.close_submenu();
+        self.highlighted = None;
+        if let Some(overlay_id) = self.overlay_id.take() {
+            global_overlay_layer().remove(overlay_id);
+        }
+        self.dirty = true;
+    }
+
+    fn measure(&mut self) {
+        let char_width = self.font_size * 0.6;
+        let mut content_width: f32 = 0.0;
+        let mut height = self.padding * 2.0;
+        for item in &self.items {
+            if item.is_separator() {
+                height += SEPARATOR_HEIGHT;
+                continue;
+            }
+            height += self.item_height;
+            let label_width = item.label.len() as f32 * char_width;
+            let shortcut_width = item.shortcut
+                .map(|shortcut| shortcut.label().len() as f32 * char_width + 24.0)
+                .unwrap_or(0.0);
+            content_width = content_width.max(label_width + shortcut_width);
+        }
+        self.width = (content_width + MARK_GUTTER + ARROW_GUTTER).max(self.min_width);
+        self.height = height;
+    }
+
+    fn row_height(&self, index: usize) -> f32 {
+        if self.items[index].is_separator() {
+            SEPARATOR_HEIGHT
+        } else {
+            self.item_height
+        }
+    }
+
+    fn row_top(&self, index: usize) -> f32 {
+        let mut top = self.y + self.padding;
+        for i in 0..index {
+            top += self.row_height(i);
+        }
+        top
+    }
+
+    fn row_at(&self, x: f32, y: f32) -> Option<usize> {
+        if !self.is_point_inside(x, y) {
+            return None;
+        }
+        let mut top = self.y + self.padding;
+        for index in 0..self.items.len() {
+            let bottom = top + self.row_height(index);
+            if y >= top && y < bottom {
+                return Some(index);
+            }
+            top = bottom;
+        }
+        None
+    }
+
+    fn step_highlight(&mut self, forward: bool) {
+        let count = self.items.len();
+        if count == 0 {
+            return;
+        }
+        let mut index = match (self.highlighted, forward) {
+            (Some(current), true) => (current + 1) % count,
+            (Some(current), false) => (current + count - 1) % count,
+            (None, true) => 0,
+            (None, false) => count - 1,
+        };
+        // Skip separators and disabled items, wrapping around at most once
+        for _ in 0..count {
+            if self.items[index].selectable() {
+                self.highlighted = Some(index);
+                self.dirty = true;
+                return;
+            }
+            index = if forward { (index + 1) % count } else { (index + count - 1) % count };
+        }
+    }
+
+    fn highlight_first(&mut self) {
+        self.highlighted = None;
+        self.step_highlight(true);
+    }
+
+    fn open_submenu_at(&mut self, index: usize) {
+        if self.submenu_index == Some(index) {
+            return;
+        }
+        self.close_submenu();
+        let MenuItemKind::Submenu(items) = &self.items[index].kind else {
+            return;
+        };
+        if !self.items[index].enabled {
+            return;
+        }
+
+        let mut submenu = MenuWidget::new(items.clone());
+        submenu.viewport = self.viewport;
+        submenu.background = self.background.clone();
+        submenu.highlight_color = self.highlight_color;
+        submenu.highlight_text_color = self.highlight_text_color;
+        submenu.text_color = self.text_color;
+        submenu.disabled_text_color = self.disabled_text_color;
+        submenu.shortcut_color = self.shortcut_color;
+        submenu.separator_color = self.separator_color;
+        submenu.shadow = self.shadow.as_ref().map(|shadow| {
+            Shadow::new(0.0, 0.0, 0.0, 0.0, shadow.offset_x, shadow.offset_y, shadow.blur_radius, shadow.color)
+        });
+        submenu.overrides = self.overrides.clone();
+        submenu.font_size = self.font_size;
+        submenu.item_height = self.item_height;
+        submenu.measure();
+
+        // Open to the right, or to the left when there is no room
+        let mut x = self.x + self.width - 2.0;
+        if x + submenu.width > self.viewport.0 {
+            x = self.x - submenu.width + 2.0;
+        }
+        submenu.set_position(x, self.row_top(index) - submenu.padding);
+
+        self.submenu = Some(Box::new(submenu));
+        self.submenu_index = Some(index);
+        self.dirty = true;
+    }
+
+    fn close_submenu(&mut self) {
+        if self.submenu.take().is_some() {
+            self.dirty = true;
+        }
+        self.submenu_index = None;
+    }
+
+    fn activate(&mut self, index: usize) -> MenuResponse {
+        let item = &self.items[index];
+        if !item.selectable() {
+            return MenuResponse::Consumed;
+        }
+        if item.is_submenu() {
+            self.open_submenu_at(index);
+            if let Some(submenu) = self.submenu.as_mut() {
+                submenu.highlight_first();
+            }
+            return MenuResponse::Consumed;
+        }
+        item.trigger();
+        MenuResponse::Close
+    }
+
+    fn handle_menu_event(&mut self, event: &Event, is_root: bool) -> MenuResponse {
+        // The open submenu sits on top and gets the first chance
+        if let Some(submenu) = self.submenu.as_mut() {
+            let keyboard_in_submenu = matches!(event, Event::Keyboard(_)) && submenu.highlighted.is_some();
+            if keyboard_in_submenu || matches!(event, Event::Mouse(_)) {
+                match submenu.handle_menu_event(event, false) {
+                    MenuResponse::Ignored => {},
+                    MenuResponse::CloseSubmenu => {
+                        self.close_submenu();
+                        return MenuResponse::Consumed;
+                    },
+                    response => return response,
+                }
+            }
+        }
+
+        match event {
+            Event::Mouse(mouse_event) => {
+                let x = mouse_event.position.x as f32;
+                let y = mouse_event.position.y as f32;
+                let row = self.row_at(x, y);
+
+                if mouse_event.button.is_none() {
+                    // Mouse move
+                    if !self.is_point_inside(x, y) {
+                        if self.submenu.is_none() && self.highlighted.is_some() {
+                            self.highlighted = None;
+                            self.dirty = true;
+                        }
+                        return MenuResponse::Ignored;
+                    }
+                    let highlighted = row.filter(|&index| self.items[index].selectable());
+                    if highlighted != self.highlighted {
+                        self.highlighted = highlighted;
+                        self.dirty = true;
+                    }
+                    match highlighted {
+                        Some(index) if self.items[index].is_submenu() => self.open_submenu_at(index),
+                        _ => self.close_submenu(),
+                    }
+                    MenuResponse::Consumed
+                } else if !self.is_point_inside(x, y) {
+                    MenuResponse::Ignored
+                } else if mouse_event.state == ElementState::Released && mouse_event.button == Some(MouseButton::Left) {
+                    match row {
+                        Some(index) => self.activate(index),
+                        None => MenuResponse::Consumed,
+                    }
+                } else {
+                    // Presses inside the menu are consumed; items activate on release
+                    MenuResponse::Consumed
+                }
+            },
+            Event::Keyboard(keyboard_event) if keyboard_event.state == ElementState::Pressed => {
+                match keyboard_event.key_code {
+                    Some(KeyCode::ArrowDown) => {
+                        self.step_highlight(true);
+                        MenuResponse::Consumed
+                    },
+                    Some(KeyCode::ArrowUp) => {
+                        self.step_highlight(false);
+                        MenuResponse::Consumed
+                    },
+                    Some(KeyCode::Home) => {
+                        self.highlight_first();
+                        MenuResponse::Consumed
+                    },
+                    Some(KeyCode::End) => {
+                        self.highlighted = None;
+                        self.step_highlight(false);
+                        MenuResponse::Consumed
+                    },
+                    Some(KeyCode::ArrowRight) => {
+                        match self.highlighted {
+                            Some(index) if self.items[index].is_submenu() => self.activate(index),
+                            _ => MenuResponse::Ignored,
+                        }
+                    },
+                    Some(KeyCode::ArrowLeft) if !is_root => MenuResponse::CloseSubmenu,
+                    Some(KeyCode::Escape) if !is_root => MenuResponse::CloseSubmenu,
+                    Some(KeyCode::Escape) => MenuResponse::Close,
+                    Some(KeyCode::Enter) | Some(KeyCode::Space) => {
+                        match self.highlighted {
+                            Some(index) => self.activate(index),
+                            None => MenuResponse::Consumed,
+                        }
+                    },
+                    _ => MenuResponse::Ignored,
+                }
+            },
+            _ => MenuResponse::Ignored,
+        }
+    }
+
+    fn in_passthrough(&self, x: f32, y: f32) -> bool {
+        self.passthrough.as_ref().is_some_and(|region| {
+            x >= region.x && x <= region.x + region.width &&
+            y >= region.y && y <= region.y + region.height
+        })
+    }
+
+    pub fn create_background_rectangle(&self) -> Rectangle {
+        Rectangle::new_with_brush(self.x, self.y, self.width, self.height, self.background.to_brush())
+            .with_border_radius(self.border_radius)
+    }
+
+    pub fn create_shadow(&self) -> Option<Shadow> {
+        self.shadow.as_ref().map(|shadow| {
+            Shadow::new(self.x, self.y, self.width, self.height,
+                       shadow.offset_x, shadow.offset_y, shadow.blur_radius, shadow.color)
+        })
+    }
+
+    /// Highlight and separator rectangles
+    pub fn create_row_rectangles(&self) -> Vec<Rectangle> {
+        let mut rectangles = Vec::new();
+        for (index, item) in self.items.iter().enumerate() {
+            let top = self.row_top(index);
+            if item.is_separator() {
+                let line_y = top + SEPARATOR_HEIGHT / 2.0;
+                rectangles.push(Rectangle::new(self.x + 8.0, line_y, self.width - 16.0, 1.0, self.separator_color));
+            } else if self.highlighted == Some(index) {
+                rectangles.push(
+                    Rectangle::new(self.x + 4.0, top, self.width - 8.0, self.item_height, self.highlight_color)
+                        .with_border_radius(4.0)
+                );
+            }
+        }
+        rectangles
+    }
+
+    /// Check marks, labels, accelerator hints and submenu arrows
+    pub fn create_text_primitives(&self) -> Vec<Text> {
+        let char_width = self.font_size * 0.6;
+        let mut texts = Vec::new();
+        for (index, item) in self.items.iter().enumerate() {
+            if item.is_separator() {
+                continue;
+            }
+            let highlighted = self.highlighted == Some(index);
+            let color = if !item.enabled {
+                self.disabled_text_color
+            } else if highlighted {
+                self.highlight_text_color
+            } else {
+                self.text_color
+            };
+            let shortcut_color = if highlighted { color } else { self.shortcut_color };
+            let text_y = self.row_top(index) + (self.item_height / 2.0) + (self.font_size * 0.25);
+
+            if item.is_checked() {
+                let mark = match item.kind {
+                    MenuItemKind::Radio { .. } => "●",
+                    _ => "✓",
+                };
+                texts.push(Text::new(self.x + 10.0, text_y, mark.to_string(), color, self.font_size));
+            }
+
+            texts.push(Text::new(self.x + MARK_GUTTER, text_y, item.label.clone(), color, self.font_size));
+
+            if let Some(shortcut) = item.shortcut {
+                let hint = shortcut.label();
+                let hint_x = self.x + self.width - ARROW_GUTTER - hint.len() as f32 * char_width;
+                texts.push(Text::new(hint_x, text_y, hint, shortcut_color, self.font_size));
+            }
+
+            if item.is_submenu() {
+                texts.push(Text::new(self.x + self.width - 18.0, text_y, "›".to_string(), color, self.font_size));
+            }
+        }
+        texts
+    }
+}
+
+impl Widget for MenuWidget {
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        let viewport = ctx.viewport_size();
+        self.viewport = (viewport.width, viewport.height);
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        match self.handle_menu_event(event, true) {
+            MenuResponse::Consumed => EventResult::Handled,
+            MenuResponse::Close | MenuResponse::CloseSubmenu => {
+                self.dismiss();
+                EventResult::Handled
+            },
+            MenuResponse::Ignored => match event {
+                Event::Mouse(mouse_event) if mouse_event.button.is_some() && mouse_event.state == ElementState::Pressed => {
+                    let x = mouse_event.position.x as f32;
+                    let y = mouse_event.position.y as f32;
+                    if self.in_passthrough(x, y) {
+                        return EventResult::Ignored;
+                    }
+                    // Click outside the menu tree dismisses it without reaching the widgets below
+                    self.dismiss();
+                    if self.context && mouse_event.button == Some(MouseButton::Right) {
+                        return EventResult::Ignored;
+                    }
+                    EventResult::Handled
+                },
+                // Left/Right fall through so a menu bar can move between its menus
+                Event::Keyboard(keyboard_event) if matches!(keyboard_event.key_code, Some(KeyCode::ArrowLeft) | Some(KeyCode::ArrowRight)) => {
+                    EventResult::Ignored
+                },
+                // An open menu keeps keyboard focus
+                Event::Keyboard(_) => EventResult::Handled,
+                _ => EventResult::Ignored,
+            },
+        }
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        // Only lives on the overlay while open, so it always draws
+        true
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            }],
+            z_index: MENU_Z_INDEX,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+struct MenuBarEntry {
+    title: String,
+    items: Vec<MenuItem>,
+}
+
+/// Horizontal application menu bar. Opens its menus on the overlay layer and
+/// triggers bound shortcuts while no menu is open.
+pub struct MenuBarWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    menus: Vec<MenuBarEntry>,
+    open_index: Option<usize>,
+    open_overlay: Option<OverlayId>,
+    hovered: Option<usize>,
+    viewport: (f32, f32),
+    background: Background,
+    highlight_color: Color,
+    text_color: Color,
+    font_size: f32,
+    title_padding: f32,
+    overrides: StyleOverrides,
+    // Handed to the menus the bar opens
+    theme: Theme,
+    pub dirty: bool,
+}
+
+impl MenuBarWidget {
+    pub fn new() -> Self {
+        let theme = current_theme();
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 800.0,
+            height: 28.0,
+            menus: Vec::new(),
+            open_index: None,
+            open_overlay: None,
+            hovered: None,
+            viewport: (800.0, 600.0),
+            background: Background::Color(theme.palette.background),
+            highlight_color: theme.palette.selection,
+            text_color: theme.palette.text,
+            font_size: 13.0,
+            title_padding: 10.0,
+            overrides: StyleOverrides::default(),
+            theme,
+            dirty: true,
+        }
+    }
+
+    pub fn with_menu(mut self, title: impl Into<String>, items: Vec<MenuItem>) -> Self {
+        self.menus.push(MenuBarEntry { title: title.into(), items });
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_background(mut self, background: Background) -> Self {
+        self.background = background;
+        self.overrides.mark("background");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_highlight_color(mut self, color: Color) -> Self {
+        self.highlight_color = color;
+        self.overrides.mark("highlight_color");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_text_color(mut self, color: Color) -> Self {
+        self.text_color = color;
+        self.overrides.mark("text_color");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self.dirty = true;
+        self
+    }
+
+    /// Take every color not set through a builder from `theme`; menus opened
+    /// from the bar use it too
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let palette = &theme.palette;
+        if !self.overrides.is_set("background") {
+            self.background = Background::Color(palette.background);
+        }
+        if !self.overrides.is_set("highlight_color") {
+            self.highlight_color = palette.selection;
+        }
+        if !self.overrides.is_set("text_color") {
+            self.text_color = palette.text;
+        }
+        self.theme = theme.clone();
+        self.dirty = true;
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    pub fn is_menu_open(&self) -> bool {
+        self.open_index.is_some()
+    }
+
+    /// Horizontal extent (x, width) of each menu title
+    fn title_bounds(&self) -> Vec<(f32, f32)> {
+        let mut x = self.x + 4.0;
+        self.menus.iter().map(|menu| {
+            let width = menu.title.len() as f32 * self.font_size * 0.6 + self.title_padding * 2.0;
+            let bounds = (x, width);
+            x += width;
+            bounds
+        }).collect()
+    }
+
+    fn title_at(&self, x: f32, y: f32) -> Option<usize> {
+        if !self.is_point_inside(x, y) {
+            return None;
+        }
+        self.title_bounds().iter().position(|&(title_x, width)| x >= title_x && x < title_x + width)
+    }
+
+    // The menu removes itself from the overlay when dismissed; notice that here
+    fn sync_open_state(&mut self) {
+        if let Some(overlay_id) = self.open_overlay {
+            if !global_overlay_layer().contains(overlay_id) {
+                self.open_overlay = None;
+                self.open_index = None;
+                self.dirty = true;
+            }
+        }
+    }
+
+    fn open_menu(&mut self, index: usize, highlight_first: bool) {
+        self.close_menu();
+        let Some(&(title_x, _)) = self.title_bounds().get(index) else {
+            return;
+        };
+
+        let mut menu = MenuWidget::new(self.menus[index].items.clone())
+            .with_theme(&self.theme)
+            .with_viewport(self.viewport.0, self.viewport.1)
+            .with_passthrough(DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            });
+        if highlight_first {
+            menu.highlight_first();
+        }
+        self.open_overlay = Some(menu.popup(title_x, self.y + self.height));
+        self.open_index = Some(index);
+        self.dirty = true;
+    }
+
+    fn close_menu(&mut self) {
+        if let Some(overlay_id) = self.open_overlay.take() {
+            global_overlay_layer().remove(overlay_id);
+        }
+        self.open_index = None;
+        self.dirty = true;
+    }
+
+    pub fn create_background_rectangle(&self) -> Rectangle {
+        Rectangle::new_with_brush(self.x, self.y, self.width, self.height, self.background.to_brush())
+    }
+
+    pub fn create_title_primitives(&self) -> Vec<(Option<Rectangle>, Text)> {
+        let text_y = self.y + (self.height / 2.0) + (self.font_size * 0.25);
+        self.menus.iter().zip(self.title_bounds()).enumerate().map(|(index, (menu, (title_x, width)))| {
+            let highlighted = self.open_index == Some(index) || (self.open_index.is_none() && self.hovered == Some(index));
+            let highlight = highlighted.then(|| {
+                Rectangle::new(title_x, self.y + 2.0, width, self.height - 4.0, self.highlight_color)
+                    .with_border_radius(4.0)
+            });
+            let text = Text::new(title_x + self.title_padding, text_y, menu.title.clone(), self.text_color, self.font_size);
+            (highlight, text)
+        }).collect()
+    }
+}
+
+impl Widget for MenuBarWidget {
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        self.close_menu();
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        let viewport = ctx.viewport_size();
+        self.viewport = (viewport.width, viewport.height);
+        self.sync_open_state();
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        self.sync_open_state();
+
+        match event {
+            Event::Mouse(mouse_event) => {
+                let x = mouse_event.position.x as f32;
+                let y = mouse_event.position.y as f32;
+                let title = self.title_at(x, y);
+
+                if mouse_event.button.is_none() {
+                    if title != self.hovered {
+                        self.hovered = title;
+                        self.dirty = true;
+                    }
+                    // Sliding across titles while a menu is open switches menus
+                    if let (Some(index), Some(open)) = (title, self.open_index) {
+                        if index != open {
+                            self.open_menu(index, false);
+                        }
+                    }
+                    return if title.is_some() { EventResult::Handled } else { EventResult::Ignored };
+                }
+
+                if mouse_event.state != ElementState::Pressed || !self.is_point_inside(x, y) {
+                    return EventResult::Ignored;
+                }
+                match title {
+                    Some(index) if self.open_index == Some(index) => self.close_menu(),
+                    Some(index) => self.open_menu(index, false),
+                    None => self.close_menu(),
+                }
+                EventResult::Handled
+            },
+            Event::Keyboard(keyboard_event) if keyboard_event.state == ElementState::Pressed => {
+                if let Some(open) = self.open_index {
+                    let count = self.menus.len();
+                    let next = match keyboard_event.key_code {
+                        Some(KeyCode::ArrowRight) => (open + 1) % count,
+                        Some(KeyCode::ArrowLeft) => (open + count - 1) % count,
+                        _ => return EventResult::Ignored,
+                    };
+                    self.open_menu(next, true);
+                    return EventResult::Handled;
+                }
+
+                let item = self.menus.iter()
+                    .find_map(|menu| MenuItem::find_shortcut(&menu.items, keyboard_event));
+                match item {
+                    Some(item) => {
+                        item.trigger();
+                        EventResult::Handled
+                    },
+                    None => EventResult::Ignored,
+                }
+            },
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            }],
+            z_index: 1,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+pub type ContextMenuBuilder = Arc<dyn Fn() -> Vec<MenuItem> + Send + Sync>;
+
+/// Wraps a target element and opens a menu at the pointer when it is right-clicked.
+///
+/// The items are built when the menu opens, so they can reflect the current state.
+pub struct ContextMenuWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    builder: ContextMenuBuilder,
+    overlay_id: Option<OverlayId>,
+    viewport: (f32, f32),
+    // Theme around the target, handed to the menus it opens
+    theme: Theme,
+    pub dirty: bool,
+}
+
+impl ContextMenuWidget {
+    pub fn new<F>(builder: F) -> Self
+    where
+        F: Fn() -> Vec<MenuItem> + Send + Sync + 'static,
+    {
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+            builder: Arc::new(builder),
+            overlay_id: None,
+            viewport: (800.0, 600.0),
+            theme: current_theme(),
+            dirty: true,
+        }
+    }
+
+    /// Wrap the target element, producing the container element to place in the tree
+    pub fn wrap(self, target: Element) -> Element {
+        Element::new_container(Box::new(self), vec![target])
+    }
+
+    /// Menus opened from here take `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        self.theme = theme.clone();
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        self.width = width;
+        self.height = height;
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    /// Open the menu on a right click inside the target, before the target sees the event
+    pub fn observe_event(&mut self, event: &Event) -> EventResult {
+        let Event::Mouse(mouse_event) = event else {
+            return EventResult::Ignored;
+        };
+        if mouse_event.button != Some(MouseButton::Right) || mouse_event.state != ElementState::Pressed {
+            return EventResult::Ignored;
+        }
+        let x = mouse_event.position.x as f32;
+        let y = mouse_event.position.y as f32;
+        if !self.is_point_inside(x, y) {
+            return EventResult::Ignored;
+        }
+
+        self.close();
+        let items = (self.builder)();
+        if items.is_empty() {
+            return EventResult::Ignored;
+        }
+        let menu = MenuWidget::new(items)
+            .with_theme(&self.theme)
+            .with_viewport(self.viewport.0, self.viewport.1)
+            .into_context_menu();
+        self.overlay_id = Some(menu.popup(x, y));
+        EventResult::Handled
+    }
+
+    fn close(&mut self) {
+        if let Some(overlay_id) = self.overlay_id.take() {
+            global_overlay_layer().remove(overlay_id);
+        }
+    }
+}
+
+impl Widget for ContextMenuWidget {
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        self.close();
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        let viewport = ctx.viewport_size();
+        self.viewport = (viewport.width, viewport.height);
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        // The target decides its own rendering; the wrapper draws nothing
+        true
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            }],
+            z_index: 0,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+impl Element {
+    /// Open a context menu built by `builder` when this element is right-clicked
+    pub fn with_context_menu<F>(self, builder: F) -> Element
+    where
+        F: Fn() -> Vec<MenuItem> + Send + Sync + 'static,
+    {
+        ContextMenuWidget::new(builder).wrap(self)
+    }
+}
+
+// Convenience functions for creating menus
+pub fn menu(items: Vec<MenuItem>) -> MenuWidget {
+    MenuWidget::new(items)
+}
+
+pub fn menu_bar() -> MenuBarWidget {
+    MenuBarWidget::new()
+}
+
+pub fn context_menu<F>(builder: F) -> ContextMenuWidget
+where
+    F: Fn() -> Vec<MenuItem> + Send + Sync + 'static,
+{
+    ContextMenuWidget::new(builder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_press(key: KeyCode) -> Event {
+        Event::Keyboard(KeyboardEvent {
+            key_code: Some(key),
+            character: None,
+            scancode: 0,
+            state: ElementState::Pressed,
+            modifiers: Default::default(),
+        })
+    }
+
+    #[test]
+    fn keyboard_navigation_skips_separators_and_disabled_items() {
+        let mut menu = MenuWidget::new(vec![
+            MenuItem::action("Cut"),
+            MenuItem::separator(),
+            MenuItem::action("Paste").disabled(),
+            MenuItem::action("Select All"),
+        ]);
+
+        menu.handle_event(&key_press(KeyCode::ArrowDown));
+        assert_eq!(menu.highlighted(), Some(0));
+        menu.handle_event(&key_press(KeyCode::ArrowDown));
+        assert_eq!(menu.highlighted(), Some(3));
+        menu.handle_event(&key_press(KeyCode::ArrowDown));
+        assert_eq!(menu.highlighted(), Some(0));
+        menu.handle_event(&key_press(KeyCode::ArrowUp));
+        assert_eq!(menu.highlighted(), Some(3));
+    }
+
+    #[test]
+    fn submenu_opens_with_right_and_closes_with_left() {
+        let mut menu = MenuWidget::new(vec![
+            MenuItem::submenu("Recent", vec![MenuItem::action("a.scene"), MenuItem::action("b.scene")]),
+        ]);
+
+        menu.handle_event(&key_press(KeyCode::ArrowDown));
+        menu.handle_event(&key_press(KeyCode::ArrowRight));
+        assert_eq!(menu.open_submenu().and_then(MenuWidget::highlighted), Some(0));
+
+        menu.handle_event(&key_press(KeyCode::ArrowLeft));
+        assert!(menu.open_submenu().is_none());
+        assert_eq!(menu.highlighted(), Some(0));
+    }
+
+    #[test]
+    fn selecting_check_and_radio_items_updates_signals() {
+        let grid = Signal::new(false);
+        let size = Signal::new("small".to_string());
+        let mut menu = MenuWidget::new(vec![
+            MenuItem::checkbox("Show Grid", grid.clone()),
+            MenuItem::radio("Large", size.clone(), "large"),
+        ]);
+
+        menu.handle_event(&key_press(KeyCode::ArrowDown));
+        menu.handle_event(&key_press(KeyCode::Enter));
+        assert!(grid.get());
+
+        menu.handle_event(&key_press(KeyCode::ArrowDown));
+        menu.handle_event(&key_press(KeyCode::ArrowDown));
+        menu.handle_event(&key_press(KeyCode::Enter));
+        assert_eq!(size.get(), "large");
+    }
+
+    fn right_press(x: f64, y: f64) -> Event {
+        Event::Mouse(crate::event::MouseEvent {
+            position: crate::event::Point::new(x, y),
+            button: Some(MouseButton::Right),
+            state: ElementState::Pressed,
+            modifiers: Default::default(),
+        })
+    }
+
+    #[test]
+    fn right_click_while_open_reopens_context_menu_at_pointer() {
+        let mut context = context_menu(|| vec![MenuItem::action("Copy")]);
+        context.set_size(400.0, 300.0);
+        assert!(matches!(context.observe_event(&right_press(10.0, 10.0)), EventResult::Handled));
+        let first = context.overlay_id.unwrap();
+
+        // The open menu sees the next right click first: it closes but lets the click through
+        let mut open_menu = global_overlay_layer().remove(first).unwrap();
+        assert!(matches!(open_menu.handle_event(&right_press(200.0, 150.0)), EventResult::Ignored));
+        assert!(matches!(context.observe_event(&right_press(200.0, 150.0)), EventResult::Handled));
+
+        let second = context.overlay_id.unwrap();
+        assert_ne!(first, second);
+        let position = global_overlay_layer().with_element_mut(second, |element| match element {
+            Element::Widget(widget) => widget.as_any().downcast_ref::<MenuWidget>().map(MenuWidget::get_position),
+            _ => None,
+        }).flatten();
+        assert_eq!(position, Some((200.0, 150.0)));
+        context.close();
+    }
+
+    #[test]
+    fn shortcut_label_lists_modifiers_before_key() {
+        assert_eq!(Shortcut::ctrl(KeyCode::KeyS).with_shift().label(), "Ctrl+Shift+S");
+        assert_eq!(Shortcut::new(KeyCode::F2).label(), "F2");
+    }
+}
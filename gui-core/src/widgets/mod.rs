@@ -5,6 +5,17 @@ pub mod interactive;
 pub mod canvas;
 pub mod dropdown;
 pub mod property_inspector;
+pub mod tooltip;
+pub mod menu;
+pub mod color_picker;
+pub mod image_view;
+pub mod icon;
+pub mod progress;
+pub mod splitter;
+pub mod docking;
+pub mod number_field;
+pub mod multi_select;
+pub mod segmented;
 
 pub use text::*;
 pub use container::*;
@@ -12,4 +23,15 @@ pub use layout::*;
 pub use interactive::*;
 pub use canvas::*;
 pub use dropdown::*;
-pub use property_inspector::*;
\ No newline at end of file
+pub use property_inspector::*;
+pub use tooltip::*;
+pub use menu::*;
+pub use color_picker::*;
+pub use image_view::*;
+pub use icon::*;
+pub use progress::*;
+pub use splitter::*;
+pub use docking::*;
+pub use number_field::*;
+pub use multi_select::*;
+pub use segmented::*;
@@ -0,0 +1,760 @@
+use crate::{Widget, WidgetId, EventResult, WidgetError, RenderData, DirtyRegion, WidgetUpdateContext};
+use crate::event::Event;
+use winit::event::ElementState;
+use winit::keyboard::KeyCode;
+use gui_reactive::Signal;
+use gui_render::primitives::{Rectangle, Text};
+use std::any::Any;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use vello::peniko::Color;
+use super::container::Background;
+use super::dropdown::DropdownOption;
+use crate::theme::{current_theme, StyleOverrides, Theme};
+
+static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(15000);
+
+const CHECK_BOX_SIZE: f32 = 14.0;
+const CHIP_GAP: f32 = 4.0;
+
+/// How the closed box describes the selection
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MultiSelectSummary {
+    /// "3 selected", the label when only one is, "All selected" when all are
+    Count,
+    /// A chip per selected option, with a "+N" chip for those that don't fit
+    Chips,
+}
+
+/// Dropdown whose options each carry a checkbox, for picking any number of them.
+/// The open list starts with "Select all" and "None" actions.
+pub struct MultiSelectWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    selected: Signal<Vec<String>>,
+    options: Vec<DropdownOption>,
+    options_signal: Option<Signal<Vec<DropdownOption>>>,
+    summary: MultiSelectSummary,
+    placeholder: String,
+    is_open: bool,
+    is_hovering: bool,
+    // Option index under the mouse or moved to with the arrow keys
+    highlighted: Option<usize>,
+    // First listed option; only rows that fit below it are drawn
+    scroll_row: usize,
+    background: Background,
+    hover_background: Background,
+    border_color: Color,
+    text_color: Color,
+    muted_color: Color,
+    accent_color: Color,
+    chip_color: Color,
+    border_radius: f32,
+    font_size: f32,
+    max_height: f32,
+    on_change: Option<Box<dyn Fn(&[String]) + Send + Sync>>,
+    // Style set through builders, which the theme leaves alone
+    overrides: StyleOverrides,
+    pub dirty: bool,
+    // Shared dirty flag that reactive signals can set
+    reactive_dirty: Arc<RwLock<bool>>,
+}
+
+impl MultiSelectWidget {
+    pub fn new(selected: Signal<Vec<String>>) -> Self {
+        let theme = current_theme();
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 200.0,
+            height: 32.0,
+            selected,
+            options: Vec::new(),
+            options_signal: None,
+            summary: MultiSelectSummary::Count,
+            placeholder: "None".to_string(),
+            is_open: false,
+            is_hovering: false,
+            highlighted: None,
+            scroll_row: 0,
+            background: Background::Color(theme.palette.surface),
+            hover_background: Background::Color(theme.palette.surface_hover),
+            border_color: theme.palette.border,
+            text_color: theme.palette.text,
+            muted_color: theme.palette.text_muted,
+            accent_color: theme.palette.primary,
+            chip_color: theme.palette.selection,
+            border_radius: theme.radii.medium,
+            font_size: theme.typography.font_size,
+            max_height: 240.0,
+            on_change: None,
+            overrides: StyleOverrides::default(),
+            dirty: true,
+            reactive_dirty: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_options(mut self, options: Vec<DropdownOption>) -> Self {
+        self.options = options;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_options_signal(mut self, options_signal: Signal<Vec<DropdownOption>>) -> Self {
+        self.options_signal = Some(options_signal);
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_summary(mut self, summary: MultiSelectSummary) -> Self {
+        self.summary = summary;
+        self.dirty = true;
+        self
+    }
+
+    /// Shown when nothing is selected
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self.dirty = true;
+        self
+    }
+
+    /// Height the open list may take; longer lists scroll
+    pub fn with_max_height(mut self, max_height: f32) -> Self {
+        self.max_height = max_height;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self.overrides.mark("font_size");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_colors(mut self, text: Color, accent: Color, chip: Color) -> Self {
+        self.text_color = text;
+        self.accent_color = accent;
+        self.chip_color = chip;
+        self.overrides.mark("text_color");
+        self.overrides.mark("accent_color");
+        self.overrides.mark("chip_color");
+        self.dirty = true;
+        self
+    }
+
+    /// Take every style property not set through a builder from `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let palette = &theme.palette;
+        let overrides = &self.overrides;
+        if !overrides.is_set("text_color") {
+            self.text_color = palette.text;
+        }
+        if !overrides.is_set("accent_color") {
+            self.accent_color = palette.primary;
+        }
+        if !overrides.is_set("chip_color") {
+            self.chip_color = palette.selection;
+        }
+        if !overrides.is_set("font_size") {
+            self.font_size = theme.typography.font_size;
+        }
+        self.background = Background::Color(palette.surface);
+        self.hover_background = Background::Color(palette.surface_hover);
+        self.border_color = palette.border;
+        self.muted_color = palette.text_muted;
+        self.border_radius = theme.radii.medium;
+        self.dirty = true;
+    }
+
+    pub fn on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&[String]) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Box::new(callback));
+        self
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        if self.width != width || self.height != height {
+            self.width = width;
+            self.height = height;
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn get_selected_signal(&self) -> Signal<Vec<String>> {
+        self.selected.clone()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    fn get_effective_options(&self) -> Vec<DropdownOption> {
+        if let Some(signal) = &self.options_signal {
+            signal.get()
+        } else {
+            self.options.clone()
+        }
+    }
+
+    pub fn is_selected(&self, value: &str) -> bool {
+        self.selected.with(|selected| selected.iter().any(|selected| selected == value))
+    }
+
+    // Store a selection, in option order, keeping values that name no option at the end
+    fn set_selection(&mut self, mut values: Vec<String>) {
+        let options = self.get_effective_options();
+        values.sort_by_key(|value| {
+            options.iter().position(|option| option.value == *value).unwrap_or(usize::MAX)
+        });
+        values.dedup();
+        if values == self.selected.get() {
+            return;
+        }
+        self.selected.set(values.clone());
+        if let Some(ref callback) = self.on_change {
+            callback(&values);
+        }
+        self.dirty = true;
+    }
+
+    pub fn toggle_value(&mut self, value: &str) {
+        let mut values = self.selected.get();
+        match values.iter().position(|selected| selected == value) {
+            Some(index) => {
+                values.remove(index);
+            },
+            None => values.push(value.to_string()),
+        }
+        self.set_selection(values);
+    }
+
+    pub fn select_all(&mut self) {
+        let mut values: Vec<String> = self.get_effective_options().into_iter().map(|option| option.value).collect();
+        // Values set from elsewhere that name no option stay selected
+        let extra: Vec<String> = self.selected.get().into_iter().filter(|value| !values.contains(value)).collect();
+        values.extend(extra);
+        self.set_selection(values);
+    }
+
+    pub fn select_none(&mut self) {
+        self.set_selection(Vec::new());
+    }
+
+    /// Text of the closed box in `Count` mode
+    pub fn summary_text(&self) -> String {
+        let options = self.get_effective_options();
+        let selected = self.selected.get();
+        match selected.as_slice() {
+            [] => self.placeholder.clone(),
+            [value] => options.iter()
+                .find(|option| option.value == *value)
+                .map(|option| option.label.clone())
+                .unwrap_or_else(|| value.clone()),
+            _ if !options.is_empty() && options.iter().all(|option| selected.contains(&option.value)) => {
+                "All selected".to_string()
+            },
+            _ => format!("{} selected", selected.len()),
+        }
+    }
+
+    pub fn open(&mut self) {
+        if !self.is_open {
+            self.is_open = true;
+            self.highlighted = None;
+            self.scroll_row = 0;
+            self.dirty = true;
+        }
+    }
+
+    pub fn close(&mut self) {
+        if self.is_open {
+            self.is_open = false;
+            self.dirty = true;
+        }
+    }
+
+    pub fn toggle_dropdown(&mut self) {
+        if self.is_open {
+            self.close();
+        } else {
+            self.open();
+        }
+    }
+
+    fn visible_row_count(&self) -> usize {
+        // The action row takes the first slot
+        (((self.max_height / self.height).floor() as usize).max(2)) - 1
+    }
+
+    fn list_top(&self) -> f32 {
+        self.y + self.height * 2.0
+    }
+
+    fn popup_height(&self) -> f32 {
+        let rows = self.get_effective_options().len().min(self.visible_row_count());
+        (rows + 1) as f32 * self.height
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    pub fn is_point_in_dropdown(&self, x: f32, y: f32) -> bool {
+        self.is_open &&
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y + self.height && y <= self.y + self.height + self.popup_height()
+    }
+
+    // Option index of the row under a point
+    fn row_at_position(&self, x: f32, y: f32) -> Option<usize> {
+        if !self.is_point_in_dropdown(x, y) || y < self.list_top() {
+            return None;
+        }
+        let row = ((y - self.list_top()) / self.height) as usize;
+        let index = self.scroll_row + row;
+        (row < self.visible_row_count() && index < self.get_effective_options().len()).then_some(index)
+    }
+
+    // Bounds of the "Select all" and "None" actions
+    fn action_bounds(&self) -> [(f32, f32); 2] {
+        let char_width = self.font_size * 0.6;
+        let all_x = self.x + 8.0;
+        let all_width = "Select all".len() as f32 * char_width;
+        let none_x = all_x + all_width + 16.0;
+        [(all_x, all_width), (none_x, "None".len() as f32 * char_width)]
+    }
+
+    fn scroll_to_highlight(&mut self) {
+        let Some(highlighted) = self.highlighted else {
+            return;
+        };
+        let rows = self.visible_row_count();
+        if highlighted < self.scroll_row {
+            self.scroll_row = highlighted;
+        } else if highlighted >= self.scroll_row + rows {
+            self.scroll_row = highlighted + 1 - rows;
+        }
+    }
+
+    pub fn move_highlight(&mut self, delta: isize) {
+        let count = self.get_effective_options().len();
+        if count == 0 {
+            return;
+        }
+        let last = count as isize - 1;
+        let next = match self.highlighted {
+            Some(current) => (current as isize + delta).clamp(0, last),
+            None if delta < 0 => last,
+            None => 0,
+        } as usize;
+        self.highlighted = Some(next);
+        self.scroll_to_highlight();
+        self.dirty = true;
+    }
+
+    pub fn create_background_rectangle(&self) -> Rectangle {
+        let background = if self.is_hovering && !self.is_open {
+            &self.hover_background
+        } else {
+            &self.background
+        };
+        Rectangle::new_with_brush(self.x, self.y, self.width, self.height, background.to_brush())
+            .with_border_radius(self.border_radius)
+            .with_stroke_width(1.0)
+    }
+
+    pub fn create_arrow_primitive(&self) -> Text {
+        let arrow_char = if self.is_open { "▲" } else { "▼" };
+        let arrow_y = self.y + (self.height / 2.0) + (self.font_size * 0.25);
+        Text::new(self.x + self.width - 20.0, arrow_y, arrow_char.to_string(), self.muted_color, self.font_size)
+    }
+
+    /// The `Count` summary, or the placeholder of an empty `Chips` box
+    pub fn create_summary_text(&self) -> Option<Text> {
+        if self.summary == MultiSelectSummary::Chips && !self.selected.with(|selected| selected.is_empty()) {
+            return None;
+        }
+        let color = if self.selected.with(|selected| selected.is_empty()) { self.muted_color } else { self.text_color };
+        let text_y = self.y + (self.height / 2.0) + (self.font_size * 0.25);
+        Some(Text::new(self.x + 8.0, text_y, self.summary_text(), color, self.font_size))
+    }
+
+    /// Chips for the selected options that fit before the arrow, then "+N" for the rest
+    pub fn create_chip_primitives(&self) -> Vec<(Rectangle, Text)> {
+        if self.summary != MultiSelectSummary::Chips {
+            return Vec::new();
+        }
+        let options = self.get_effective_options();
+        let selected = self.selected.get();
+        let chip_font = self.font_size * 0.85;
+        let chip_height = (self.height - 10.0).max(chip_font + 4.0);
+        let chip_y = self.y + (self.height - chip_height) / 2.0;
+        let text_y = chip_y + chip_height / 2.0 + chip_font * 0.25;
+        let right = self.x + self.width - 28.0;
+        let chip_width = |label: &str| label.chars().count() as f32 * chip_font * 0.6 + 12.0;
+        // Room kept for the overflow chip
+        let overflow_width = chip_width("+99");
+
+        let mut chips = Vec::new();
+        let mut chip_x = self.x + 6.0;
+        for (index, value) in selected.iter().enumerate() {
+            let label = options.iter()
+                .find(|option| option.value == *value)
+                .map_or(value.as_str(), |option| option.label.as_str());
+            let width = chip_width(label);
+            let remaining = selected.len() - index - 1;
+            let limit = if remaining > 0 { right - overflow_width - CHIP_GAP } else { right };
+            if chip_x + width > limit {
+                let overflow = format!("+{}", selected.len() - index);
+                let width = chip_width(&overflow);
+                chips.push((
+                    Rectangle::new(chip_x, chip_y, width, chip_height, self.chip_color).with_border_radius(chip_height / 2.0),
+                    Text::new(chip_x + 6.0, text_y, overflow, self.muted_color, chip_font),
+                ));
+                break;
+            }
+            chips.push((
+                Rectangle::new(chip_x, chip_y, width, chip_height, self.chip_color).with_border_radius(chip_height / 2.0),
+                Text::new(chip_x + 6.0, text_y, label.to_string(), self.text_color, chip_font),
+            ));
+            chip_x += width + CHIP_GAP;
+        }
+        chips
+    }
+
+    pub fn create_dropdown_background(&self) -> Option<Rectangle> {
+        if !self.is_open {
+            return None;
+        }
+        Some(
+            Rectangle::new_with_brush(self.x, self.y + self.height, self.width, self.popup_height(), self.background.to_brush())
+                .with_border_radius(self.border_radius)
+                .with_stroke_width(1.0)
+        )
+    }
+
+    /// Row highlights, checkbox squares and the separator under the action row
+    pub fn create_option_rectangles(&self) -> Vec<Rectangle> {
+        if !self.is_open {
+            return Vec::new();
+        }
+        let mut rectangles = vec![
+            Rectangle::new(self.x, self.list_top() - 1.0, self.width, 1.0, self.border_color),
+        ];
+        let options = self.get_effective_options();
+        let visible = options.iter().enumerate().skip(self.scroll_row).take(self.visible_row_count());
+        for (row, (index, option)) in visible.enumerate() {
+            let row_y = self.list_top() + row as f32 * self.height;
+            if self.highlighted == Some(index) {
+                rectangles.push(Rectangle::new(self.x, row_y, self.width, self.height, self.chip_color));
+            }
+            let box_y = row_y + (self.height - CHECK_BOX_SIZE) / 2.0;
+            let checked = self.is_selected(&option.value);
+            let fill = if checked { self.accent_color } else { self.border_color };
+            rectangles.push(
+                Rectangle::new(self.x + 8.0, box_y, CHECK_BOX_SIZE, CHECK_BOX_SIZE, fill)
+                    .with_border_radius(3.0)
+                    .with_stroke_width(1.0)
+            );
+        }
+        if let Some(scrollbar) = self.create_scrollbar_rectangle() {
+            rectangles.push(scrollbar);
+        }
+        rectangles
+    }
+
+    /// Action labels, check marks and option labels
+    pub fn create_option_texts(&self) -> Vec<Text> {
+        if !self.is_open {
+            return Vec::new();
+        }
+        let action_y = self.y + self.height + (self.height / 2.0) + (self.font_size * 0.25);
+        let [(all_x, _), (none_x, _)] = self.action_bounds();
+        let mut texts = vec![
+            Text::new(all_x, action_y, "Select all".to_string(), self.accent_color, self.font_size),
+            Text::new(none_x, action_y, "None".to_string(), self.accent_color, self.font_size),
+        ];
+
+        let options = self.get_effective_options();
+        let visible = options.iter().skip(self.scroll_row).take(self.visible_row_count());
+        for (row, option) in visible.enumerate() {
+            let row_y = self.list_top() + row as f32 * self.height;
+            let text_y = row_y + (self.height / 2.0) + (self.font_size * 0.25);
+            if self.is_selected(&option.value) {
+                texts.push(Text::new(self.x + 10.0, row_y + self.height / 2.0 + CHECK_BOX_SIZE * 0.3, "✓".to_string(), Color::rgba8(255, 255, 255, 255), CHECK_BOX_SIZE));
+            }
+            texts.push(Text::new(self.x + 16.0 + CHECK_BOX_SIZE, text_y, option.label.clone(), self.text_color, self.font_size));
+        }
+        texts
+    }
+
+    fn create_scrollbar_rectangle(&self) -> Option<Rectangle> {
+        let count = self.get_effective_options().len();
+        let rows = self.visible_row_count();
+        if count <= rows {
+            return None;
+        }
+        let track_height = rows as f32 * self.height;
+        let thumb_height = (track_height * rows as f32 / count as f32).max(12.0);
+        let thumb_y = self.list_top() + (track_height - thumb_height) * self.scroll_row as f32 / (count - rows) as f32;
+        Some(Rectangle::new(self.x + self.width - 6.0, thumb_y, 4.0, thumb_height, Color::rgba8(0, 0, 0, 70)).with_border_radius(2.0))
+    }
+
+    fn handle_press(&mut self, x: f32, y: f32) -> EventResult {
+        if self.is_point_inside(x, y) {
+            self.toggle_dropdown();
+            return EventResult::Handled;
+        }
+        if !self.is_open {
+            return EventResult::Ignored;
+        }
+        if !self.is_point_in_dropdown(x, y) {
+            // Click outside closes the list
+            self.close();
+            return EventResult::Handled;
+        }
+        if y < self.list_top() {
+            let [(all_x, all_width), (none_x, none_width)] = self.action_bounds();
+            if x >= all_x && x <= all_x + all_width {
+                self.select_all();
+            } else if x >= none_x && x <= none_x + none_width {
+                self.select_none();
+            }
+        } else if let Some(index) = self.row_at_position(x, y) {
+            // Options toggle without closing, so several can be picked in a row
+            if let Some(option) = self.get_effective_options().get(index) {
+                self.toggle_value(&option.value);
+            }
+        }
+        EventResult::Handled
+    }
+}
+
+impl Widget for MultiSelectWidget {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        self.dirty = true;
+
+        let reactive_dirty = self.reactive_dirty.clone();
+        self.selected.subscribe_fn(move |_| {
+            if let Ok(mut dirty) = reactive_dirty.write() {
+                *dirty = true;
+            }
+        });
+        if let Some(options_signal) = &self.options_signal {
+            let reactive_dirty = self.reactive_dirty.clone();
+            options_signal.subscribe_fn(move |_| {
+                if let Ok(mut dirty) = reactive_dirty.write() {
+                    *dirty = true;
+                }
+            });
+        }
+
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        self.is_open = false;
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        if let Ok(mut reactive_dirty) = self.reactive_dirty.write() {
+            if *reactive_dirty {
+                *reactive_dirty = false;
+                self.dirty = true;
+            }
+        }
+
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        match event {
+            Event::Mouse(mouse_event) => {
+                let x = mouse_event.position.x as f32;
+                let y = mouse_event.position.y as f32;
+
+                if mouse_event.button.is_none() {
+                    let hovering = self.is_point_inside(x, y);
+                    if hovering != self.is_hovering {
+                        self.is_hovering = hovering;
+                        self.dirty = true;
+                    }
+                    if let Some(row) = self.row_at_position(x, y) {
+                        if self.highlighted != Some(row) {
+                            self.highlighted = Some(row);
+                            self.dirty = true;
+                        }
+                    }
+                    EventResult::Ignored
+                } else if mouse_event.state == ElementState::Pressed {
+                    self.handle_press(x, y)
+                } else {
+                    EventResult::Ignored
+                }
+            },
+            Event::Scroll(scroll_event) => {
+                let (x, y) = (scroll_event.position.x as f32, scroll_event.position.y as f32);
+                if !self.is_point_in_dropdown(x, y) || scroll_event.delta.y == 0.0 {
+                    return EventResult::Ignored;
+                }
+                let max_scroll = self.get_effective_options().len().saturating_sub(self.visible_row_count());
+                let scroll_row = if scroll_event.delta.y > 0.0 {
+                    self.scroll_row.saturating_sub(1)
+                } else {
+                    (self.scroll_row + 1).min(max_scroll)
+                };
+                if scroll_row != self.scroll_row {
+                    self.scroll_row = scroll_row;
+                    self.dirty = true;
+                }
+                EventResult::Handled
+            },
+            Event::Keyboard(keyboard_event) if self.is_open && keyboard_event.state == ElementState::Pressed => {
+                let modifiers = &keyboard_event.modifiers;
+                match keyboard_event.key_code {
+                    Some(KeyCode::ArrowDown) => self.move_highlight(1),
+                    Some(KeyCode::ArrowUp) => self.move_highlight(-1),
+                    Some(KeyCode::Space) | Some(KeyCode::Enter) => {
+                        let option = self.highlighted.and_then(|index| self.get_effective_options().get(index).cloned());
+                        if let Some(option) = option {
+                            self.toggle_value(&option.value);
+                        }
+                    },
+                    Some(KeyCode::KeyA) if modifiers.control_key() || modifiers.super_key() => self.select_all(),
+                    Some(KeyCode::Escape) => self.close(),
+                    _ => return EventResult::Ignored,
+                }
+                EventResult::Handled
+            },
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        let mut dirty_regions = vec![DirtyRegion {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        }];
+        if self.is_open {
+            dirty_regions.push(DirtyRegion {
+                x: self.x,
+                y: self.y + self.height,
+                width: self.width,
+                height: self.popup_height(),
+            });
+        }
+
+        Ok(RenderData {
+            dirty_regions,
+            z_index: if self.is_open { 10 } else { 1 },
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+// Convenience function for creating multi-select dropdowns
+pub fn multi_select(selected: Signal<Vec<String>>) -> MultiSelectWidget {
+    MultiSelectWidget::new(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layers() -> Vec<DropdownOption> {
+        ["Background", "Props", "Characters", "UI"]
+            .iter()
+            .map(|name| DropdownOption::new(name.to_lowercase(), *name))
+            .collect()
+    }
+
+    #[test]
+    fn selection_keeps_option_order_and_summarizes() {
+        let selected = Signal::new(Vec::new());
+        let mut layer_filter = multi_select(selected.clone()).with_options(layers());
+        assert_eq!(layer_filter.summary_text(), "None");
+
+        layer_filter.toggle_value("ui");
+        assert_eq!(layer_filter.summary_text(), "UI");
+        layer_filter.toggle_value("background");
+        assert_eq!(selected.get(), ["background", "ui"]);
+        assert_eq!(layer_filter.summary_text(), "2 selected");
+
+        layer_filter.select_all();
+        assert_eq!(layer_filter.summary_text(), "All selected");
+        layer_filter.toggle_value("props");
+        assert_eq!(selected.get(), ["background", "characters", "ui"]);
+
+        layer_filter.select_none();
+        assert!(selected.get().is_empty());
+    }
+
+    #[test]
+    fn chips_overflow_into_a_count() {
+        let selected = Signal::new(vec!["background".to_string(), "props".to_string(), "characters".to_string(), "ui".to_string()]);
+        let layer_filter = multi_select(selected)
+            .with_options(layers())
+            .with_size(160.0, 32.0)
+            .with_summary(MultiSelectSummary::Chips);
+        let chips = layer_filter.create_chip_primitives();
+        assert!(chips.len() < 4);
+        assert!(chips.last().is_some_and(|(_, text)| text.content.starts_with('+')));
+    }
+}
@@ -0,0 +1,823 @@
+use crate::{Widget, WidgetId, EventResult, WidgetError, RenderData, DirtyRegion, WidgetUpdateContext};
+use crate::event::Event;
+use winit::event::{ElementState, MouseButton};
+use winit::keyboard::{KeyCode, ModifiersState};
+use gui_reactive::Signal;
+use gui_render::primitives::{Rectangle, Text};
+use std::any::Any;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use vello::peniko::Color;
+use super::interactive::InputWidget;
+use crate::theme::{current_theme, StyleOverrides, Theme};
+
+static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(14000);
+
+// Pointer travel before a press turns into a scrub instead of a click
+const SCRUB_THRESHOLD: f32 = 3.0;
+const FINE_FACTOR: f32 = 0.1;
+const COARSE_FACTOR: f32 = 10.0;
+
+/// Unit shown after the value. Typing the suffix back in while editing is accepted.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NumberUnit {
+    None,
+    Pixels,
+    Degrees,
+    Percent,
+    Custom(String),
+}
+
+impl NumberUnit {
+    pub fn suffix(&self) -> String {
+        match self {
+            NumberUnit::None => String::new(),
+            NumberUnit::Pixels => " px".to_string(),
+            NumberUnit::Degrees => "°".to_string(),
+            NumberUnit::Percent => "%".to_string(),
+            NumberUnit::Custom(unit) => format!(" {}", unit),
+        }
+    }
+
+    fn accepted_suffixes(&self) -> Vec<&str> {
+        match self {
+            NumberUnit::None => Vec::new(),
+            NumberUnit::Pixels => vec!["px"],
+            NumberUnit::Degrees => vec!["°", "deg"],
+            NumberUnit::Percent => vec!["%"],
+            NumberUnit::Custom(unit) => vec![unit.as_str()],
+        }
+    }
+}
+
+/// Evaluate an arithmetic expression such as `2*pi` or `(10 + 4) / 3`.
+///
+/// Supports `+ - * / ^`, parentheses, implicit multiplication (`2pi`), the
+/// constants `pi`, `tau` and `e`, and common functions like `sqrt`, `sin`,
+/// `min` and `max`. Returns `None` for malformed or non-finite results.
+pub fn evaluate_expression(expression: &str) -> Option<f64> {
+    let mut parser = ExpressionParser {
+        chars: expression.chars().collect(),
+        position: 0,
+    };
+    let value = parser.expression()?;
+    parser.skip_whitespace();
+    if parser.position == parser.chars.len() && value.is_finite() {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Parse what the user typed into a number field, dropping a trailing unit suffix
+pub fn parse_number_input(text: &str, unit: &NumberUnit) -> Option<f32> {
+    let mut text = text.trim();
+    for suffix in unit.accepted_suffixes() {
+        if text.len() >= suffix.len() && text.is_char_boundary(text.len() - suffix.len())
+            && text[text.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+        {
+            text = text[..text.len() - suffix.len()].trim_end();
+            break;
+        }
+    }
+    evaluate_expression(text).map(|value| value as f32)
+}
+
+struct ExpressionParser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl ExpressionParser {
+    fn skip_whitespace(&mut self) {
+        while self.chars.get(self.position).is_some_and(|c| c.is_whitespace()) {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.get(self.position).copied()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    // expression := term (('+' | '-') term)*
+    fn expression(&mut self) -> Option<f64> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat('+') {
+                value += self.term()?;
+            } else if self.eat('-') {
+                value -= self.term()?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    // term := unary (('*' | '/' | implicit) unary)*
+    fn term(&mut self) -> Option<f64> {
+        let mut value = self.unary()?;
+        loop {
+            if self.eat('*') {
+                value *= self.unary()?;
+            } else if self.eat('/') {
+                value /= self.unary()?;
+            } else if self.peek().is_some_and(|c| c.is_alphanumeric() || c == '.' || c == '(') {
+                value *= self.power()?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    // unary := ('-' | '+') unary | power
+    fn unary(&mut self) -> Option<f64> {
+        if self.eat('-') {
+            Some(-self.unary()?)
+        } else if self.eat('+') {
+            self.unary()
+        } else {
+            self.power()
+        }
+    }
+
+    // power := primary ('^' unary)?
+    fn power(&mut self) -> Option<f64> {
+        let base = self.primary()?;
+        if self.eat('^') {
+            Some(base.powf(self.unary()?))
+        } else {
+            Some(base)
+        }
+    }
+
+    fn primary(&mut self) -> Option<f64> {
+        let c = self.peek()?;
+        if c == '(' {
+            self.position += 1;
+            let value = self.expression()?;
+            return self.eat(')').then_some(value);
+        }
+        if c.is_ascii_digit() || c == '.' {
+            return self.number();
+        }
+        if c.is_alphabetic() {
+            let start = self.position;
+            while self.chars.get(self.position).is_some_and(|c| c.is_alphanumeric()) {
+                self.position += 1;
+            }
+            let name: String = self.chars[start..self.position].iter().collect::<String>().to_lowercase();
+            return self.identifier(&name);
+        }
+        None
+    }
+
+    fn number(&mut self) -> Option<f64> {
+        let start = self.position;
+        while self.chars.get(self.position).is_some_and(|c| c.is_ascii_digit() || *c == '.') {
+            self.position += 1;
+        }
+        // Scientific notation, only when an exponent actually follows so `2e` stays 2 * e
+        if matches!(self.chars.get(self.position), Some('e' | 'E')) {
+            let mut lookahead = self.position + 1;
+            if matches!(self.chars.get(lookahead), Some('+' | '-')) {
+                lookahead += 1;
+            }
+            if self.chars.get(lookahead).is_some_and(|c| c.is_ascii_digit()) {
+                self.position = lookahead;
+                while self.chars.get(self.position).is_some_and(|c| c.is_ascii_digit()) {
+                    self.position += 1;
+                }
+            }
+        }
+        let literal: String = self.chars[start..self.position].iter().collect();
+        literal.parse().ok()
+    }
+
+    fn identifier(&mut self, name: &str) -> Option<f64> {
+        match name {
+            "pi" => return Some(std::f64::consts::PI),
+            "tau" => return Some(std::f64::consts::TAU),
+            "e" => return Some(std::f64::consts::E),
+            _ => {}
+        }
+
+        if !self.eat('(') {
+            return None;
+        }
+        let mut arguments = vec![self.expression()?];
+        while self.eat(',') {
+            arguments.push(self.expression()?);
+        }
+        if !self.eat(')') {
+            return None;
+        }
+
+        match (name, arguments.as_slice()) {
+            ("sqrt", [x]) => Some(x.sqrt()),
+            ("abs", [x]) => Some(x.abs()),
+            ("sin", [x]) => Some(x.sin()),
+            ("cos", [x]) => Some(x.cos()),
+            ("tan", [x]) => Some(x.tan()),
+            ("asin", [x]) => Some(x.asin()),
+            ("acos", [x]) => Some(x.acos()),
+            ("atan", [x]) => Some(x.atan()),
+            ("floor", [x]) => Some(x.floor()),
+            ("ceil", [x]) => Some(x.ceil()),
+            ("round", [x]) => Some(x.round()),
+            ("ln", [x]) => Some(x.ln()),
+            ("log", [x]) => Some(x.log10()),
+            ("exp", [x]) => Some(x.exp()),
+            ("rad", [x]) => Some(x.to_radians()),
+            ("deg", [x]) => Some(x.to_degrees()),
+            ("min", [first, rest @ ..]) => Some(rest.iter().fold(*first, |a, b| a.min(*b))),
+            ("max", [first, rest @ ..]) => Some(rest.iter().fold(*first, |a, b| a.max(*b))),
+            _ => None,
+        }
+    }
+}
+
+// Press on the field that has not yet become a scrub or a click
+struct ScrubState {
+    start_x: f32,
+    last_x: f32,
+    scrubbing: bool,
+}
+
+/// Numeric field in the style of 3D editors: drag horizontally to scrub the
+/// value (Shift for fine, Ctrl for coarse steps), click to type a value or an
+/// expression into the embedded `InputWidget`.
+pub struct NumberFieldWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    value: Signal<f32>,
+    // Set while the field stands for several differing values; cleared by an edit
+    mixed: Signal<bool>,
+    label: Option<String>,
+    unit: NumberUnit,
+    min: Option<f32>,
+    max: Option<f32>,
+    step: f32,
+    precision: usize,
+    input: InputWidget,
+    edit_text: Signal<String>,
+    editing: bool,
+    hovered: bool,
+    scrub: Option<ScrubState>,
+    background_color: Color,
+    hover_color: Color,
+    fill_color: Color,
+    text_color: Color,
+    label_color: Color,
+    font_size: f32,
+    border_radius: f32,
+    on_change: Option<Box<dyn Fn(f32) + Send + Sync>>,
+    // Style set through builders, which the theme leaves alone
+    overrides: StyleOverrides,
+    pub dirty: bool,
+    // Shared dirty flag that reactive signals can set
+    reactive_dirty: Arc<RwLock<bool>>,
+}
+
+impl NumberFieldWidget {
+    pub fn new(value: Signal<f32>) -> Self {
+        let edit_text = Signal::new(String::new());
+        let theme = current_theme();
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 120.0,
+            height: 24.0,
+            value,
+            mixed: Signal::new(false),
+            label: None,
+            unit: NumberUnit::None,
+            min: None,
+            max: None,
+            step: 0.1,
+            precision: 2,
+            input: InputWidget::new().with_size(120.0, 24.0).with_signal(edit_text.clone()),
+            edit_text,
+            editing: false,
+            hovered: false,
+            scrub: None,
+            background_color: theme.palette.surface,
+            hover_color: theme.palette.surface_hover,
+            fill_color: theme.palette.selection,
+            text_color: theme.palette.text,
+            label_color: theme.palette.text_muted,
+            font_size: theme.typography.font_size_small,
+            border_radius: theme.radii.medium,
+            on_change: None,
+            overrides: StyleOverrides::default(),
+            dirty: true,
+            reactive_dirty: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self.input.set_size(width, height);
+        self.dirty = true;
+        self
+    }
+
+    /// Show "—" instead of the value while `mixed` is true, e.g. when editing
+    /// several objects that disagree. The first edit clears it.
+    pub fn with_mixed_signal(mut self, mixed: Signal<bool>) -> Self {
+        self.mixed = mixed;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_unit(mut self, unit: NumberUnit) -> Self {
+        self.unit = unit;
+        self.dirty = true;
+        self
+    }
+
+    /// Clamp the value to `min..=max`; the field shows how far along the range it is
+    pub fn with_range(mut self, min: f32, max: f32) -> Self {
+        self.min = Some(min.min(max));
+        self.max = Some(max.max(min));
+        self.set_value(self.value.get());
+        self
+    }
+
+    /// Value change per pixel of horizontal drag, before Shift/Ctrl scaling
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = step.abs();
+        self
+    }
+
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_colors(mut self, background: Color, hover: Color, fill: Color) -> Self {
+        self.background_color = background;
+        self.hover_color = hover;
+        self.fill_color = fill;
+        self.overrides.mark("background_color");
+        self.overrides.mark("hover_color");
+        self.overrides.mark("fill_color");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_text_colors(mut self, text_color: Color, label_color: Color) -> Self {
+        self.text_color = text_color;
+        self.label_color = label_color;
+        self.overrides.mark("text_color");
+        self.overrides.mark("label_color");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self.overrides.mark("font_size");
+        self.dirty = true;
+        self
+    }
+
+    /// Take every style property not set through a builder from `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let palette = &theme.palette;
+        let overrides = &self.overrides;
+        if !overrides.is_set("background_color") {
+            self.background_color = palette.surface;
+        }
+        if !overrides.is_set("hover_color") {
+            self.hover_color = palette.surface_hover;
+        }
+        if !overrides.is_set("fill_color") {
+            self.fill_color = palette.selection;
+        }
+        if !overrides.is_set("text_color") {
+            self.text_color = palette.text;
+        }
+        if !overrides.is_set("label_color") {
+            self.label_color = palette.text_muted;
+        }
+        if !overrides.is_set("font_size") {
+            self.font_size = theme.typography.font_size_small;
+        }
+        self.border_radius = theme.radii.medium;
+        self.input.apply_theme(theme);
+        self.dirty = true;
+    }
+
+    pub fn on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(f32) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Box::new(callback));
+        self
+    }
+
+    pub fn get_value(&self) -> f32 {
+        self.value.get()
+    }
+
+    pub fn get_value_signal(&self) -> Signal<f32> {
+        self.value.clone()
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.editing
+    }
+
+    pub fn is_mixed(&self) -> bool {
+        self.mixed.get()
+    }
+
+    /// Store a clamped value, notifying `on_change` when it actually changed
+    pub fn set_value(&mut self, value: f32) {
+        let mut value = value;
+        if let Some(min) = self.min {
+            value = value.max(min);
+        }
+        if let Some(max) = self.max {
+            value = value.min(max);
+        }
+        if !value.is_finite() || (value == self.value.get() && !self.mixed.get()) {
+            return;
+        }
+        self.mixed.set(false);
+        self.value.set(value);
+        if let Some(ref callback) = self.on_change {
+            callback(value);
+        }
+        self.dirty = true;
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.input.set_position(x, y);
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        if self.width != width || self.height != height {
+            self.width = width;
+            self.height = height;
+            self.input.set_size(width, height);
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    /// The value as displayed, with its unit suffix
+    pub fn display_text(&self) -> String {
+        if self.mixed.get() {
+            return "—".to_string();
+        }
+        format!("{:.*}{}", self.precision, self.value.get(), self.unit.suffix())
+    }
+
+    /// Switch to text entry with the current value selected for editing
+    pub fn begin_editing(&mut self) {
+        if self.editing {
+            return;
+        }
+        let text = format!("{:.*}", self.precision, self.value.get());
+        let text = if self.mixed.get() {
+            String::new()
+        } else if text.contains('.') {
+            text.trim_end_matches('0').trim_end_matches('.').to_string()
+        } else {
+            text
+        };
+        self.edit_text.set(text);
+        self.input.set_focus(false);
+        self.input.set_focus(true);
+        self.editing = true;
+        self.dirty = true;
+    }
+
+    /// Leave text entry, evaluating the typed expression when `commit` is set.
+    /// Text that does not parse leaves the value untouched.
+    pub fn end_editing(&mut self, commit: bool) {
+        if !self.editing {
+            return;
+        }
+        if commit {
+            if let Some(value) = parse_number_input(&self.edit_text.get(), &self.unit) {
+                self.set_value(value);
+            }
+        }
+        self.input.set_focus(false);
+        self.editing = false;
+        self.dirty = true;
+    }
+
+    fn scrub_factor(modifiers: &ModifiersState) -> f32 {
+        if modifiers.shift_key() {
+            FINE_FACTOR
+        } else if modifiers.control_key() || modifiers.super_key() {
+            COARSE_FACTOR
+        } else {
+            1.0
+        }
+    }
+
+    // Fraction of the range covered by the value, when the field has one
+    fn range_fraction(&self) -> Option<f32> {
+        let (min, max) = (self.min?, self.max?);
+        if max <= min {
+            return None;
+        }
+        Some(((self.value.get() - min) / (max - min)).clamp(0.0, 1.0))
+    }
+
+    pub fn create_rectangles(&self) -> Vec<Rectangle> {
+        if self.editing {
+            return vec![self.input.create_background_rectangle()];
+        }
+
+        let background = if self.hovered || self.scrub.is_some() { self.hover_color } else { self.background_color };
+        let mut rectangles = vec![
+            Rectangle::new(self.x, self.y, self.width, self.height, background)
+                .with_border_radius(self.border_radius),
+        ];
+        if let Some(fraction) = self.range_fraction() {
+            if fraction > 0.0 {
+                rectangles.push(
+                    Rectangle::new(self.x, self.y, self.width * fraction, self.height, self.fill_color)
+                        .with_border_radius(self.border_radius),
+                );
+            }
+        }
+        rectangles
+    }
+
+    pub fn create_text_primitives(&self) -> Vec<Text> {
+        if self.editing {
+            return self.input.create_text_primitive().into_iter().collect();
+        }
+
+        let padding = 8.0;
+        let text_y = self.y + (self.height / 2.0) + (self.font_size * 0.25);
+        let value_text = self.display_text();
+        let value_width = value_text.chars().count() as f32 * self.font_size * 0.6;
+        let mut texts = Vec::new();
+
+        match &self.label {
+            Some(label) => {
+                texts.push(Text::new(self.x + padding, text_y, label.clone(), self.label_color, self.font_size));
+                let value_x = self.x + self.width - padding - value_width;
+                texts.push(Text::new(value_x, text_y, value_text, self.text_color, self.font_size));
+            },
+            None => {
+                let value_x = self.x + (self.width - value_width) / 2.0;
+                texts.push(Text::new(value_x, text_y, value_text, self.text_color, self.font_size));
+            },
+        }
+        texts
+    }
+}
+
+impl Widget for NumberFieldWidget {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        self.dirty = true;
+        self.input.mount()?;
+
+        let reactive_dirty = self.reactive_dirty.clone();
+        self.value.subscribe_fn(move |_| {
+            if let Ok(mut dirty) = reactive_dirty.write() {
+                *dirty = true;
+            }
+        });
+        let reactive_dirty = self.reactive_dirty.clone();
+        self.mixed.subscribe_fn(move |_| {
+            if let Ok(mut dirty) = reactive_dirty.write() {
+                *dirty = true;
+            }
+        });
+
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        self.scrub = None;
+        self.editing = false;
+        self.input.unmount()
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        if let Ok(mut reactive_dirty) = self.reactive_dirty.write() {
+            if *reactive_dirty {
+                *reactive_dirty = false;
+                self.dirty = true;
+            }
+        }
+
+        if self.editing {
+            self.input.update(ctx)?;
+            if self.input.dirty {
+                self.input.dirty = false;
+                self.dirty = true;
+            }
+        }
+
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        match event {
+            Event::Mouse(mouse_event) => {
+                let x = mouse_event.position.x as f32;
+                let y = mouse_event.position.y as f32;
+
+                if self.editing {
+                    if mouse_event.button.is_some() && mouse_event.state == ElementState::Pressed
+                        && !self.is_point_inside(x, y)
+                    {
+                        // Clicking elsewhere keeps what was typed
+                        self.end_editing(true);
+                        return EventResult::Propagate;
+                    }
+                    return self.input.handle_event(event);
+                }
+
+                if mouse_event.button.is_none() {
+                    // Mouse move
+                    if let Some(scrub) = self.scrub.as_mut() {
+                        if !scrub.scrubbing && (x - scrub.start_x).abs() < SCRUB_THRESHOLD {
+                            return EventResult::Handled;
+                        }
+                        scrub.scrubbing = true;
+                        let delta = x - scrub.last_x;
+                        scrub.last_x = x;
+                        let factor = Self::scrub_factor(&mouse_event.modifiers);
+                        let value = self.value.get() + delta * self.step * factor;
+                        self.set_value(value);
+                        self.dirty = true;
+                        return EventResult::Handled;
+                    }
+                    let hovered = self.is_point_inside(x, y);
+                    if hovered != self.hovered {
+                        self.hovered = hovered;
+                        self.dirty = true;
+                    }
+                    return EventResult::Ignored;
+                }
+
+                if mouse_event.button != Some(MouseButton::Left) {
+                    return EventResult::Ignored;
+                }
+
+                match mouse_event.state {
+                    ElementState::Pressed => {
+                        if !self.is_point_inside(x, y) {
+                            return EventResult::Ignored;
+                        }
+                        self.scrub = Some(ScrubState { start_x: x, last_x: x, scrubbing: false });
+                        self.dirty = true;
+                        EventResult::Handled
+                    },
+                    ElementState::Released => {
+                        let Some(scrub) = self.scrub.take() else {
+                            return EventResult::Ignored;
+                        };
+                        if !scrub.scrubbing {
+                            self.begin_editing();
+                        }
+                        self.dirty = true;
+                        EventResult::Handled
+                    },
+                }
+            },
+            Event::Keyboard(keyboard_event) if self.editing && keyboard_event.state == ElementState::Pressed => {
+                match keyboard_event.key_code {
+                    Some(KeyCode::Enter | KeyCode::NumpadEnter) => {
+                        self.end_editing(true);
+                        EventResult::Handled
+                    },
+                    Some(KeyCode::Escape) => {
+                        self.end_editing(false);
+                        EventResult::Handled
+                    },
+                    _ => self.input.handle_event(event),
+                }
+            },
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty || (self.editing && self.input.needs_render())
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            }],
+            z_index: 1,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+// Convenience function for creating number fields
+pub fn number_field(value: Signal<f32>) -> NumberFieldWidget {
+    NumberFieldWidget::new(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluates_expressions() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        assert!(close(evaluate_expression("2*pi").unwrap(), std::f64::consts::TAU));
+        assert!(close(evaluate_expression("2pi").unwrap(), std::f64::consts::TAU));
+        assert!(close(evaluate_expression("(1 + 2) * 3 - 4 / 2").unwrap(), 7.0));
+        assert!(close(evaluate_expression("-2^2").unwrap(), -4.0));
+        assert!(close(evaluate_expression("2^3^2").unwrap(), 512.0));
+        assert!(close(evaluate_expression("1.5e2").unwrap(), 150.0));
+        assert!(close(evaluate_expression("max(1, sqrt(16), 3)").unwrap(), 4.0));
+        assert!(evaluate_expression("2 +").is_none());
+        assert!(evaluate_expression("1/0").is_none());
+        assert!(evaluate_expression("foo(1)").is_none());
+    }
+
+    #[test]
+    fn strips_unit_suffix() {
+        assert_eq!(parse_number_input("12px", &NumberUnit::Pixels), Some(12.0));
+        assert_eq!(parse_number_input(" 45 deg ", &NumberUnit::Degrees), Some(45.0));
+        assert_eq!(parse_number_input("90°", &NumberUnit::Degrees), Some(90.0));
+        assert_eq!(parse_number_input("50%", &NumberUnit::Percent), Some(50.0));
+        assert_eq!(parse_number_input("12px", &NumberUnit::None), None);
+    }
+
+    #[test]
+    fn clamps_to_range() {
+        let mut field = number_field(Signal::new(5.0)).with_range(0.0, 10.0);
+        field.set_value(20.0);
+        assert_eq!(field.get_value(), 10.0);
+        field.set_value(-1.0);
+        assert_eq!(field.get_value(), 0.0);
+    }
+}
@@ -0,0 +1,543 @@
+use crate::{Widget, WidgetId, EventResult, WidgetError, RenderData, DirtyRegion, WidgetUpdateContext};
+use crate::event::Event;
+use crate::theme::{current_theme, StyleOverrides, Theme};
+use gui_reactive::{Signal, global_frame_scheduler};
+use gui_render::primitives::{Rectangle, Text, VectorPath};
+use std::any::Any;
+use std::f64::consts::{PI, TAU};
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use vello::kurbo::{Arc as ArcShape, BezPath, Point, Shape, Vec2};
+use vello::peniko::Color;
+
+static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(11000);
+
+// Seconds for the indeterminate segment to cross the track once
+const INDETERMINATE_PERIOD: f32 = 1.5;
+
+fn animation_seconds() -> f32 {
+    global_frame_scheduler().frame_timestamp().as_secs_f32()
+}
+
+/// Horizontal progress bar. Determinate bars show a `Signal<f32>` in 0..1;
+/// indeterminate bars animate a sliding segment until a value is known.
+pub struct ProgressBarWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    value: Signal<f32>,
+    indeterminate: bool,
+    show_label: bool,
+    track_color: Color,
+    fill_color: Color,
+    text_color: Color,
+    font_size: f32,
+    border_radius: f32,
+    overrides: StyleOverrides,
+    pub dirty: bool,
+    // Shared dirty flag that reactive signals can set
+    reactive_dirty: Arc<RwLock<bool>>,
+}
+
+impl ProgressBarWidget {
+    pub fn new(value: Signal<f32>) -> Self {
+        let theme = current_theme();
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 200.0,
+            height: 8.0,
+            value,
+            indeterminate: false,
+            show_label: false,
+            track_color: theme.palette.border,
+            fill_color: theme.palette.primary,
+            text_color: theme.palette.text,
+            font_size: 11.0,
+            border_radius: 4.0,
+            overrides: StyleOverrides::default(),
+            dirty: true,
+            reactive_dirty: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self.border_radius = self.border_radius.min(height / 2.0);
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_colors(mut self, track_color: Color, fill_color: Color) -> Self {
+        self.track_color = track_color;
+        self.fill_color = fill_color;
+        self.overrides.mark("colors");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_text_color(mut self, color: Color) -> Self {
+        self.text_color = color;
+        self.overrides.mark("text_color");
+        self.dirty = true;
+        self
+    }
+
+    /// Take every color not set through a builder from `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        if !self.overrides.is_set("colors") {
+            self.track_color = theme.palette.border;
+            self.fill_color = theme.palette.primary;
+        }
+        if !self.overrides.is_set("text_color") {
+            self.text_color = theme.palette.text;
+        }
+        self.dirty = true;
+    }
+
+    pub fn with_border_radius(mut self, radius: f32) -> Self {
+        self.border_radius = radius;
+        self.dirty = true;
+        self
+    }
+
+    /// Draw the percentage centered on the bar
+    pub fn with_label(mut self) -> Self {
+        self.show_label = true;
+        self.dirty = true;
+        self
+    }
+
+    pub fn indeterminate(mut self) -> Self {
+        self.indeterminate = true;
+        self.dirty = true;
+        self
+    }
+
+    pub fn set_indeterminate(&mut self, indeterminate: bool) {
+        if self.indeterminate != indeterminate {
+            self.indeterminate = indeterminate;
+            self.dirty = true;
+        }
+    }
+
+    pub fn is_indeterminate(&self) -> bool {
+        self.indeterminate
+    }
+
+    pub fn get_value(&self) -> f32 {
+        self.value.get().clamp(0.0, 1.0)
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    /// Filled span (start, end) as fractions of the track width
+    fn fill_span(&self) -> (f32, f32) {
+        self.fill_span_at(animation_seconds())
+    }
+
+    fn fill_span_at(&self, seconds: f32) -> (f32, f32) {
+        if !self.indeterminate {
+            return (0.0, self.get_value());
+        }
+        // A segment a third of the track wide slides in from the left and out the right
+        let segment = 1.0 / 3.0;
+        let phase = (seconds / INDETERMINATE_PERIOD).fract();
+        let start = phase * (1.0 + segment) - segment;
+        (start.max(0.0), (start + segment).min(1.0))
+    }
+
+    pub fn create_track_rectangle(&self) -> Rectangle {
+        Rectangle::new(self.x, self.y, self.width, self.height, self.track_color)
+            .with_border_radius(self.border_radius)
+    }
+
+    pub fn create_fill_rectangle(&self) -> Option<Rectangle> {
+        let (start, end) = self.fill_span();
+        if end <= start {
+            return None;
+        }
+        Some(Rectangle::new(
+            self.x + start * self.width,
+            self.y,
+            (end - start) * self.width,
+            self.height,
+            self.fill_color,
+        ).with_border_radius(self.border_radius))
+    }
+
+    pub fn create_text_primitive(&self) -> Option<Text> {
+        if !self.show_label || self.indeterminate {
+            return None;
+        }
+        let label = format!("{:.0}%", self.get_value() * 100.0);
+        let text_width = label.len() as f32 * self.font_size * 0.6;
+        let text_x = self.x + (self.width - text_width) / 2.0;
+        let text_y = self.y + (self.height / 2.0) + (self.font_size * 0.25);
+        Some(Text::new(text_x, text_y, label, self.text_color, self.font_size))
+    }
+}
+
+impl Widget for ProgressBarWidget {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        self.dirty = true;
+
+        let reactive_dirty = self.reactive_dirty.clone();
+        self.value.subscribe_fn(move |_| {
+            if let Ok(mut dirty) = reactive_dirty.write() {
+                *dirty = true;
+            }
+        });
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        if let Ok(mut reactive_dirty) = self.reactive_dirty.write() {
+            if *reactive_dirty {
+                *reactive_dirty = false;
+                self.dirty = true;
+            }
+        }
+
+        if self.indeterminate {
+            // Keep frames coming while the segment moves
+            self.dirty = true;
+            global_frame_scheduler().request_update();
+        }
+
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> EventResult {
+        EventResult::Ignored
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            }],
+            z_index: 1,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+/// Circular activity indicator: an arc rotating around a faint track
+pub struct SpinnerWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    size: f32,
+    thickness: f32,
+    // Revolutions per second
+    speed: f32,
+    active: Signal<bool>,
+    track_color: Color,
+    arc_color: Color,
+    overrides: StyleOverrides,
+    pub dirty: bool,
+    // Shared dirty flag that reactive signals can set
+    reactive_dirty: Arc<RwLock<bool>>,
+}
+
+impl SpinnerWidget {
+    pub fn new() -> Self {
+        let theme = current_theme();
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            size: 24.0,
+            thickness: 3.0,
+            speed: 1.0,
+            active: Signal::new(true),
+            track_color: theme.palette.border,
+            arc_color: theme.palette.primary,
+            overrides: StyleOverrides::default(),
+            dirty: true,
+            reactive_dirty: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_speed(mut self, revolutions_per_second: f32) -> Self {
+        self.speed = revolutions_per_second;
+        self
+    }
+
+    pub fn with_colors(mut self, track_color: Color, arc_color: Color) -> Self {
+        self.track_color = track_color;
+        self.arc_color = arc_color;
+        self.overrides.mark("colors");
+        self.dirty = true;
+        self
+    }
+
+    /// Take the track and arc colors from `theme` unless set through a builder
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        if !self.overrides.is_set("colors") {
+            self.track_color = theme.palette.border;
+            self.arc_color = theme.palette.primary;
+            self.dirty = true;
+        }
+    }
+
+    /// Only spin and draw while the signal is true
+    pub fn with_active_signal(mut self, active: Signal<bool>) -> Self {
+        self.active = active;
+        self.dirty = true;
+        self
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.get()
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.size, self.size)
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.size &&
+        y >= self.y && y <= self.y + self.size
+    }
+
+    fn arc_path(&self, start_angle: f64, sweep_angle: f64) -> BezPath {
+        let radius = ((self.size - self.thickness) / 2.0) as f64;
+        let center = Point::new((self.x + self.size / 2.0) as f64, (self.y + self.size / 2.0) as f64);
+        let arc = ArcShape::new(center, Vec2::new(radius, radius), start_angle, sweep_angle, 0.0);
+        BezPath::from_iter(arc.path_elements(0.1))
+    }
+
+    /// Start and sweep angles of the arc at `seconds` on the frame clock
+    fn arc_angles_at(&self, seconds: f64) -> (f64, f64) {
+        let rotation = (seconds * self.speed as f64).fract() * TAU;
+        let sweep = PI * (0.5 + 0.4 * (seconds * PI).sin().abs());
+        (rotation, sweep)
+    }
+
+    /// Track circle and the rotating arc, whose length breathes as it turns
+    pub fn create_path_primitives(&self) -> Vec<VectorPath> {
+        if !self.is_active() {
+            return Vec::new();
+        }
+        let (rotation, sweep) = self.arc_angles_at(animation_seconds() as f64);
+
+        let mut track = self.arc_path(0.0, TAU);
+        track.close_path();
+        vec![
+            VectorPath::new_stroke(track, self.track_color, self.thickness),
+            VectorPath::new_stroke(self.arc_path(rotation, sweep), self.arc_color, self.thickness),
+        ]
+    }
+}
+
+impl Default for SpinnerWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for SpinnerWidget {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        self.dirty = true;
+
+        let reactive_dirty = self.reactive_dirty.clone();
+        self.active.subscribe_fn(move |_| {
+            if let Ok(mut dirty) = reactive_dirty.write() {
+                *dirty = true;
+            }
+        });
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        if let Ok(mut reactive_dirty) = self.reactive_dirty.write() {
+            if *reactive_dirty {
+                *reactive_dirty = false;
+                self.dirty = true;
+            }
+        }
+
+        if self.is_active() {
+            self.dirty = true;
+            global_frame_scheduler().request_update();
+        }
+
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> EventResult {
+        EventResult::Ignored
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.size,
+                height: self.size,
+            }],
+            z_index: 1,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+// Convenience functions for creating progress indicators
+pub fn progress_bar(value: Signal<f32>) -> ProgressBarWidget {
+    ProgressBarWidget::new(value)
+}
+
+pub fn spinner() -> SpinnerWidget {
+    SpinnerWidget::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_is_clamped_to_the_track() {
+        let value = Signal::new(1.5);
+        let bar = ProgressBarWidget::new(value.clone()).with_size(200.0, 8.0);
+        assert_eq!(bar.get_value(), 1.0);
+        assert_eq!(bar.create_fill_rectangle().map(|fill| fill.width), Some(200.0));
+
+        value.set(-0.25);
+        assert_eq!(bar.get_value(), 0.0);
+        assert!(bar.create_fill_rectangle().is_none());
+
+        value.set(0.25);
+        assert_eq!(bar.create_fill_rectangle().map(|fill| fill.width), Some(50.0));
+    }
+
+    #[test]
+    fn indeterminate_segment_slides_with_the_frame_clock() {
+        let bar = ProgressBarWidget::new(Signal::new(0.0)).indeterminate();
+        // Entering from the left: nothing visible yet
+        assert_eq!(bar.fill_span_at(0.0), (0.0, 0.0));
+        // Half way through a period the segment sits in the middle third
+        let (start, end) = bar.fill_span_at(INDETERMINATE_PERIOD * 0.5);
+        assert!((start - 1.0 / 3.0).abs() < 1e-5 && (end - 2.0 / 3.0).abs() < 1e-5);
+        // The phase repeats every period
+        assert_eq!(bar.fill_span_at(INDETERMINATE_PERIOD * 1.5), bar.fill_span_at(INDETERMINATE_PERIOD * 0.5));
+    }
+
+    #[test]
+    fn spinner_arc_turns_and_breathes_over_time() {
+        let spinner = SpinnerWidget::new().with_speed(1.0);
+        assert_eq!(spinner.arc_angles_at(0.0), (0.0, PI * 0.5));
+
+        let (rotation, sweep) = spinner.arc_angles_at(0.25);
+        assert!((rotation - TAU / 4.0).abs() < 1e-9);
+        assert!((sweep - PI * (0.5 + 0.4 * (PI / 4.0).sin())).abs() < 1e-9);
+
+        // Longest arc at half a second, back to the shortest after a full one
+        assert!((spinner.arc_angles_at(0.5).1 - PI * 0.9).abs() < 1e-9);
+        assert!((spinner.arc_angles_at(1.0).1 - PI * 0.5).abs() < 1e-9);
+    }
+}
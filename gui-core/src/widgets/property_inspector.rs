@@ -1,27 +1,154 @@
 use crate::{Widget, WidgetId, EventResult, WidgetError, RenderData, DirtyRegion, WidgetUpdateContext};
 use crate::event::Event;
+use winit::event::ElementState;
 use crate::element::Element;
-use gui_reactive::Signal;
+use crate::theme::{current_theme, StyleOverrides, Theme};
+use gui_reactive::{Signal, global_frame_scheduler};
+use gui_reactive::signal::SignalId;
 use gui_render::primitives::{Rectangle, Text};
 use std::any::Any;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 use vello::peniko::Color;
 use super::container::{Background, BoxWidget, Padding, container};
-use super::layout::{column, row};
-use super::text::text;
-use super::interactive::{input, InputWidget};
+use super::layout::{column, row, CrossAxisAlignment};
+use super::text::{text, text_signal};
+use super::interactive::{input, InputWidget, checkbox, slider, CheckState};
 use super::dropdown::{dropdown, DropdownWidget, DropdownOption};
+use super::color_picker::color_swatch;
+use super::number_field::{number_field, NumberUnit};
 
 static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(5000);
 
-#[derive(Clone, Debug)]
+// Shows a new value in an existing row's widgets
+type RowRefresher = Box<dyn Fn(&PropertyValue) + Send + Sync>;
+
+// Seconds for a group to fully expand or collapse
+const GROUP_ANIMATION_SECONDS: f32 = 0.15;
+const RESET_GLYPH_WIDTH: f32 = 18.0;
+
+fn ease_in_out(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+// What each child of the inspector is, in build order
+enum InspectorSlot {
+    Header { state_key: String },
+    Row { key: String, label: String, height: f32 },
+}
+
+const AXIS_LABELS: [&str; 4] = ["X", "Y", "Z", "W"];
+
+fn axis_color(axis: usize) -> Color {
+    match axis {
+        0 => Color::rgba8(230, 95, 95, 255),
+        1 => Color::rgba8(135, 200, 95, 255),
+        2 => Color::rgba8(95, 145, 230, 255),
+        _ => Color::rgba8(170, 170, 175, 255),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum PropertyValue {
     Text(String),
     Number(f32),
     Color(Color),
     Boolean(bool),
     Select(String), // Selected value from dropdown
+    Vec2([f32; 2]),
+    Vec3([f32; 3]),
+    Vec4([f32; 4]),
+    Flags(u32), // Bit i set when flag i of `PropertyType::Flags` is on
+    Asset(Option<String>), // Path of the referenced asset
+    Mixed, // Differs between the objects being edited together
+}
+
+impl PropertyValue {
+    /// Vector value with as many components as `values` has (2 to 4)
+    pub fn from_components(values: &[f32]) -> Option<Self> {
+        match values {
+            [x, y] => Some(PropertyValue::Vec2([*x, *y])),
+            [x, y, z] => Some(PropertyValue::Vec3([*x, *y, *z])),
+            [x, y, z, w] => Some(PropertyValue::Vec4([*x, *y, *z, *w])),
+            _ => None,
+        }
+    }
+
+    pub fn components(&self) -> Option<Vec<f32>> {
+        match self {
+            PropertyValue::Vec2(v) => Some(v.to_vec()),
+            PropertyValue::Vec3(v) => Some(v.to_vec()),
+            PropertyValue::Vec4(v) => Some(v.to_vec()),
+            _ => None,
+        }
+    }
+
+    /// Human readable form, used by read-only rows
+    pub fn display_text(&self) -> String {
+        match self {
+            PropertyValue::Text(s) | PropertyValue::Select(s) => s.clone(),
+            PropertyValue::Number(n) => format!("{:.3}", n).trim_end_matches('0').trim_end_matches('.').to_string(),
+            PropertyValue::Color(c) => super::color_picker::color_to_hex(*c),
+            PropertyValue::Boolean(b) => if *b { "Yes".to_string() } else { "No".to_string() },
+            PropertyValue::Vec2(_) | PropertyValue::Vec3(_) | PropertyValue::Vec4(_) => {
+                let parts: Vec<String> = self.components().unwrap_or_default().iter()
+                    .map(|c| format!("{:.2}", c))
+                    .collect();
+                format!("({})", parts.join(", "))
+            },
+            PropertyValue::Flags(bits) => format!("{:#b}", bits),
+            PropertyValue::Asset(path) => path.clone().unwrap_or_else(|| "None".to_string()),
+            PropertyValue::Mixed => "—".to_string(),
+        }
+    }
+
+    /// The shared value, or `Mixed` when the values are not all equal
+    pub fn merge<'a>(values: impl IntoIterator<Item = &'a PropertyValue>) -> Option<Self> {
+        let mut values = values.into_iter();
+        let first = values.next()?;
+        if values.all(|value| value == first) {
+            Some(first.clone())
+        } else {
+            Some(PropertyValue::Mixed)
+        }
+    }
+
+    /// Per-component (vectors) or per-bit (flags) flags telling which parts differ
+    /// between `values`
+    fn mixed_parts(values: &[PropertyValue], count: usize) -> Vec<bool> {
+        (0..count).map(|index| {
+            let mut parts = values.iter().map(|value| match value {
+                PropertyValue::Flags(bits) => Some(((bits >> index) & 1) as f32),
+                _ => value.components().and_then(|components| components.get(index).copied()),
+            });
+            let first = parts.next().flatten();
+            parts.any(|part| part != first)
+        }).collect()
+    }
+
+    /// `self` with the parts not marked in `keep` taken from `edited`. Used to apply
+    /// an edit of some vector components or flags without touching the others.
+    fn with_parts_from(&self, edited: &PropertyValue, keep: &[bool]) -> PropertyValue {
+        match (self, edited) {
+            (PropertyValue::Flags(old), PropertyValue::Flags(new)) => {
+                let kept = keep.iter().enumerate()
+                    .filter(|(_, keep)| **keep)
+                    .fold(0u32, |mask, (index, _)| mask | (1 << index));
+                PropertyValue::Flags((old & kept) | (new & !kept))
+            },
+            _ => match (self.components(), edited.components()) {
+                (Some(old), Some(new)) if old.len() == new.len() => {
+                    let merged: Vec<f32> = old.iter().zip(&new).enumerate()
+                        .map(|(index, (old, new))| if keep.get(index).copied().unwrap_or(false) { *old } else { *new })
+                        .collect();
+                    PropertyValue::from_components(&merged).unwrap_or_else(|| edited.clone())
+                },
+                _ => edited.clone(),
+            },
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -30,6 +157,12 @@ pub struct PropertyDefinition {
     pub label: String,
     pub value: PropertyValue,
     pub property_type: PropertyType,
+    pub unit: NumberUnit,
+    // Two-way binding: external sets refresh the row, edits are written back
+    pub binding: Option<Signal<PropertyValue>>,
+    // Value restored by "reset to default"; the inspector fills in the initial
+    // value when none was given
+    pub default: Option<PropertyValue>,
 }
 
 #[derive(Clone, Debug)]
@@ -39,6 +172,13 @@ pub enum PropertyType {
     ColorPicker,
     Checkbox,
     Dropdown(Vec<DropdownOption>),
+    Vec2,
+    Vec3,
+    Vec4,
+    Range { min: f32, max: f32, step: f32 },
+    Flags(Vec<String>),
+    ReadOnly,
+    Asset(Vec<String>), // Paths the picker offers
 }
 
 impl PropertyDefinition {
@@ -48,6 +188,9 @@ impl PropertyDefinition {
             label: label.into(),
             value: PropertyValue::Text(value.into()),
             property_type: PropertyType::TextInput,
+            unit: NumberUnit::None,
+            binding: None,
+            default: None,
         }
     }
     
@@ -57,6 +200,9 @@ impl PropertyDefinition {
             label: label.into(),
             value: PropertyValue::Number(value),
             property_type: PropertyType::NumberInput,
+            unit: NumberUnit::None,
+            binding: None,
+            default: None,
         }
     }
     
@@ -66,6 +212,9 @@ impl PropertyDefinition {
             label: label.into(),
             value: PropertyValue::Select(selected.into()),
             property_type: PropertyType::Dropdown(options),
+            unit: NumberUnit::None,
+            binding: None,
+            default: None,
         }
     }
     
@@ -75,6 +224,9 @@ impl PropertyDefinition {
             label: label.into(),
             value: PropertyValue::Color(value),
             property_type: PropertyType::ColorPicker,
+            unit: NumberUnit::None,
+            binding: None,
+            default: None,
         }
     }
     
@@ -84,13 +236,137 @@ impl PropertyDefinition {
             label: label.into(),
             value: PropertyValue::Boolean(value),
             property_type: PropertyType::Checkbox,
+            unit: NumberUnit::None,
+            binding: None,
+            default: None,
+        }
+    }
+    
+    pub fn vec2(key: impl Into<String>, label: impl Into<String>, value: [f32; 2]) -> Self {
+        Self {
+            key: key.into(),
+            label: label.into(),
+            value: PropertyValue::Vec2(value),
+            property_type: PropertyType::Vec2,
+            unit: NumberUnit::None,
+            binding: None,
+            default: None,
         }
     }
+    
+    pub fn vec3(key: impl Into<String>, label: impl Into<String>, value: [f32; 3]) -> Self {
+        Self {
+            key: key.into(),
+            label: label.into(),
+            value: PropertyValue::Vec3(value),
+            property_type: PropertyType::Vec3,
+            unit: NumberUnit::None,
+            binding: None,
+            default: None,
+        }
+    }
+    
+    pub fn vec4(key: impl Into<String>, label: impl Into<String>, value: [f32; 4]) -> Self {
+        Self {
+            key: key.into(),
+            label: label.into(),
+            value: PropertyValue::Vec4(value),
+            property_type: PropertyType::Vec4,
+            unit: NumberUnit::None,
+            binding: None,
+            default: None,
+        }
+    }
+    
+    /// Number edited with a slider between `min` and `max`; `with_step` snaps it
+    pub fn range(key: impl Into<String>, label: impl Into<String>, value: f32, min: f32, max: f32) -> Self {
+        Self {
+            key: key.into(),
+            label: label.into(),
+            value: PropertyValue::Number(value.clamp(min.min(max), max.max(min))),
+            property_type: PropertyType::Range { min: min.min(max), max: max.max(min), step: 0.0 },
+            unit: NumberUnit::None,
+            binding: None,
+            default: None,
+        }
+    }
+    
+    /// Set of named flags shown as checkboxes; bit i of `bits` is flag i
+    pub fn flags(key: impl Into<String>, label: impl Into<String>, names: Vec<String>, bits: u32) -> Self {
+        Self {
+            key: key.into(),
+            label: label.into(),
+            value: PropertyValue::Flags(bits),
+            property_type: PropertyType::Flags(names),
+            unit: NumberUnit::None,
+            binding: None,
+            default: None,
+        }
+    }
+    
+    pub fn read_only(key: impl Into<String>, label: impl Into<String>, value: PropertyValue) -> Self {
+        Self {
+            key: key.into(),
+            label: label.into(),
+            value,
+            property_type: PropertyType::ReadOnly,
+            unit: NumberUnit::None,
+            binding: None,
+            default: None,
+        }
+    }
+    
+    pub fn asset(key: impl Into<String>, label: impl Into<String>, assets: Vec<String>, selected: Option<String>) -> Self {
+        Self {
+            key: key.into(),
+            label: label.into(),
+            value: PropertyValue::Asset(selected),
+            property_type: PropertyType::Asset(assets),
+            unit: NumberUnit::None,
+            binding: None,
+            default: None,
+        }
+    }
+    
+    /// Unit displayed next to a number property, e.g. px, deg or %
+    pub fn with_unit(mut self, unit: NumberUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+    
+    /// Keep the property in sync with `signal` in both directions. Values set on
+    /// the signal elsewhere refresh the row in place; edits are written back to it.
+    pub fn with_binding(mut self, signal: Signal<PropertyValue>) -> Self {
+        self.value = signal.get();
+        self.binding = Some(signal);
+        self
+    }
+    
+    /// Value the reset button restores. Defaults to the value the property had
+    /// when it was handed to the inspector.
+    pub fn with_default(mut self, value: PropertyValue) -> Self {
+        self.default = Some(value);
+        self
+    }
+    
+    pub fn is_modified(&self) -> bool {
+        self.default.as_ref().is_some_and(|default| *default != self.value)
+    }
+    
+    /// Snap increment for range properties
+    pub fn with_step(mut self, step: f32) -> Self {
+        if let PropertyType::Range { step: ref mut current, .. } = self.property_type {
+            *current = step;
+        }
+        self
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct PropertyGroup {
     pub title: String,
+    // Identifies the group for remembering its expanded state; the title is used when unset
+    pub key: Option<String>,
     pub properties: Vec<PropertyDefinition>,
     pub expanded: bool,
 }
@@ -99,11 +375,21 @@ impl PropertyGroup {
     pub fn new(title: impl Into<String>) -> Self {
         Self {
             title: title.into(),
+            key: None,
             properties: Vec::new(),
             expanded: true,
         }
     }
     
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+    
+    pub fn state_key(&self) -> &str {
+        self.key.as_deref().unwrap_or(&self.title)
+    }
+    
     pub fn with_property(mut self, property: PropertyDefinition) -> Self {
         self.properties.push(property);
         self
@@ -120,6 +406,79 @@ impl PropertyGroup {
     }
 }
 
+/// One of several objects edited together, identified by `id` in change events
+#[derive(Clone, Debug)]
+pub struct PropertyTarget {
+    pub id: String,
+    pub groups: Vec<PropertyGroup>,
+}
+
+impl PropertyTarget {
+    pub fn new(id: impl Into<String>, groups: Vec<PropertyGroup>) -> Self {
+        Self {
+            id: id.into(),
+            groups,
+        }
+    }
+
+    pub fn find_property(&self, key: &str) -> Option<&PropertyDefinition> {
+        self.groups.iter()
+            .flat_map(|group| group.properties.iter())
+            .find(|property| property.key == key)
+    }
+
+    fn find_property_mut(&mut self, key: &str) -> Option<&mut PropertyDefinition> {
+        self.groups.iter_mut()
+            .flat_map(|group| group.properties.iter_mut())
+            .find(|property| property.key == key)
+    }
+}
+
+/// New value of a property on one target after an edit
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetChange {
+    pub target: String,
+    pub key: String,
+    pub value: PropertyValue,
+}
+
+/// Groups holding the properties every target has, by key and type, in the first
+/// target's order. Values that differ become `PropertyValue::Mixed`.
+pub fn merge_targets(targets: &[PropertyTarget]) -> Vec<PropertyGroup> {
+    let Some((first, rest)) = targets.split_first() else {
+        return Vec::new();
+    };
+    let mut groups = Vec::new();
+    for group in &first.groups {
+        let mut merged = PropertyGroup { properties: Vec::new(), ..group.clone() };
+        for property in &group.properties {
+            let others: Option<Vec<&PropertyDefinition>> = rest.iter()
+                .map(|target| target.find_property(&property.key).filter(|other| {
+                    std::mem::discriminant(&other.property_type) == std::mem::discriminant(&property.property_type)
+                }))
+                .collect();
+            let Some(others) = others else {
+                continue;
+            };
+            let mut shared = property.clone();
+            if !others.is_empty() {
+                // Bindings and defaults belong to single objects
+                shared.binding = None;
+                shared.default = property.default.clone()
+                    .filter(|default| others.iter().all(|other| other.default.as_ref() == Some(default)));
+                shared.value = PropertyValue::merge(
+                    std::iter::once(&property.value).chain(others.iter().map(|other| &other.value))
+                ).unwrap_or(PropertyValue::Mixed);
+            }
+            merged.properties.push(shared);
+        }
+        if !merged.properties.is_empty() {
+            groups.push(merged);
+        }
+    }
+    groups
+}
+
 pub struct PropertyInspectorWidget {
     id: WidgetId,
     x: f32,
@@ -132,18 +491,53 @@ pub struct PropertyInspectorWidget {
     border_color: Color,
     text_color: Color,
     header_text_color: Color,
+    // Read-only values
+    muted_text_color: Color,
     font_size: f32,
     header_font_size: f32,
     padding: Padding,
     row_height: f32,
     header_height: f32,
     on_property_change: Option<Box<dyn Fn(&str, &PropertyValue) + Send + Sync>>,
+    // Objects edited together; empty when editing `groups` directly
+    targets: Vec<PropertyTarget>,
+    on_targets_change: Option<Box<dyn Fn(&[TargetChange]) + Send + Sync>>,
+    // Vector components and flags of each row still showing "mixed"
+    mixed_masks: Mutex<HashMap<String, Arc<Mutex<Vec<bool>>>>>,
+    // Edits made through row widgets, applied on the next update
+    pending_changes: Arc<Mutex<Vec<(String, PropertyValue)>>>,
+    // Link-proportions toggles of vector rows, kept across rebuilds
+    link_states: Mutex<HashMap<String, Signal<bool>>>,
+    // Top of each child relative to the content area, and whether it is shown
+    child_offsets: Vec<f32>,
+    child_visible: Vec<bool>,
+    slots: Vec<InspectorSlot>,
+    // Expanded state by group key, remembered across rebuilds and group changes
+    group_states: HashMap<String, bool>,
+    // Animated 0..1 openness of each group
+    group_openness: HashMap<String, f32>,
+    last_animation_time: Option<Duration>,
+    show_search: bool,
+    search: Signal<String>,
+    search_input: InputWidget,
+    applied_search: String,
+    accent_color: Color,
+    overrides: StyleOverrides,
+    // Set when the groups changed and the rows have to be recreated
+    needs_rebuild: bool,
+    // Values set on bound signals, applied on the next update
+    external_changes: Arc<Mutex<Vec<(String, PropertyValue)>>>,
+    bound_signals: HashSet<SignalId>,
+    row_refreshers: HashMap<String, RowRefresher>,
+    writing_back: Arc<AtomicBool>,
     pub dirty: bool,
     pub children: Vec<Element>,
 }
 
 impl PropertyInspectorWidget {
     pub fn new() -> Self {
+        let search = Signal::new(String::new());
+        let theme = current_theme();
         Self {
             id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
             x: 0.0,
@@ -151,17 +545,43 @@ impl PropertyInspectorWidget {
             width: 300.0,
             height: 600.0,
             groups: Vec::new(),
-            background: Background::Color(Color::rgba8(45, 45, 50, 255)),
-            header_background: Background::Color(Color::rgba8(35, 35, 40, 255)),
-            border_color: Color::rgba8(60, 60, 65, 255),
-            text_color: Color::rgba8(220, 220, 220, 255),
-            header_text_color: Color::rgba8(255, 255, 255, 255),
+            background: Background::Color(theme.palette.surface),
+            header_background: Background::Color(theme.palette.background),
+            border_color: theme.palette.border,
+            text_color: theme.palette.text,
+            header_text_color: theme.palette.text,
+            muted_text_color: theme.palette.text_muted,
             font_size: 12.0,
             header_font_size: 14.0,
             padding: Padding::all(8.0),
             row_height: 32.0,
             header_height: 28.0,
             on_property_change: None,
+            targets: Vec::new(),
+            on_targets_change: None,
+            mixed_masks: Mutex::new(HashMap::new()),
+            pending_changes: Arc::new(Mutex::new(Vec::new())),
+            link_states: Mutex::new(HashMap::new()),
+            child_offsets: Vec::new(),
+            child_visible: Vec::new(),
+            slots: Vec::new(),
+            group_states: HashMap::new(),
+            group_openness: HashMap::new(),
+            last_animation_time: None,
+            show_search: true,
+            search: search.clone(),
+            search_input: input()
+                .with_size(300.0 - 16.0, 28.0)
+                .with_placeholder("Search properties...")
+                .with_signal(search),
+            applied_search: String::new(),
+            accent_color: theme.palette.primary,
+            overrides: StyleOverrides::default(),
+            needs_rebuild: true,
+            external_changes: Arc::new(Mutex::new(Vec::new())),
+            bound_signals: HashSet::new(),
+            row_refreshers: HashMap::new(),
+            writing_back: Arc::new(AtomicBool::new(false)),
             dirty: true,
             children: Vec::new(),
         }
@@ -170,22 +590,129 @@ impl PropertyInspectorWidget {
     pub fn with_size(mut self, width: f32, height: f32) -> Self {
         self.width = width;
         self.height = height;
+        self.needs_rebuild = true;
         self.dirty = true;
         self
     }
 
     pub fn with_groups(mut self, groups: Vec<PropertyGroup>) -> Self {
-        self.groups = groups;
-        self.dirty = true;
+        self.set_groups(groups);
         self
     }
 
     pub fn add_group(mut self, group: PropertyGroup) -> Self {
-        self.groups.push(group);
+        let mut groups = std::mem::take(&mut self.groups);
+        groups.push(group);
+        self.set_groups(groups);
+        self
+    }
+
+    /// Show a search box above the groups (on by default)
+    pub fn with_search_box(mut self, show: bool) -> Self {
+        self.show_search = show;
+        self.needs_rebuild = true;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_accent_color(mut self, color: Color) -> Self {
+        self.accent_color = color;
+        self.overrides.mark("accent_color");
+        self.dirty = true;
+        self
+    }
+
+    /// Take every color not set through a builder from `theme`. Rows are
+    /// rebuilt so their editors pick the new colors up.
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let palette = &theme.palette;
+        self.background = Background::Color(palette.surface);
+        self.header_background = Background::Color(palette.background);
+        self.border_color = palette.border;
+        self.text_color = palette.text;
+        self.header_text_color = palette.text;
+        self.muted_text_color = palette.text_muted;
+        if !self.overrides.is_set("accent_color") {
+            self.accent_color = palette.primary;
+        }
+        self.search_input.apply_theme(theme);
+        self.needs_rebuild = true;
+        self.dirty = true;
+    }
+
+    /// Replace the displayed groups, e.g. when another object gets selected.
+    /// Group expanded state is kept for groups with the same key or title.
+    pub fn set_groups(&mut self, groups: Vec<PropertyGroup>) {
+        self.groups = groups;
+        for property in self.groups.iter_mut().flat_map(|group| group.properties.iter_mut()) {
+            if property.default.is_none() && property.value != PropertyValue::Mixed {
+                property.default = Some(property.value.clone());
+            }
+        }
+        self.needs_rebuild = true;
         self.dirty = true;
+    }
+
+    /// Edit several objects at once. Only properties all of them have are shown;
+    /// values that differ show as mixed, and an edit applies to every target.
+    pub fn with_targets(mut self, targets: Vec<PropertyTarget>) -> Self {
+        self.set_targets(targets);
+        self
+    }
+
+    pub fn set_targets(&mut self, targets: Vec<PropertyTarget>) {
+        let groups = merge_targets(&targets);
+        self.targets = targets;
+        self.set_groups(groups);
+    }
+
+    pub fn get_targets(&self) -> &[PropertyTarget] {
+        &self.targets
+    }
+
+    /// Called once per edit with the new value of the property on each target
+    /// that changed
+    pub fn on_targets_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&[TargetChange]) + Send + Sync + 'static,
+    {
+        self.on_targets_change = Some(Box::new(callback));
         self
     }
 
+    pub fn get_search_signal(&self) -> Signal<String> {
+        self.search.clone()
+    }
+
+    pub fn is_group_expanded(&self, key: &str) -> bool {
+        self.group_states.get(key).copied()
+            .or_else(|| self.groups.iter().find(|group| group.state_key() == key).map(|group| group.expanded))
+            .unwrap_or(true)
+    }
+
+    /// Expand or collapse a group by key (or title), animating the change
+    pub fn set_group_expanded(&mut self, key: &str, expanded: bool) {
+        if self.is_group_expanded(key) == expanded {
+            return;
+        }
+        self.group_states.insert(key.to_string(), expanded);
+        self.last_animation_time = Some(global_frame_scheduler().frame_timestamp());
+        global_frame_scheduler().request_update();
+        self.dirty = true;
+    }
+
+    pub fn toggle_group(&mut self, key: &str) {
+        let expanded = self.is_group_expanded(key);
+        self.set_group_expanded(key, !expanded);
+    }
+
+    /// Restore a property's default value, updating its row in place
+    pub fn reset_property(&mut self, key: &str) {
+        if let Some(default) = self.find_property(key).and_then(|property| property.default.clone()) {
+            self.update_property(key, default);
+        }
+    }
+
     pub fn on_property_change<F>(mut self, callback: F) -> Self
     where
         F: Fn(&str, &PropertyValue) + Send + Sync + 'static,
@@ -230,30 +757,191 @@ impl PropertyInspectorWidget {
         self.row_height
     }
 
+    pub fn get_child_offsets(&self) -> &[f32] {
+        &self.child_offsets
+    }
+
+    /// Height of the row a property occupies; flag sets grow one line per flag
+    pub fn property_height(&self, property: &PropertyDefinition) -> f32 {
+        match &property.property_type {
+            PropertyType::Flags(names) => self.row_height.max(names.len() as f32 * self.flag_line_height()),
+            _ => self.row_height,
+        }
+    }
+
+    fn flag_line_height(&self) -> f32 {
+        self.row_height - 8.0
+    }
+
+    fn link_state(&self, key: &str) -> Signal<bool> {
+        match self.link_states.lock() {
+            Ok(mut states) => states.entry(key.to_string()).or_insert_with(|| Signal::new(false)).clone(),
+            Err(_) => Signal::new(false),
+        }
+    }
+
+    /// Set a property (on every target when editing several) and show it
     pub fn update_property(&mut self, key: &str, value: PropertyValue) {
+        if let Some(shown) = self.commit_change(key, value, false) {
+            self.refresh_row(key, &shown);
+        }
+    }
+
+    // A row widget already shows the edited value, so rebuilding it would only
+    // throw away its interaction state (e.g. a number field mid-scrub)
+    fn apply_row_change(&mut self, key: &str, value: PropertyValue) {
+        self.commit_change(key, value, true);
+    }
+
+    // Store an edit and notify listeners, returning the value the row now stands for.
+    // `partial` keeps the still-mixed vector components and flags of each target.
+    fn commit_change(&mut self, key: &str, value: PropertyValue, partial: bool) -> Option<PropertyValue> {
+        let shown = if self.targets.is_empty() {
+            value.clone()
+        } else {
+            self.apply_to_targets(key, &value, partial)?
+        };
+        if !self.set_property_value(key, shown.clone()) {
+            return None;
+        }
+        if let Some(ref callback) = self.on_property_change {
+            callback(key, &value);
+        }
+        self.write_back(key, &shown);
+        Some(shown)
+    }
+
+    fn apply_to_targets(&mut self, key: &str, value: &PropertyValue, partial: bool) -> Option<PropertyValue> {
+        let keep = if partial { self.mixed_mask(key) } else { Vec::new() };
+        let mut changes = Vec::new();
+        for target in &mut self.targets {
+            let Some(property) = target.find_property_mut(key) else {
+                continue;
+            };
+            let new_value = property.value.with_parts_from(value, &keep);
+            if new_value != property.value {
+                property.value = new_value.clone();
+                changes.push(TargetChange {
+                    target: target.id.clone(),
+                    key: key.to_string(),
+                    value: new_value,
+                });
+            }
+        }
+        if !changes.is_empty() {
+            if let Some(ref callback) = self.on_targets_change {
+                callback(&changes);
+            }
+        }
+        PropertyValue::merge(self.targets.iter().filter_map(|target| target.find_property(key)).map(|property| &property.value))
+    }
+
+    fn mixed_mask(&self, key: &str) -> Vec<bool> {
+        self.mixed_masks.lock().ok()
+            .and_then(|masks| masks.get(key).cloned())
+            .and_then(|mask| mask.lock().ok().map(|mask| mask.clone()))
+            .unwrap_or_default()
+    }
+
+    // Track which parts of a row still show "mixed"; edits clear them
+    fn register_mixed_mask(&self, key: &str, mask: Vec<bool>) -> Arc<Mutex<Vec<bool>>> {
+        let mask = Arc::new(Mutex::new(mask));
+        if let Ok(mut masks) = self.mixed_masks.lock() {
+            masks.insert(key.to_string(), mask.clone());
+        }
+        mask
+    }
+
+    // Values of a property on each target
+    fn target_values(&self, key: &str) -> Vec<PropertyValue> {
+        self.targets.iter()
+            .filter_map(|target| target.find_property(key))
+            .map(|property| property.value.clone())
+            .collect()
+    }
+
+    // A bound signal changed elsewhere: refresh the row in place
+    fn apply_external_change(&mut self, key: &str, value: PropertyValue) {
+        if self.set_property_value(key, value.clone()) {
+            self.refresh_row(key, &value);
+        }
+    }
+
+    fn refresh_row(&mut self, key: &str, value: &PropertyValue) {
+        // Rows show "mixed" per part, which only building them works out
+        match self.row_refreshers.get(key) {
+            Some(refresh) if *value != PropertyValue::Mixed => refresh(value),
+            _ => self.needs_rebuild = true,
+        }
+        self.dirty = true;
+    }
+
+    fn find_property(&self, key: &str) -> Option<&PropertyDefinition> {
+        self.groups.iter()
+            .flat_map(|group| group.properties.iter())
+            .find(|property| property.key == key)
+    }
+
+    fn set_property_value(&mut self, key: &str, value: PropertyValue) -> bool {
         for group in &mut self.groups {
             for property in &mut group.properties {
                 if property.key == key {
-                    property.value = value.clone();
-                    if let Some(ref callback) = self.on_property_change {
-                        callback(key, &value);
-                    }
-                    self.dirty = true;
-                    return;
+                    property.value = value;
+                    return true;
                 }
             }
         }
+        false
+    }
+
+    fn write_back(&self, key: &str, value: &PropertyValue) {
+        let binding = self.find_property(key).and_then(|property| property.binding.clone());
+        if let Some(binding) = binding {
+            // Our own subscription would otherwise echo the value straight back
+            self.writing_back.store(true, Ordering::SeqCst);
+            binding.set(value.clone());
+            self.writing_back.store(false, Ordering::SeqCst);
+        }
+    }
+
+    // Subscribe once to every bound signal; changes are applied on the next update
+    fn subscribe_bindings(&mut self) {
+        for property in self.groups.iter().flat_map(|group| group.properties.iter()) {
+            let Some(binding) = &property.binding else {
+                continue;
+            };
+            if !self.bound_signals.insert(binding.id()) {
+                continue;
+            }
+            let key = property.key.clone();
+            let external_changes = self.external_changes.clone();
+            let writing_back = self.writing_back.clone();
+            binding.subscribe_fn(move |value| {
+                if writing_back.load(Ordering::SeqCst) {
+                    return;
+                }
+                if let Ok(mut changes) = external_changes.lock() {
+                    changes.push((key.clone(), value.clone()));
+                }
+            });
+        }
     }
 
     pub fn rebuild_ui(&mut self) {
+        self.subscribe_bindings();
         self.children.clear();
-        let mut current_y = self.y + self.padding.top;
+        self.slots.clear();
+        self.row_refreshers.clear();
+        for group in &self.groups {
+            self.group_states.entry(group.state_key().to_string()).or_insert(group.expanded);
+        }
 
+        // Rows of collapsed groups are built too, so expanding only has to show them
         for group in &self.groups {
-            // Create group header
             let header_container = container()
                 .with_size(self.width - self.padding.left - self.padding.right, self.header_height)
                 .with_background(self.header_background.clone())
+                .with_padding(Padding::only(0.0, 0.0, 0.0, 20.0))
                 .with_child(
                     text(&group.title)
                         .with_color(self.header_text_color)
@@ -262,99 +950,633 @@ impl PropertyInspectorWidget {
                 );
 
             self.children.push(header_container.into_container_element());
-            current_y += self.header_height + 4.0;
-
-            if group.expanded {
-                // Create property rows
-                for property in &group.properties {
-                    let row_element = self.create_property_row(property, current_y);
-                    self.children.push(row_element);
-                    current_y += self.row_height + 4.0;
+            self.slots.push(InspectorSlot::Header { state_key: group.state_key().to_string() });
+
+            for property in &group.properties {
+                let (row_element, refresh) = self.create_property_row(property);
+                self.children.push(row_element);
+                self.row_refreshers.insert(property.key.clone(), refresh);
+                self.slots.push(InspectorSlot::Row {
+                    key: property.key.clone(),
+                    label: property.label.clone(),
+                    height: self.property_height(property),
+                });
+            }
+        }
+        self.layout_rows();
+    }
+
+    fn search_bar_height(&self) -> f32 {
+        if self.show_search {
+            self.row_height - 4.0 + 8.0
+        } else {
+            0.0
+        }
+    }
+
+    fn search_query(&self) -> String {
+        self.applied_search.trim().to_lowercase()
+    }
+
+    fn slot_matches(slot: &InspectorSlot, query: &str) -> bool {
+        match slot {
+            InspectorSlot::Header { .. } => false,
+            InspectorSlot::Row { key, label, .. } => {
+                label.to_lowercase().contains(query) || key.to_lowercase().contains(query)
+            }
+        }
+    }
+
+    // Eased openness of a group, 0 when collapsed and 1 when expanded
+    fn eased_openness(&self, state_key: &str) -> f32 {
+        let progress = self.group_openness.get(state_key).copied()
+            .unwrap_or(if self.is_group_expanded(state_key) { 1.0 } else { 0.0 });
+        ease_in_out(progress)
+    }
+
+    /// Place headers and rows below the search bar. While searching only matching
+    /// rows (and groups that have some) are shown, with every group open. A group
+    /// that is animating reveals as many rows as fit its current height.
+    fn layout_rows(&mut self) {
+        let query = self.search_query();
+        let mut offsets = Vec::with_capacity(self.slots.len());
+        let mut visible = Vec::with_capacity(self.slots.len());
+        let mut y = self.search_bar_height();
+
+        let mut start = 0;
+        while start < self.slots.len() {
+            let end = self.slots[start + 1..].iter()
+                .position(|slot| matches!(slot, InspectorSlot::Header { .. }))
+                .map_or(self.slots.len(), |position| start + 1 + position);
+
+            let title_matches = match &self.slots[start] {
+                InspectorSlot::Header { state_key } => self.groups.iter()
+                    .find(|group| group.state_key() == state_key)
+                    .is_some_and(|group| group.title.to_lowercase().contains(&query)),
+                InspectorSlot::Row { .. } => false,
+            };
+            let matches: Vec<bool> = self.slots[start + 1..end].iter()
+                .map(|slot| query.is_empty() || title_matches || Self::slot_matches(slot, &query))
+                .collect();
+
+            if !matches.contains(&true) && !query.is_empty() && !title_matches {
+                offsets.extend(std::iter::repeat_n(y, end - start));
+                visible.extend(std::iter::repeat_n(false, end - start));
+                start = end;
+                continue;
+            }
+
+            let openness = match &self.slots[start] {
+                InspectorSlot::Header { state_key } if query.is_empty() => self.eased_openness(state_key),
+                _ => 1.0,
+            };
+            offsets.push(y);
+            visible.push(true);
+            y += self.header_height + 4.0;
+
+            let full_height: f32 = self.slots[start + 1..end].iter().zip(&matches)
+                .filter(|(_, matched)| **matched)
+                .map(|(slot, _)| match slot {
+                    InspectorSlot::Row { height, .. } => height + 4.0,
+                    InspectorSlot::Header { .. } => 0.0,
+                })
+                .sum();
+            let shown_height = full_height * openness;
+
+            let mut row_y = 0.0;
+            for (slot, matched) in self.slots[start + 1..end].iter().zip(&matches) {
+                let height = match slot {
+                    InspectorSlot::Row { height, .. } => *height,
+                    InspectorSlot::Header { .. } => 0.0,
+                };
+                offsets.push(y + row_y);
+                visible.push(*matched && openness > 0.0 && row_y + height <= shown_height + 0.5);
+                if *matched {
+                    row_y += height + 4.0;
                 }
             }
+            y += shown_height + 8.0; // Group spacing
+            start = end;
+        }
+
+        self.child_offsets = offsets;
+        self.child_visible = visible;
+        self.dirty = true;
+    }
+
+    // Step group animations towards their expanded state; true while any is moving
+    fn animate_groups(&mut self) -> bool {
+        let now = global_frame_scheduler().frame_timestamp();
+        let elapsed = self.last_animation_time
+            .map_or(0.0, |last| now.saturating_sub(last).as_secs_f32());
+        let step = elapsed / GROUP_ANIMATION_SECONDS;
+
+        let mut animating = false;
+        for (key, expanded) in &self.group_states {
+            let target = if *expanded { 1.0 } else { 0.0 };
+            let progress = self.group_openness.entry(key.clone()).or_insert(target);
+            if *progress != target {
+                *progress = if target > *progress {
+                    (*progress + step).min(target)
+                } else {
+                    (*progress - step).max(target)
+                };
+                animating |= *progress != target;
+            }
+        }
+
+        self.last_animation_time = if animating { Some(now) } else { None };
+        animating
+    }
+
+    pub fn visible_children(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().enumerate()
+            .filter(|(index, _)| self.child_visible.get(*index).copied().unwrap_or(true))
+            .map(|(_, child)| child)
+    }
+
+    /// The search box, when shown
+    pub fn get_search_input(&self) -> Option<&InputWidget> {
+        self.show_search.then_some(&self.search_input)
+    }
 
-            current_y += 8.0; // Group spacing
+    fn content_origin(&self) -> (f32, f32) {
+        (self.x + self.padding.left, self.y + self.padding.top)
+    }
+
+    fn label_width(&self) -> f32 {
+        (self.width - self.padding.left - self.padding.right) * 0.4
+    }
+
+    // Visible slot under a point
+    fn slot_at(&self, x: f32, y: f32) -> Option<usize> {
+        let (content_x, content_y) = self.content_origin();
+        if x < content_x || x > self.x + self.width - self.padding.right {
+            return None;
         }
+        self.slots.iter().enumerate().find_map(|(index, slot)| {
+            if !self.child_visible.get(index).copied().unwrap_or(false) {
+                return None;
+            }
+            let top = content_y + self.child_offsets.get(index).copied().unwrap_or(0.0);
+            let height = match slot {
+                InspectorSlot::Header { .. } => self.header_height,
+                InspectorSlot::Row { height, .. } => *height,
+            };
+            (y >= top && y <= top + height).then_some(index)
+        })
     }
 
-    fn create_property_row(&self, property: &PropertyDefinition, y: f32) -> Element {
+    fn is_on_reset_glyph(&self, x: f32) -> bool {
+        let glyph_x = self.content_origin().0 + self.label_width() - RESET_GLYPH_WIDTH;
+        x >= glyph_x && x <= glyph_x + RESET_GLYPH_WIDTH
+    }
+
+    /// Collapse arrows, search match highlights and the modified marker with its
+    /// reset button for each visible row
+    pub fn create_decoration_rectangles(&self) -> Vec<Rectangle> {
+        let (content_x, content_y) = self.content_origin();
+        let query = self.search_query();
+        let char_width = self.font_size * 0.6;
+        let mut rectangles = Vec::new();
+
+        for (index, slot) in self.slots.iter().enumerate() {
+            let InspectorSlot::Row { key, label, height } = slot else {
+                continue;
+            };
+            if !self.child_visible.get(index).copied().unwrap_or(false) {
+                continue;
+            }
+            let top = content_y + self.child_offsets.get(index).copied().unwrap_or(0.0);
+
+            if !query.is_empty() {
+                if let Some(start) = label.to_lowercase().find(&query) {
+                    let start_chars = label[..start].chars().count() as f32;
+                    let match_chars = query.chars().count() as f32;
+                    rectangles.push(
+                        Rectangle::new(
+                            content_x + start_chars * char_width,
+                            top + self.row_height / 2.0 - self.font_size * 0.7,
+                            match_chars * char_width,
+                            self.font_size * 1.4,
+                            Color::rgba8(self.accent_color.r, self.accent_color.g, self.accent_color.b, 90),
+                        ).with_border_radius(2.0)
+                    );
+                }
+            }
+
+            if self.find_property(key).is_some_and(|property| property.is_modified()) {
+                rectangles.push(
+                    Rectangle::new(content_x - 5.0, top + 4.0, 2.0, height - 8.0, self.accent_color)
+                        .with_border_radius(1.0)
+                );
+            }
+        }
+        rectangles
+    }
+
+    pub fn create_decoration_texts(&self) -> Vec<Text> {
+        let (content_x, content_y) = self.content_origin();
+        let mut texts = Vec::new();
+
+        for (index, slot) in self.slots.iter().enumerate() {
+            if !self.child_visible.get(index).copied().unwrap_or(false) {
+                continue;
+            }
+            let top = content_y + self.child_offsets.get(index).copied().unwrap_or(0.0);
+            match slot {
+                InspectorSlot::Header { state_key } => {
+                    let arrow = if self.is_group_expanded(state_key) || !self.search_query().is_empty() { "▾" } else { "▸" };
+                    texts.push(Text::new(
+                        content_x + 6.0,
+                        top + self.header_height / 2.0 + self.header_font_size * 0.25,
+                        arrow.to_string(),
+                        self.header_text_color,
+                        self.header_font_size,
+                    ));
+                }
+                InspectorSlot::Row { key, .. } => {
+                    if self.find_property(key).is_some_and(|property| property.is_modified()) {
+                        texts.push(Text::new(
+                            content_x + self.label_width() - RESET_GLYPH_WIDTH + 3.0,
+                            top + self.row_height / 2.0 + self.font_size * 0.25,
+                            "↺".to_string(),
+                            self.accent_color,
+                            self.font_size,
+                        ));
+                    }
+                }
+            }
+        }
+        texts
+    }
+
+    // Queue an edit made through a row widget
+    fn change_sink(&self, key: &str) -> impl Fn(PropertyValue) + Clone + Send + Sync + 'static {
+        let key = key.to_string();
+        let pending_changes = self.pending_changes.clone();
+        move |value| {
+            if let Ok(mut pending) = pending_changes.lock() {
+                pending.push((key.clone(), value));
+            }
+        }
+    }
+
+    /// Build the row for a property along with a callback that shows a new value
+    /// in the row's widgets without recreating them
+    fn create_property_row(&self, property: &PropertyDefinition) -> (Element, RowRefresher) {
         let label_width = (self.width - self.padding.left - self.padding.right) * 0.4;
         let input_width = (self.width - self.padding.left - self.padding.right) * 0.6 - 8.0;
 
-        let label_element = text(&property.label)
+        let row_height = self.property_height(property);
+
+        let label_text = text(&property.label)
             .with_color(self.text_color)
             .with_font_size(self.font_size)
             .into_text_element();
+        
+        // Vector rows carry their link-proportions toggle next to the label
+        let label_element = match &property.property_type {
+            PropertyType::Vec2 | PropertyType::Vec3 | PropertyType::Vec4 => {
+                row()
+                    .with_size(label_width, self.row_height)
+                    .with_child(label_text)
+                    .with_child(Element::new_widget(Box::new(
+                        checkbox("")
+                            .with_size(16.0, 16.0)
+                            .with_checked_signal(self.link_state(&property.key))
+                    )))
+                    .into_container_element()
+            },
+            _ => label_text,
+        };
+
+        let sink = self.change_sink(&property.key);
 
-        let input_element = match &property.property_type {
+        // A mixed row starts out showing the first target's value, with the parts
+        // that differ marked
+        let mixed = property.value == PropertyValue::Mixed;
+        let values = if mixed { self.target_values(&property.key) } else { vec![property.value.clone()] };
+        let value = values.first().cloned().unwrap_or(PropertyValue::Mixed);
+
+        let (input_element, refresh): (Element, RowRefresher) = match &property.property_type {
             PropertyType::TextInput => {
-                let text_value = match &property.value {
+                let text_value = Signal::new(match &value {
                     PropertyValue::Text(s) => s.clone(),
                     _ => String::new(),
-                };
+                });
                 
-                input()
+                if mixed {
+                    text_value.set(String::new());
+                }
+                
+                let element = input()
                     .with_size(input_width, self.row_height - 4.0)
-                    .with_text(text_value)
-                    .into_input_element()
+                    .with_placeholder(if mixed { "—" } else { "" })
+                    .with_signal(text_value.clone())
+                    .on_change(move |text| sink(PropertyValue::Text(text.to_string())))
+                    .into_input_element();
+                (element, Box::new(move |value| {
+                    if let PropertyValue::Text(s) = value {
+                        text_value.set(s.clone());
+                    }
+                }))
             },
             PropertyType::NumberInput => {
-                let number_value = match &property.value {
-                    PropertyValue::Number(n) => n.to_string(),
-                    _ => "0".to_string(),
-                };
+                let number_value = Signal::new(match &value {
+                    PropertyValue::Number(n) => *n,
+                    _ => 0.0,
+                });
                 
-                input()
-                    .with_size(input_width, self.row_height - 4.0)
-                    .with_text(number_value)
-                    .into_input_element()
+                let number_mixed = Signal::new(mixed);
+                let element = Element::new_widget(Box::new(
+                    number_field(number_value.clone())
+                        .with_size(input_width, self.row_height - 4.0)
+                        .with_mixed_signal(number_mixed.clone())
+                        .with_unit(property.unit.clone())
+                        .with_font_size(self.font_size)
+                        .on_change(move |value| sink(PropertyValue::Number(value)))
+                ));
+                (element, Box::new(move |value| {
+                    if let PropertyValue::Number(n) = value {
+                        number_mixed.set(false);
+                        number_value.set(*n);
+                    }
+                }))
             },
             PropertyType::Dropdown(options) => {
-                let selected_value = match &property.value {
+                let selected_value = Signal::new(match &value {
+                    _ if mixed => "—".to_string(),
                     PropertyValue::Select(s) => s.clone(),
                     _ => String::new(),
-                };
+                });
                 
-                dropdown()
+                let element = dropdown()
                     .with_size(input_width, self.row_height - 4.0)
                     .with_options(options.clone())
-                    .with_selected_value(selected_value)
-                    .into_dropdown_element()
+                    .with_selected_signal(selected_value.clone())
+                    .on_selection_changed(move |selected| sink(PropertyValue::Select(selected)))
+                    .into_dropdown_element();
+                (element, Box::new(move |value| {
+                    if let PropertyValue::Select(s) = value {
+                        selected_value.set(s.clone());
+                    }
+                }))
             },
             PropertyType::ColorPicker => {
-                // For now, represent color as hex string
-                let color_value = match &property.value {
-                    PropertyValue::Color(c) => format!("#{:02x}{:02x}{:02x}", 
-                        (c.r as f32 * 255.0) as u8, 
-                        (c.g as f32 * 255.0) as u8, 
-                        (c.b as f32 * 255.0) as u8),
-                    _ => "#000000".to_string(),
+                let color_value = Signal::new(match &value {
+                    PropertyValue::Color(c) => *c,
+                    _ => Color::rgba8(0, 0, 0, 255),
+                });
+                
+                let element = Element::new_widget(Box::new(
+                    color_swatch(color_value.clone())
+                        .with_size(input_width, self.row_height - 4.0)
+                        .on_change(move |color| sink(PropertyValue::Color(color)))
+                ));
+                (element, Box::new(move |value| {
+                    if let PropertyValue::Color(c) = value {
+                        color_value.set(*c);
+                    }
+                }))
+            },
+            PropertyType::Vec2 | PropertyType::Vec3 | PropertyType::Vec4 => {
+                let mut components = property.value.components().unwrap_or_default();
+                let expected = match &property.property_type {
+                    PropertyType::Vec2 => 2,
+                    PropertyType::Vec3 => 3,
+                    _ => 4,
                 };
+                components.resize(expected, 0.0);
+                let mask = self.register_mixed_mask(&property.key, PropertyValue::mixed_parts(&values, expected));
+                self.create_vector_editor(&property.key, components, mask, input_width)
+            },
+            PropertyType::Range { min, max, step } => {
+                let number_value = Signal::new(match &value {
+                    PropertyValue::Number(n) => n.clamp(*min, *max),
+                    _ => *min,
+                });
                 
-                input()
-                    .with_size(input_width, self.row_height - 4.0)
-                    .with_text(color_value)
-                    .into_input_element()
+                let element = Element::new_widget(Box::new(
+                    slider(*min, *max)
+                        .with_size(input_width, self.row_height - 4.0)
+                        .with_value_signal(number_value.clone())
+                        .with_step(*step)
+                        .on_change(move |value| sink(PropertyValue::Number(value)))
+                ));
+                let (min, max) = (*min, *max);
+                (element, Box::new(move |value| {
+                    if let PropertyValue::Number(n) = value {
+                        number_value.set(n.clamp(min, max));
+                    }
+                }))
             },
-            PropertyType::Checkbox => {
-                // For now, represent as text "true" or "false"
-                let bool_value = match &property.value {
-                    PropertyValue::Boolean(b) => b.to_string(),
-                    _ => "false".to_string(),
+            PropertyType::Flags(names) => {
+                let bits = match &value {
+                    PropertyValue::Flags(bits) => *bits,
+                    _ => 0,
                 };
                 
-                input()
+                let line_height = self.flag_line_height();
+                let shared_bits = Arc::new(Mutex::new(bits));
+                let mask = self.register_mixed_mask(&property.key, PropertyValue::mixed_parts(&values, names.len().min(32)));
+                let mut flag_signals = Vec::new();
+                let mut flags = column().with_size(input_width, line_height * names.len() as f32);
+                for (index, name) in names.iter().enumerate().take(32) {
+                    let flag_mixed = mask.lock().map(|mask| mask[index]).unwrap_or(false);
+                    let state = Signal::new(if flag_mixed {
+                        CheckState::Indeterminate
+                    } else {
+                        CheckState::from(bits & (1 << index) != 0)
+                    });
+                    flag_signals.push(state.clone());
+                    let sink = sink.clone();
+                    let shared_bits = shared_bits.clone();
+                    let mask = mask.clone();
+                    let flag = checkbox(name.clone())
+                        .with_size(input_width, line_height)
+                        .with_state_signal(state)
+                        .with_text_color(self.text_color)
+                        .with_font_size(self.font_size)
+                        .on_change(move |state| {
+                            let Ok(mut bits) = shared_bits.lock() else {
+                                return;
+                            };
+                            if let Ok(mut mask) = mask.lock() {
+                                mask[index] = false;
+                            }
+                            if state.is_checked() {
+                                *bits |= 1 << index;
+                            } else {
+                                *bits &= !(1 << index);
+                            }
+                            sink(PropertyValue::Flags(*bits));
+                        });
+                    flags = flags.with_child(Element::new_widget(Box::new(flag)));
+                }
+                (flags.into_container_element(), Box::new(move |value| {
+                    if let PropertyValue::Flags(bits) = value {
+                        if let Ok(mut shared) = shared_bits.lock() {
+                            *shared = *bits;
+                        }
+                        if let Ok(mut mask) = mask.lock() {
+                            mask.fill(false);
+                        }
+                        for (index, state) in flag_signals.iter().enumerate() {
+                            state.set(CheckState::from(bits & (1 << index) != 0));
+                        }
+                    }
+                }))
+            },
+            PropertyType::ReadOnly => {
+                let display = Signal::new(property.value.display_text());
+                let element = text_signal(display.clone())
+                    .with_color(self.muted_text_color)
+                    .with_font_size(self.font_size)
+                    .into_text_element();
+                (element, Box::new(move |value| display.set(value.display_text())))
+            },
+            PropertyType::Asset(assets) => {
+                let selected = Signal::new(match &value {
+                    _ if mixed => "—".to_string(),
+                    PropertyValue::Asset(path) => path.clone().unwrap_or_default(),
+                    _ => String::new(),
+                });
+                
+                let mut options = vec![DropdownOption::new("", "None")];
+                options.extend(assets.iter().map(|path| {
+                    let name = std::path::Path::new(path)
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| path.clone());
+                    DropdownOption::new(path.clone(), name)
+                }));
+                
+                let element = dropdown()
                     .with_size(input_width, self.row_height - 4.0)
-                    .with_text(bool_value)
-                    .into_input_element()
+                    .with_options(options)
+                    .with_selected_signal(selected.clone())
+                    .on_selection_changed(move |path| {
+                        let asset = if path.is_empty() { None } else { Some(path) };
+                        sink(PropertyValue::Asset(asset));
+                    })
+                    .into_dropdown_element();
+                (element, Box::new(move |value| {
+                    if let PropertyValue::Asset(path) = value {
+                        selected.set(path.clone().unwrap_or_default());
+                    }
+                }))
+            },
+            PropertyType::Checkbox => {
+                let state = Signal::new(match &value {
+                    _ if mixed => CheckState::Indeterminate,
+                    PropertyValue::Boolean(b) => CheckState::from(*b),
+                    _ => CheckState::Unchecked,
+                });
+                
+                let element = Element::new_widget(Box::new(
+                    checkbox("")
+                        .with_size(input_width, self.row_height - 4.0)
+                        .with_state_signal(state.clone())
+                        .on_change(move |state| sink(PropertyValue::Boolean(state.is_checked())))
+                ));
+                (element, Box::new(move |value| {
+                    if let PropertyValue::Boolean(b) = value {
+                        state.set(CheckState::from(*b));
+                    }
+                }))
             },
         };
 
-        row()
-            .with_size(self.width - self.padding.left - self.padding.right, self.row_height)
+        let cross_axis_alignment = match &property.property_type {
+            PropertyType::Flags(_) => CrossAxisAlignment::Start,
+            _ => CrossAxisAlignment::Center,
+        };
+
+        let element = row()
+            .with_size(self.width - self.padding.left - self.padding.right, row_height)
+            .with_cross_axis_alignment(cross_axis_alignment)
             .with_child(label_element)
             .with_child(input_element)
-            .into_container_element()
+            .into_container_element();
+        (element, refresh)
+    }
+
+    /// One number field per component, each with a colored axis label. While the
+    /// row's link toggle is on, editing one component scales the others with it.
+    /// Components flagged in `mixed` show "—" until edited, and are left alone
+    /// on targets while they stay mixed.
+    fn create_vector_editor(&self, key: &str, values: Vec<f32>, mixed: Arc<Mutex<Vec<bool>>>, width: f32) -> (Element, RowRefresher) {
+        let gap = 4.0;
+        let component_width = (width - gap * (values.len() as f32 - 1.0)) / values.len() as f32;
+        let signals: Vec<Signal<f32>> = values.iter().map(|value| Signal::new(*value)).collect();
+        let mixed_signals: Vec<Signal<bool>> = match mixed.lock() {
+            Ok(mask) => (0..values.len()).map(|axis| Signal::new(mask.get(axis).copied().unwrap_or(false))).collect(),
+            Err(_) => values.iter().map(|_| Signal::new(false)).collect(),
+        };
+        let previous = Arc::new(Mutex::new(values));
+        let linked = self.link_state(key);
+        let sink = self.change_sink(key);
+
+        let mut editor = row()
+            .with_size(width, self.row_height - 4.0)
+            .with_gap(gap);
+        for (axis, signal) in signals.iter().enumerate() {
+            let sink = sink.clone();
+            let components = signals.clone();
+            let previous = previous.clone();
+            let linked = linked.clone();
+            let mixed = mixed.clone();
+            let field = number_field(signal.clone())
+                .with_size(component_width, self.row_height - 4.0)
+                .with_mixed_signal(mixed_signals[axis].clone())
+                .with_label(AXIS_LABELS[axis])
+                .with_text_colors(self.text_color, axis_color(axis))
+                .with_font_size(self.font_size)
+                .on_change(move |value| {
+                    let Ok(mut previous) = previous.lock() else {
+                        return;
+                    };
+                    let Ok(mut mixed) = mixed.lock() else {
+                        return;
+                    };
+                    let old = previous[axis];
+                    // Scaling from a mixed value would be meaningless
+                    if linked.get() && old != 0.0 && !mixed[axis] {
+                        let ratio = value / old;
+                        for (other, component) in components.iter().enumerate() {
+                            if other != axis && !mixed[other] {
+                                component.set(previous[other] * ratio);
+                            }
+                        }
+                    }
+                    mixed[axis] = false;
+                    let current: Vec<f32> = components.iter().map(|component| component.get()).collect();
+                    previous.copy_from_slice(&current);
+                    if let Some(value) = PropertyValue::from_components(&current) {
+                        sink(value);
+                    }
+                });
+            editor = editor.with_child(Element::new_widget(Box::new(field)));
+        }
+
+        (editor.into_container_element(), Box::new(move |value| {
+            let Some(values) = value.components() else {
+                return;
+            };
+            if let Ok(mut mask) = mixed.lock() {
+                mask.fill(false);
+            }
+            for mixed_signal in &mixed_signals {
+                mixed_signal.set(false);
+            }
+            if let Ok(mut previous) = previous.lock() {
+                for ((signal, stored), new_value) in signals.iter().zip(previous.iter_mut()).zip(values) {
+                    *stored = new_value;
+                    signal.set(new_value);
+                }
+            }
+        }))
     }
 
     pub fn create_background_rectangle(&self) -> Rectangle {
@@ -366,9 +1588,11 @@ impl PropertyInspectorWidget {
 impl Widget for PropertyInspectorWidget {
     fn mount(&mut self) -> Result<(), WidgetError> {
         self.rebuild_ui();
+        self.needs_rebuild = false;
         for child in &mut self.children {
             child.mount()?;
         }
+        self.search_input.mount()?;
         self.dirty = true;
         Ok(())
     }
@@ -377,13 +1601,52 @@ impl Widget for PropertyInspectorWidget {
         for child in &mut self.children {
             child.unmount()?;
         }
+        self.search_input.unmount()?;
         Ok(())
     }
 
     fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        let pending = self.pending_changes.lock()
+            .map(|mut pending| std::mem::take(&mut *pending))
+            .unwrap_or_default();
+        for (key, value) in pending {
+            self.apply_row_change(&key, value);
+        }
+        
+        let external = self.external_changes.lock()
+            .map(|mut changes| std::mem::take(&mut *changes))
+            .unwrap_or_default();
+        for (key, value) in external {
+            self.apply_external_change(&key, value);
+        }
+        
+        // Only a changed set of groups recreates the rows; edits refresh them in place
+        if self.needs_rebuild {
+            self.needs_rebuild = false;
+            self.rebuild_ui();
+            for child in &mut self.children {
+                child.mount()?;
+            }
+        }
+        
+        let search = self.search.get();
+        if search != self.applied_search {
+            self.applied_search = search;
+            self.layout_rows();
+        }
+        
+        if self.animate_groups() {
+            self.layout_rows();
+            global_frame_scheduler().request_update();
+        }
+        
+        let (content_x, content_y) = self.content_origin();
+        self.search_input.set_position(content_x, content_y);
+        self.search_input.set_size(self.width - self.padding.left - self.padding.right, self.row_height - 4.0);
+        self.search_input.update(ctx)?;
+        
         if self.dirty {
             ctx.mark_dirty(self.id);
-            self.rebuild_ui();
         }
         
         for child in &mut self.children {
@@ -393,7 +1656,37 @@ impl Widget for PropertyInspectorWidget {
     }
 
     fn handle_event(&mut self, event: &Event) -> EventResult {
-        for child in &mut self.children {
+        if self.show_search {
+            if let EventResult::Handled = self.search_input.handle_event(event) {
+                return EventResult::Handled;
+            }
+        }
+        
+        if let Event::Mouse(mouse_event) = event {
+            if mouse_event.button.is_some() && mouse_event.state == ElementState::Pressed {
+                let (x, y) = (mouse_event.position.x as f32, mouse_event.position.y as f32);
+                match self.slot_at(x, y).map(|index| &self.slots[index]) {
+                    Some(InspectorSlot::Header { state_key }) => {
+                        let state_key = state_key.clone();
+                        self.toggle_group(&state_key);
+                        return EventResult::Handled;
+                    }
+                    Some(InspectorSlot::Row { key, .. }) => {
+                        let key = key.clone();
+                        if self.is_on_reset_glyph(x) && self.find_property(&key).is_some_and(|property| property.is_modified()) {
+                            self.reset_property(&key);
+                            return EventResult::Handled;
+                        }
+                    }
+                    None => {}
+                }
+            }
+        }
+        
+        for (index, child) in self.children.iter_mut().enumerate() {
+            if !self.child_visible.get(index).copied().unwrap_or(true) {
+                continue;
+            }
             match child.handle_event(event) {
                 EventResult::Handled => return EventResult::Handled,
                 EventResult::Propagate => continue,
@@ -445,6 +1738,10 @@ impl Widget for PropertyInspectorWidget {
         self
     }
 
+    fn children_mut(&mut self) -> Vec<&mut Element> {
+        self.children.iter_mut().collect()
+    }
+
     fn get_id(&self) -> WidgetId {
         self.id
     }
@@ -567,4 +1864,260 @@ impl IntoElement<RowWidget> for RowWidget {
     fn into_row_element(self) -> Element {
         Element::new_widget(Box::new(self))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WidgetManager;
+    use crate::widgets::number_field::NumberFieldWidget;
+    use crate::widgets::interactive::{CheckboxWidget, SliderWidget};
+
+    fn sample_inspector() -> PropertyInspectorWidget {
+        property_inspector().with_groups(vec![
+            PropertyGroup::new("Transform")
+                .with_property(PropertyDefinition::number("x", "Position X", 1.0))
+                .with_property(PropertyDefinition::number("angle", "Rotation", 0.0)),
+            PropertyGroup::new("Rendering")
+                .with_key("render")
+                .collapsed()
+                .with_property(PropertyDefinition::text("material", "Material", "default")),
+        ])
+    }
+
+    #[test]
+    fn search_filters_rows_and_groups() {
+        let mut inspector = sample_inspector();
+        inspector.rebuild_ui();
+        // Header, two rows, header, row of the collapsed group
+        assert_eq!(inspector.child_visible, [true, true, true, true, false]);
+
+        inspector.applied_search = "rot".to_string();
+        inspector.layout_rows();
+        assert_eq!(inspector.child_visible, [true, false, true, false, false]);
+
+        // Searching opens collapsed groups that have matches
+        inspector.applied_search = "MATER".to_string();
+        inspector.layout_rows();
+        assert_eq!(inspector.child_visible, [false, false, false, true, true]);
+        assert!(!inspector.is_group_expanded("render"));
+    }
+
+    #[test]
+    fn reset_restores_initial_value() {
+        let mut inspector = sample_inspector();
+        inspector.rebuild_ui();
+        inspector.update_property("x", PropertyValue::Number(5.0));
+        assert!(inspector.find_property("x").is_some_and(|property| property.is_modified()));
+
+        inspector.reset_property("x");
+        let property = inspector.find_property("x").unwrap();
+        assert_eq!(property.value, PropertyValue::Number(1.0));
+        assert!(!property.is_modified());
+    }
+
+    fn transform_target(id: &str, position: [f32; 3], visible: bool) -> PropertyTarget {
+        PropertyTarget::new(id, vec![
+            PropertyGroup::new("Transform")
+                .with_property(PropertyDefinition::vec3("position", "Position", position))
+                .with_property(PropertyDefinition::boolean("visible", "Visible", visible))
+                .with_property(PropertyDefinition::text("name", "Name", id)),
+        ])
+    }
+
+    #[test]
+    fn merged_targets_show_mixed_values() {
+        let mut targets = vec![
+            transform_target("a", [1.0, 2.0, 3.0], true),
+            transform_target("b", [1.0, 5.0, 3.0], true),
+        ];
+        targets[1].groups[0].properties.retain(|property| property.key != "name");
+
+        let groups = merge_targets(&targets);
+        let properties = &groups[0].properties;
+        assert_eq!(properties.len(), 2);
+        assert_eq!(properties[0].value, PropertyValue::Mixed);
+        assert_eq!(properties[1].value, PropertyValue::Boolean(true));
+        assert_eq!(
+            PropertyValue::mixed_parts(&[PropertyValue::Vec3([1.0, 2.0, 3.0]), PropertyValue::Vec3([1.0, 5.0, 3.0])], 3),
+            [false, true, false]
+        );
+    }
+
+    #[test]
+    fn edits_apply_to_every_target() {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let recorded = changes.clone();
+        let mut inspector = property_inspector()
+            .with_targets(vec![
+                transform_target("a", [1.0, 2.0, 3.0], true),
+                transform_target("b", [1.0, 5.0, 3.0], false),
+            ])
+            .on_targets_change(move |batch| recorded.lock().unwrap().extend_from_slice(batch));
+        inspector.rebuild_ui();
+
+        // Editing X leaves the mixed Y component of each target alone
+        inspector.apply_row_change("position", PropertyValue::Vec3([4.0, 2.0, 3.0]));
+        let positions: Vec<PropertyValue> = inspector.target_values("position");
+        assert_eq!(positions, [PropertyValue::Vec3([4.0, 2.0, 3.0]), PropertyValue::Vec3([4.0, 5.0, 3.0])]);
+        assert_eq!(inspector.find_property("position").unwrap().value, PropertyValue::Mixed);
+
+        inspector.update_property("visible", PropertyValue::Boolean(false));
+        assert_eq!(inspector.find_property("visible").unwrap().value, PropertyValue::Boolean(false));
+
+        let changes = changes.lock().unwrap();
+        let targets: Vec<(&str, &str)> = changes.iter().map(|change| (change.target.as_str(), change.key.as_str())).collect();
+        assert_eq!(targets, [("a", "position"), ("b", "position"), ("a", "visible")]);
+    }
+
+    fn bound_inspector(binding: &Signal<PropertyValue>) -> PropertyInspectorWidget {
+        let mut inspector = property_inspector().with_groups(vec![
+            PropertyGroup::new("Transform")
+                .with_property(PropertyDefinition::number("x", "Position X", 1.0).with_binding(binding.clone())),
+        ]);
+        inspector.mount().unwrap();
+        // Subscribing reports the current value once; let the first update take it
+        inspector.update(&mut WidgetManager::new()).unwrap();
+        inspector
+    }
+
+    fn child_ids(inspector: &PropertyInspectorWidget) -> Vec<WidgetId> {
+        inspector.children.iter().filter_map(|child| match child {
+            Element::Widget(widget) | Element::Container { widget, .. } => Some(widget.get_id()),
+            Element::Fragment(_) => None,
+        }).collect()
+    }
+
+    fn number_field_value(element: &Element) -> Option<f32> {
+        match element {
+            Element::Widget(widget) => widget.as_any().downcast_ref::<NumberFieldWidget>().map(|field| field.get_value()),
+            Element::Container { children, .. } | Element::Fragment(children) => children.iter().find_map(number_field_value),
+        }
+    }
+
+    #[test]
+    fn bound_signal_refreshes_row_in_place() {
+        let binding = Signal::new(PropertyValue::Number(1.0));
+        let mut inspector = bound_inspector(&binding);
+        let ids = child_ids(&inspector);
+
+        binding.set(PropertyValue::Number(7.0));
+        inspector.update(&mut WidgetManager::new()).unwrap();
+
+        assert_eq!(inspector.find_property("x").unwrap().value, PropertyValue::Number(7.0));
+        assert_eq!(inspector.children.iter().find_map(number_field_value), Some(7.0));
+        assert_eq!(child_ids(&inspector), ids);
+    }
+
+    #[test]
+    fn edits_write_back_to_bound_signal() {
+        let binding = Signal::new(PropertyValue::Number(1.0));
+        let mut inspector = bound_inspector(&binding);
+
+        inspector.change_sink("x")(PropertyValue::Number(3.0));
+        inspector.update(&mut WidgetManager::new()).unwrap();
+
+        assert_eq!(binding.get(), PropertyValue::Number(3.0));
+    }
+
+    #[test]
+    fn write_back_does_not_echo_into_the_inspector() {
+        let binding = Signal::new(PropertyValue::Number(1.0));
+        let mut inspector = bound_inspector(&binding);
+        let ids = child_ids(&inspector);
+
+        inspector.apply_row_change("x", PropertyValue::Number(4.0));
+
+        assert!(inspector.external_changes.lock().unwrap().is_empty());
+        assert!(!inspector.writing_back.load(Ordering::SeqCst));
+        inspector.update(&mut WidgetManager::new()).unwrap();
+        assert_eq!(child_ids(&inspector), ids);
+        assert_eq!(binding.get(), PropertyValue::Number(4.0));
+    }
+
+    fn single_property(property: PropertyDefinition) -> PropertyInspectorWidget {
+        let mut inspector = property_inspector().with_groups(vec![
+            PropertyGroup::new("General").with_property(property),
+        ]);
+        inspector.mount().unwrap();
+        inspector
+    }
+
+    fn widgets_of<'a, T: 'static>(element: &'a mut Element, found: &mut Vec<&'a mut T>) {
+        match element {
+            Element::Widget(widget) => found.extend(widget.as_any_mut().downcast_mut::<T>()),
+            Element::Container { widget, children } => {
+                found.extend(widget.as_any_mut().downcast_mut::<T>());
+                for child in children {
+                    widgets_of(child, found);
+                }
+            },
+            Element::Fragment(children) => {
+                for child in children {
+                    widgets_of(child, found);
+                }
+            },
+        }
+    }
+
+    fn row_widgets<T: 'static>(inspector: &mut PropertyInspectorWidget) -> Vec<&mut T> {
+        let mut found = Vec::new();
+        for child in &mut inspector.children {
+            widgets_of(child, &mut found);
+        }
+        found
+    }
+
+    #[test]
+    fn linked_vector_scales_the_other_components() {
+        let mut inspector = single_property(PropertyDefinition::vec3("scale", "Scale", [2.0, 4.0, 1.0]));
+
+        // Unlinked, only the edited component changes
+        row_widgets::<NumberFieldWidget>(&mut inspector)[0].set_value(3.0);
+        inspector.update(&mut WidgetManager::new()).unwrap();
+        assert_eq!(inspector.find_property("scale").unwrap().value, PropertyValue::Vec3([3.0, 4.0, 1.0]));
+
+        inspector.link_state("scale").set(true);
+        row_widgets::<NumberFieldWidget>(&mut inspector)[0].set_value(6.0);
+        inspector.update(&mut WidgetManager::new()).unwrap();
+        assert_eq!(inspector.find_property("scale").unwrap().value, PropertyValue::Vec3([6.0, 8.0, 2.0]));
+        let shown: Vec<f32> = row_widgets::<NumberFieldWidget>(&mut inspector).iter().map(|field| field.get_value()).collect();
+        assert_eq!(shown, [6.0, 8.0, 2.0]);
+    }
+
+    #[test]
+    fn flag_checkboxes_toggle_their_bit() {
+        let names = vec!["Static".to_string(), "Visible".to_string(), "Locked".to_string()];
+        let mut inspector = single_property(PropertyDefinition::flags("flags", "Flags", names, 0b001));
+        let states: Vec<CheckState> = row_widgets::<CheckboxWidget>(&mut inspector).iter().map(|flag| flag.get_state()).collect();
+        assert_eq!(states, [CheckState::Checked, CheckState::Unchecked, CheckState::Unchecked]);
+
+        row_widgets::<CheckboxWidget>(&mut inspector)[2].toggle();
+        inspector.update(&mut WidgetManager::new()).unwrap();
+        assert_eq!(inspector.find_property("flags").unwrap().value, PropertyValue::Flags(0b101));
+
+        row_widgets::<CheckboxWidget>(&mut inspector)[0].toggle();
+        inspector.update(&mut WidgetManager::new()).unwrap();
+        assert_eq!(inspector.find_property("flags").unwrap().value, PropertyValue::Flags(0b100));
+
+        // A value set from outside updates every box
+        inspector.update_property("flags", PropertyValue::Flags(0b011));
+        let states: Vec<CheckState> = row_widgets::<CheckboxWidget>(&mut inspector).iter().map(|flag| flag.get_state()).collect();
+        assert_eq!(states, [CheckState::Checked, CheckState::Checked, CheckState::Unchecked]);
+    }
+
+    #[test]
+    fn range_clamps_and_snaps_to_step() {
+        let property = PropertyDefinition::range("opacity", "Opacity", 1.5, 0.0, 1.0).with_step(0.25);
+        assert_eq!(property.value, PropertyValue::Number(1.0));
+        let mut inspector = single_property(property);
+
+        row_widgets::<SliderWidget>(&mut inspector)[0].set_value(0.6);
+        inspector.update(&mut WidgetManager::new()).unwrap();
+        assert_eq!(inspector.find_property("opacity").unwrap().value, PropertyValue::Number(0.5));
+
+        // The slider never shows a value outside the range
+        inspector.update_property("opacity", PropertyValue::Number(-2.0));
+        assert_eq!(row_widgets::<SliderWidget>(&mut inspector)[0].get_value(), 0.0);
+    }
+}
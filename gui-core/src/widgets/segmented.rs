@@ -0,0 +1,581 @@
+use crate::{Widget, WidgetId, EventResult, WidgetError, RenderData, DirtyRegion, WidgetUpdateContext};
+use crate::event::Event;
+use winit::event::ElementState;
+use gui_reactive::Signal;
+use gui_render::primitives::{Rectangle, Text};
+use std::any::Any;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use vello::peniko::Color;
+use super::icon::{icon, IconWidget};
+use crate::theme::{current_theme, StyleOverrides, Theme};
+
+static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(16000);
+
+const ICON_SIZE: f32 = 16.0;
+const ICON_GAP: f32 = 6.0;
+
+/// One button of a segmented control
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segment {
+    pub value: String,
+    pub label: String,
+    pub icon: Option<String>,
+    /// Draw the icon without the label
+    pub icon_only: bool,
+}
+
+impl Segment {
+    pub fn new(value: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            label: label.into(),
+            icon: None,
+            icon_only: false,
+        }
+    }
+
+    /// Icon-only segment, as in a tool palette; `label` names it
+    pub fn icon(value: impl Into<String>, icon_name: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            label: label.into(),
+            icon: Some(icon_name.into()),
+            icon_only: true,
+        }
+    }
+
+    pub fn with_icon(mut self, icon_name: impl Into<String>) -> Self {
+        self.icon = Some(icon_name.into());
+        self
+    }
+}
+
+/// What a segmented control selects
+#[derive(Clone)]
+pub enum SegmentSelection {
+    /// Exactly one segment, by value
+    Exclusive(Signal<String>),
+    /// Any number of segments, by value in segment order
+    Multiple(Signal<Vec<String>>),
+}
+
+/// Row of joined buttons where one (or several) stay pressed, e.g. tool or
+/// alignment pickers in a toolbar
+pub struct SegmentedControl {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    segments: Vec<Segment>,
+    // Built from the segments' icon names, same order
+    icons: Vec<Option<IconWidget>>,
+    selection: SegmentSelection,
+    hovered: Option<usize>,
+    pressed: Option<usize>,
+    background_color: Color,
+    hover_color: Color,
+    selected_color: Color,
+    border_color: Color,
+    text_color: Color,
+    selected_text_color: Color,
+    border_radius: f32,
+    font_size: f32,
+    on_change: Option<Box<dyn Fn(&[String]) + Send + Sync>>,
+    // Style set through builders, which the theme leaves alone
+    overrides: StyleOverrides,
+    pub dirty: bool,
+    // Shared dirty flag that reactive signals can set
+    reactive_dirty: Arc<RwLock<bool>>,
+}
+
+impl SegmentedControl {
+    pub fn new(selection: SegmentSelection) -> Self {
+        let theme = current_theme();
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 240.0,
+            height: 32.0,
+            segments: Vec::new(),
+            icons: Vec::new(),
+            selection,
+            hovered: None,
+            pressed: None,
+            background_color: theme.palette.surface,
+            hover_color: theme.palette.surface_hover,
+            selected_color: theme.palette.primary,
+            border_color: theme.palette.border,
+            text_color: theme.palette.text,
+            selected_text_color: theme.palette.on_primary,
+            border_radius: theme.radii.medium,
+            font_size: theme.typography.font_size,
+            on_change: None,
+            overrides: StyleOverrides::default(),
+            dirty: true,
+            reactive_dirty: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    /// Exactly one segment pressed at a time
+    pub fn exclusive(selected: Signal<String>) -> Self {
+        Self::new(SegmentSelection::Exclusive(selected))
+    }
+
+    /// Each segment toggles on its own
+    pub fn multiple(selected: Signal<Vec<String>>) -> Self {
+        Self::new(SegmentSelection::Multiple(selected))
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_segments(mut self, segments: Vec<Segment>) -> Self {
+        self.icons = segments.iter()
+            .map(|segment| segment.icon.as_ref().map(|name| icon(name.clone()).with_size(ICON_SIZE)))
+            .collect();
+        self.segments = segments;
+        self.layout_icons();
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_segment(mut self, segment: Segment) -> Self {
+        self.icons.push(segment.icon.as_ref().map(|name| icon(name.clone()).with_size(ICON_SIZE)));
+        self.segments.push(segment);
+        self.layout_icons();
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_colors(mut self, background: Color, selected: Color, text: Color) -> Self {
+        self.background_color = background;
+        self.selected_color = selected;
+        self.text_color = text;
+        self.overrides.mark("background_color");
+        self.overrides.mark("selected_color");
+        self.overrides.mark("text_color");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_border_radius(mut self, radius: f32) -> Self {
+        self.border_radius = radius;
+        self.overrides.mark("border_radius");
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self.overrides.mark("font_size");
+        self.layout_icons();
+        self.dirty = true;
+        self
+    }
+
+    /// Take every style property not set through a builder from `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let palette = &theme.palette;
+        let overrides = &self.overrides;
+        if !overrides.is_set("background_color") {
+            self.background_color = palette.surface;
+        }
+        if !overrides.is_set("selected_color") {
+            self.selected_color = palette.primary;
+        }
+        if !overrides.is_set("text_color") {
+            self.text_color = palette.text;
+        }
+        if !overrides.is_set("border_radius") {
+            self.border_radius = theme.radii.medium;
+        }
+        if !overrides.is_set("font_size") {
+            self.font_size = theme.typography.font_size;
+        }
+        self.hover_color = palette.surface_hover;
+        self.border_color = palette.border;
+        self.selected_text_color = palette.on_primary;
+        self.layout_icons();
+        self.dirty = true;
+    }
+
+    /// Called with the selected values after a click changes them
+    pub fn on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&[String]) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Box::new(callback));
+        self
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.layout_icons();
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        if self.width != width || self.height != height {
+            self.width = width;
+            self.height = height;
+            self.layout_icons();
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn get_segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    pub fn get_icons(&self) -> impl Iterator<Item = &IconWidget> {
+        self.icons.iter().flatten()
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    fn segment_width(&self) -> f32 {
+        self.width / self.segments.len().max(1) as f32
+    }
+
+    pub fn segment_at(&self, x: f32, y: f32) -> Option<usize> {
+        if !self.is_point_inside(x, y) || self.segments.is_empty() {
+            return None;
+        }
+        let index = ((x - self.x) / self.segment_width()) as usize;
+        Some(index.min(self.segments.len() - 1))
+    }
+
+    pub fn selected_values(&self) -> Vec<String> {
+        match &self.selection {
+            SegmentSelection::Exclusive(selected) => vec![selected.get()],
+            SegmentSelection::Multiple(selected) => selected.get(),
+        }
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        let Some(segment) = self.segments.get(index) else {
+            return false;
+        };
+        match &self.selection {
+            SegmentSelection::Exclusive(selected) => selected.with(|selected| *selected == segment.value),
+            SegmentSelection::Multiple(selected) => selected.with(|selected| selected.contains(&segment.value)),
+        }
+    }
+
+    /// What a click on the segment does: select it, or toggle it in multiple mode
+    pub fn activate(&mut self, index: usize) {
+        let Some(value) = self.segments.get(index).map(|segment| segment.value.clone()) else {
+            return;
+        };
+        let changed = match &self.selection {
+            SegmentSelection::Exclusive(selected) => {
+                if selected.get() == value {
+                    false
+                } else {
+                    selected.set(value);
+                    true
+                }
+            },
+            SegmentSelection::Multiple(selected) => {
+                let current = selected.get();
+                let order: Vec<&String> = self.segments.iter().map(|segment| &segment.value).collect();
+                let mut values: Vec<String> = if current.contains(&value) {
+                    current.iter().filter(|selected| **selected != value).cloned().collect()
+                } else {
+                    current.iter().cloned().chain(std::iter::once(value)).collect()
+                };
+                // Keep segment order; values naming no segment go last
+                values.sort_by_key(|value| order.iter().position(|ordered| *ordered == value).unwrap_or(usize::MAX));
+                selected.set(values);
+                true
+            },
+        };
+        if changed {
+            self.dirty = true;
+            if let Some(ref callback) = self.on_change {
+                callback(&self.selected_values());
+            }
+        }
+    }
+
+    fn shows_label(&self, index: usize) -> bool {
+        self.segments.get(index).is_some_and(|segment| !(segment.icon_only && segment.icon.is_some()))
+    }
+
+    fn label_width(&self, index: usize) -> f32 {
+        self.segments.get(index).map_or(0.0, |segment| segment.label.chars().count() as f32 * self.font_size * 0.6)
+    }
+
+    // Left edge of the centered icon + label group of a segment
+    fn content_start(&self, index: usize) -> f32 {
+        let has_icon = self.icons.get(index).is_some_and(Option::is_some);
+        let content_width = match (has_icon, self.shows_label(index)) {
+            (true, true) => ICON_SIZE + ICON_GAP + self.label_width(index),
+            (true, false) => ICON_SIZE,
+            (false, _) => self.label_width(index),
+        };
+        self.x + self.segment_width() * index as f32 + (self.segment_width() - content_width) / 2.0
+    }
+
+    fn layout_icons(&mut self) {
+        let starts: Vec<f32> = (0..self.icons.len()).map(|index| self.content_start(index)).collect();
+        let colors: Vec<Color> = (0..self.icons.len()).map(|index| self.content_color(index)).collect();
+        let icon_y = self.y + (self.height - ICON_SIZE) / 2.0;
+        for ((icon, start), color) in self.icons.iter_mut().zip(starts).zip(colors) {
+            if let Some(icon) = icon {
+                icon.set_position(start, icon_y);
+                icon.set_color(Some(color));
+            }
+        }
+    }
+
+    fn content_color(&self, index: usize) -> Color {
+        if self.is_selected(index) {
+            self.selected_text_color
+        } else {
+            self.text_color
+        }
+    }
+
+    /// Outline, then each segment's fill, then the dividers between unselected segments
+    pub fn create_rectangles(&self) -> Vec<Rectangle> {
+        let mut rectangles = vec![
+            Rectangle::new(self.x, self.y, self.width, self.height, self.border_color)
+                .with_border_radius(self.border_radius),
+            Rectangle::new(self.x + 1.0, self.y + 1.0, self.width - 2.0, self.height - 2.0, self.background_color)
+                .with_border_radius((self.border_radius - 1.0).max(0.0)),
+        ];
+        let segment_width = self.segment_width();
+        let last = self.segments.len().saturating_sub(1);
+        for index in 0..self.segments.len() {
+            let color = if self.is_selected(index) {
+                self.selected_color
+            } else if self.pressed == Some(index) || self.hovered == Some(index) {
+                self.hover_color
+            } else {
+                continue;
+            };
+            // Only the outer segments are rounded
+            let radius = if index == 0 || index == last { (self.border_radius - 1.0).max(0.0) } else { 0.0 };
+            rectangles.push(Rectangle::new(
+                self.x + segment_width * index as f32 + 1.0,
+                self.y + 1.0,
+                segment_width - 2.0,
+                self.height - 2.0,
+                color,
+            ).with_border_radius(radius));
+        }
+        for index in 1..self.segments.len() {
+            if !self.is_selected(index - 1) && !self.is_selected(index) {
+                rectangles.push(Rectangle::new(
+                    self.x + segment_width * index as f32 - 0.5,
+                    self.y + 4.0,
+                    1.0,
+                    self.height - 8.0,
+                    self.border_color,
+                ));
+            }
+        }
+        rectangles
+    }
+
+    pub fn create_label_texts(&self) -> Vec<Text> {
+        let text_y = self.y + self.height / 2.0 + self.font_size * 0.25;
+        self.segments.iter().enumerate()
+            .filter(|(index, _)| self.shows_label(*index))
+            .map(|(index, segment)| {
+                let text_x = if self.icons.get(index).is_some_and(Option::is_some) {
+                    self.content_start(index) + ICON_SIZE + ICON_GAP
+                } else {
+                    self.content_start(index)
+                };
+                Text::new(text_x, text_y, segment.label.clone(), self.content_color(index), self.font_size)
+            })
+            .collect()
+    }
+}
+
+impl Widget for SegmentedControl {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        self.dirty = true;
+
+        let reactive_dirty = self.reactive_dirty.clone();
+        let mark_dirty = move || {
+            if let Ok(mut dirty) = reactive_dirty.write() {
+                *dirty = true;
+            }
+        };
+        match &self.selection {
+            SegmentSelection::Exclusive(selected) => selected.subscribe_fn(move |_| mark_dirty()),
+            SegmentSelection::Multiple(selected) => selected.subscribe_fn(move |_| mark_dirty()),
+        }
+        for icon in self.icons.iter_mut().flatten() {
+            icon.mount()?;
+        }
+        self.layout_icons();
+
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        self.hovered = None;
+        self.pressed = None;
+        for icon in self.icons.iter_mut().flatten() {
+            icon.unmount()?;
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        if let Ok(mut reactive_dirty) = self.reactive_dirty.write() {
+            if *reactive_dirty {
+                *reactive_dirty = false;
+                self.dirty = true;
+            }
+        }
+        for icon in self.icons.iter_mut().flatten() {
+            icon.update(ctx)?;
+        }
+
+        if self.dirty {
+            // Icon colors follow the selection
+            self.layout_icons();
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        match event {
+            Event::Mouse(mouse_event) => {
+                let x = mouse_event.position.x as f32;
+                let y = mouse_event.position.y as f32;
+                let segment = self.segment_at(x, y);
+
+                if mouse_event.button.is_none() {
+                    if segment != self.hovered {
+                        self.hovered = segment;
+                        self.dirty = true;
+                    }
+                    EventResult::Ignored
+                } else if mouse_event.state == ElementState::Pressed {
+                    if segment.is_some() {
+                        self.pressed = segment;
+                        self.dirty = true;
+                        EventResult::Handled
+                    } else {
+                        EventResult::Ignored
+                    }
+                } else {
+                    let Some(pressed) = self.pressed.take() else {
+                        return EventResult::Ignored;
+                    };
+                    self.dirty = true;
+                    // Releasing over another segment cancels, like a button
+                    if segment == Some(pressed) {
+                        self.activate(pressed);
+                    }
+                    EventResult::Handled
+                }
+            },
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            }],
+            z_index: 1,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+// Convenience functions for creating segmented controls
+pub fn segmented_control(selected: Signal<String>, segments: Vec<Segment>) -> SegmentedControl {
+    SegmentedControl::exclusive(selected).with_segments(segments)
+}
+
+pub fn button_group(selected: Signal<Vec<String>>, segments: Vec<Segment>) -> SegmentedControl {
+    SegmentedControl::multiple(selected).with_segments(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alignments() -> Vec<Segment> {
+        vec![Segment::new("left", "Left"), Segment::new("center", "Center"), Segment::new("right", "Right")]
+    }
+
+    #[test]
+    fn exclusive_selection_follows_clicks() {
+        let selected = Signal::new("left".to_string());
+        let mut control = segmented_control(selected.clone(), alignments()).with_size(300.0, 30.0);
+
+        assert_eq!(control.segment_at(150.0, 15.0), Some(1));
+        assert_eq!(control.segment_at(150.0, 40.0), None);
+
+        control.activate(2);
+        assert_eq!(selected.get(), "right");
+        assert!(control.is_selected(2) && !control.is_selected(0));
+    }
+
+    #[test]
+    fn multiple_selection_toggles_in_segment_order() {
+        let selected = Signal::new(Vec::new());
+        let mut control = button_group(selected.clone(), alignments());
+
+        control.activate(2);
+        control.activate(0);
+        assert_eq!(selected.get(), vec!["left".to_string(), "right".to_string()]);
+
+        control.activate(2);
+        assert_eq!(selected.get(), vec!["left".to_string()]);
+    }
+}
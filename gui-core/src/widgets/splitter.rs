@@ -0,0 +1,581 @@
+use crate::{Widget, WidgetId, EventResult, WidgetError, RenderData, DirtyRegion, WidgetUpdateContext};
+use crate::event::Event;
+use crate::element::Element;
+use crate::theme::{current_theme, StyleOverrides, Theme};
+use winit::event::{ElementState, MouseButton};
+use winit::keyboard::KeyCode;
+use gui_reactive::Signal;
+use gui_render::primitives::Rectangle;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use vello::peniko::Color;
+
+static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(12000);
+
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+
+/// `Horizontal` places panes side by side, `Vertical` stacks them
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SplitDirection {
+    Horizontal,
+    Vertical,
+}
+
+/// Size limits for one pane, in pixels. Collapsing ignores `min`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PaneConstraints {
+    pub min: f32,
+    pub max: f32,
+    pub collapsible: bool,
+}
+
+impl Default for PaneConstraints {
+    fn default() -> Self {
+        Self {
+            min: 0.0,
+            max: f32::INFINITY,
+            collapsible: true,
+        }
+    }
+}
+
+/// Move the divider after pane `index` by `delta` pixels, trading size with the
+/// next pane while keeping both within their limits
+pub fn resize_adjacent_panes(sizes: &mut [f32], constraints: &[PaneConstraints], index: usize, delta: f32) {
+    if index + 1 >= sizes.len() {
+        return;
+    }
+    let first = constraints.get(index).copied().unwrap_or_default();
+    let second = constraints.get(index + 1).copied().unwrap_or_default();
+    let total = sizes[index] + sizes[index + 1];
+
+    let lower = first.min.max(total - second.max).max(0.0);
+    let upper = first.max.min(total - second.min).min(total);
+    let new_size = (sizes[index] + delta).clamp(lower, upper.max(lower));
+    sizes[index] = new_size;
+    sizes[index + 1] = total - new_size;
+}
+
+/// Divides its children between draggable dividers. Pane sizes are kept as
+/// fractions in a `Signal<Vec<f32>>` so layouts can be saved and restored.
+pub struct SplitterWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    direction: SplitDirection,
+    pane_count: usize,
+    sizes: Signal<Vec<f32>>,
+    constraints: Vec<PaneConstraints>,
+    // Size a collapsed pane had before collapsing, restored on the next toggle
+    restore_sizes: Vec<Option<f32>>,
+    divider_thickness: f32,
+    divider_color: Color,
+    divider_hover_color: Color,
+    hovered_divider: Option<usize>,
+    // Divider index and last pointer coordinate along the split axis
+    dragging: Option<(usize, f32)>,
+    focused_divider: Option<usize>,
+    last_click: Option<(usize, Instant)>,
+    keyboard_step: f32,
+    children: Vec<Element>,
+    overrides: StyleOverrides,
+    pub dirty: bool,
+    // Shared dirty flag that reactive signals can set
+    reactive_dirty: Arc<RwLock<bool>>,
+}
+
+impl SplitterWidget {
+    pub fn new(direction: SplitDirection) -> Self {
+        let theme = current_theme();
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 800.0,
+            height: 600.0,
+            direction,
+            pane_count: 0,
+            sizes: Signal::new(Vec::new()),
+            constraints: Vec::new(),
+            restore_sizes: Vec::new(),
+            divider_thickness: 6.0,
+            divider_color: theme.palette.border,
+            divider_hover_color: theme.palette.primary,
+            hovered_divider: None,
+            dragging: None,
+            focused_divider: None,
+            last_click: None,
+            keyboard_step: 10.0,
+            children: Vec::new(),
+            overrides: StyleOverrides::default(),
+            dirty: true,
+            reactive_dirty: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self.dirty = true;
+        self
+    }
+
+    /// Bind pane sizes, as fractions of the space left after dividers
+    pub fn with_sizes_signal(mut self, sizes: Signal<Vec<f32>>) -> Self {
+        self.sizes = sizes;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_sizes(self, sizes: Vec<f32>) -> Self {
+        self.sizes.set(sizes);
+        self
+    }
+
+    pub fn with_pane_constraints(mut self, index: usize, min: f32, max: f32) -> Self {
+        if self.constraints.len() <= index {
+            self.constraints.resize(index + 1, PaneConstraints::default());
+        }
+        self.constraints[index].min = min;
+        self.constraints[index].max = max;
+        self
+    }
+
+    pub fn with_collapsible(mut self, index: usize, collapsible: bool) -> Self {
+        if self.constraints.len() <= index {
+            self.constraints.resize(index + 1, PaneConstraints::default());
+        }
+        self.constraints[index].collapsible = collapsible;
+        self
+    }
+
+    pub fn with_divider_thickness(mut self, thickness: f32) -> Self {
+        self.divider_thickness = thickness;
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_divider_colors(mut self, color: Color, hover_color: Color) -> Self {
+        self.divider_color = color;
+        self.divider_hover_color = hover_color;
+        self.overrides.mark("divider_colors");
+        self.dirty = true;
+        self
+    }
+
+    /// Take the divider colors from `theme` unless set through a builder
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        if !self.overrides.is_set("divider_colors") {
+            self.divider_color = theme.palette.border;
+            self.divider_hover_color = theme.palette.primary;
+            self.dirty = true;
+        }
+    }
+
+    pub fn with_keyboard_step(mut self, step: f32) -> Self {
+        self.keyboard_step = step;
+        self
+    }
+
+    pub fn with_child(mut self, child: Element) -> Self {
+        self.children.push(child);
+        self.dirty = true;
+        self
+    }
+
+    pub fn with_children(mut self, children: Vec<Element>) -> Self {
+        self.children.extend(children);
+        self.dirty = true;
+        self
+    }
+
+    pub fn into_container_element(mut self) -> Element {
+        let children = std::mem::take(&mut self.children);
+        self.set_pane_count(children.len());
+        Element::new_container(Box::new(self), children)
+    }
+
+    pub fn get_direction(&self) -> SplitDirection {
+        self.direction
+    }
+
+    pub fn get_sizes_signal(&self) -> Signal<Vec<f32>> {
+        self.sizes.clone()
+    }
+
+    /// Called during layout with the number of children
+    pub fn set_pane_count(&mut self, count: usize) {
+        if self.pane_count != count {
+            self.pane_count = count;
+            self.restore_sizes.resize(count, None);
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        self.width = width;
+        self.height = height;
+        self.dirty = true;
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    fn constraint(&self, index: usize) -> PaneConstraints {
+        self.constraints.get(index).copied().unwrap_or_default()
+    }
+
+    fn axis_length(&self) -> f32 {
+        match self.direction {
+            SplitDirection::Horizontal => self.width,
+            SplitDirection::Vertical => self.height,
+        }
+    }
+
+    fn axis_coordinate(&self, x: f32, y: f32) -> f32 {
+        match self.direction {
+            SplitDirection::Horizontal => x,
+            SplitDirection::Vertical => y,
+        }
+    }
+
+    fn available_length(&self) -> f32 {
+        let dividers = self.pane_count.saturating_sub(1) as f32;
+        (self.axis_length() - dividers * self.divider_thickness).max(0.0)
+    }
+
+    /// Pane sizes in pixels along the split axis
+    pub fn pane_sizes(&self) -> Vec<f32> {
+        if self.pane_count == 0 {
+            return Vec::new();
+        }
+        let available = self.available_length();
+        let fractions = self.sizes.get();
+        let total: f32 = fractions.iter().sum();
+        if fractions.len() != self.pane_count || total <= 0.0 {
+            return vec![available / self.pane_count as f32; self.pane_count];
+        }
+        fractions.iter().map(|fraction| fraction / total * available).collect()
+    }
+
+    fn store_pane_sizes(&mut self, sizes: &[f32]) {
+        let available = self.available_length();
+        if available <= 0.0 {
+            return;
+        }
+        let fractions: Vec<f32> = sizes.iter().map(|size| size / available).collect();
+        if self.sizes.get() != fractions {
+            self.sizes.set(fractions);
+        }
+        self.dirty = true;
+    }
+
+    /// Bounds (x, y, width, height) of each pane
+    pub fn pane_rects(&self) -> Vec<(f32, f32, f32, f32)> {
+        let mut offset = 0.0;
+        self.pane_sizes().into_iter().map(|size| {
+            let rect = match self.direction {
+                SplitDirection::Horizontal => (self.x + offset, self.y, size, self.height),
+                SplitDirection::Vertical => (self.x, self.y + offset, self.width, size),
+            };
+            offset += size + self.divider_thickness;
+            rect
+        }).collect()
+    }
+
+    fn divider_rects(&self) -> Vec<(f32, f32, f32, f32)> {
+        let rects = self.pane_rects();
+        rects.iter().take(rects.len().saturating_sub(1)).map(|&(x, y, width, height)| {
+            match self.direction {
+                SplitDirection::Horizontal => (x + width, self.y, self.divider_thickness, self.height),
+                SplitDirection::Vertical => (self.x, y + height, self.width, self.divider_thickness),
+            }
+        }).collect()
+    }
+
+    fn divider_at(&self, px: f32, py: f32) -> Option<usize> {
+        self.divider_rects().iter().position(|&(x, y, width, height)| {
+            px >= x && px <= x + width && py >= y && py <= y + height
+        })
+    }
+
+    pub fn move_divider(&mut self, index: usize, delta: f32) {
+        let mut sizes = self.pane_sizes();
+        resize_adjacent_panes(&mut sizes, &self.constraints, index, delta);
+        // A pane dragged open is no longer collapsed
+        for pane in [index, index + 1] {
+            if sizes.get(pane).is_some_and(|size| *size > 0.0) {
+                if let Some(restore) = self.restore_sizes.get_mut(pane) {
+                    *restore = None;
+                }
+            }
+        }
+        self.store_pane_sizes(&sizes);
+    }
+
+    pub fn is_collapsed(&self, pane: usize) -> bool {
+        self.restore_sizes.get(pane).is_some_and(|restore| restore.is_some())
+    }
+
+    /// Collapse the smaller collapsible pane next to the divider, or restore
+    /// whichever of them is collapsed
+    pub fn toggle_collapse(&mut self, divider: usize) {
+        if divider + 1 >= self.pane_count {
+            return;
+        }
+        let mut sizes = self.pane_sizes();
+
+        for (pane, neighbor) in [(divider, divider + 1), (divider + 1, divider)] {
+            if let Some(restore) = self.restore_sizes[pane].take() {
+                let spare = (sizes[neighbor] - self.constraint(neighbor).min).max(0.0);
+                let size = restore.min(spare);
+                sizes[pane] += size;
+                sizes[neighbor] -= size;
+                self.store_pane_sizes(&sizes);
+                return;
+            }
+        }
+
+        let candidates = [(divider, divider + 1), (divider + 1, divider)];
+        let target = candidates.iter()
+            .filter(|(pane, _)| self.constraint(*pane).collapsible)
+            .min_by(|(a, _), (b, _)| sizes[*a].total_cmp(&sizes[*b]))
+            .copied();
+        if let Some((pane, neighbor)) = target {
+            self.restore_sizes[pane] = Some(sizes[pane]);
+            sizes[neighbor] += sizes[pane];
+            sizes[pane] = 0.0;
+            self.store_pane_sizes(&sizes);
+        }
+    }
+
+    /// Divider interaction runs before the panes see the event, so a drag keeps
+    /// working when the pointer crosses into a pane
+    pub fn observe_event(&mut self, event: &Event) -> EventResult {
+        match event {
+            Event::Mouse(mouse_event) => {
+                let x = mouse_event.position.x as f32;
+                let y = mouse_event.position.y as f32;
+
+                if mouse_event.button.is_none() {
+                    // Mouse move
+                    if let Some((index, last)) = self.dragging {
+                        let position = self.axis_coordinate(x, y);
+                        self.move_divider(index, position - last);
+                        self.dragging = Some((index, position));
+                        return EventResult::Handled;
+                    }
+                    let hovered = if self.is_point_inside(x, y) { self.divider_at(x, y) } else { None };
+                    if hovered != self.hovered_divider {
+                        self.hovered_divider = hovered;
+                        self.dirty = true;
+                    }
+                    return EventResult::Ignored;
+                }
+
+                if mouse_event.button != Some(MouseButton::Left) {
+                    return EventResult::Ignored;
+                }
+
+                match mouse_event.state {
+                    ElementState::Pressed => {
+                        let Some(index) = self.divider_at(x, y) else {
+                            if self.focused_divider.take().is_some() {
+                                self.dirty = true;
+                            }
+                            return EventResult::Ignored;
+                        };
+                        self.focused_divider = Some(index);
+                        self.dirty = true;
+
+                        let now = Instant::now();
+                        let double_click = matches!(self.last_click,
+                            Some((last_index, at)) if last_index == index && now.duration_since(at) < DOUBLE_CLICK_TIME);
+                        if double_click {
+                            self.last_click = None;
+                            self.toggle_collapse(index);
+                        } else {
+                            self.last_click = Some((index, now));
+                            self.dragging = Some((index, self.axis_coordinate(x, y)));
+                        }
+                        EventResult::Handled
+                    },
+                    ElementState::Released => {
+                        if self.dragging.take().is_some() {
+                            EventResult::Handled
+                        } else {
+                            EventResult::Ignored
+                        }
+                    },
+                }
+            },
+            Event::Keyboard(keyboard_event) if keyboard_event.state == ElementState::Pressed => {
+                let Some(index) = self.focused_divider else {
+                    return EventResult::Ignored;
+                };
+                let step = if keyboard_event.modifiers.shift_key() { self.keyboard_step * 5.0 } else { self.keyboard_step };
+                match keyboard_event.key_code {
+                    Some(KeyCode::ArrowLeft) | Some(KeyCode::ArrowUp) => self.move_divider(index, -step),
+                    Some(KeyCode::ArrowRight) | Some(KeyCode::ArrowDown) => self.move_divider(index, step),
+                    Some(KeyCode::Home) => self.move_divider(index, -self.axis_length()),
+                    Some(KeyCode::End) => self.move_divider(index, self.axis_length()),
+                    Some(KeyCode::Enter) => self.toggle_collapse(index),
+                    Some(KeyCode::Escape) => {
+                        self.focused_divider = None;
+                        self.dirty = true;
+                    },
+                    _ => return EventResult::Ignored,
+                }
+                EventResult::Handled
+            },
+            _ => EventResult::Ignored,
+        }
+    }
+
+    pub fn create_divider_rectangles(&self) -> Vec<Rectangle> {
+        self.divider_rects().into_iter().enumerate().map(|(index, (x, y, width, height))| {
+            let active = self.hovered_divider == Some(index)
+                || self.focused_divider == Some(index)
+                || self.dragging.is_some_and(|(dragged, _)| dragged == index);
+            let color = if active { self.divider_hover_color } else { self.divider_color };
+            Rectangle::new(x, y, width, height, color)
+        }).collect()
+    }
+}
+
+impl Widget for SplitterWidget {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        self.dirty = true;
+
+        let reactive_dirty = self.reactive_dirty.clone();
+        self.sizes.subscribe_fn(move |_| {
+            if let Ok(mut dirty) = reactive_dirty.write() {
+                *dirty = true;
+            }
+        });
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        self.dragging = None;
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        if let Ok(mut reactive_dirty) = self.reactive_dirty.write() {
+            if *reactive_dirty {
+                *reactive_dirty = false;
+                self.dirty = true;
+            }
+        }
+
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> EventResult {
+        // Divider input is handled in observe_event before the panes
+        EventResult::Ignored
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            }],
+            z_index: 0,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+// Convenience functions for creating splitters
+pub fn hsplit() -> SplitterWidget {
+    SplitterWidget::new(SplitDirection::Horizontal)
+}
+
+pub fn vsplit() -> SplitterWidget {
+    SplitterWidget::new(SplitDirection::Vertical)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resize_respects_both_panes_limits() {
+        let constraints = [
+            PaneConstraints { min: 50.0, max: 300.0, collapsible: true },
+            PaneConstraints { min: 100.0, ..Default::default() },
+        ];
+        let mut sizes = vec![200.0, 200.0];
+        resize_adjacent_panes(&mut sizes, &constraints, 0, 500.0);
+        assert_eq!(sizes, vec![300.0, 100.0]);
+        resize_adjacent_panes(&mut sizes, &constraints, 0, -500.0);
+        assert_eq!(sizes, vec![50.0, 350.0]);
+    }
+
+    #[test]
+    fn collapse_and_restore_through_sizes_signal() {
+        let sizes = Signal::new(vec![0.25, 0.75]);
+        let mut splitter = SplitterWidget::new(SplitDirection::Horizontal)
+            .with_size(406.0, 100.0)
+            .with_sizes_signal(sizes.clone());
+        splitter.set_pane_count(2);
+
+        splitter.toggle_collapse(0);
+        assert!(splitter.is_collapsed(0));
+        assert_eq!(sizes.get(), vec![0.0, 1.0]);
+
+        splitter.toggle_collapse(0);
+        assert!(!splitter.is_collapsed(0));
+        assert_eq!(sizes.get(), vec![0.25, 0.75]);
+    }
+}
@@ -6,6 +6,9 @@ use std::any::Any;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use vello::peniko::Color;
+use crate::theme::{current_theme, StyleOverrides, Theme};
+use crate::style::StyleSheet;
+use crate::media_query::ViewportSize;
 
 static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
 
@@ -21,42 +24,59 @@ pub struct TextWidget {
     shadow: Option<Shadow>,
     pub dirty: bool,
     text_renderer: Option<TextRenderer>,
+    // Style sheet classes, applied between the theme and explicit overrides
+    classes: Vec<String>,
+    // Style set through builders, which the theme leaves alone
+    overrides: StyleOverrides,
     // Shared dirty flag that reactive signals can set
     reactive_dirty: Arc<RwLock<bool>>,
 }
 
 impl TextWidget {
     pub fn new(content: String) -> Self {
+        let theme = current_theme();
         Self {
             id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
             content: Signal::new(content),
-            color: Signal::new(Color::rgba8(0, 0, 0, 255)), // Black by default
-            font_size: Signal::new(14.0),
-            font_weight: Signal::new(400),
+            color: Signal::new(theme.palette.text),
+            font_size: Signal::new(theme.typography.font_size),
+            font_weight: Signal::new(theme.typography.font_weight),
             italic: Signal::new(false),
             x: 0.0,
             y: 0.0,
             shadow: None,
             dirty: true,
             text_renderer: None,
+            classes: Vec::new(),
+            overrides: StyleOverrides::default(),
             reactive_dirty: Arc::new(RwLock::new(false)),
         }
     }
 
     pub fn with_color(mut self, color: Color) -> Self {
         self.color = Signal::new(color);
+        self.overrides.mark("color");
         self.dirty = true;
         self
     }
 
     pub fn with_font_size(mut self, size: f32) -> Self {
         self.font_size = Signal::new(size);
+        self.overrides.mark("font_size");
         self.dirty = true;
         self
     }
 
     pub fn with_font_weight(mut self, weight: u16) -> Self {
         self.font_weight = Signal::new(weight);
+        self.overrides.mark("font_weight");
+        self.dirty = true;
+        self
+    }
+
+    /// Style this text from a style sheet class; can be called repeatedly
+    pub fn with_class(mut self, class: impl Into<String>) -> Self {
+        self.classes.push(class.into());
         self.dirty = true;
         self
     }
@@ -91,9 +111,46 @@ impl TextWidget {
 
     pub fn set_color(&mut self, color: Color) {
         self.color.set(color);
+        self.overrides.mark("color");
+        self.dirty = true;
+    }
+
+    /// Take every style property not set through a builder from `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        if !self.overrides.is_set("color") {
+            self.color.set(theme.palette.text);
+        }
+        if !self.overrides.is_set("font_size") {
+            self.font_size.set(theme.typography.font_size);
+        }
+        if !self.overrides.is_set("font_weight") {
+            self.font_weight.set(theme.typography.font_weight);
+        }
         self.dirty = true;
     }
 
+    /// Layer this text's classes over the theme
+    pub fn apply_style_sheet(&mut self, sheet: &StyleSheet, viewport: ViewportSize) {
+        if self.classes.is_empty() {
+            return;
+        }
+        let style = sheet.resolve(&self.classes, None, viewport);
+        if let (false, Some(color)) = (self.overrides.is_set("color"), style.text_color) {
+            self.color.set(color);
+        }
+        if let (false, Some(size)) = (self.overrides.is_set("font_size"), style.font_size) {
+            self.font_size.set(size);
+        }
+        if let (false, Some(weight)) = (self.overrides.is_set("font_weight"), style.font_weight) {
+            self.font_weight.set(weight);
+        }
+        self.dirty = true;
+    }
+
+    pub fn get_classes(&self) -> &[String] {
+        &self.classes
+    }
+
     pub fn get_content(&self) -> String {
         self.content.get()
     }
@@ -0,0 +1,709 @@
+use crate::{Widget, WidgetId, WidgetError, RenderData, DirtyRegion, WidgetUpdateContext};
+use crate::event::Event;
+use crate::element::Element;
+use crate::overlay::{global_overlay_layer, OverlayId};
+use crate::theme::{current_theme, StyleOverrides, Theme};
+use winit::event::ElementState;
+use gui_reactive::global_frame_scheduler;
+use gui_render::primitives::{Rectangle, Shadow, Text};
+use std::any::Any;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use vello::peniko::Color;
+use super::container::{Background, Padding};
+
+static WIDGET_ID_COUNTER: AtomicU64 = AtomicU64::new(6000);
+
+const TOOLTIP_Z_INDEX: i32 = 100;
+
+/// What a tooltip shows: plain text or an arbitrary element tree
+pub enum TooltipContent {
+    Text(String),
+    Element(Element),
+}
+
+impl From<&str> for TooltipContent {
+    fn from(text: &str) -> Self {
+        TooltipContent::Text(text.to_string())
+    }
+}
+
+impl From<String> for TooltipContent {
+    fn from(text: String) -> Self {
+        TooltipContent::Text(text)
+    }
+}
+
+impl From<Element> for TooltipContent {
+    fn from(element: Element) -> Self {
+        TooltipContent::Element(element)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TooltipSide {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TooltipPlacement {
+    /// Follow the pointer, offset slightly below and to the right
+    FollowPointer,
+    /// Anchor to a side of the target widget
+    Anchor(TooltipSide),
+}
+
+/// The bubble that is pushed onto the overlay layer while a tooltip is visible
+pub struct TooltipBubbleWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    text: Option<String>,
+    background: Background,
+    text_color: Color,
+    font_size: f32,
+    border_radius: f32,
+    padding: Padding,
+    shadow: Option<Shadow>,
+    overrides: StyleOverrides,
+    pub dirty: bool,
+}
+
+impl TooltipBubbleWidget {
+    pub fn new() -> Self {
+        let theme = current_theme();
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+            text: None,
+            // Inverted against the page so the bubble stands out in either mode
+            background: Background::Color(theme.palette.text),
+            text_color: theme.palette.background,
+            font_size: 12.0,
+            border_radius: theme.radii.small,
+            padding: Padding::symmetric(4.0, 8.0),
+            shadow: Some(theme.shadows.medium.to_shadow()),
+            overrides: StyleOverrides::default(),
+            dirty: true,
+        }
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn set_background(&mut self, background: Background) {
+        self.background = background;
+        self.overrides.mark("background");
+        self.dirty = true;
+    }
+
+    pub fn set_text_color(&mut self, color: Color) {
+        self.text_color = color;
+        self.overrides.mark("text_color");
+        self.dirty = true;
+    }
+
+    /// Take every style property not set through a builder from `theme`
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        if !self.overrides.is_set("background") {
+            self.background = Background::Color(theme.palette.text);
+        }
+        if !self.overrides.is_set("text_color") {
+            self.text_color = theme.palette.background;
+        }
+        self.border_radius = theme.radii.small;
+        self.shadow = Some(theme.shadows.medium.to_shadow());
+        self.dirty = true;
+    }
+
+    pub fn get_content_area(&self) -> (f32, f32, f32, f32) {
+        (
+            self.x + self.padding.left,
+            self.y + self.padding.top,
+            self.width - self.padding.left - self.padding.right,
+            self.height - self.padding.top - self.padding.bottom,
+        )
+    }
+
+    pub fn create_background_rectangle(&self) -> Rectangle {
+        Rectangle::new_with_brush(self.x, self.y, self.width, self.height, self.background.to_brush())
+            .with_border_radius(self.border_radius)
+    }
+
+    pub fn create_shadow(&self) -> Option<Shadow> {
+        self.shadow.as_ref().map(|shadow| {
+            Shadow::new(self.x, self.y, self.width, self.height,
+                       shadow.offset_x, shadow.offset_y, shadow.blur_radius, shadow.color)
+        })
+    }
+
+    pub fn create_text_primitive(&self) -> Option<Text> {
+        self.text.as_ref().map(|text| {
+            let text_x = self.x + self.padding.left;
+            let text_y = self.y + (self.height / 2.0) + (self.font_size * 0.25);
+            Text::new(text_x, text_y, text.clone(), self.text_color, self.font_size)
+        })
+    }
+}
+
+impl Widget for TooltipBubbleWidget {
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        self.dirty
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            }],
+            z_index: TOOLTIP_Z_INDEX,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TooltipPhase {
+    Idle,
+    // Pointer entered the target at this frame time, waiting for the show delay
+    Pending(Duration),
+    Visible,
+    // Pointer left the target at this frame time, waiting for the hide delay
+    Hiding(Duration),
+}
+
+/// Wraps a target element and shows a tooltip bubble after the pointer rests on it.
+///
+/// Used as the widget of an `Element::Container` whose single child is the target.
+pub struct TooltipWidget {
+    id: WidgetId,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    bubble: Option<Element>,
+    bubble_size: (f32, f32),
+    placement: TooltipPlacement,
+    show_delay: Duration,
+    hide_delay: Duration,
+    offset: f32,
+    phase: TooltipPhase,
+    pointer: (f32, f32),
+    viewport: (f32, f32),
+    overlay_id: Option<OverlayId>,
+    enabled: bool,
+    pub dirty: bool,
+}
+
+impl TooltipWidget {
+    pub fn new(content: impl Into<TooltipContent>) -> Self {
+        let mut bubble = TooltipBubbleWidget::new();
+        let (bubble_element, bubble_size) = match content.into() {
+            TooltipContent::Text(text) => {
+                // Approximate width per character, as TextWidget estimates its bounds
+                let width = text.chars().count() as f32 * bubble.font_size * 0.6 + bubble.padding.left + bubble.padding.right;
+                let height = bubble.font_size * 1.2 + bubble.padding.top + bubble.padding.bottom;
+                bubble.width = width;
+                bubble.height = height;
+                bubble.text = Some(text);
+                (Element::new_widget(Box::new(bubble)), (width, height))
+            },
+            TooltipContent::Element(element) => {
+                bubble.width = 220.0;
+                bubble.height = 80.0;
+                (Element::new_container(Box::new(bubble), vec![element]), (220.0, 80.0))
+            },
+        };
+
+        Self {
+            id: WIDGET_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+            bubble: Some(bubble_element),
+            bubble_size,
+            placement: TooltipPlacement::Anchor(TooltipSide::Bottom),
+            show_delay: Duration::from_millis(500),
+            hide_delay: Duration::from_millis(100),
+            offset: 6.0,
+            phase: TooltipPhase::Idle,
+            pointer: (0.0, 0.0),
+            viewport: (800.0, 600.0),
+            overlay_id: None,
+            enabled: true,
+            dirty: true,
+        }
+    }
+
+    pub fn with_placement(mut self, placement: TooltipPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    pub fn follow_pointer(mut self) -> Self {
+        self.placement = TooltipPlacement::FollowPointer;
+        self
+    }
+
+    pub fn with_show_delay(mut self, delay: Duration) -> Self {
+        self.show_delay = delay;
+        self
+    }
+
+    pub fn with_hide_delay(mut self, delay: Duration) -> Self {
+        self.hide_delay = delay;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Size of the bubble. Text tooltips are sized from their text by default,
+    /// element tooltips default to 220x80.
+    pub fn with_tooltip_size(mut self, width: f32, height: f32) -> Self {
+        self.bubble_size = (width, height);
+        if let Some(bubble) = self.bubble.as_mut().and_then(Self::bubble_widget_mut) {
+            bubble.width = width;
+            bubble.height = height;
+        }
+        self
+    }
+
+    pub fn with_background(mut self, background: Background) -> Self {
+        if let Some(bubble) = self.bubble.as_mut().and_then(Self::bubble_widget_mut) {
+            bubble.set_background(background);
+        }
+        self
+    }
+
+    pub fn with_text_color(mut self, color: Color) -> Self {
+        if let Some(bubble) = self.bubble.as_mut().and_then(Self::bubble_widget_mut) {
+            bubble.set_text_color(color);
+        }
+        self
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.hide();
+        }
+    }
+
+    /// Wrap the target element, producing the container element to place in the tree
+    pub fn wrap(self, target: Element) -> Element {
+        Element::new_container(Box::new(self), vec![target])
+    }
+
+    /// Theme the hidden bubble and its content; while shown it is themed
+    /// with the rest of the overlay layer
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        if let Some(bubble) = self.bubble.as_mut() {
+            bubble.apply_theme(theme);
+        }
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        if self.x != x || self.y != y {
+            self.x = x;
+            self.y = y;
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        self.width = width;
+        self.height = height;
+    }
+
+    pub fn get_position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.overlay_id.is_some()
+    }
+
+    pub fn is_point_inside(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width &&
+        y >= self.y && y <= self.y + self.height
+    }
+
+    fn bubble_widget_mut(element: &mut Element) -> Option<&mut TooltipBubbleWidget> {
+        match element {
+            Element::Widget(widget) | Element::Container { widget, .. } => {
+                widget.as_any_mut().downcast_mut::<TooltipBubbleWidget>()
+            },
+            Element::Fragment(_) => None,
+        }
+    }
+
+    /// Observe pointer events before the target sees them, so a target that
+    /// consumes hover events still gets a tooltip
+    pub fn observe_event(&mut self, event: &Event) {
+        let Event::Mouse(mouse_event) = event else {
+            return;
+        };
+        if !self.enabled {
+            return;
+        }
+
+        let x = mouse_event.position.x as f32;
+        let y = mouse_event.position.y as f32;
+        let inside = self.is_point_inside(x, y);
+        let now = global_frame_scheduler().frame_timestamp();
+        self.pointer = (x, y);
+
+        if mouse_event.button.is_some() {
+            // Any click dismisses the tooltip until the pointer re-enters
+            if mouse_event.state == ElementState::Pressed {
+                self.hide();
+            }
+            return;
+        }
+
+        match self.phase {
+            TooltipPhase::Idle if inside => {
+                self.phase = TooltipPhase::Pending(now);
+                global_frame_scheduler().request_update();
+            },
+            TooltipPhase::Pending(_) if !inside => {
+                self.phase = TooltipPhase::Idle;
+            },
+            TooltipPhase::Visible if !inside => {
+                self.phase = TooltipPhase::Hiding(now);
+                global_frame_scheduler().request_update();
+            },
+            TooltipPhase::Visible if self.placement == TooltipPlacement::FollowPointer => {
+                self.reposition_bubble();
+                global_frame_scheduler().request_update();
+            },
+            TooltipPhase::Hiding(_) if inside => {
+                self.phase = TooltipPhase::Visible;
+            },
+            _ => {}
+        }
+    }
+
+    /// Show or hide once a pending delay has passed at frame time `now`;
+    /// returns whether a delay is still running
+    fn advance(&mut self, now: Duration) -> bool {
+        match self.phase {
+            TooltipPhase::Pending(since) => {
+                if now.saturating_sub(since) >= self.show_delay {
+                    self.show();
+                    return false;
+                }
+                true
+            },
+            TooltipPhase::Hiding(since) => {
+                if now.saturating_sub(since) >= self.hide_delay {
+                    self.hide();
+                    return false;
+                }
+                true
+            },
+            _ => false,
+        }
+    }
+
+    fn bubble_position(&self) -> (f32, f32) {
+        let (viewport_width, viewport_height) = self.viewport;
+        let (bubble_width, bubble_height) = self.bubble_size;
+        let (x, y) = match self.placement {
+            TooltipPlacement::FollowPointer => {
+                (self.pointer.0 + 12.0, self.pointer.1 + 16.0)
+            },
+            TooltipPlacement::Anchor(TooltipSide::Bottom) => {
+                (self.x + (self.width - bubble_width) / 2.0, self.y + self.height + self.offset)
+            },
+            TooltipPlacement::Anchor(TooltipSide::Top) => {
+                (self.x + (self.width - bubble_width) / 2.0, self.y - bubble_height - self.offset)
+            },
+            TooltipPlacement::Anchor(TooltipSide::Left) => {
+                (self.x - bubble_width - self.offset, self.y + (self.height - bubble_height) / 2.0)
+            },
+            TooltipPlacement::Anchor(TooltipSide::Right) => {
+                (self.x + self.width + self.offset, self.y + (self.height - bubble_height) / 2.0)
+            },
+        };
+
+        // Keep the bubble inside the window
+        (
+            x.min(viewport_width - bubble_width).max(0.0),
+            y.min(viewport_height - bubble_height).max(0.0),
+        )
+    }
+
+    fn reposition_bubble(&mut self) {
+        let Some(overlay_id) = self.overlay_id else {
+            return;
+        };
+        let (x, y) = self.bubble_position();
+        global_overlay_layer().with_element_mut(overlay_id, |element| {
+            if let Some(bubble) = Self::bubble_widget_mut(element) {
+                bubble.set_position(x, y);
+            }
+        });
+    }
+
+    fn show(&mut self) {
+        if self.overlay_id.is_some() {
+            return;
+        }
+        let (x, y) = self.bubble_position();
+        if let Some(mut bubble) = self.bubble.take() {
+            if let Some(bubble_widget) = Self::bubble_widget_mut(&mut bubble) {
+                bubble_widget.set_position(x, y);
+                bubble_widget.dirty = true;
+            }
+            self.overlay_id = Some(global_overlay_layer().push_with_owner(bubble, TOOLTIP_Z_INDEX, Some(self.id)));
+        }
+        self.phase = TooltipPhase::Visible;
+    }
+
+    fn hide(&mut self) {
+        if let Some(overlay_id) = self.overlay_id.take() {
+            if let Some(bubble) = global_overlay_layer().remove(overlay_id) {
+                self.bubble = Some(bubble);
+            }
+        }
+        self.phase = TooltipPhase::Idle;
+    }
+}
+
+impl Widget for TooltipWidget {
+    fn mount(&mut self) -> Result<(), WidgetError> {
+        if let Some(bubble) = self.bubble.as_mut() {
+            bubble.mount()?;
+        }
+        self.dirty = true;
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> Result<(), WidgetError> {
+        self.hide();
+        if let Some(bubble) = self.bubble.as_mut() {
+            bubble.unmount()?;
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut dyn WidgetUpdateContext) -> Result<(), WidgetError> {
+        let viewport = ctx.viewport_size();
+        self.viewport = (viewport.width, viewport.height);
+        if self.advance(global_frame_scheduler().frame_timestamp()) {
+            // Keep frames coming so the end of the delay is noticed
+            global_frame_scheduler().request_update();
+        }
+
+        if self.dirty {
+            ctx.mark_dirty(self.id);
+        }
+        Ok(())
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn needs_render(&self) -> bool {
+        // The target decides its own rendering; the wrapper draws nothing
+        true
+    }
+
+    fn render(&self) -> Result<RenderData, WidgetError> {
+        Ok(RenderData {
+            dirty_regions: vec![DirtyRegion {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            }],
+            z_index: 0,
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+impl Element {
+    /// Show a tooltip when the pointer rests on this element
+    pub fn with_tooltip(self, content: impl Into<TooltipContent>) -> Element {
+        TooltipWidget::new(content).wrap(self)
+    }
+}
+
+// Convenience function for creating tooltips
+pub fn tooltip(content: impl Into<TooltipContent>) -> TooltipWidget {
+    TooltipWidget::new(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{MouseEvent, Point};
+    use winit::event::MouseButton;
+
+    fn pointer(x: f64, y: f64, button: Option<MouseButton>) -> Event {
+        Event::Mouse(MouseEvent {
+            position: Point::new(x, y),
+            button,
+            state: if button.is_some() { ElementState::Pressed } else { ElementState::Released },
+            modifiers: Default::default(),
+        })
+    }
+
+    // An 80x24 target at (100, 100) with a 60x20 bubble
+    fn target_tooltip() -> TooltipWidget {
+        let mut tooltip = tooltip("Save the scene")
+            .with_show_delay(Duration::from_millis(500))
+            .with_hide_delay(Duration::from_millis(100))
+            .with_tooltip_size(60.0, 20.0);
+        tooltip.set_position(100.0, 100.0);
+        tooltip.set_size(80.0, 24.0);
+        tooltip
+    }
+
+    fn phase_time(tooltip: &TooltipWidget) -> Duration {
+        match tooltip.phase {
+            TooltipPhase::Pending(since) | TooltipPhase::Hiding(since) => since,
+            phase => panic!("no pending delay in {:?}", phase),
+        }
+    }
+
+    fn show_now(tooltip: &mut TooltipWidget) {
+        tooltip.observe_event(&pointer(120.0, 110.0, None));
+        let entered = phase_time(tooltip);
+        tooltip.advance(entered + tooltip.show_delay);
+        assert!(tooltip.is_visible());
+    }
+
+    #[test]
+    fn shows_after_the_delay() {
+        let mut tooltip = target_tooltip();
+        tooltip.observe_event(&pointer(120.0, 110.0, None));
+        let entered = phase_time(&tooltip);
+
+        // Still waiting, so the tooltip asks for another frame
+        assert!(tooltip.advance(entered + Duration::from_millis(499)));
+        assert!(!tooltip.is_visible());
+
+        assert!(!tooltip.advance(entered + Duration::from_millis(500)));
+        assert!(tooltip.is_visible());
+        tooltip.hide();
+
+        // Leaving before the delay cancels it
+        tooltip.observe_event(&pointer(120.0, 110.0, None));
+        tooltip.observe_event(&pointer(20.0, 20.0, None));
+        tooltip.advance(entered + Duration::from_secs(5));
+        assert!(!tooltip.is_visible());
+    }
+
+    #[test]
+    fn hides_after_leaving_or_on_press() {
+        let mut tooltip = target_tooltip();
+        show_now(&mut tooltip);
+
+        tooltip.observe_event(&pointer(20.0, 20.0, None));
+        let left = phase_time(&tooltip);
+        tooltip.advance(left + Duration::from_millis(99));
+        assert!(tooltip.is_visible());
+        tooltip.advance(left + Duration::from_millis(100));
+        assert!(!tooltip.is_visible());
+        assert!(tooltip.bubble.is_some());
+
+        show_now(&mut tooltip);
+        tooltip.observe_event(&pointer(120.0, 110.0, Some(MouseButton::Left)));
+        assert!(!tooltip.is_visible());
+        assert_eq!(tooltip.phase, TooltipPhase::Idle);
+    }
+
+    #[test]
+    fn bubble_is_placed_beside_the_target_and_kept_in_the_window() {
+        let mut tooltip = target_tooltip();
+        assert_eq!(tooltip.bubble_position(), (110.0, 130.0));
+
+        tooltip.placement = TooltipPlacement::Anchor(TooltipSide::Top);
+        assert_eq!(tooltip.bubble_position(), (110.0, 74.0));
+        tooltip.placement = TooltipPlacement::Anchor(TooltipSide::Right);
+        assert_eq!(tooltip.bubble_position(), (186.0, 102.0));
+
+        tooltip.placement = TooltipPlacement::FollowPointer;
+        tooltip.observe_event(&pointer(790.0, 590.0, None));
+        assert_eq!(tooltip.bubble_position(), (740.0, 580.0));
+
+        // The shown bubble sits where the placement puts it
+        tooltip.placement = TooltipPlacement::Anchor(TooltipSide::Bottom);
+        show_now(&mut tooltip);
+        let overlay_id = tooltip.overlay_id.unwrap();
+        let position = global_overlay_layer()
+            .with_element_mut(overlay_id, |element| TooltipWidget::bubble_widget_mut(element).map(|bubble| bubble.get_position()))
+            .flatten();
+        assert_eq!(position, Some((110.0, 130.0)));
+        tooltip.hide();
+    }
+}
@@ -0,0 +1,213 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{Data, DeriveInput, Expr, Fields, LitStr, Token};
+
+#[derive(Default)]
+struct FieldOptions {
+    label: Option<String>,
+    group: Option<String>,
+    range: Option<(Expr, Expr)>,
+    step: Option<Expr>,
+    unit: Option<String>,
+    options: Option<Vec<String>>,
+    read_only: bool,
+    hidden: bool,
+}
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(name, "Inspect can only be derived for structs with named fields")),
+        },
+        _ => return Err(syn::Error::new_spanned(name, "Inspect can only be derived for structs")),
+    };
+
+    let mut default_group = title_case(&name.to_string());
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("inspect")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("group") {
+                default_group = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else {
+                Err(meta.error("expected `group = \"..\"`"))
+            }
+        })?;
+    }
+
+    let mut definitions = Vec::new();
+    let mut apply_arms = Vec::new();
+
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let options = parse_field_options(field)?;
+        if options.hidden {
+            continue;
+        }
+
+        let key = ident.to_string().trim_start_matches("r#").to_string();
+        let label = options.label.clone().unwrap_or_else(|| title_case(&key));
+        let group = options.group.clone().unwrap_or_else(|| default_group.clone());
+
+        let value = if options.options.is_some() {
+            quote! {
+                match ::gui_core::InspectValue::to_property_value(&self.#ident) {
+                    ::gui_core::PropertyValue::Text(text) => ::gui_core::PropertyValue::Select(text),
+                    other => other,
+                }
+            }
+        } else {
+            quote! { ::gui_core::InspectValue::to_property_value(&self.#ident) }
+        };
+
+        let property_type = if options.read_only {
+            quote! { ::gui_core::PropertyType::ReadOnly }
+        } else if let Some((min, max)) = &options.range {
+            let step = options.step.as_ref().map(|step| quote! { (#step) as f32 }).unwrap_or_else(|| quote! { 0.0 });
+            quote! { ::gui_core::PropertyType::Range { min: (#min) as f32, max: (#max) as f32, step: #step } }
+        } else if let Some(choices) = &options.options {
+            quote! {
+                ::gui_core::PropertyType::Dropdown(vec![
+                    #( ::gui_core::DropdownOption::new(#choices, #choices) ),*
+                ])
+            }
+        } else {
+            quote! { <#ty as ::gui_core::InspectValue>::property_type() }
+        };
+
+        let unit = match options.unit.as_deref() {
+            None | Some("") => quote! { ::gui_core::NumberUnit::None },
+            Some("px") => quote! { ::gui_core::NumberUnit::Pixels },
+            Some("deg") | Some("°") => quote! { ::gui_core::NumberUnit::Degrees },
+            Some("%") => quote! { ::gui_core::NumberUnit::Percent },
+            Some(other) => quote! { ::gui_core::NumberUnit::Custom(#other.to_string()) },
+        };
+
+        definitions.push(quote! {
+            ::gui_core::inspect::push_property(&mut groups, #group, ::gui_core::PropertyDefinition {
+                key: #key.to_string(),
+                label: #label.to_string(),
+                value: #value,
+                property_type: #property_type,
+                unit: #unit,
+                default: ::std::option::Option::None,
+                binding: ::std::option::Option::None,
+            });
+        });
+
+        if !options.read_only {
+            apply_arms.push(quote! {
+                #key => match <#ty as ::gui_core::InspectValue>::from_property_value(value) {
+                    Some(new_value) => {
+                        self.#ident = new_value;
+                        true
+                    },
+                    None => false,
+                },
+            });
+        }
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::gui_core::Inspect for #name #type_generics #where_clause {
+            fn inspector_groups(&self) -> ::std::vec::Vec<::gui_core::PropertyGroup> {
+                let mut groups = ::std::vec::Vec::new();
+                #(#definitions)*
+                groups
+            }
+
+            fn apply_property(&mut self, key: &str, value: &::gui_core::PropertyValue) -> bool {
+                match key {
+                    #(#apply_arms)*
+                    _ => false,
+                }
+            }
+        }
+    })
+}
+
+fn parse_field_options(field: &syn::Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("inspect")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("label") {
+                options.label = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("group") {
+                options.group = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("unit") {
+                options.unit = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("step") {
+                options.step = Some(meta.value()?.parse::<Expr>()?);
+            } else if meta.path.is_ident("read_only") {
+                options.read_only = true;
+            } else if meta.path.is_ident("hidden") {
+                options.hidden = true;
+            } else if meta.path.is_ident("range") {
+                let mut min = None;
+                let mut max = None;
+                meta.parse_nested_meta(|bound| {
+                    if bound.path.is_ident("min") {
+                        min = Some(bound.value()?.parse::<Expr>()?);
+                        Ok(())
+                    } else if bound.path.is_ident("max") {
+                        max = Some(bound.value()?.parse::<Expr>()?);
+                        Ok(())
+                    } else {
+                        Err(bound.error("expected `min` or `max`"))
+                    }
+                })?;
+                match (min, max) {
+                    (Some(min), Some(max)) => options.range = Some((min, max)),
+                    _ => return Err(meta.error("`range` needs both `min = ..` and `max = ..`")),
+                }
+            } else if meta.path.is_ident("options") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let choices = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+                options.options = Some(choices.iter().map(LitStr::value).collect());
+            } else {
+                return Err(meta.error("unknown inspect attribute"));
+            }
+            Ok(())
+        })?;
+    }
+
+    if options.step.is_some() && options.range.is_none() {
+        return Err(syn::Error::new_spanned(field, "`step` only applies together with `range(..)`"));
+    }
+
+    Ok(options)
+}
+
+/// `max_speed` -> `Max Speed`
+fn title_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c == '_' {
+            previous = Some(' ');
+            continue;
+        }
+        let word_start = match previous {
+            None | Some(' ') => true,
+            // Split CamelCase struct names like `MeshRenderer`
+            Some(p) => c.is_uppercase() && p.is_lowercase(),
+        };
+        if word_start && previous.is_some() {
+            result.push(' ');
+        }
+        if word_start {
+            result.extend(c.to_uppercase());
+        } else {
+            result.push(c);
+        }
+        previous = Some(c);
+    }
+    result.trim().to_string()
+}
@@ -0,0 +1,46 @@
+//! Procedural macros for CommonUI. Use them through `gui_core`, which re-exports them.
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod inspect;
+mod ui;
+
+/// Derive `gui_core::Inspect`, turning a struct's fields into inspector property
+/// groups and applying edited values back onto them. See `gui_core::Inspect` for
+/// the supported `#[inspect(..)]` attributes.
+#[proc_macro_derive(Inspect, attributes(inspect))]
+pub fn derive_inspect(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    inspect::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Build an element tree from a declarative description.
+///
+/// ```ignore
+/// ui! {
+///     column(gap: 20.0, size: (400.0, 300.0)) {
+///         text("Hello").with_font_size(24.0),
+///         button("Click", on_click: move || println!("clicked")),
+///         if show_details { text("Details") } else { text("Nothing to show") }
+///         for item in &items { text(item.name.clone()) }
+///         { existing_element }
+///     }
+/// }
+/// ```
+///
+/// A widget is a constructor call such as `text("..")`. Named arguments become
+/// builder calls: `gap: 20.0` calls `.with_gap(20.0)`, a tuple like `size: (400.0, 300.0)`
+/// is spread over the arguments, `on_*` keys are called as written, and
+/// `value: @signal` binds a signal through `.with_value_signal(signal.clone())`.
+/// Arguments are passed through unchanged, so they must already have the type
+/// the builder takes (`20.0`, not `20`, for an `f32`).
+/// Children in braces are attached with `with_child`. Several top-level elements
+/// produce an `Element::Fragment`.
+#[proc_macro]
+pub fn ui(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ui::UiInput);
+    ui::expand(input).into()
+}
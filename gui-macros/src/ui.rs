@@ -0,0 +1,351 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{braced, parenthesized, Block, Expr, Ident, Pat, Path, Stmt, Token};
+
+/// Everything inside `ui! { .. }`: one or more elements
+pub struct UiInput {
+    nodes: Vec<Node>,
+}
+
+enum Node {
+    Widget(WidgetNode),
+    If(IfNode),
+    // Boxed to keep `Node` small; a `for` carries a pattern and an expression
+    For(Box<ForNode>),
+    // `{ expr }`: anything that converts into an element
+    Splice(Block),
+}
+
+struct WidgetNode {
+    path: Path,
+    positional: Vec<Expr>,
+    named: Vec<NamedArg>,
+    // `.method(args)` calls written after the widget, passed through as is
+    chains: Vec<(Ident, Punctuated<Expr, Token![,]>)>,
+    children: Option<(Span, Vec<Node>)>,
+}
+
+struct NamedArg {
+    key: Ident,
+    // `key: @signal` binds a signal through `with_<key>_signal`
+    bind: bool,
+    value: Expr,
+}
+
+struct IfNode {
+    condition: TokenStream,
+    then_nodes: Vec<Node>,
+    else_branch: Option<ElseBranch>,
+}
+
+enum ElseBranch {
+    If(Box<IfNode>),
+    Nodes(Vec<Node>),
+}
+
+struct ForNode {
+    pat: Pat,
+    iter: Expr,
+    body: Vec<Node>,
+}
+
+impl Parse for UiInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let nodes = parse_nodes(input)?;
+        if nodes.is_empty() {
+            return Err(input.error("`ui!` needs at least one element, e.g. `ui! { text(\"Hello\") }`"));
+        }
+        Ok(Self { nodes })
+    }
+}
+
+/// Comma-separated nodes; the comma is optional after a node ending in `}`
+fn parse_nodes(input: ParseStream) -> syn::Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    while !input.is_empty() {
+        let node = input.parse::<Node>()?;
+        let ends_with_brace = node.ends_with_brace();
+        nodes.push(node);
+        if input.is_empty() {
+            break;
+        }
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        } else if !ends_with_brace {
+            return Err(input.error("expected `,` between elements"));
+        }
+    }
+    Ok(nodes)
+}
+
+fn parse_braced_nodes(input: ParseStream) -> syn::Result<(Span, Vec<Node>)> {
+    let content;
+    let brace = braced!(content in input);
+    Ok((brace.span.join(), parse_nodes(&content)?))
+}
+
+impl Node {
+    fn ends_with_brace(&self) -> bool {
+        match self {
+            Node::Widget(widget) => widget.children.is_some(),
+            Node::If(_) | Node::For(_) | Node::Splice(_) => true,
+        }
+    }
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![if]) {
+            Ok(Node::If(input.parse()?))
+        } else if input.peek(Token![for]) {
+            Ok(Node::For(Box::new(input.parse()?)))
+        } else if input.peek(syn::token::Brace) {
+            Ok(Node::Splice(input.parse()?))
+        } else if input.peek(Ident)
+            || input.peek(Token![::])
+            || input.peek(Token![crate])
+            || input.peek(Token![self])
+            || input.peek(Token![super])
+        {
+            Ok(Node::Widget(input.parse()?))
+        } else {
+            Err(input.error(
+                "expected an element: a widget call like `text(\"..\")`, an `if`, a `for`, or a `{ expression }`",
+            ))
+        }
+    }
+}
+
+impl Parse for WidgetNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = Path::parse_mod_style(input)?;
+        if !input.peek(syn::token::Paren) {
+            return Err(syn::Error::new_spanned(
+                &path,
+                "expected `(` after the widget name; wrap existing elements in braces: `{ my_element }`",
+            ));
+        }
+
+        let args;
+        parenthesized!(args in input);
+        let mut positional = Vec::new();
+        let mut named = Vec::new();
+        while !args.is_empty() {
+            if args.peek(Ident) && args.peek2(Token![:]) && !args.peek2(Token![::]) {
+                let key: Ident = args.parse()?;
+                args.parse::<Token![:]>()?;
+                let bind = args.peek(Token![@]);
+                if bind {
+                    args.parse::<Token![@]>()?;
+                }
+                named.push(NamedArg { key, bind, value: args.parse()? });
+            } else {
+                let value: Expr = args.parse()?;
+                if !named.is_empty() {
+                    return Err(syn::Error::new_spanned(value, "positional arguments must come before named ones"));
+                }
+                positional.push(value);
+            }
+            if args.is_empty() {
+                break;
+            }
+            args.parse::<Token![,]>()?;
+        }
+
+        let mut chains = Vec::new();
+        while input.peek(Token![.]) {
+            input.parse::<Token![.]>()?;
+            let method: Ident = input.parse()?;
+            let call_args;
+            parenthesized!(call_args in input);
+            chains.push((method, Punctuated::parse_terminated(&call_args)?));
+        }
+
+        let children = if input.peek(syn::token::Brace) {
+            Some(parse_braced_nodes(input)?)
+        } else {
+            None
+        };
+
+        Ok(Self { path, positional, named, chains, children })
+    }
+}
+
+impl Parse for IfNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![if]>()?;
+        let condition = if input.peek(Token![let]) {
+            input.parse::<Token![let]>()?;
+            let pat = Pat::parse_multi_with_leading_vert(input)?;
+            input.parse::<Token![=]>()?;
+            let expr = Expr::parse_without_eager_brace(input)?;
+            quote! { let #pat = #expr }
+        } else {
+            let expr = Expr::parse_without_eager_brace(input)?;
+            quote! { #expr }
+        };
+        let (_, then_nodes) = parse_braced_nodes(input)?;
+        let else_branch = if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            if input.peek(Token![if]) {
+                Some(ElseBranch::If(Box::new(input.parse()?)))
+            } else {
+                Some(ElseBranch::Nodes(parse_braced_nodes(input)?.1))
+            }
+        } else {
+            None
+        };
+        Ok(Self { condition, then_nodes, else_branch })
+    }
+}
+
+impl Parse for ForNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![for]>()?;
+        let pat = Pat::parse_multi_with_leading_vert(input)?;
+        input.parse::<Token![in]>()?;
+        let iter = Expr::parse_without_eager_brace(input)?;
+        let (_, body) = parse_braced_nodes(input)?;
+        Ok(Self { pat, iter, body })
+    }
+}
+
+fn children_ident() -> Ident {
+    Ident::new("__ui_children", Span::mixed_site())
+}
+
+pub fn expand(input: UiInput) -> TokenStream {
+    match input.nodes.as_slice() {
+        [Node::Widget(widget)] => widget_element(widget),
+        [Node::Splice(block)] => splice_element(block),
+        nodes => {
+            // Several elements, or ones that may produce none or many
+            let children = children_ident();
+            let statements = nodes.iter().map(|node| node_statements(node, &children));
+            quote! {
+                {
+                    #[allow(unused_mut)]
+                    let mut #children: ::std::vec::Vec<::gui_core::Element> = ::std::vec::Vec::new();
+                    #(#statements)*
+                    ::gui_core::Element::Fragment(#children)
+                }
+            }
+        },
+    }
+}
+
+/// Statements pushing whatever `node` produces onto `children`
+fn node_statements(node: &Node, children: &Ident) -> TokenStream {
+    match node {
+        Node::Widget(widget) => {
+            let element = widget_element(widget);
+            quote! { #children.push(#element); }
+        },
+        Node::Splice(block) => {
+            let element = splice_element(block);
+            quote! { #children.push(#element); }
+        },
+        Node::If(if_node) => if_statements(if_node, children),
+        Node::For(for_node) => {
+            let ForNode { pat, iter, body } = &**for_node;
+            let statements = body.iter().map(|node| node_statements(node, children));
+            quote! {
+                for #pat in #iter {
+                    #(#statements)*
+                }
+            }
+        },
+    }
+}
+
+fn if_statements(if_node: &IfNode, children: &Ident) -> TokenStream {
+    let condition = &if_node.condition;
+    let then_statements = if_node.then_nodes.iter().map(|node| node_statements(node, children));
+    let else_part = match &if_node.else_branch {
+        Some(ElseBranch::If(nested)) => {
+            let nested = if_statements(nested, children);
+            quote! { else #nested }
+        },
+        Some(ElseBranch::Nodes(nodes)) => {
+            let statements = nodes.iter().map(|node| node_statements(node, children));
+            quote! { else { #(#statements)* } }
+        },
+        None => quote! {},
+    };
+    quote! {
+        if #condition {
+            #(#then_statements)*
+        } #else_part
+    }
+}
+
+fn splice_element(block: &Block) -> TokenStream {
+    // A lone expression goes in without its braces, which would only draw an
+    // `unused_braces` warning
+    let spliced = match block.stmts.as_slice() {
+        [Stmt::Expr(expr, None)] => quote! { #expr },
+        _ => quote! { #block },
+    };
+    quote_spanned! {block.span()=>
+        ::gui_core::IntoElement::into_element(#spliced)
+    }
+}
+
+/// `widget(args).with_key(value)...`, its children attached, as an `Element`
+fn widget_element(widget: &WidgetNode) -> TokenStream {
+    let path = &widget.path;
+    let positional = &widget.positional;
+    let mut builder = quote! { #path(#(#positional),*) };
+
+    for NamedArg { key, bind, value } in &widget.named {
+        let name = key.to_string();
+        let method = if *bind {
+            Ident::new(&format!("with_{}_signal", name), key.span())
+        } else if name.starts_with("on_") || name.starts_with("with_") {
+            key.clone()
+        } else {
+            Ident::new(&format!("with_{}", name), key.span())
+        };
+        let arguments = if *bind {
+            quote_spanned! {value.span()=> ::std::clone::Clone::clone(&(#value)) }
+        } else if let Expr::Tuple(tuple) = value {
+            // `size: (300.0, 200.0)` spreads into `with_size(300.0, 200.0)`
+            let elements = &tuple.elems;
+            quote! { #elements }
+        } else {
+            quote! { #value }
+        };
+        builder = quote_spanned! {key.span()=> #builder.#method(#arguments) };
+    }
+
+    for (method, arguments) in &widget.chains {
+        builder = quote! { #builder.#method(#arguments) };
+    }
+
+    let Some((brace_span, nodes)) = &widget.children else {
+        return quote! { ::gui_core::IntoElement::into_element(#builder) };
+    };
+    let children = children_ident();
+    let widget_ident = Ident::new("__ui_widget", Span::mixed_site());
+    let child_ident = Ident::new("__ui_child", Span::mixed_site());
+    let statements = nodes.iter().map(|node| node_statements(node, &children));
+    // Spanned at the braces, so a widget without `with_child` is reported there
+    let attach = quote_spanned! {*brace_span=>
+        for #child_ident in #children {
+            #widget_ident = #widget_ident.with_child(#child_ident);
+        }
+    };
+    quote! {
+        {
+            let mut #widget_ident = #builder;
+            #[allow(unused_mut)]
+            let mut #children: ::std::vec::Vec<::gui_core::Element> = ::std::vec::Vec::new();
+            #(#statements)*
+            #attach
+            ::gui_core::IntoElement::into_element(#widget_ident)
+        }
+    }
+}
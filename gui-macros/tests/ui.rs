@@ -0,0 +1,43 @@
+use gui_core::widgets::{button, column, row, text};
+use gui_core::{ui, Element};
+
+fn child_count(element: &Element) -> usize {
+    match element {
+        Element::Widget(_) => 0,
+        Element::Container { children, .. } | Element::Fragment(children) => children.len(),
+    }
+}
+
+#[test]
+fn ui_macro_builds_conditionals_and_loops() {
+    let items = vec!["a", "b", "c"];
+    let show_details = false;
+    let extra = Element::new_widget(Box::new(text("spliced")));
+
+    let element = ui! {
+        column(gap: 20.0, size: (300.0, 200.0)) {
+            text("Title").with_font_size(24.0),
+            if show_details { text("Details") } else { button("Show details", on_click: || {}) }
+            for item in &items { text(*item) }
+            row() { text("left"), text("right") },
+            { extra },
+        }
+    };
+
+    assert_eq!(child_count(&element), 7);
+    if let Element::Container { children, .. } = &element {
+        assert_eq!(child_count(&children[5]), 2);
+    }
+}
+
+#[test]
+fn ui_macro_wraps_several_roots_in_a_fragment() {
+    let element = ui! { text("one"), text("two") };
+    assert!(matches!(element, Element::Fragment(ref children) if children.len() == 2));
+}
+
+#[test]
+fn ui_macro_reports_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,6 @@
+use gui_core::ui;
+use gui_core::widgets::text;
+
+fn main() {
+    let _ = ui! { text("parent") { text("child") } };
+}
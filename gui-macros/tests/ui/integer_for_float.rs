@@ -0,0 +1,6 @@
+use gui_core::ui;
+use gui_core::widgets::column;
+
+fn main() {
+    let _ = ui! { column(gap: 20) };
+}
@@ -0,0 +1,5 @@
+use gui_core::ui;
+
+fn main() {
+    let _ = ui! { button(on_click: || {}, "Save") };
+}
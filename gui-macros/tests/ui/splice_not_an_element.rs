@@ -0,0 +1,7 @@
+use gui_core::ui;
+use gui_core::widgets::column;
+
+fn main() {
+    let count = 3;
+    let _ = ui! { column() { { count } } };
+}
@@ -1,4 +1,5 @@
 use std::sync::{Arc, Mutex, RwLock};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use std::collections::VecDeque;
 use crate::batch::global_batch_manager;
@@ -12,7 +13,9 @@ pub struct FrameScheduler {
     target_fps: Arc<RwLock<u32>>,
     frame_time: Arc<RwLock<Duration>>,
     last_frame: Arc<Mutex<Instant>>,
+    start_time: Instant,
     frame_counter: Arc<Mutex<u64>>,
+    update_requested: Arc<AtomicBool>,
 }
 
 impl FrameScheduler {
@@ -26,7 +29,9 @@ impl FrameScheduler {
             target_fps: Arc::new(RwLock::new(target_fps)),
             frame_time: Arc::new(RwLock::new(frame_time)),
             last_frame: Arc::new(Mutex::new(Instant::now())),
+            start_time: Instant::now(),
             frame_counter: Arc::new(Mutex::new(0)),
+            update_requested: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -110,10 +115,29 @@ impl FrameScheduler {
         self.frame_time.read().map(|time| *time).unwrap_or(Duration::from_millis(16))
     }
 
+    /// Time since the scheduler started, sampled at the start of the current frame.
+    /// Animations derive their phase from this so every widget sees the same clock.
+    pub fn frame_timestamp(&self) -> Duration {
+        self.last_frame.lock()
+            .map(|last_frame| last_frame.saturating_duration_since(self.start_time))
+            .unwrap_or_default()
+    }
+
     pub fn get_frame_count(&self) -> u64 {
         self.frame_counter.lock().map(|counter| *counter).unwrap_or(0)
     }
 
+    /// Ask the app loop to run a widget update pass on the next frame.
+    /// Widgets with pending timers call this until the timer has fired.
+    pub fn request_update(&self) {
+        self.update_requested.store(true, Ordering::Release);
+    }
+
+    /// Returns whether an update was requested since the last call, clearing the request
+    pub fn take_update_request(&self) -> bool {
+        self.update_requested.swap(false, Ordering::AcqRel)
+    }
+
     pub fn enable_frame_sync(&self, enabled: bool) {
         if let Ok(mut sync_enabled) = self.frame_sync_enabled.write() {
             *sync_enabled = enabled;
@@ -108,4 +108,16 @@ where
             subscribers: self.subscribers.clone(),
         }
     }
-}
\ No newline at end of file
+}
+
+impl<T> std::fmt::Debug for Signal<T>
+where
+    T: Clone + Send + Sync + std::fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Signal")
+            .field("id", &self.id)
+            .field("value", &self.get())
+            .finish()
+    }
+}
@@ -5,7 +5,7 @@ pub mod batch;
 
 pub use vello_renderer::{VelloRenderer, RenderError};
 pub use scene_cache::{SceneCache, CacheKey};
-pub use primitives::{Rectangle, Text, TextRenderer, Shadow, Image};
+pub use primitives::{Rectangle, Text, TextRenderer, Shadow, Image, VectorPath};
 pub use batch::{BatchRenderer, RenderBatch, RenderCommand, BlendMode};
 
 // Re-export common types from dependencies
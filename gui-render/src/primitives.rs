@@ -1,5 +1,5 @@
-use vello::kurbo::{Rect, RoundedRect, Affine, Stroke};
-use vello::peniko::{Color, Fill, Image as VelloImage, Format, Brush};
+use vello::kurbo::{Rect, RoundedRect, Affine, Stroke, BezPath};
+use vello::peniko::{Blob, Color, Fill, Image as VelloImage, Format, Brush, BlendMode, Mix, Compose};
 use vello::Scene;
 
 pub struct Rectangle {
@@ -59,6 +59,54 @@ impl Rectangle {
     }
 }
 
+/// Arbitrary vector outline, filled or stroked, e.g. a path from an SVG icon
+pub struct VectorPath {
+    pub path: BezPath,
+    pub transform: Affine,
+    pub brush: Brush,
+    pub fill_rule: Fill,
+    pub stroke_width: Option<f32>,
+}
+
+impl VectorPath {
+    pub fn new_fill(path: BezPath, color: Color) -> Self {
+        Self {
+            path,
+            transform: Affine::IDENTITY,
+            brush: Brush::Solid(color),
+            fill_rule: Fill::NonZero,
+            stroke_width: None,
+        }
+    }
+
+    pub fn new_stroke(path: BezPath, color: Color, width: f32) -> Self {
+        Self {
+            path,
+            transform: Affine::IDENTITY,
+            brush: Brush::Solid(color),
+            fill_rule: Fill::NonZero,
+            stroke_width: Some(width),
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Affine) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    pub fn with_fill_rule(mut self, fill_rule: Fill) -> Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+
+    pub fn draw(&self, scene: &mut Scene) {
+        match self.stroke_width {
+            Some(width) => scene.stroke(&Stroke::new(width as f64), self.transform, &self.brush, None, &self.path),
+            None => scene.fill(self.fill_rule, self.transform, &self.brush, None, &self.path),
+        }
+    }
+}
+
 use cosmic_text::{FontSystem, SwashCache, Buffer, Attrs, Metrics, Shaping};
 
 pub struct Text {
@@ -307,9 +355,16 @@ pub struct Image {
     pub y: f32,
     pub width: f32,
     pub height: f32,
-    pub data: Vec<u8>,
+    /// Shared with whoever decoded the pixels, so building a primitive copies nothing
+    pub data: Blob<u8>,
     pub format: ImageFormat,
     pub opacity: f32,
+    /// Pixel dimensions of `data`; the image is scaled to `width` x `height` when they differ
+    pub source_size: Option<(u32, u32)>,
+    /// Drawing is limited to this rectangle (x, y, width, height)
+    pub clip: Option<(f32, f32, f32, f32)>,
+    /// Multiplied over the image's own pixels, e.g. to recolor monochrome icons
+    pub tint: Option<Color>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -321,15 +376,18 @@ pub enum ImageFormat {
 }
 
 impl Image {
-    pub fn new(x: f32, y: f32, width: f32, height: f32, data: Vec<u8>, format: ImageFormat) -> Self {
+    pub fn new(x: f32, y: f32, width: f32, height: f32, data: impl Into<Blob<u8>>, format: ImageFormat) -> Self {
         Self {
             x,
             y,
             width,
             height,
-            data,
+            data: data.into(),
             format,
             opacity: 1.0,
+            source_size: None,
+            clip: None,
+            tint: None,
         }
     }
 
@@ -338,6 +396,25 @@ impl Image {
         self
     }
 
+    pub fn with_source_size(mut self, width: u32, height: u32) -> Self {
+        self.source_size = Some((width, height));
+        self
+    }
+
+    pub fn with_clip(mut self, x: f32, y: f32, width: f32, height: f32) -> Self {
+        self.clip = Some((x, y, width, height));
+        self
+    }
+
+    pub fn with_tint(mut self, tint: Color) -> Self {
+        self.tint = Some(tint);
+        self
+    }
+
+    fn pixel_size(&self) -> (u32, u32) {
+        self.source_size.unwrap_or((self.width as u32, self.height as u32))
+    }
+
     pub fn draw(&self, scene: &mut Scene) {
         let image_rect = Rect::new(
             self.x as f64,
@@ -354,23 +431,50 @@ impl Image {
                 ImageFormat::Rgb8 | ImageFormat::Bgra8 | ImageFormat::Bgr8 => Format::Rgba8,
             };
             
+            let (pixel_width, pixel_height) = self.pixel_size();
             let vello_image = VelloImage::new(
-                self.data.clone().into(),
+                self.data.clone(),
                 vello_format,
-                self.width as u32,
-                self.height as u32,
+                pixel_width,
+                pixel_height,
             );
             
             // Apply opacity by using an alpha transform
             let alpha = (self.opacity * 255.0) as u8;
             let transform = Affine::translate((self.x as f64, self.y as f64));
+            let scale = Affine::scale_non_uniform(
+                self.width as f64 / pixel_width.max(1) as f64,
+                self.height as f64 / pixel_height.max(1) as f64,
+            );
             
-            scene.draw_image(&vello_image, transform);
+            let clip_rect = self.clip.map(|(x, y, width, height)| {
+                Rect::new(x as f64, y as f64, (x + width) as f64, (y + height) as f64)
+            });
+            if let Some(clip_rect) = clip_rect {
+                scene.push_layer(Mix::Clip, 1.0, Affine::IDENTITY, &clip_rect);
+            }
+            
+            // Multiply the tint over the image, keeping the image's alpha. The image gets
+            // a layer of its own so the tint only sees its pixels, not what is behind them.
+            if let Some(tint) = self.tint {
+                scene.push_layer(BlendMode::default(), 1.0, Affine::IDENTITY, &image_rect);
+                scene.draw_image(&vello_image, transform * scale);
+                scene.push_layer(BlendMode::new(Mix::Multiply, Compose::SrcAtop), 1.0, Affine::IDENTITY, &image_rect);
+                scene.fill(Fill::NonZero, Affine::IDENTITY, tint, None, &image_rect);
+                scene.pop_layer();
+                scene.pop_layer();
+            } else {
+                scene.draw_image(&vello_image, transform * scale);
+            }
             
             // If opacity is less than 1.0, overlay a semi-transparent rectangle
             if self.opacity < 1.0 {
                 let overlay_color = Color::rgba8(255, 255, 255, 255 - alpha);
-                scene.fill(Fill::NonZero, transform, overlay_color, None, &image_rect);
+                scene.fill(Fill::NonZero, Affine::IDENTITY, overlay_color, None, &image_rect);
+            }
+            
+            if clip_rect.is_some() {
+                scene.pop_layer();
             }
         } else {
             // Fallback: draw a placeholder rectangle with a border
@@ -391,7 +495,8 @@ impl Image {
     }
 
     pub fn expected_data_size(&self) -> usize {
-        (self.width as usize) * (self.height as usize) * self.bytes_per_pixel()
+        let (pixel_width, pixel_height) = self.pixel_size();
+        (pixel_width as usize) * (pixel_height as usize) * self.bytes_per_pixel()
     }
 
     pub fn is_valid(&self) -> bool {